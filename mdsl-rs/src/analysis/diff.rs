@@ -0,0 +1,620 @@
+//! Semantic diff between two MDSL programs
+//!
+//! Compares two releases of a dataset entity by entity instead of line by
+//! line: outlets by ID, links by relationship type and endpoints, metrics by
+//! outlet, year and name, and events by name. Each entity is flattened into
+//! named fields (`identity.title`, `lifecycle.1.end_date`,
+//! `entities.buyer.stake_after`, ...), so a modified entity reports exactly
+//! the fields that changed.
+
+use crate::ir::nodes::*;
+use crate::utils::json;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Kind of compared entity, in report order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EntityKind {
+    /// An outlet, matched by ID
+    Outlet,
+    /// A diachronic or synchronous link, matched by type and endpoints
+    Link,
+    /// A yearly metric, matched by outlet, year and metric name
+    Metric,
+    /// An event, matched by name
+    Event,
+}
+
+impl EntityKind {
+    /// Lowercase name used in reports
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntityKind::Outlet => "outlet",
+            EntityKind::Link => "link",
+            EntityKind::Metric => "metric",
+            EntityKind::Event => "event",
+        }
+    }
+}
+
+/// Identity of an entity across two programs
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EntityKey {
+    /// Outlet ID
+    Outlet(u32),
+    /// Outlet without an ID, by name
+    UnnumberedOutlet(String),
+    /// Link type and endpoints; the endpoints of synchronous links are sorted
+    Link {
+        /// Relationship type
+        link_type: String,
+        /// Predecessor or lower outlet ID
+        from: u32,
+        /// Successor or higher outlet ID
+        to: u32,
+        /// Whether the link is directed (diachronic)
+        directed: bool,
+    },
+    /// Outlet ID, year and metric name
+    Metric {
+        /// Outlet ID
+        outlet: u32,
+        /// Year
+        year: u32,
+        /// Metric name
+        name: String,
+    },
+    /// Event name
+    Event(String),
+}
+
+impl EntityKey {
+    /// Kind of the keyed entity
+    pub fn kind(&self) -> EntityKind {
+        match self {
+            EntityKey::Outlet(_) | EntityKey::UnnumberedOutlet(_) => EntityKind::Outlet,
+            EntityKey::Link { .. } => EntityKind::Link,
+            EntityKey::Metric { .. } => EntityKind::Metric,
+            EntityKey::Event(_) => EntityKind::Event,
+        }
+    }
+}
+
+impl fmt::Display for EntityKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntityKey::Outlet(id) => write!(f, "outlet {}", id),
+            EntityKey::UnnumberedOutlet(name) => write!(f, "outlet \"{}\"", name),
+            EntityKey::Link {
+                link_type,
+                from,
+                to,
+                directed,
+            } => {
+                let arrow = if *directed { "->" } else { "<->" };
+                write!(f, "link {} {} {} {}", link_type, from, arrow, to)
+            }
+            EntityKey::Metric { outlet, year, name } => {
+                write!(f, "metric {}/{}/{}", outlet, year, name)
+            }
+            EntityKey::Event(name) => write!(f, "event {}", name),
+        }
+    }
+}
+
+/// Kind of change of an entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// Only in the new program
+    Added,
+    /// Only in the old program
+    Removed,
+    /// In both programs with different fields
+    Modified,
+}
+
+impl ChangeKind {
+    /// Lowercase name used in reports
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Modified => "modified",
+        }
+    }
+}
+
+/// A field whose value differs between the programs
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    /// Dotted field path
+    pub field: String,
+    /// Value in the old program, if the field was set
+    pub old: Option<String>,
+    /// Value in the new program, if the field is set
+    pub new: Option<String>,
+}
+
+/// An added, removed or modified entity
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityChange {
+    /// Entity identity
+    pub key: EntityKey,
+    /// Human-readable name (outlet title, link name, ...)
+    pub label: String,
+    /// What happened to the entity
+    pub change: ChangeKind,
+    /// Changed fields of a modified entity; all fields of an added or removed one
+    pub fields: Vec<FieldChange>,
+}
+
+/// Entity-level differences between two programs
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProgramDiff {
+    /// Changes ordered by entity kind and key
+    pub changes: Vec<EntityChange>,
+}
+
+/// An entity flattened into its fields
+struct Entity {
+    label: String,
+    fields: BTreeMap<String, String>,
+}
+
+impl ProgramDiff {
+    /// Compare an old and a new program
+    pub fn between(old: &IRProgram, new: &IRProgram) -> Self {
+        let old_entities = entities(old);
+        let mut new_entities = entities(new);
+
+        let mut changes = Vec::new();
+        for (key, old_entity) in old_entities {
+            match new_entities.remove(&key) {
+                Some(new_entity) => {
+                    let fields = field_changes(&old_entity.fields, &new_entity.fields);
+                    if !fields.is_empty() {
+                        changes.push(EntityChange {
+                            key,
+                            label: new_entity.label,
+                            change: ChangeKind::Modified,
+                            fields,
+                        });
+                    }
+                }
+                None => changes.push(EntityChange {
+                    fields: field_changes(&old_entity.fields, &BTreeMap::new()),
+                    key,
+                    label: old_entity.label,
+                    change: ChangeKind::Removed,
+                }),
+            }
+        }
+        for (key, new_entity) in new_entities {
+            changes.push(EntityChange {
+                fields: field_changes(&BTreeMap::new(), &new_entity.fields),
+                key,
+                label: new_entity.label,
+                change: ChangeKind::Added,
+            });
+        }
+        changes.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Self { changes }
+    }
+
+    /// Whether the programs have the same entities
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Number of changes of an entity kind and change kind
+    pub fn count(&self, kind: EntityKind, change: ChangeKind) -> usize {
+        self.changes
+            .iter()
+            .filter(|c| c.key.kind() == kind && c.change == change)
+            .count()
+    }
+
+    /// Format the diff as a human-readable changelog
+    pub fn format_text(&self) -> String {
+        let mut out = String::new();
+        for kind in [
+            EntityKind::Outlet,
+            EntityKind::Link,
+            EntityKind::Metric,
+            EntityKind::Event,
+        ] {
+            out.push_str(&format!(
+                "{}s: {} added, {} removed, {} modified\n",
+                kind.as_str(),
+                self.count(kind, ChangeKind::Added),
+                self.count(kind, ChangeKind::Removed),
+                self.count(kind, ChangeKind::Modified)
+            ));
+        }
+        if !self.changes.is_empty() {
+            out.push('\n');
+        }
+
+        for change in &self.changes {
+            let sign = match change.change {
+                ChangeKind::Added => '+',
+                ChangeKind::Removed => '-',
+                ChangeKind::Modified => '~',
+            };
+            out.push_str(&format!("{} {} ({})\n", sign, change.key, change.label));
+            // Added and removed entities are summarised, not listed field by field
+            if change.change != ChangeKind::Modified {
+                continue;
+            }
+            for field in &change.fields {
+                out.push_str(&format!(
+                    "    {}: {} -> {}\n",
+                    field.field,
+                    field.old.as_deref().unwrap_or("(unset)"),
+                    field.new.as_deref().unwrap_or("(unset)")
+                ));
+            }
+        }
+        out
+    }
+
+    /// Format the diff as JSON
+    pub fn format_json(&self) -> String {
+        let mut json = String::from("{\n  \"summary\": {\n");
+        let kinds = [
+            EntityKind::Outlet,
+            EntityKind::Link,
+            EntityKind::Metric,
+            EntityKind::Event,
+        ];
+        for (i, kind) in kinds.iter().enumerate() {
+            json.push_str(&format!(
+                "    \"{}s\": {{ \"added\": {}, \"removed\": {}, \"modified\": {} }}{}\n",
+                kind.as_str(),
+                self.count(*kind, ChangeKind::Added),
+                self.count(*kind, ChangeKind::Removed),
+                self.count(*kind, ChangeKind::Modified),
+                if i + 1 < kinds.len() { "," } else { "" }
+            ));
+        }
+        json.push_str("  },\n  \"changes\": [");
+
+        for (i, change) in self.changes.iter().enumerate() {
+            json.push_str(if i == 0 { "\n" } else { ",\n" });
+            json.push_str("    {\n");
+            json.push_str(&format!(
+                "      \"entity\": \"{}\",\n",
+                change.key.kind().as_str()
+            ));
+            json.push_str(&format!(
+                "      \"key\": {},\n",
+                json::quote(&change.key.to_string())
+            ));
+            json.push_str(&format!(
+                "      \"label\": {},\n",
+                json::quote(&change.label)
+            ));
+            json.push_str(&format!(
+                "      \"change\": \"{}\",\n",
+                change.change.as_str()
+            ));
+            json.push_str("      \"fields\": [");
+            for (j, field) in change.fields.iter().enumerate() {
+                let value = |value: &Option<String>| {
+                    value.as_deref().map_or("null".to_string(), json::quote)
+                };
+                json.push_str(&format!(
+                    "{}\n        {{ \"field\": {}, \"old\": {}, \"new\": {} }}",
+                    if j == 0 { "" } else { "," },
+                    json::quote(&field.field),
+                    value(&field.old),
+                    value(&field.new)
+                ));
+            }
+            if !change.fields.is_empty() {
+                json.push_str("\n      ");
+            }
+            json.push_str("]\n    }");
+        }
+        if !self.changes.is_empty() {
+            json.push_str("\n  ");
+        }
+        json.push_str("]\n}\n");
+        json
+    }
+}
+
+fn field_changes(
+    old: &BTreeMap<String, String>,
+    new: &BTreeMap<String, String>,
+) -> Vec<FieldChange> {
+    let mut names: Vec<&String> = old.keys().chain(new.keys()).collect();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .filter(|name| old.get(*name) != new.get(*name))
+        .map(|name| FieldChange {
+            field: name.clone(),
+            old: old.get(name).cloned(),
+            new: new.get(name).cloned(),
+        })
+        .collect()
+}
+
+/// All comparable entities of a program; the first of duplicate keys wins
+fn entities(program: &IRProgram) -> BTreeMap<EntityKey, Entity> {
+    let mut entities = BTreeMap::new();
+    let mut insert = |key: EntityKey, entity: Entity| {
+        entities.entry(key).or_insert(entity);
+    };
+
+    let outlet_names: BTreeMap<u32, &str> = program
+        .families
+        .iter()
+        .flat_map(|family| &family.outlets)
+        .filter_map(|outlet| Some((outlet.id?, outlet.name.as_str())))
+        .collect();
+
+    for family in &program.families {
+        for outlet in &family.outlets {
+            let key = match outlet.id {
+                Some(id) => EntityKey::Outlet(id),
+                None => EntityKey::UnnumberedOutlet(outlet.name.clone()),
+            };
+            insert(key, outlet_entity(&family.name, outlet));
+        }
+
+        for relationship in &family.relationships {
+            let (key, entity) = match relationship {
+                IRRelationship::Diachronic(link) => diachronic_entity(link),
+                IRRelationship::Synchronous(link) => synchronous_entity(link),
+            };
+            insert(key, entity);
+        }
+
+        for block in &family.data_blocks {
+            for year in &block.years {
+                for metric in &year.metrics {
+                    let mut fields = BTreeMap::new();
+                    fields.insert("value".to_string(), metric.value.to_string());
+                    fields.insert("unit".to_string(), metric.unit.clone());
+                    fields.insert("source".to_string(), metric.source.clone());
+                    set(&mut fields, "comment", &metric.comment);
+                    insert(
+                        EntityKey::Metric {
+                            outlet: block.outlet_id,
+                            year: year.year,
+                            name: metric.name.clone(),
+                        },
+                        Entity {
+                            label: match outlet_names.get(&block.outlet_id) {
+                                Some(name) => name.to_string(),
+                                None => format!("outlet {}", block.outlet_id),
+                            },
+                            fields,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    for event in &program.events {
+        insert(EntityKey::Event(event.name.clone()), event_entity(event));
+    }
+
+    entities
+}
+
+fn outlet_entity(family: &str, outlet: &IROutlet) -> Entity {
+    let mut fields = BTreeMap::new();
+    fields.insert("name".to_string(), outlet.name.clone());
+    fields.insert("family".to_string(), family.to_string());
+    set(&mut fields, "template", &outlet.template_ref);
+    set(
+        &mut fields,
+        "based_on",
+        &outlet.base_ref.map(|id| id.to_string()),
+    );
+
+    for block in &outlet.blocks {
+        match block {
+            IROutletBlock::Identity(identity) => {
+                for field in identity {
+                    flatten(
+                        &mut fields,
+                        &format!("identity.{}", field.name),
+                        &field.value,
+                    );
+                }
+            }
+            IROutletBlock::Lifecycle(statuses) => {
+                for (i, status) in statuses.iter().enumerate() {
+                    let prefix = format!("lifecycle.{}", i + 1);
+                    fields.insert(format!("{}.status", prefix), status.status.clone());
+                    set(
+                        &mut fields,
+                        &format!("{}.start_date", prefix),
+                        &status.start_date,
+                    );
+                    set(
+                        &mut fields,
+                        &format!("{}.end_date", prefix),
+                        &status.end_date,
+                    );
+                    set(
+                        &mut fields,
+                        &format!("{}.precision_start", prefix),
+                        &status.precision_start,
+                    );
+                    set(
+                        &mut fields,
+                        &format!("{}.precision_end", prefix),
+                        &status.precision_end,
+                    );
+                    set(&mut fields, &format!("{}.comment", prefix), &status.comment);
+                }
+            }
+            IROutletBlock::Characteristics(characteristics) => {
+                for field in characteristics {
+                    flatten(
+                        &mut fields,
+                        &format!("characteristics.{}", field.name),
+                        &field.value,
+                    );
+                }
+            }
+            IROutletBlock::Metadata(metadata) => {
+                for field in metadata {
+                    flatten(
+                        &mut fields,
+                        &format!("metadata.{}", field.name),
+                        &field.value,
+                    );
+                }
+            }
+        }
+    }
+
+    Entity {
+        label: outlet.name.clone(),
+        fields,
+    }
+}
+
+fn diachronic_entity(link: &IRDiachronicLink) -> (EntityKey, Entity) {
+    let mut fields = BTreeMap::new();
+    fields.insert("name".to_string(), link.name.clone());
+    set(&mut fields, "event_start_date", &link.event_start_date);
+    set(&mut fields, "event_end_date", &link.event_end_date);
+    set(&mut fields, "comment", &link.comment);
+    set(&mut fields, "maps_to", &link.maps_to);
+
+    let key = EntityKey::Link {
+        link_type: link.relationship_type.clone(),
+        from: link.predecessor,
+        to: link.successor,
+        directed: true,
+    };
+    (
+        key,
+        Entity {
+            label: link.name.clone(),
+            fields,
+        },
+    )
+}
+
+fn synchronous_entity(link: &IRSynchronousLink) -> (EntityKey, Entity) {
+    let mut fields = BTreeMap::new();
+    fields.insert("name".to_string(), link.name.clone());
+    set(&mut fields, "period_start", &link.period_start);
+    set(&mut fields, "period_end", &link.period_end);
+    set(&mut fields, "details", &link.details);
+    set(&mut fields, "maps_to", &link.maps_to);
+    for end in [&link.outlet_1, &link.outlet_2] {
+        fields.insert(format!("role.{}", end.id), end.role.clone());
+    }
+
+    let key = EntityKey::Link {
+        link_type: link.relationship_type.clone(),
+        from: link.outlet_1.id.min(link.outlet_2.id),
+        to: link.outlet_1.id.max(link.outlet_2.id),
+        directed: false,
+    };
+    (
+        key,
+        Entity {
+            label: link.name.clone(),
+            fields,
+        },
+    )
+}
+
+fn event_entity(event: &IREvent) -> Entity {
+    let mut fields = BTreeMap::new();
+    fields.insert("type".to_string(), event.event_type.clone());
+    set(&mut fields, "date", &event.date);
+    set(&mut fields, "status", &event.status);
+    for entity in &event.entities {
+        let prefix = format!("entities.{}", entity.name);
+        fields.insert(format!("{}.id", prefix), entity.id.to_string());
+        set(
+            &mut fields,
+            &format!("{}.person", prefix),
+            &entity.person.map(|person| person.to_string()),
+        );
+        fields.insert(format!("{}.role", prefix), entity.role.clone());
+        set(
+            &mut fields,
+            &format!("{}.stake_before", prefix),
+            &entity.stake_before.map(|stake| stake.to_string()),
+        );
+        set(
+            &mut fields,
+            &format!("{}.stake_after", prefix),
+            &entity.stake_after.map(|stake| stake.to_string()),
+        );
+    }
+    for impact in &event.impact {
+        flatten(
+            &mut fields,
+            &format!("impact.{}", impact.name),
+            &impact.value,
+        );
+    }
+    for metadata in &event.metadata {
+        flatten(
+            &mut fields,
+            &format!("metadata.{}", metadata.name),
+            &metadata.value,
+        );
+    }
+
+    Entity {
+        label: event.name.clone(),
+        fields,
+    }
+}
+
+fn set(fields: &mut BTreeMap<String, String>, name: &str, value: &Option<String>) {
+    if let Some(value) = value {
+        fields.insert(name.to_string(), value.clone());
+    }
+}
+
+/// Add an expression, with objects spread over one field per member
+fn flatten(fields: &mut BTreeMap<String, String>, path: &str, value: &IRExpression) {
+    match value {
+        IRExpression::Object(members) => {
+            for member in members {
+                flatten(fields, &format!("{}.{}", path, member.name), &member.value);
+            }
+        }
+        other => {
+            fields.insert(path.to_string(), render(other));
+        }
+    }
+}
+
+fn render(value: &IRExpression) -> String {
+    match value {
+        IRExpression::String(s) => s.clone(),
+        IRExpression::Number(n) => n.to_string(),
+        IRExpression::Boolean(b) => b.to_string(),
+        IRExpression::Variable(name) => format!("${}", name),
+        IRExpression::Object(members) => {
+            let members: Vec<String> = members
+                .iter()
+                .map(|member| format!("{} = {}", member.name, render(&member.value)))
+                .collect();
+            format!("{{ {} }}", members.join("; "))
+        }
+        IRExpression::Array(items) => {
+            let items: Vec<String> = items.iter().map(render).collect();
+            format!("[{}]", items.join(", "))
+        }
+    }
+}
@@ -0,0 +1,518 @@
+//! Entity resolution between curated and crawled companies
+//!
+//! Hand-curated company declarations (Medienunternehmen) and companies
+//! imported from a company register describe the same organisations under
+//! slightly different names and legal forms. [`EntityMatcher`] proposes
+//! candidate pairs scored on normalised names, legal forms, registry IDs and
+//! location; reviewed pairs are written back as IDENTIFIER_LINK declarations.
+
+use crate::error::{Error, Result};
+use crate::ir::nodes::{IRCompany, IRExpression, IRIdentifierLink, IRProgram};
+use crate::utils::csv::{write_record, CsvTable};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+/// Minimum score for a pair to be proposed
+pub const DEFAULT_MATCH_THRESHOLD: f64 = 0.6;
+
+/// Maximum number of candidates proposed per curated company
+pub const DEFAULT_MAX_CANDIDATES: usize = 3;
+
+/// Default identifier scheme of the crawled company IDs
+pub const DEFAULT_SCHEME: &str = "northdata";
+
+/// Issuer recorded on generated identifier links
+const ISSUER: &str = "mdsl match";
+
+/// Name tokens that only denote a legal form (after lowercasing and removing dots)
+const LEGAL_FORM_TOKENS: &[&str] = &[
+    "gmbh",
+    "gesmbh",
+    "mbh",
+    "ag",
+    "kg",
+    "kgaa",
+    "ohg",
+    "og",
+    "keg",
+    "oeg",
+    "eg",
+    "ev",
+    "se",
+    "ug",
+    "haftungsbeschraenkt",
+    "co",
+    "gbr",
+    "ltd",
+    "limited",
+    "inc",
+    "llc",
+    "sarl",
+    "sa",
+    "bv",
+    "nv",
+    "plc",
+];
+
+/// Company fields holding a register number
+const REGISTRY_FIELDS: &[&str] = &["registry_id", "register_number", "firmenbuch"];
+
+/// Company fields holding a seat or location
+const LOCATION_FIELDS: &[&str] = &["location", "city", "seat", "jurisdiction", "region"];
+
+/// Company attributes used for matching
+#[derive(Debug, Clone)]
+pub struct MatchRecord {
+    /// Company ID
+    pub id: u32,
+    /// Company name as declared
+    pub name: String,
+    /// Name with legal form, punctuation and case removed
+    pub normalized_name: String,
+    /// Normalised legal form, from the `legal_form` field or the name
+    pub legal_form: Option<String>,
+    /// Normalised register number
+    pub registry_id: Option<String>,
+    /// Normalised seat or location
+    pub location: Option<String>,
+    /// Dataset the record was read from, such as its file; curated and
+    /// crawled IDs are separate namespaces, so only records of one source
+    /// with the same ID are the same company
+    pub source: Option<String>,
+}
+
+impl MatchRecord {
+    /// Create a record from a name alone
+    pub fn new(id: u32, name: &str) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            normalized_name: normalize_name(name),
+            legal_form: legal_form_of(name),
+            registry_id: None,
+            location: None,
+            source: None,
+        }
+    }
+
+    /// Create a record from a company declaration
+    pub fn from_company(company: &IRCompany) -> Self {
+        let mut record = Self::new(company.id, &company.name);
+
+        let field = |names: &[&str]| {
+            names.iter().find_map(|name| {
+                company
+                    .fields
+                    .iter()
+                    .find(|field| field.name == *name)
+                    .and_then(|field| match &field.value {
+                        IRExpression::String(value) => Some(value.clone()),
+                        IRExpression::Number(value) => Some(value.to_string()),
+                        _ => None,
+                    })
+            })
+        };
+
+        if let Some(legal_form) = field(&["legal_form"]) {
+            record.legal_form = legal_form_of(&legal_form).or(Some(legal_form.to_lowercase()));
+        }
+        record.registry_id = field(REGISTRY_FIELDS)
+            .map(|id| normalize_registry_id(&id))
+            .filter(|id| !id.is_empty());
+        record.location = field(LOCATION_FIELDS)
+            .map(|location| fold_text(&location).trim().to_string())
+            .filter(|location| !location.is_empty());
+
+        record
+    }
+
+    /// Records for all companies of a program
+    pub fn from_program(ir: &IRProgram) -> Vec<Self> {
+        ir.companies.iter().map(Self::from_company).collect()
+    }
+
+    /// Records for all companies of a program, tagged with their source
+    pub fn from_source(ir: &IRProgram, source: &str) -> Vec<Self> {
+        ir.companies
+            .iter()
+            .map(|company| Self {
+                source: Some(source.to_string()),
+                ..Self::from_company(company)
+            })
+            .collect()
+    }
+
+    /// Whether two records are the same company of the same source
+    pub fn is_same(&self, other: &MatchRecord) -> bool {
+        self.source.is_some() && self.source == other.source && self.id == other.id
+    }
+}
+
+/// Proposed match between a curated and a crawled company
+#[derive(Debug, Clone)]
+pub struct MatchCandidate {
+    /// Curated company ID
+    pub curated_id: u32,
+    /// Curated company name
+    pub curated_name: String,
+    /// Crawled company ID
+    pub crawled_id: u32,
+    /// Crawled company name
+    pub crawled_name: String,
+    /// Overall score between 0 and 1
+    pub score: f64,
+    /// Similarity of the normalised names
+    pub name_score: f64,
+    /// Whether the register numbers agree (`None` if either is missing)
+    pub registry_match: Option<bool>,
+    /// Whether the locations agree (`None` if either is missing)
+    pub location_match: Option<bool>,
+    /// Whether the legal forms agree (`None` if either is missing)
+    pub legal_form_match: Option<bool>,
+}
+
+/// Proposes candidate pairs between curated and crawled companies
+#[derive(Debug, Clone)]
+pub struct EntityMatcher {
+    /// Minimum score for a pair to be proposed
+    pub threshold: f64,
+    /// Maximum number of candidates per curated company
+    pub max_candidates: usize,
+    /// Identifier scheme of the crawled company IDs
+    pub scheme: String,
+}
+
+impl Default for EntityMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EntityMatcher {
+    /// Create a matcher with default settings
+    pub fn new() -> Self {
+        Self {
+            threshold: DEFAULT_MATCH_THRESHOLD,
+            max_candidates: DEFAULT_MAX_CANDIDATES,
+            scheme: DEFAULT_SCHEME.to_string(),
+        }
+    }
+
+    /// Set the minimum score
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Set the maximum number of candidates per curated company
+    pub fn with_max_candidates(mut self, max_candidates: usize) -> Self {
+        self.max_candidates = max_candidates;
+        self
+    }
+
+    /// Set the identifier scheme
+    pub fn with_scheme(mut self, scheme: &str) -> Self {
+        self.scheme = scheme.to_string();
+        self
+    }
+
+    /// Score a single pair
+    pub fn score(&self, curated: &MatchRecord, crawled: &MatchRecord) -> MatchCandidate {
+        let name_score = name_similarity(&curated.normalized_name, &crawled.normalized_name);
+        let compare = |a: &Option<String>, b: &Option<String>| match (a, b) {
+            (Some(a), Some(b)) => Some(a == b),
+            _ => None,
+        };
+        let registry_match = compare(&curated.registry_id, &crawled.registry_id);
+        let location_match = match (&curated.location, &crawled.location) {
+            (Some(a), Some(b)) => Some(a.contains(b.as_str()) || b.contains(a.as_str())),
+            _ => None,
+        };
+        let legal_form_match = compare(&curated.legal_form, &crawled.legal_form);
+
+        let mut score = name_score;
+        match location_match {
+            Some(true) => score += 0.05,
+            Some(false) => score -= 0.1,
+            None => {}
+        }
+        // "X GmbH" and "X GmbH & Co KG" are usually two distinct legal entities
+        match legal_form_match {
+            Some(true) => score += 0.05,
+            Some(false) => score -= 0.15,
+            None => {}
+        }
+        match registry_match {
+            Some(true) => score = score.max(0.95),
+            Some(false) => score *= 0.5,
+            None => {}
+        }
+
+        MatchCandidate {
+            curated_id: curated.id,
+            curated_name: curated.name.clone(),
+            crawled_id: crawled.id,
+            crawled_name: crawled.name.clone(),
+            score: score.clamp(0.0, 1.0),
+            name_score,
+            registry_match,
+            location_match,
+            legal_form_match,
+        }
+    }
+
+    /// Propose the best-scoring crawled companies for each curated company
+    pub fn propose(&self, curated: &[MatchRecord], crawled: &[MatchRecord]) -> Vec<MatchCandidate> {
+        let mut proposals = Vec::new();
+
+        for record in curated {
+            let mut candidates: Vec<MatchCandidate> = crawled
+                .iter()
+                .filter(|other| !record.is_same(other))
+                .map(|other| self.score(record, other))
+                .filter(|candidate| candidate.score >= self.threshold)
+                .collect();
+            candidates.sort_by(|a, b| {
+                b.score
+                    .partial_cmp(&a.score)
+                    .unwrap_or(Ordering::Equal)
+                    .then(a.crawled_id.cmp(&b.crawled_id))
+            });
+            candidates.truncate(self.max_candidates);
+            proposals.extend(candidates);
+        }
+
+        proposals
+    }
+
+    /// Render candidates as a review CSV; reviewers fill in the `decision` column
+    pub fn to_csv(&self, candidates: &[MatchCandidate]) -> String {
+        let mut csv = write_record(&[
+            "curated_id",
+            "curated_name",
+            "crawled_id",
+            "crawled_name",
+            "score",
+            "name_score",
+            "registry_match",
+            "location_match",
+            "legal_form_match",
+            "decision",
+        ]);
+        let flag = |value: Option<bool>| value.map_or(String::new(), |v| v.to_string());
+
+        for candidate in candidates {
+            csv.push_str(&write_record(&[
+                candidate.curated_id.to_string(),
+                candidate.curated_name.clone(),
+                candidate.crawled_id.to_string(),
+                candidate.crawled_name.clone(),
+                format!("{:.3}", candidate.score),
+                format!("{:.3}", candidate.name_score),
+                flag(candidate.registry_match),
+                flag(candidate.location_match),
+                flag(candidate.legal_form_match),
+                String::new(),
+            ]));
+        }
+
+        csv
+    }
+
+    /// Render candidates as proposed IDENTIFIER_LINK declarations
+    pub fn to_mdsl(&self, candidates: &[MatchCandidate]) -> String {
+        let mut mdsl = String::new();
+        mdsl.push_str("// Proposed matches between curated and crawled companies\n");
+        mdsl.push_str("// Set status = \"accepted\" or \"rejected\" after review\n\n");
+
+        for candidate in candidates {
+            mdsl.push_str(&render_identifier_link(&self.candidate_link(candidate, "proposed")));
+        }
+
+        mdsl
+    }
+
+    /// Identifier link for a candidate pair
+    pub fn candidate_link(&self, candidate: &MatchCandidate, status: &str) -> IRIdentifierLink {
+        IRIdentifierLink {
+            name: format!("match_{}_{}", candidate.curated_id, candidate.crawled_id),
+            company_id: candidate.curated_id,
+            scheme: self.scheme.clone(),
+            value: candidate.crawled_id.to_string(),
+            status: Some(status.to_string()),
+            score: Some((candidate.score * 1000.0).round() / 1000.0),
+            issued_by: Some(ISSUER.to_string()),
+            comment: Some(format!(
+                "{} ~ {}",
+                candidate.curated_name, candidate.crawled_name
+            )),
+            provenance: Vec::new(),
+            reviews: Vec::new(),
+        }
+    }
+
+    /// Accepted links from a reviewed CSV (rows whose `decision` is accept/yes/x)
+    pub fn accepted_from_csv(&self, content: &str) -> Result<Vec<IRIdentifierLink>> {
+        let table = CsvTable::parse(content);
+        for column in ["curated_id", "crawled_id", "decision"] {
+            if !table.has_column(column) {
+                return Err(Error::Io(format!(
+                    "review file is missing column '{}'",
+                    column
+                )));
+            }
+        }
+
+        let mut links = Vec::new();
+        for row in &table.rows {
+            let accepted = table.get(row, "decision").is_some_and(|decision| {
+                matches!(
+                    decision.trim().to_lowercase().as_str(),
+                    "accept" | "accepted" | "yes" | "y" | "x" | "1" | "true"
+                )
+            });
+            if !accepted {
+                continue;
+            }
+
+            let id = |column: &str| table.get(row, column).and_then(|v| v.trim().parse().ok());
+            let (Some(curated_id), Some(crawled_id)) = (id("curated_id"), id("crawled_id")) else {
+                continue;
+            };
+            let candidate = MatchCandidate {
+                curated_id,
+                curated_name: table.get(row, "curated_name").unwrap_or_default().to_string(),
+                crawled_id,
+                crawled_name: table.get(row, "crawled_name").unwrap_or_default().to_string(),
+                score: table
+                    .get(row, "score")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(1.0),
+                name_score: 0.0,
+                registry_match: None,
+                location_match: None,
+                legal_form_match: None,
+            };
+            links.push(self.candidate_link(&candidate, "accepted"));
+        }
+
+        Ok(links)
+    }
+}
+
+/// Accepted links from a reviewed MDSL file
+pub fn accepted_from_program(ir: &IRProgram) -> Vec<IRIdentifierLink> {
+    ir.identifier_links
+        .iter()
+        .filter(|link| link.status.as_deref() == Some("accepted"))
+        .cloned()
+        .collect()
+}
+
+/// Render an identifier link as an MDSL declaration
+pub fn render_identifier_link(link: &IRIdentifierLink) -> String {
+    let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+    let mut mdsl = format!("IDENTIFIER_LINK {} {{\n", link.name);
+    if let Some(comment) = &link.comment {
+        mdsl.push_str(&format!("    @comment \"{}\"\n", escape(comment)));
+    }
+    mdsl.push_str(&format!("    company = {};\n", link.company_id));
+    mdsl.push_str(&format!("    scheme = \"{}\";\n", escape(&link.scheme)));
+    mdsl.push_str(&format!("    value = \"{}\";\n", escape(&link.value)));
+    if let Some(status) = &link.status {
+        mdsl.push_str(&format!("    status = \"{}\";\n", escape(status)));
+    }
+    if let Some(score) = link.score {
+        mdsl.push_str(&format!("    score = {};\n", score));
+    }
+    if let Some(issued_by) = &link.issued_by {
+        mdsl.push_str(&format!("    issued_by = \"{}\";\n", escape(issued_by)));
+    }
+    mdsl.push_str("}\n\n");
+    mdsl
+}
+
+/// Lowercase, transliterate umlauts and replace punctuation with spaces
+fn fold_text(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.to_lowercase().replace('.', "").chars() {
+        match c {
+            'ä' => folded.push_str("ae"),
+            'ö' => folded.push_str("oe"),
+            'ü' => folded.push_str("ue"),
+            'ß' => folded.push_str("ss"),
+            c if c.is_alphanumeric() => folded.push(c),
+            _ => folded.push(' '),
+        }
+    }
+    folded
+}
+
+/// Normalise a company name: fold case and umlauts, drop punctuation and legal-form tokens
+pub fn normalize_name(name: &str) -> String {
+    fold_text(name)
+        .split_whitespace()
+        .filter(|token| !LEGAL_FORM_TOKENS.contains(token))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Extract the normalised legal form from a name (e.g. "gmbh co kg")
+pub fn legal_form_of(name: &str) -> Option<String> {
+    let folded = fold_text(name);
+    let tokens: Vec<&str> = folded
+        .split_whitespace()
+        .filter(|token| LEGAL_FORM_TOKENS.contains(token))
+        .map(|token| if token == "gesmbh" { "gmbh" } else { token })
+        .collect();
+    if tokens.is_empty() {
+        None
+    } else {
+        Some(tokens.join(" "))
+    }
+}
+
+/// Normalise a register number ("HRB 12 345" and "hrb12345" compare equal)
+pub fn normalize_registry_id(id: &str) -> String {
+    id.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Similarity of two normalised names: bigram Dice blended with token overlap
+pub fn name_similarity(a: &str, b: &str) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    if a == b {
+        return 1.0;
+    }
+
+    let bigrams = |s: &str| -> Vec<(char, char)> {
+        let chars: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+        chars.windows(2).map(|w| (w[0], w[1])).collect()
+    };
+    let bigrams_a = bigrams(a);
+    let mut bigrams_b = bigrams(b);
+    let total = bigrams_a.len() + bigrams_b.len();
+    let mut shared = 0;
+    for bigram in &bigrams_a {
+        if let Some(pos) = bigrams_b.iter().position(|other| other == bigram) {
+            bigrams_b.swap_remove(pos);
+            shared += 1;
+        }
+    }
+    let dice = if total == 0 {
+        0.0
+    } else {
+        2.0 * shared as f64 / total as f64
+    };
+
+    let tokens_a: HashSet<&str> = a.split_whitespace().collect();
+    let tokens_b: HashSet<&str> = b.split_whitespace().collect();
+    let union = tokens_a.union(&tokens_b).count();
+    let jaccard = tokens_a.intersection(&tokens_b).count() as f64 / union as f64;
+
+    0.6 * dice + 0.4 * jaccard
+}
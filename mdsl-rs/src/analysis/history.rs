@@ -0,0 +1,320 @@
+//! Chronological history of a single outlet
+//!
+//! Lifecycle statuses, diachronic predecessor and successor links, the start
+//! and end of synchronous links, events the outlet took part in and its yearly
+//! metrics are merged into one timeline. Entries are ordered by date, partial
+//! dates sorting at the start of their period; undated entries come last.
+
+use crate::ir::nodes::*;
+use crate::utils::dates::first_day;
+use crate::utils::json;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Kind of a history entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    /// Lifecycle status
+    Status,
+    /// Diachronic link from a predecessor
+    Predecessor,
+    /// Diachronic link to a successor
+    Successor,
+    /// Start of a synchronous link
+    LinkStart,
+    /// End of a synchronous link
+    LinkEnd,
+    /// Event the outlet took part in
+    Event,
+    /// Metrics of one year
+    Metrics,
+}
+
+impl fmt::Display for EntryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            EntryKind::Status => "status",
+            EntryKind::Predecessor => "predecessor",
+            EntryKind::Successor => "successor",
+            EntryKind::LinkStart => "link start",
+            EntryKind::LinkEnd => "link end",
+            EntryKind::Event => "event",
+            EntryKind::Metrics => "metrics",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// One entry of an outlet's timeline
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    /// Date as declared (a year for metrics), `None` if undated
+    pub date: Option<String>,
+    /// Kind of entry
+    pub kind: EntryKind,
+    /// Human-readable description
+    pub description: String,
+    /// Other outlet involved, for links
+    pub related: Option<u32>,
+}
+
+/// Timeline of one outlet
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutletHistory {
+    /// Outlet ID
+    pub outlet_id: u32,
+    /// Outlet name, if the outlet is declared
+    pub name: Option<String>,
+    /// Family the outlet is declared in
+    pub family: Option<String>,
+    /// Entries in chronological order
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl OutletHistory {
+    /// Collect the history of an outlet; `None` if the program neither
+    /// declares nor mentions it
+    pub fn build(program: &IRProgram, outlet_id: u32) -> Option<Self> {
+        let mut names: HashMap<u32, &str> = HashMap::new();
+        let mut declared = None;
+        for family in &program.families {
+            for outlet in &family.outlets {
+                if let Some(id) = outlet.id {
+                    names.entry(id).or_insert(&outlet.name);
+                    if id == outlet_id && declared.is_none() {
+                        declared = Some((family, outlet));
+                    }
+                }
+            }
+        }
+        let describe = |id: u32| match names.get(&id) {
+            Some(name) => format!("{} ({})", name, id),
+            None => id.to_string(),
+        };
+
+        let mut entries = Vec::new();
+        if let Some((_, outlet)) = declared {
+            for block in &outlet.blocks {
+                let IROutletBlock::Lifecycle(statuses) = block else {
+                    continue;
+                };
+                for status in statuses {
+                    let mut description = status.status.clone();
+                    match status.end_date.as_deref() {
+                        None | Some("CURRENT") => description.push_str(" (ongoing)"),
+                        Some(end) => description.push_str(&format!(" until {}", end)),
+                    }
+                    if let Some(comment) = &status.comment {
+                        description.push_str(&format!(" - {}", comment));
+                    }
+                    entries.push(HistoryEntry {
+                        date: status.start_date.clone(),
+                        kind: EntryKind::Status,
+                        description,
+                        related: None,
+                    });
+                }
+            }
+        }
+
+        for relationship in program.families.iter().flat_map(|f| &f.relationships) {
+            match relationship {
+                IRRelationship::Diachronic(link) => {
+                    let (kind, other) = if link.successor == outlet_id {
+                        (EntryKind::Predecessor, link.predecessor)
+                    } else if link.predecessor == outlet_id {
+                        (EntryKind::Successor, link.successor)
+                    } else {
+                        continue;
+                    };
+                    let mut description =
+                        format!("{}: {}", link.relationship_type, describe(other));
+                    if let Some(end) = &link.event_end_date {
+                        description.push_str(&format!(" (until {})", end));
+                    }
+                    if let Some(comment) = &link.comment {
+                        description.push_str(&format!(" - {}", comment));
+                    }
+                    entries.push(HistoryEntry {
+                        date: link.event_start_date.clone(),
+                        kind,
+                        description,
+                        related: Some(other),
+                    });
+                }
+                IRRelationship::Synchronous(link) => {
+                    let (own, other) = if link.outlet_1.id == outlet_id {
+                        (&link.outlet_1, &link.outlet_2)
+                    } else if link.outlet_2.id == outlet_id {
+                        (&link.outlet_2, &link.outlet_1)
+                    } else {
+                        continue;
+                    };
+                    let description = format!(
+                        "{} with {} as {}",
+                        link.relationship_type,
+                        describe(other.id),
+                        own.role
+                    );
+                    entries.push(HistoryEntry {
+                        date: link.period_start.clone(),
+                        kind: EntryKind::LinkStart,
+                        description: description.clone(),
+                        related: Some(other.id),
+                    });
+                    if let Some(end) = link.period_end.as_ref().filter(|end| *end != "CURRENT") {
+                        entries.push(HistoryEntry {
+                            date: Some(end.clone()),
+                            kind: EntryKind::LinkEnd,
+                            description,
+                            related: Some(other.id),
+                        });
+                    }
+                }
+            }
+        }
+
+        for event in &program.events {
+            for entity in event.entities.iter().filter(|e| e.id == outlet_id) {
+                let mut description =
+                    format!("{} {} as {}", event.event_type, event.name, entity.role);
+                match (entity.stake_before, entity.stake_after) {
+                    (Some(before), Some(after)) => {
+                        description.push_str(&format!(", stake {}% -> {}%", before, after))
+                    }
+                    (None, Some(after)) => description.push_str(&format!(", stake {}%", after)),
+                    (Some(before), None) => {
+                        description.push_str(&format!(", stake before {}%", before))
+                    }
+                    (None, None) => {}
+                }
+                entries.push(HistoryEntry {
+                    date: event.date.clone(),
+                    kind: EntryKind::Event,
+                    description,
+                    related: None,
+                });
+            }
+        }
+
+        let mut years: Vec<&IRDataYear> = program
+            .families
+            .iter()
+            .flat_map(|f| &f.data_blocks)
+            .filter(|block| block.outlet_id == outlet_id)
+            .flat_map(|block| &block.years)
+            .collect();
+        years.sort_by_key(|year| year.year);
+        for year in years {
+            let metrics: Vec<String> = year
+                .metrics
+                .iter()
+                .map(|metric| {
+                    let mut metric_text = format!("{} {}", metric.name, metric.value);
+                    if !metric.unit.is_empty() {
+                        metric_text.push_str(&format!(" {}", metric.unit));
+                    }
+                    metric_text
+                })
+                .collect();
+            entries.push(HistoryEntry {
+                date: Some(year.year.to_string()),
+                kind: EntryKind::Metrics,
+                description: metrics.join(", "),
+                related: None,
+            });
+        }
+
+        if declared.is_none() && entries.is_empty() {
+            return None;
+        }
+
+        // Stable, so entries of one date keep the order above
+        entries.sort_by_key(|entry| match entry.date.as_deref() {
+            Some(date) if date != "CURRENT" => (false, first_day(date)),
+            _ => (true, String::new()),
+        });
+
+        Some(OutletHistory {
+            outlet_id,
+            name: declared.map(|(_, outlet)| outlet.name.clone()),
+            family: declared.map(|(family, _)| family.name.clone()),
+            entries,
+        })
+    }
+
+    fn title(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{} ({})", name, self.outlet_id),
+            None => format!("Outlet {} (not declared)", self.outlet_id),
+        }
+    }
+
+    /// Format the history as aligned text
+    pub fn format_text(&self) -> String {
+        let mut out = format!("History of {}\n", self.title());
+        if let Some(family) = &self.family {
+            out.push_str(&format!("Family: {}\n", family));
+        }
+        out.push('\n');
+        for entry in &self.entries {
+            out.push_str(&format!(
+                "{:<10}  {:<11}  {}\n",
+                entry.date.as_deref().unwrap_or("undated"),
+                entry.kind.to_string(),
+                entry.description
+            ));
+        }
+        if self.entries.is_empty() {
+            out.push_str("(no entries)\n");
+        }
+        out
+    }
+
+    /// Format the history as a Markdown section with a table
+    pub fn format_markdown(&self) -> String {
+        let escape = |text: &str| text.replace('|', "\\|");
+        let mut out = format!("## {}\n\n", escape(&self.title()));
+        if let Some(family) = &self.family {
+            out.push_str(&format!("Family: {}\n\n", escape(family)));
+        }
+        out.push_str("| Date | Type | Entry |\n|------|------|-------|\n");
+        for entry in &self.entries {
+            out.push_str(&format!(
+                "| {} | {} | {} |\n",
+                entry.date.as_deref().unwrap_or("undated"),
+                entry.kind,
+                escape(&entry.description)
+            ));
+        }
+        out
+    }
+
+    /// Format the history as a JSON object
+    pub fn format_json(&self) -> String {
+        let optional = |value: Option<&str>| value.map_or("null".to_string(), json::quote);
+        let entries: Vec<String> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    "\n    {{ \"date\": {}, \"type\": {}, \"description\": {}, \"related\": {} }}",
+                    optional(entry.date.as_deref()),
+                    json::quote(&entry.kind.to_string()),
+                    json::quote(&entry.description),
+                    entry
+                        .related
+                        .map_or("null".to_string(), |id| id.to_string())
+                )
+            })
+            .collect();
+        format!(
+            "{{\n  \"outlet_id\": {},\n  \"name\": {},\n  \"family\": {},\n  \"entries\": [{}{}]\n}}",
+            self.outlet_id,
+            optional(self.name.as_deref()),
+            optional(self.family.as_deref()),
+            entries.join(","),
+            if entries.is_empty() { "" } else { "\n  " }
+        )
+    }
+}
@@ -0,0 +1,686 @@
+//! Outlet genealogies built from diachronic links
+//!
+//! Every DIACHRONIC_LINK (succession, amalgamation, split-off, offshoot,
+//! merger, ...) is an edge from predecessor to successor. The resulting graph
+//! should be a DAG whose dates agree with the outlets' lifecycles;
+//! [`LineageGraph::check`] reports cycles and orderings that cannot have
+//! happened. [`LineageGraph::tree`] collects the ancestors and descendants of
+//! one outlet, which can be written as DOT, SVG or JSON.
+
+use crate::ir::nodes::*;
+use crate::utils::dates::{first_day, last_day};
+use crate::utils::{dot, json, xml};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+
+/// Link types after which the predecessor no longer exists on its own
+const ENDING_TYPES: &[&str] = &["succession", "amalgamation", "merger"];
+
+/// An outlet taking part in the lineage graph
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineageOutlet {
+    /// Outlet ID
+    pub id: u32,
+    /// Outlet name, if the outlet is declared
+    pub name: Option<String>,
+    /// Earliest lifecycle start, as declared
+    pub start: Option<String>,
+    /// Latest lifecycle end, as declared; `None` if still running or unknown
+    pub end: Option<String>,
+}
+
+impl LineageOutlet {
+    /// Name and ID for reports
+    pub fn describe(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{} ({})", name, self.id),
+            None => self.id.to_string(),
+        }
+    }
+
+    /// Title and second line (ID and years) of the outlet's box
+    fn caption(&self) -> (String, String) {
+        match &self.name {
+            Some(name) => (
+                name.clone(),
+                format!("{} {}", self.id, self.years())
+                    .trim_end()
+                    .to_string(),
+            ),
+            None => (format!("Outlet {}", self.id), self.years()),
+        }
+    }
+
+    fn years(&self) -> String {
+        let year = |date: &Option<String>| {
+            date.as_deref()
+                .map(|d| d.chars().take(4).collect::<String>())
+        };
+        match (year(&self.start), year(&self.end)) {
+            (Some(start), Some(end)) => format!("{}-{}", start, end),
+            (Some(start), None) => format!("{}-", start),
+            (None, Some(end)) => format!("-{}", end),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// A diachronic link between two outlets
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineageLink {
+    /// Link name
+    pub name: String,
+    /// Predecessor outlet ID
+    pub predecessor: u32,
+    /// Successor outlet ID
+    pub successor: u32,
+    /// Relationship type (succession, merger, ...)
+    pub relationship_type: String,
+    /// Date of the event, as declared
+    pub date: Option<String>,
+}
+
+/// Kind of a lineage problem
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    /// Links lead back to an outlet they started from
+    Cycle,
+    /// The link is dated before the predecessor started
+    BeforePredecessorStart,
+    /// The link is dated after the successor ended
+    AfterSuccessorEnd,
+    /// An outlet receives a link after a succession, amalgamation or merger
+    /// had already ended it
+    OutOfOrder,
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            IssueKind::Cycle => "cycle",
+            IssueKind::BeforePredecessorStart => "before predecessor start",
+            IssueKind::AfterSuccessorEnd => "after successor end",
+            IssueKind::OutOfOrder => "out of order",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// A problem found in the lineage graph
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineageIssue {
+    /// Kind of problem
+    pub kind: IssueKind,
+    /// Names of the links involved
+    pub links: Vec<String>,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl fmt::Display for LineageIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} [{}]",
+            self.kind,
+            self.message,
+            self.links.join(", ")
+        )
+    }
+}
+
+/// An outlet of a genealogy tree with its generation relative to the root
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeMember {
+    /// The outlet
+    pub outlet: LineageOutlet,
+    /// Generation: 0 for the root, negative for ancestors, positive for
+    /// descendants
+    pub generation: i32,
+}
+
+/// Ancestors and descendants of one outlet
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineageTree {
+    /// Outlet the tree was built for
+    pub root: u32,
+    /// Members ordered by generation, then start date
+    pub members: Vec<TreeMember>,
+    /// Links between members
+    pub links: Vec<LineageLink>,
+    /// Issues involving the links of the tree
+    pub issues: Vec<LineageIssue>,
+}
+
+/// Graph of all diachronic links of a program
+#[derive(Debug, Clone, Default)]
+pub struct LineageGraph {
+    outlets: BTreeMap<u32, LineageOutlet>,
+    links: Vec<LineageLink>,
+}
+
+impl LineageGraph {
+    /// Collect outlets and diachronic links from the IR
+    pub fn from_ir(ir: &IRProgram) -> Self {
+        let mut outlets = BTreeMap::new();
+        for outlet in ir.families.iter().flat_map(|f| &f.outlets) {
+            let Some(id) = outlet.id else { continue };
+            if outlets.contains_key(&id) {
+                continue;
+            }
+            let statuses: Vec<&IRLifecycleStatus> = outlet
+                .blocks
+                .iter()
+                .filter_map(|block| match block {
+                    IROutletBlock::Lifecycle(statuses) => Some(statuses),
+                    _ => None,
+                })
+                .flatten()
+                .collect();
+            let start = statuses
+                .iter()
+                .filter_map(|s| s.start_date.as_deref())
+                .min_by_key(|date| first_day(date))
+                .map(str::to_string);
+            let open = statuses
+                .iter()
+                .any(|s| matches!(s.end_date.as_deref(), None | Some("CURRENT")));
+            let end = if open {
+                None
+            } else {
+                statuses
+                    .iter()
+                    .filter_map(|s| s.end_date.as_deref())
+                    .max_by_key(|date| last_day(date))
+                    .map(str::to_string)
+            };
+            outlets.insert(
+                id,
+                LineageOutlet {
+                    id,
+                    name: Some(outlet.name.clone()),
+                    start,
+                    end,
+                },
+            );
+        }
+
+        let mut links = Vec::new();
+        for relationship in ir.families.iter().flat_map(|f| &f.relationships) {
+            let IRRelationship::Diachronic(link) = relationship else {
+                continue;
+            };
+            for id in [link.predecessor, link.successor] {
+                outlets.entry(id).or_insert_with(|| LineageOutlet {
+                    id,
+                    name: None,
+                    start: None,
+                    end: None,
+                });
+            }
+            links.push(LineageLink {
+                name: link.name.clone(),
+                predecessor: link.predecessor,
+                successor: link.successor,
+                relationship_type: link.relationship_type.clone(),
+                date: link.event_start_date.clone(),
+            });
+        }
+
+        LineageGraph { outlets, links }
+    }
+
+    /// All diachronic links, in declaration order
+    pub fn links(&self) -> &[LineageLink] {
+        &self.links
+    }
+
+    /// An outlet declared in or referenced by the links
+    pub fn outlet(&self, id: u32) -> Option<&LineageOutlet> {
+        self.outlets.get(&id)
+    }
+
+    fn describe(&self, id: u32) -> String {
+        self.outlets
+            .get(&id)
+            .map_or_else(|| id.to_string(), LineageOutlet::describe)
+    }
+
+    /// Report cycles and temporally impossible orderings
+    pub fn check(&self) -> Vec<LineageIssue> {
+        let mut issues: Vec<LineageIssue> = self
+            .cycles()
+            .into_iter()
+            .map(|cycle| {
+                let mut path: Vec<String> = cycle
+                    .iter()
+                    .map(|&i| self.describe(self.links[i].predecessor))
+                    .collect();
+                path.push(path[0].clone());
+                LineageIssue {
+                    kind: IssueKind::Cycle,
+                    links: cycle.iter().map(|&i| self.links[i].name.clone()).collect(),
+                    message: path.join(" -> "),
+                }
+            })
+            .collect();
+
+        for link in &self.links {
+            let Some(date) = link.date.as_deref() else {
+                continue;
+            };
+            let predecessor = &self.outlets[&link.predecessor];
+            if let Some(start) = &predecessor.start {
+                if last_day(date) < first_day(start) {
+                    issues.push(LineageIssue {
+                        kind: IssueKind::BeforePredecessorStart,
+                        links: vec![link.name.clone()],
+                        message: format!(
+                            "{} on {}, but {} started {}",
+                            link.relationship_type,
+                            date,
+                            predecessor.describe(),
+                            start
+                        ),
+                    });
+                }
+            }
+            let successor = &self.outlets[&link.successor];
+            if let Some(end) = &successor.end {
+                if first_day(date) > last_day(end) {
+                    issues.push(LineageIssue {
+                        kind: IssueKind::AfterSuccessorEnd,
+                        links: vec![link.name.clone()],
+                        message: format!(
+                            "{} on {}, but {} ended {}",
+                            link.relationship_type,
+                            date,
+                            successor.describe(),
+                            end
+                        ),
+                    });
+                }
+            }
+        }
+
+        for ending in &self.links {
+            if !ENDING_TYPES.contains(&ending.relationship_type.as_str()) {
+                continue;
+            }
+            let Some(ended) = ending.date.as_deref() else {
+                continue;
+            };
+            for incoming in self
+                .links
+                .iter()
+                .filter(|l| l.successor == ending.predecessor)
+            {
+                let Some(date) = incoming.date.as_deref() else {
+                    continue;
+                };
+                if first_day(date) > last_day(ended) {
+                    issues.push(LineageIssue {
+                        kind: IssueKind::OutOfOrder,
+                        links: vec![incoming.name.clone(), ending.name.clone()],
+                        message: format!(
+                            "{} into {} on {}, after its {} on {}",
+                            incoming.relationship_type,
+                            self.describe(ending.predecessor),
+                            date,
+                            ending.relationship_type,
+                            ended
+                        ),
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// One cycle (as link indices) per back edge found by a depth-first search
+    fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut outgoing: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+        for (index, link) in self.links.iter().enumerate() {
+            outgoing.entry(link.predecessor).or_default().push(index);
+        }
+
+        // 1: on the current path, 2: finished
+        let mut state: HashMap<u32, u8> = HashMap::new();
+        let mut cycles = Vec::new();
+        for &start in outgoing.keys() {
+            if !state.contains_key(&start) {
+                self.visit(
+                    start,
+                    &outgoing,
+                    &mut state,
+                    &mut Vec::new(),
+                    &mut Vec::new(),
+                    &mut cycles,
+                );
+            }
+        }
+        cycles
+    }
+
+    fn visit(
+        &self,
+        node: u32,
+        outgoing: &BTreeMap<u32, Vec<usize>>,
+        state: &mut HashMap<u32, u8>,
+        path_nodes: &mut Vec<u32>,
+        path_links: &mut Vec<usize>,
+        cycles: &mut Vec<Vec<usize>>,
+    ) {
+        state.insert(node, 1);
+        path_nodes.push(node);
+        for &index in outgoing.get(&node).into_iter().flatten() {
+            let next = self.links[index].successor;
+            match state.get(&next) {
+                None => {
+                    path_links.push(index);
+                    self.visit(next, outgoing, state, path_nodes, path_links, cycles);
+                    path_links.pop();
+                }
+                Some(1) => {
+                    let from = path_nodes.iter().position(|&n| n == next).unwrap_or(0);
+                    let mut cycle = path_links[from..].to_vec();
+                    cycle.push(index);
+                    cycles.push(cycle);
+                }
+                _ => {}
+            }
+        }
+        path_nodes.pop();
+        state.insert(node, 2);
+    }
+
+    /// Ancestors and descendants of an outlet; `None` if it takes part in
+    /// no diachronic link and is not declared
+    pub fn tree(&self, outlet_id: u32) -> Option<LineageTree> {
+        self.outlets.get(&outlet_id)?;
+
+        // Shortest distance upwards and downwards
+        let mut generations: HashMap<u32, i32> = HashMap::from([(outlet_id, 0)]);
+        for direction in [-1, 1] {
+            let mut queue = VecDeque::from([(outlet_id, 0)]);
+            while let Some((id, generation)) = queue.pop_front() {
+                for link in &self.links {
+                    let next = match direction {
+                        -1 if link.successor == id => link.predecessor,
+                        1 if link.predecessor == id => link.successor,
+                        _ => continue,
+                    };
+                    if let Entry::Vacant(entry) = generations.entry(next) {
+                        entry.insert(generation + direction);
+                        queue.push_back((next, generation + direction));
+                    }
+                }
+            }
+        }
+
+        let mut members: Vec<TreeMember> = generations
+            .iter()
+            .map(|(id, &generation)| TreeMember {
+                outlet: self.outlets[id].clone(),
+                generation,
+            })
+            .collect();
+        members.sort_by_key(|member| {
+            (
+                member.generation,
+                member.outlet.start.as_deref().map(first_day),
+                member.outlet.id,
+            )
+        });
+
+        let links: Vec<LineageLink> = self
+            .links
+            .iter()
+            .filter(|link| {
+                generations.contains_key(&link.predecessor)
+                    && generations.contains_key(&link.successor)
+            })
+            .cloned()
+            .collect();
+        let issues = self
+            .check()
+            .into_iter()
+            .filter(|issue| {
+                issue
+                    .links
+                    .iter()
+                    .any(|name| links.iter().any(|link| &link.name == name))
+            })
+            .collect();
+
+        Some(LineageTree {
+            root: outlet_id,
+            members,
+            links,
+            issues,
+        })
+    }
+}
+
+const BOX_WIDTH: i32 = 200;
+const BOX_HEIGHT: i32 = 50;
+const COLUMN_GAP: i32 = 40;
+const ROW_GAP: i32 = 70;
+const MARGIN: i32 = 20;
+
+impl LineageTree {
+    fn link_label(link: &LineageLink) -> String {
+        match &link.date {
+            Some(date) => format!("{} {}", link.relationship_type, date),
+            None => link.relationship_type.clone(),
+        }
+    }
+
+    /// Format the tree as a Graphviz digraph, one rank per generation
+    pub fn format_dot(&self) -> String {
+        let mut out = format!(
+            "digraph {} {{\n",
+            dot::quote(&format!("lineage_{}", self.root))
+        );
+        out.push_str("  rankdir=TB;\n  node [shape=box];\n\n");
+        for member in &self.members {
+            let outlet = &member.outlet;
+            let (title, details) = outlet.caption();
+            let label = if details.is_empty() {
+                title
+            } else {
+                format!("{}\n{}", title, details)
+            };
+            out.push_str(&format!(
+                "  {} [label={}{}];\n",
+                outlet.id,
+                dot::quote(&label),
+                if outlet.id == self.root {
+                    ", style=bold"
+                } else {
+                    ""
+                }
+            ));
+        }
+
+        let mut ranks: BTreeMap<i32, Vec<u32>> = BTreeMap::new();
+        for member in &self.members {
+            ranks
+                .entry(member.generation)
+                .or_default()
+                .push(member.outlet.id);
+        }
+        for ids in ranks.values().filter(|ids| ids.len() > 1) {
+            let ids: Vec<String> = ids.iter().map(u32::to_string).collect();
+            out.push_str(&format!("  {{ rank=same; {}; }}\n", ids.join("; ")));
+        }
+
+        if !self.links.is_empty() {
+            out.push('\n');
+        }
+        for link in &self.links {
+            out.push_str(&format!(
+                "  {} -> {} [label={}];\n",
+                link.predecessor,
+                link.successor,
+                dot::quote(&Self::link_label(link))
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Format the tree as a standalone SVG image, one row per generation
+    pub fn format_svg(&self) -> String {
+        let mut rows: BTreeMap<i32, Vec<&TreeMember>> = BTreeMap::new();
+        for member in &self.members {
+            rows.entry(member.generation).or_default().push(member);
+        }
+        let widest = rows.values().map(Vec::len).max().unwrap_or(1) as i32;
+        let width = 2 * MARGIN + widest * BOX_WIDTH + (widest - 1) * COLUMN_GAP;
+        let height = 2 * MARGIN + rows.len() as i32 * (BOX_HEIGHT + ROW_GAP) - ROW_GAP;
+
+        // Top-left corner of each box; rows are centred
+        let mut positions: HashMap<u32, (i32, i32)> = HashMap::new();
+        for (row, members) in rows.values().enumerate() {
+            let count = members.len() as i32;
+            let row_width = count * BOX_WIDTH + (count - 1) * COLUMN_GAP;
+            let left = (width - row_width) / 2;
+            for (column, member) in members.iter().enumerate() {
+                positions.insert(
+                    member.outlet.id,
+                    (
+                        left + column as i32 * (BOX_WIDTH + COLUMN_GAP),
+                        MARGIN + row as i32 * (BOX_HEIGHT + ROW_GAP),
+                    ),
+                );
+            }
+        }
+
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+            width, height
+        );
+        out.push_str("  <defs>\n    <marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\">\n      <path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"#555\"/>\n    </marker>\n  </defs>\n");
+
+        for link in &self.links {
+            let (Some(&(x1, y1)), Some(&(x2, y2))) = (
+                positions.get(&link.predecessor),
+                positions.get(&link.successor),
+            ) else {
+                continue;
+            };
+            let (x1, x2) = (x1 + BOX_WIDTH / 2, x2 + BOX_WIDTH / 2);
+            // Leave from the side facing the successor
+            let (y1, y2) = if y2 > y1 {
+                (y1 + BOX_HEIGHT, y2)
+            } else if y2 < y1 {
+                (y1, y2 + BOX_HEIGHT)
+            } else {
+                (y1 + BOX_HEIGHT / 2, y2 + BOX_HEIGHT / 2)
+            };
+            out.push_str(&format!(
+                "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#555\" marker-end=\"url(#arrow)\"/>\n",
+                x1, y1, x2, y2
+            ));
+            out.push_str(&format!(
+                "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"#555\" font-size=\"10\">{}</text>\n",
+                (x1 + x2) / 2,
+                (y1 + y2) / 2,
+                xml::escape(&Self::link_label(link))
+            ));
+        }
+
+        for member in &self.members {
+            let outlet = &member.outlet;
+            let (x, y) = positions[&outlet.id];
+            let root = outlet.id == self.root;
+            out.push_str(&format!(
+                "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"6\" fill=\"{}\" stroke=\"#333\" stroke-width=\"{}\"/>\n",
+                x,
+                y,
+                BOX_WIDTH,
+                BOX_HEIGHT,
+                if root { "#e8f0fe" } else { "#f7f7f7" },
+                if root { 2 } else { 1 }
+            ));
+            let (title, details) = outlet.caption();
+            out.push_str(&format!(
+                "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-weight=\"bold\">{}</text>\n",
+                x + BOX_WIDTH / 2,
+                y + 21,
+                xml::escape(&title)
+            ));
+            out.push_str(&format!(
+                "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+                x + BOX_WIDTH / 2,
+                y + 38,
+                xml::escape(&details)
+            ));
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    /// Format the tree as a JSON object
+    pub fn format_json(&self) -> String {
+        let optional = |value: Option<&str>| value.map_or("null".to_string(), json::quote);
+        let members: Vec<String> = self
+            .members
+            .iter()
+            .map(|member| {
+                format!(
+                    "\n    {{ \"id\": {}, \"name\": {}, \"start\": {}, \"end\": {}, \"generation\": {} }}",
+                    member.outlet.id,
+                    optional(member.outlet.name.as_deref()),
+                    optional(member.outlet.start.as_deref()),
+                    optional(member.outlet.end.as_deref()),
+                    member.generation
+                )
+            })
+            .collect();
+        let links: Vec<String> = self
+            .links
+            .iter()
+            .map(|link| {
+                format!(
+                    "\n    {{ \"name\": {}, \"predecessor\": {}, \"successor\": {}, \"type\": {}, \"date\": {} }}",
+                    json::quote(&link.name),
+                    link.predecessor,
+                    link.successor,
+                    json::quote(&link.relationship_type),
+                    optional(link.date.as_deref())
+                )
+            })
+            .collect();
+        let issues: Vec<String> = self
+            .issues
+            .iter()
+            .map(|issue| {
+                let names: Vec<String> = issue.links.iter().map(|n| json::quote(n)).collect();
+                format!(
+                    "\n    {{ \"kind\": {}, \"links\": [{}], \"message\": {} }}",
+                    json::quote(&issue.kind.to_string()),
+                    names.join(", "),
+                    json::quote(&issue.message)
+                )
+            })
+            .collect();
+        let array = |items: &[String]| {
+            if items.is_empty() {
+                "[]".to_string()
+            } else {
+                format!("[{}\n  ]", items.join(","))
+            }
+        };
+        format!(
+            "{{\n  \"outlet_id\": {},\n  \"outlets\": {},\n  \"links\": {},\n  \"issues\": {}\n}}",
+            self.root,
+            array(&members),
+            array(&links),
+            array(&issues)
+        )
+    }
+}
@@ -0,0 +1,27 @@
+//! Analyses computed over the intermediate representation
+//!
+//! - [`ownership`]: beneficial owners and ultimate controlling entities
+//! - [`entity_resolution`]: matching curated against crawled companies
+//! - [`diff`]: entity-level differences between two datasets
+//! - [`history`]: chronological timeline of a single outlet
+//! - [`lineage`]: genealogies of outlets from diachronic links
+//! - [`query`]: selecting outlets with a small query language
+
+pub mod diff;
+pub mod entity_resolution;
+pub mod history;
+pub mod lineage;
+pub mod ownership;
+pub mod query;
+
+pub use diff::{ChangeKind, EntityChange, EntityKey, EntityKind, FieldChange, ProgramDiff};
+pub use entity_resolution::{EntityMatcher, MatchCandidate, MatchRecord};
+pub use history::{EntryKind, HistoryEntry, OutletHistory};
+pub use lineage::{
+    IssueKind, LineageGraph, LineageIssue, LineageLink, LineageOutlet, LineageTree, TreeMember,
+};
+pub use ownership::{
+    BeneficialOwnerSnapshot, ControlBasis, OwnershipGraph, OwnershipPath, OwnershipReport,
+    OwnershipStake, PartyKind, PartyRef, UltimateEntitySnapshot,
+};
+pub use query::{Query, QueryResult, Value};
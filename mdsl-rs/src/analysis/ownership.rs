@@ -0,0 +1,793 @@
+//! Beneficial ownership and ultimate controlling entity computation
+//!
+//! Ownership stakes are collected from the IR (EVENT `stake_after` values and
+//! OUTLET_PARTY_LINK declarations) and, optionally, from the crawled
+//! `nd_ownership.csv` table. For a given party and date the graph is walked
+//! upwards, multiplying shares along every acyclic path, to produce
+//! BENEFICIAL_OWNER_SNAPSHOT and ULTIMATE_ENTITY_SNAPSHOT records.
+
+use crate::error::{Error, Result};
+use crate::ir::nodes::*;
+use crate::utils::csv::CsvTable;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Version tag written into every snapshot record
+pub const CALC_VERSION: &str = "mdsl-ownership-1";
+
+/// Default threshold (in percent) for beneficial ownership and control
+pub const DEFAULT_THRESHOLD_PCT: f64 = 25.0;
+
+/// Maximum length of an ownership chain that is followed
+const MAX_CHAIN_DEPTH: usize = 32;
+
+/// Event entity roles that denote the owned side of a transaction
+const OWNED_ROLES: &[&str] = &[
+    "target",
+    "investee",
+    "startup",
+    "resulting_entity",
+    "joint_venture",
+    "subsidiary",
+];
+
+/// Kind of party taking part in an ownership relation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PartyKind {
+    /// Media outlet
+    Outlet,
+    /// Company (legal entity)
+    Company,
+    /// Natural person
+    Person,
+}
+
+impl fmt::Display for PartyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartyKind::Outlet => write!(f, "outlet"),
+            PartyKind::Company => write!(f, "company"),
+            PartyKind::Person => write!(f, "person"),
+        }
+    }
+}
+
+/// Reference to a party in the ownership graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PartyRef {
+    /// Party kind
+    pub kind: PartyKind,
+    /// Party ID (outlet, company or person ID)
+    pub id: u64,
+}
+
+impl PartyRef {
+    /// Reference an outlet
+    pub fn outlet(id: u64) -> Self {
+        Self {
+            kind: PartyKind::Outlet,
+            id,
+        }
+    }
+
+    /// Reference a company
+    pub fn company(id: u64) -> Self {
+        Self {
+            kind: PartyKind::Company,
+            id,
+        }
+    }
+
+    /// Reference a person
+    pub fn person(id: u64) -> Self {
+        Self {
+            kind: PartyKind::Person,
+            id,
+        }
+    }
+}
+
+impl fmt::Display for PartyRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.kind, self.id)
+    }
+}
+
+/// A single (direct) ownership stake with its validity period
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnershipStake {
+    /// Holder of the stake
+    pub owner: PartyRef,
+    /// Party being held
+    pub owned: PartyRef,
+    /// Share in percent (0-100)
+    pub share_pct: f64,
+    /// First day the stake is valid (inclusive, `None` = since always)
+    pub valid_from: Option<String>,
+    /// Day the stake ended (exclusive, `None` = still valid)
+    pub valid_to: Option<String>,
+    /// Where the stake was taken from
+    pub source: String,
+}
+
+impl OwnershipStake {
+    /// Check whether the stake is valid at the given date (`CURRENT` = open stakes only)
+    pub fn is_active_at(&self, date: &str) -> bool {
+        if date.eq_ignore_ascii_case("CURRENT") {
+            return self.valid_to.is_none();
+        }
+        let started = self.valid_from.as_deref().is_none_or(|from| from <= date);
+        let not_ended = self.valid_to.as_deref().is_none_or(|to| date < to);
+        started && not_ended
+    }
+}
+
+/// Basis on which a party owns or controls another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlBasis {
+    /// Cash-flow rights above the threshold
+    Equity,
+    /// Control (majority chain) above the threshold
+    Control,
+    /// Both equity and control
+    Both,
+}
+
+impl ControlBasis {
+    /// Numeric code used in the mart tables (1=Equity 2=Control 3=Both)
+    pub fn code(&self) -> u8 {
+        match self {
+            ControlBasis::Equity => 1,
+            ControlBasis::Control => 2,
+            ControlBasis::Both => 3,
+        }
+    }
+
+    fn from_flags(equity: bool, control: bool) -> Option<Self> {
+        match (equity, control) {
+            (true, true) => Some(ControlBasis::Both),
+            (true, false) => Some(ControlBasis::Equity),
+            (false, true) => Some(ControlBasis::Control),
+            (false, false) => None,
+        }
+    }
+}
+
+/// An indirect ownership chain from a holder down to the analysed party
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnershipPath {
+    /// Parties along the chain, starting with the holder and ending with the analysed party
+    pub parties: Vec<PartyRef>,
+    /// Effective cash-flow share (product of all shares along the chain)
+    pub cashflow_pct: f64,
+    /// Effective voting share (majority stakes count as full control)
+    pub votes_pct: f64,
+}
+
+impl OwnershipPath {
+    /// Holder at the top of this chain
+    pub fn holder(&self) -> PartyRef {
+        self.parties[0]
+    }
+}
+
+/// BENEFICIAL_OWNER_SNAPSHOT record
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeneficialOwnerSnapshot {
+    /// Party being owned
+    pub owned: PartyRef,
+    /// Beneficial owner (natural person), `None` when the fallback applies
+    pub bo_person_id: Option<u64>,
+    /// Reference date
+    pub as_of_date: String,
+    /// Effective cash-flow rights in percent
+    pub bo_cashflow_pct: f64,
+    /// Effective voting rights in percent
+    pub bo_votes_pct: f64,
+    /// Basis of beneficial ownership
+    pub bo_basis: Option<ControlBasis>,
+    /// Number of ownership chains contributing
+    pub path_count: usize,
+    /// Calculation version
+    pub calc_version: String,
+    /// Reason why no beneficial owner could be determined
+    pub fallback_reason: Option<String>,
+    /// Human-readable explanation
+    pub explanation: String,
+}
+
+/// ULTIMATE_ENTITY_SNAPSHOT record
+#[derive(Debug, Clone, PartialEq)]
+pub struct UltimateEntitySnapshot {
+    /// Party being owned
+    pub owned: PartyRef,
+    /// Ultimate controlling entity (company)
+    pub uce_entity_id: u64,
+    /// Reference date
+    pub as_of_date: String,
+    /// Basis of control
+    pub control_basis: ControlBasis,
+    /// Number of ownership chains contributing
+    pub path_count: usize,
+    /// Calculation version
+    pub calc_version: String,
+}
+
+/// Result of analysing one party at one date
+#[derive(Debug, Clone)]
+pub struct OwnershipReport {
+    /// Analysed party
+    pub target: PartyRef,
+    /// Reference date
+    pub as_of_date: String,
+    /// Threshold in percent
+    pub threshold_pct: f64,
+    /// All acyclic ownership chains ending at the target
+    pub paths: Vec<OwnershipPath>,
+    /// Beneficial owner records (at least one, possibly a fallback record)
+    pub beneficial_owners: Vec<BeneficialOwnerSnapshot>,
+    /// Ultimate controlling entity records
+    pub ultimate_entities: Vec<UltimateEntitySnapshot>,
+    /// Whether a cycle was encountered (and cut) while walking the graph
+    pub cycle_detected: bool,
+}
+
+/// Effective holding of one party, aggregated over all chains
+#[derive(Debug, Clone, Copy, Default)]
+struct Holding {
+    cashflow_pct: f64,
+    votes_pct: f64,
+    path_count: usize,
+}
+
+/// Ownership graph built from the IR and external ownership tables
+#[derive(Debug, Clone, Default)]
+pub struct OwnershipGraph {
+    /// Direct ownership stakes
+    stakes: Vec<OwnershipStake>,
+    /// Known party names
+    names: HashMap<PartyRef, String>,
+}
+
+impl OwnershipGraph {
+    /// Create an empty ownership graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the ownership graph from EVENT stakes and OUTLET_PARTY_LINK declarations
+    pub fn from_ir(ir: &IRProgram) -> Self {
+        let mut graph = Self::new();
+
+        let mut outlet_ids = HashSet::new();
+        for family in &ir.families {
+            for outlet in &family.outlets {
+                if let Some(id) = outlet.id {
+                    outlet_ids.insert(id);
+                    graph
+                        .names
+                        .insert(PartyRef::outlet(id as u64), outlet.name.clone());
+                }
+            }
+        }
+
+        for company in &ir.companies {
+            graph
+                .names
+                .insert(PartyRef::company(company.id as u64), company.name.clone());
+        }
+
+        for person in &ir.persons {
+            graph
+                .names
+                .insert(PartyRef::person(person.id as u64), person.name.clone());
+        }
+
+        // Declared ownership relations (e.g. imported from the company register)
+        for ownership in &ir.ownerships {
+            let (Some(owner), Some(share_pct)) = (ownership.owner, ownership.share_pct) else {
+                continue;
+            };
+            let owner = match owner {
+                IROwner::Company(id) => PartyRef::company(id as u64),
+                IROwner::Person(id) => PartyRef::person(id as u64),
+            };
+            graph.add_stake(OwnershipStake {
+                owner,
+                owned: PartyRef::company(ownership.owned_company_id as u64),
+                share_pct,
+                valid_from: ownership.period_start.clone().filter(|d| d != "CURRENT"),
+                valid_to: ownership.period_end.clone().filter(|d| d != "CURRENT"),
+                source: ownership
+                    .source_url
+                    .clone()
+                    .unwrap_or_else(|| format!("ownership:{}", ownership.name)),
+            });
+        }
+
+        // The company running an outlet controls it for the period of the link
+        for link in &ir.outlet_party_links {
+            graph.add_stake(OwnershipStake {
+                owner: PartyRef::company(link.party_id as u64),
+                owned: PartyRef::outlet(link.outlet_id as u64),
+                share_pct: 100.0,
+                valid_from: link.period_start.clone().filter(|d| d != "CURRENT"),
+                valid_to: link.period_end.clone().filter(|d| d != "CURRENT"),
+                source: format!("outlet_party_link:{}", link.name),
+            });
+        }
+
+        // Replay completed events in date order, undated ones first; a later
+        // stake replaces an earlier one
+        let mut events: Vec<&IREvent> = ir
+            .events
+            .iter()
+            .filter(|event| {
+                event
+                    .status
+                    .as_deref()
+                    .is_none_or(|status| status == "completed")
+            })
+            .collect();
+        events.sort_by(|a, b| a.date.cmp(&b.date));
+
+        for event in events {
+            graph.apply_event(event, &outlet_ids);
+        }
+
+        graph
+    }
+
+    /// Add a direct ownership stake
+    pub fn add_stake(&mut self, stake: OwnershipStake) {
+        self.stakes.push(stake);
+    }
+
+    /// Register a display name for a party
+    pub fn set_name(&mut self, party: PartyRef, name: &str) {
+        self.names.insert(party, name.to_string());
+    }
+
+    /// Display name of a party, if known
+    pub fn name_of(&self, party: &PartyRef) -> Option<&str> {
+        self.names.get(party).map(|name| name.as_str())
+    }
+
+    /// All direct stakes
+    pub fn stakes(&self) -> &[OwnershipStake] {
+        &self.stakes
+    }
+
+    /// Load share percentages from the crawled `nd_ownership.csv` table
+    ///
+    /// Returns the number of stakes added. Rows without an owner, an owned
+    /// company or a share percentage are skipped.
+    pub fn load_nd_ownership_csv(&mut self, content: &str) -> Result<usize> {
+        let table = CsvTable::parse(content);
+        for column in ["owner_company_id", "owner_person_id", "owned_company_id", "share_pct"] {
+            if !table.has_column(column) {
+                return Err(Error::Io(format!(
+                    "nd_ownership table is missing column '{}'",
+                    column
+                )));
+            }
+        }
+
+        let mut added = 0;
+        for row in &table.rows {
+            let owner = match (
+                table.get(row, "owner_company_id").and_then(|v| v.parse().ok()),
+                table.get(row, "owner_person_id").and_then(|v| v.parse().ok()),
+            ) {
+                (Some(id), _) => PartyRef::company(id),
+                (None, Some(id)) => PartyRef::person(id),
+                (None, None) => continue,
+            };
+            let owned = match table.get(row, "owned_company_id").and_then(|v| v.parse().ok()) {
+                Some(id) => PartyRef::company(id),
+                None => continue,
+            };
+            let share_pct = match table.get(row, "share_pct").and_then(|v| v.parse().ok()) {
+                Some(share) => share,
+                None => continue,
+            };
+
+            // Non-current stakes ended at the latest when the register was scraped
+            let scraped_on = table
+                .get(row, "scraped_at")
+                .map(|value| value.chars().take(10).collect::<String>());
+            let is_current = !matches!(
+                table.get(row, "is_current"),
+                Some("f") | Some("false") | Some("0")
+            );
+
+            self.add_stake(OwnershipStake {
+                owner,
+                owned,
+                share_pct,
+                valid_from: None,
+                valid_to: if is_current { None } else { scraped_on },
+                source: table
+                    .get(row, "source_url")
+                    .unwrap_or("nd_ownership")
+                    .to_string(),
+            });
+            added += 1;
+        }
+
+        Ok(added)
+    }
+
+    /// Stakes valid at the given date
+    pub fn active_stakes(&self, date: &str) -> Vec<&OwnershipStake> {
+        self.stakes
+            .iter()
+            .filter(|stake| stake.is_active_at(date))
+            .collect()
+    }
+
+    /// Enumerate all acyclic ownership chains ending at `target`
+    ///
+    /// Returns the chains and whether a cycle had to be cut.
+    pub fn ownership_paths(&self, target: PartyRef, date: &str) -> (Vec<OwnershipPath>, bool) {
+        let active = self.active_stakes(date);
+        let mut owners_of: HashMap<PartyRef, Vec<&OwnershipStake>> = HashMap::new();
+        for stake in active {
+            owners_of.entry(stake.owned).or_default().push(stake);
+        }
+
+        let mut paths = Vec::new();
+        let mut cycle_detected = false;
+        let mut chain = vec![target];
+        Self::walk_up(
+            &owners_of,
+            &mut chain,
+            100.0,
+            100.0,
+            &mut paths,
+            &mut cycle_detected,
+        );
+
+        (paths, cycle_detected)
+    }
+
+    fn walk_up(
+        owners_of: &HashMap<PartyRef, Vec<&OwnershipStake>>,
+        chain: &mut Vec<PartyRef>,
+        cashflow_pct: f64,
+        votes_pct: f64,
+        paths: &mut Vec<OwnershipPath>,
+        cycle_detected: &mut bool,
+    ) {
+        let current = *chain.last().expect("chain is never empty");
+        let Some(stakes) = owners_of.get(&current) else {
+            return;
+        };
+
+        for stake in stakes {
+            if chain.contains(&stake.owner) {
+                *cycle_detected = true;
+                continue;
+            }
+            if chain.len() > MAX_CHAIN_DEPTH {
+                continue;
+            }
+
+            let cashflow = cashflow_pct * stake.share_pct / 100.0;
+            // A majority stake passes on full control of the held party
+            let votes = if stake.share_pct > 50.0 {
+                votes_pct
+            } else {
+                votes_pct * stake.share_pct / 100.0
+            };
+
+            chain.push(stake.owner);
+            let mut parties = chain.clone();
+            parties.reverse();
+            paths.push(OwnershipPath {
+                parties,
+                cashflow_pct: cashflow,
+                votes_pct: votes,
+            });
+            Self::walk_up(owners_of, chain, cashflow, votes, paths, cycle_detected);
+            chain.pop();
+        }
+    }
+
+    /// Compute beneficial owners and ultimate controlling entities of `target` at `date`
+    pub fn analyze(&self, target: PartyRef, date: &str, threshold_pct: f64) -> OwnershipReport {
+        let (paths, cycle_detected) = self.ownership_paths(target, date);
+
+        let mut holdings: HashMap<PartyRef, Holding> = HashMap::new();
+        for path in &paths {
+            let holding = holdings.entry(path.holder()).or_default();
+            holding.cashflow_pct = (holding.cashflow_pct + path.cashflow_pct).min(100.0);
+            holding.votes_pct = (holding.votes_pct + path.votes_pct).min(100.0);
+            holding.path_count += 1;
+        }
+
+        let mut holders: Vec<(PartyRef, Holding)> = holdings.into_iter().collect();
+        holders.sort_by(|a, b| {
+            b.1.cashflow_pct
+                .partial_cmp(&a.1.cashflow_pct)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.0.cmp(&b.0))
+        });
+
+        let beneficial_owners = self.beneficial_owners(target, date, threshold_pct, &holders);
+        let ultimate_entities = self.ultimate_entities(target, date, threshold_pct, &holders);
+
+        OwnershipReport {
+            target,
+            as_of_date: date.to_string(),
+            threshold_pct,
+            paths,
+            beneficial_owners,
+            ultimate_entities,
+            cycle_detected,
+        }
+    }
+
+    fn beneficial_owners(
+        &self,
+        target: PartyRef,
+        date: &str,
+        threshold_pct: f64,
+        holders: &[(PartyRef, Holding)],
+    ) -> Vec<BeneficialOwnerSnapshot> {
+        let mut records = Vec::new();
+
+        for (party, holding) in holders {
+            if party.kind != PartyKind::Person {
+                continue;
+            }
+            let basis = ControlBasis::from_flags(
+                holding.cashflow_pct >= threshold_pct,
+                holding.votes_pct >= threshold_pct,
+            );
+            if let Some(basis) = basis {
+                records.push(BeneficialOwnerSnapshot {
+                    owned: target,
+                    bo_person_id: Some(party.id),
+                    as_of_date: date.to_string(),
+                    bo_cashflow_pct: holding.cashflow_pct,
+                    bo_votes_pct: holding.votes_pct,
+                    bo_basis: Some(basis),
+                    path_count: holding.path_count,
+                    calc_version: CALC_VERSION.to_string(),
+                    fallback_reason: None,
+                    explanation: format!(
+                        "{} holds {:.3}% cash-flow and {:.3}% voting rights via {} chain(s)",
+                        self.describe(party),
+                        holding.cashflow_pct,
+                        holding.votes_pct,
+                        holding.path_count
+                    ),
+                });
+            }
+        }
+
+        if records.is_empty() {
+            let explanation = match holders.first() {
+                Some((party, holding)) => format!(
+                    "Largest holder is {} with {:.3}% cash-flow rights",
+                    self.describe(party),
+                    holding.cashflow_pct
+                ),
+                None => "No ownership stakes recorded".to_string(),
+            };
+            records.push(BeneficialOwnerSnapshot {
+                owned: target,
+                bo_person_id: None,
+                as_of_date: date.to_string(),
+                bo_cashflow_pct: 0.0,
+                bo_votes_pct: 0.0,
+                bo_basis: None,
+                path_count: 0,
+                calc_version: CALC_VERSION.to_string(),
+                fallback_reason: Some(format!(
+                    "No natural person reaches the {}% threshold",
+                    threshold_pct
+                )),
+                explanation,
+            });
+        }
+
+        records
+    }
+
+    fn ultimate_entities(
+        &self,
+        target: PartyRef,
+        date: &str,
+        threshold_pct: f64,
+        holders: &[(PartyRef, Holding)],
+    ) -> Vec<UltimateEntitySnapshot> {
+        let active = self.active_stakes(date);
+        // A company is not "ultimate" if another company holds at least the threshold in it
+        let company_held: HashSet<PartyRef> = active
+            .iter()
+            .filter(|stake| {
+                stake.owner.kind == PartyKind::Company && stake.share_pct >= threshold_pct
+            })
+            .map(|stake| stake.owned)
+            .collect();
+
+        let mut records: Vec<UltimateEntitySnapshot> = holders
+            .iter()
+            .filter(|(party, _)| {
+                party.kind == PartyKind::Company && !company_held.contains(party)
+            })
+            .filter_map(|(party, holding)| {
+                ControlBasis::from_flags(
+                    holding.cashflow_pct >= threshold_pct,
+                    holding.votes_pct >= threshold_pct,
+                )
+                .map(|basis| UltimateEntitySnapshot {
+                    owned: target,
+                    uce_entity_id: party.id,
+                    as_of_date: date.to_string(),
+                    control_basis: basis,
+                    path_count: holding.path_count,
+                    calc_version: CALC_VERSION.to_string(),
+                })
+            })
+            .collect();
+
+        // An independent company is its own ultimate controlling entity
+        if records.is_empty()
+            && holders.is_empty()
+            && target.kind == PartyKind::Company
+            && self.knows(&target)
+        {
+            records.push(UltimateEntitySnapshot {
+                owned: target,
+                uce_entity_id: target.id,
+                as_of_date: date.to_string(),
+                control_basis: ControlBasis::Both,
+                path_count: 0,
+                calc_version: CALC_VERSION.to_string(),
+            });
+        }
+
+        records
+    }
+
+    /// Replay the stake changes recorded in an event
+    fn apply_event(&mut self, event: &IREvent, outlet_ids: &HashSet<u32>) {
+        let date = event.date.clone().filter(|d| d != "CURRENT");
+        let party_of = |entity: &IREventEntity| {
+            if let Some(person) = entity.person {
+                PartyRef::person(person as u64)
+            } else if outlet_ids.contains(&entity.id) {
+                PartyRef::outlet(entity.id as u64)
+            } else {
+                PartyRef::company(entity.id as u64)
+            }
+        };
+
+        let (owned, holders): (Vec<&IREventEntity>, Vec<&IREventEntity>) = event
+            .entities
+            .iter()
+            .partition(|entity| OWNED_ROLES.contains(&entity.role.as_str()));
+
+        for entity in &event.entities {
+            self.names
+                .entry(party_of(entity))
+                .or_insert_with(|| entity.name.clone());
+        }
+
+        for owned_entity in &owned {
+            let owned_party = party_of(owned_entity);
+            for holder in &holders {
+                let Some(stake_after) = holder.stake_after else {
+                    continue;
+                };
+                let owner = party_of(holder);
+
+                // Close the previous stake of this holder; an undated event
+                // cannot close it at a date, so its stake replaces the open one
+                let open = |stake: &OwnershipStake| {
+                    stake.owner == owner && stake.owned == owned_party && stake.valid_to.is_none()
+                };
+                match &date {
+                    Some(_) => {
+                        for stake in self.stakes.iter_mut().filter(|stake| open(stake)) {
+                            stake.valid_to = date.clone();
+                        }
+                    }
+                    None => self.stakes.retain(|stake| !open(stake)),
+                }
+
+                if stake_after > 0.0 {
+                    self.add_stake(OwnershipStake {
+                        owner,
+                        owned: owned_party,
+                        share_pct: stake_after,
+                        valid_from: date.clone(),
+                        valid_to: None,
+                        source: format!("event:{}", event.name),
+                    });
+                }
+            }
+        }
+    }
+
+    fn knows(&self, party: &PartyRef) -> bool {
+        self.names.contains_key(party)
+            || self
+                .stakes
+                .iter()
+                .any(|stake| stake.owner == *party || stake.owned == *party)
+    }
+
+    fn describe(&self, party: &PartyRef) -> String {
+        match self.name_of(party) {
+            Some(name) => format!("{} ({})", name, party),
+            None => party.to_string(),
+        }
+    }
+}
+
+impl OwnershipReport {
+    /// Format the report as human-readable text
+    pub fn format_text(&self, graph: &OwnershipGraph) -> String {
+        let mut out = String::new();
+        let describe = |party: &PartyRef| graph.describe(party);
+
+        out.push_str(&format!(
+            "Ownership of {} as of {} (threshold {}%)\n",
+            describe(&self.target),
+            self.as_of_date,
+            self.threshold_pct
+        ));
+        if self.cycle_detected {
+            out.push_str("Note: circular holdings were detected and cut\n");
+        }
+
+        out.push_str("\nOwnership chains:\n");
+        if self.paths.is_empty() {
+            out.push_str("  (none)\n");
+        }
+        for path in &self.paths {
+            let chain: Vec<String> = path.parties.iter().map(describe).collect();
+            out.push_str(&format!(
+                "  {} = {:.3}% cash-flow, {:.3}% votes\n",
+                chain.join(" -> "),
+                path.cashflow_pct,
+                path.votes_pct
+            ));
+        }
+
+        out.push_str("\nBeneficial owners:\n");
+        for bo in &self.beneficial_owners {
+            match bo.bo_person_id {
+                Some(id) => out.push_str(&format!(
+                    "  {}: {:.3}% cash-flow, {:.3}% votes\n",
+                    describe(&PartyRef::person(id)),
+                    bo.bo_cashflow_pct,
+                    bo.bo_votes_pct
+                )),
+                None => out.push_str(&format!(
+                    "  none - {} ({})\n",
+                    bo.fallback_reason.as_deref().unwrap_or("unknown"),
+                    bo.explanation
+                )),
+            }
+        }
+
+        out.push_str("\nUltimate controlling entities:\n");
+        if self.ultimate_entities.is_empty() {
+            out.push_str("  (none)\n");
+        }
+        for uce in &self.ultimate_entities {
+            out.push_str(&format!(
+                "  {} (basis {:?})\n",
+                describe(&PartyRef::company(uce.uce_entity_id)),
+                uce.control_basis
+            ));
+        }
+
+        out
+    }
+}
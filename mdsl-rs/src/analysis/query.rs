@@ -0,0 +1,977 @@
+//! Queries over the compiled model
+//!
+//! A small query language selects outlets of an [`IRProgram`] without loading
+//! the generated SQL into a database:
+//!
+//! ```text
+//! sector = 11 and location = "Wien" and active in 1985
+//! circulation > 100000 in 2021 order by circulation in 2021 desc
+//! linked via succession from 100101 select id, name, start, end
+//! language ~ "deutsch" and not linked via umbrella with (name ~ "ORF") limit 10
+//! ```
+//!
+//! A query is an optional condition followed by optional `select`, `order by`
+//! and `limit` clauses. Conditions combine with `and`, `or`, `not` and
+//! parentheses:
+//!
+//! - `FIELD OP VALUE` compares an outlet field. Fields are `id`, `name`,
+//!   `family`, `status`, `start` and `end` (of the lifecycle), or a field of
+//!   the identity, characteristics or metadata block, optionally prefixed with
+//!   the block (`characteristics.sector`). Operators are `=`, `!=`, `<`, `<=`,
+//!   `>`, `>=` and `~` (contains, case-insensitive).
+//! - `METRIC OP VALUE in YEAR` compares a metric of a DATA block.
+//! - `active`, `active in YEAR`, `active on DATE` and
+//!   `active between DATE and DATE` test for an `active` lifecycle status
+//!   overlapping the period (now, for a bare `active`).
+//! - `linked [via TYPE, ...] [to|from|with] [ID | (CONDITION)]` follows
+//!   relationships: `to` from the outlet as predecessor or first outlet of a
+//!   link, `from` the other way, `with` (the default) either way.
+//!
+//! Without a `select`, the result shows ID, name and family plus every field
+//! and metric the condition compares.
+
+use crate::error::{Error, ParserError, Result, SourcePosition};
+use crate::ir::nodes::*;
+use crate::utils::csv::write_record;
+use crate::utils::dates::{first_day, last_day};
+use crate::utils::json;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A field value of a query result
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// Field not set
+    Null,
+    /// Boolean
+    Bool(bool),
+    /// Number
+    Number(f64),
+    /// Text, dates included
+    Text(String),
+}
+
+impl Value {
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => Some(*n),
+            Value::Text(text) => text.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn to_json(&self) -> String {
+        match self {
+            Value::Null => "null".to_string(),
+            Value::Bool(b) => b.to_string(),
+            Value::Number(n) => json::number(*n),
+            Value::Text(text) => json::quote(text),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => Ok(()),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Number(n) => write!(f, "{}", n),
+            Value::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// A parsed query
+#[derive(Debug, Clone)]
+pub struct Query {
+    condition: Option<Condition>,
+    columns: Vec<Column>,
+    order: Option<(Column, bool)>,
+    limit: Option<usize>,
+}
+
+/// Outlets matched by a query
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryResult {
+    /// Column names
+    pub columns: Vec<String>,
+    /// One row per outlet, in column order
+    pub rows: Vec<Vec<Value>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Column {
+    Field(String),
+    Metric { name: String, year: u32 },
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Column::Field(name) => write!(f, "{}", name),
+            Column::Metric { name, year } => write!(f, "{} {}", name, year),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    To,
+    From,
+    With,
+}
+
+#[derive(Debug, Clone)]
+enum Condition {
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+    Compare {
+        column: Column,
+        op: CompareOp,
+        value: Value,
+    },
+    /// Active at some point between two dates; `None` for now
+    Active(Option<(String, String)>),
+    Linked {
+        link_types: Vec<String>,
+        direction: Direction,
+        target: Option<Target>,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum Target {
+    Id(u32),
+    Matching(Box<Condition>),
+}
+
+impl Query {
+    /// Parse a query
+    pub fn parse(text: &str) -> Result<Self> {
+        let tokens = tokenize(text)?;
+        let mut parser = QueryParser {
+            tokens,
+            current: 0,
+            end: text.len(),
+        };
+        parser.query()
+    }
+
+    /// Run the query over a program
+    pub fn run(&self, program: &IRProgram) -> QueryResult {
+        let model = Model::new(program);
+        let matched = match &self.condition {
+            Some(condition) => model.matching(condition),
+            None => vec![true; model.outlets.len()],
+        };
+
+        let columns = if self.columns.is_empty() {
+            let mut columns: Vec<Column> = ["id", "name", "family"]
+                .iter()
+                .map(|name| Column::Field(name.to_string()))
+                .collect();
+            if let Some(condition) = &self.condition {
+                compared_columns(condition, &mut columns);
+            }
+            if let Some((column, _)) = &self.order {
+                if !columns.contains(column) {
+                    columns.push(column.clone());
+                }
+            }
+            columns
+        } else {
+            self.columns.clone()
+        };
+
+        let mut rows: Vec<usize> = (0..model.outlets.len()).filter(|&i| matched[i]).collect();
+        if let Some((column, descending)) = &self.order {
+            rows.sort_by(|&a, &b| {
+                let ordering = compare_values(&model.value(a, column), &model.value(b, column));
+                // Unset values go last in either direction
+                match (model.value(a, column), model.value(b, column)) {
+                    (Value::Null, Value::Null) => Ordering::Equal,
+                    (Value::Null, _) => Ordering::Greater,
+                    (_, Value::Null) => Ordering::Less,
+                    _ if *descending => ordering.reverse(),
+                    _ => ordering,
+                }
+            });
+        }
+        if let Some(limit) = self.limit {
+            rows.truncate(limit);
+        }
+
+        QueryResult {
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+            rows: rows
+                .into_iter()
+                .map(|row| {
+                    columns
+                        .iter()
+                        .map(|column| model.value(row, column))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+impl QueryResult {
+    /// Format the result as an aligned text table
+    pub fn format_table(&self) -> String {
+        let cells: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|value| value.to_string()).collect())
+            .collect();
+        let widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                cells
+                    .iter()
+                    .map(|row| row[i].chars().count())
+                    .chain([column.chars().count()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let line = |values: &[String]| {
+            let padded: Vec<String> = values
+                .iter()
+                .zip(&widths)
+                .map(|(value, width)| {
+                    let padding = width - value.chars().count();
+                    format!("{}{}", value, " ".repeat(padding))
+                })
+                .collect();
+            format!("{}\n", padded.join("  ").trim_end())
+        };
+
+        let mut out = line(&self.columns);
+        let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+        out.push_str(&line(&rule));
+        for row in &cells {
+            out.push_str(&line(row));
+        }
+        out.push_str(&format!(
+            "({} outlet{})\n",
+            self.rows.len(),
+            if self.rows.len() == 1 { "" } else { "s" }
+        ));
+        out
+    }
+
+    /// Format the result as a JSON array of objects
+    pub fn format_json(&self) -> String {
+        let mut json = String::from("[");
+        for (i, row) in self.rows.iter().enumerate() {
+            let fields: Vec<String> = self
+                .columns
+                .iter()
+                .zip(row)
+                .map(|(column, value)| format!("{}: {}", json::quote(column), value.to_json()))
+                .collect();
+            json.push_str(if i == 0 { "\n" } else { ",\n" });
+            json.push_str(&format!("  {{ {} }}", fields.join(", ")));
+        }
+        if !self.rows.is_empty() {
+            json.push('\n');
+        }
+        json.push_str("]\n");
+        json
+    }
+
+    /// Format the result as CSV with a header row
+    pub fn format_csv(&self) -> String {
+        let mut csv = write_record(&self.columns);
+        for row in &self.rows {
+            let fields: Vec<String> = row.iter().map(|value| value.to_string()).collect();
+            csv.push_str(&write_record(&fields));
+        }
+        csv
+    }
+}
+
+/// Add the columns compared by a condition, skipping those already present
+fn compared_columns(condition: &Condition, columns: &mut Vec<Column>) {
+    match condition {
+        Condition::And(a, b) | Condition::Or(a, b) => {
+            compared_columns(a, columns);
+            compared_columns(b, columns);
+        }
+        Condition::Not(inner) => compared_columns(inner, columns),
+        Condition::Compare { column, .. } if !columns.contains(column) => {
+            columns.push(column.clone())
+        }
+        _ => {}
+    }
+}
+
+/// Program data indexed for evaluation
+struct Model<'a> {
+    /// Outlets with the name of their family, in declaration order
+    outlets: Vec<(&'a str, &'a IROutlet)>,
+    index_of: HashMap<u32, usize>,
+    metrics: HashMap<(u32, u32, &'a str), f64>,
+    /// Source, target and type of every link
+    links: Vec<(u32, u32, &'a str)>,
+}
+
+impl<'a> Model<'a> {
+    fn new(program: &'a IRProgram) -> Self {
+        let mut model = Model {
+            outlets: Vec::new(),
+            index_of: HashMap::new(),
+            metrics: HashMap::new(),
+            links: Vec::new(),
+        };
+        for family in &program.families {
+            for outlet in &family.outlets {
+                if let Some(id) = outlet.id {
+                    model.index_of.entry(id).or_insert(model.outlets.len());
+                }
+                model.outlets.push((family.name.as_str(), outlet));
+            }
+            for relationship in &family.relationships {
+                model.links.push(match relationship {
+                    IRRelationship::Diachronic(link) => (
+                        link.predecessor,
+                        link.successor,
+                        link.relationship_type.as_str(),
+                    ),
+                    IRRelationship::Synchronous(link) => (
+                        link.outlet_1.id,
+                        link.outlet_2.id,
+                        link.relationship_type.as_str(),
+                    ),
+                });
+            }
+            for block in &family.data_blocks {
+                for year in &block.years {
+                    for metric in &year.metrics {
+                        model.metrics.insert(
+                            (block.outlet_id, year.year, metric.name.as_str()),
+                            metric.value,
+                        );
+                    }
+                }
+            }
+        }
+        model
+    }
+
+    /// Which outlets match a condition, by outlet index
+    fn matching(&self, condition: &Condition) -> Vec<bool> {
+        match condition {
+            Condition::And(a, b) => {
+                let b = self.matching(b);
+                self.matching(a)
+                    .iter()
+                    .zip(b)
+                    .map(|(a, b)| *a && b)
+                    .collect()
+            }
+            Condition::Or(a, b) => {
+                let b = self.matching(b);
+                self.matching(a)
+                    .iter()
+                    .zip(b)
+                    .map(|(a, b)| *a || b)
+                    .collect()
+            }
+            Condition::Not(inner) => self.matching(inner).iter().map(|m| !m).collect(),
+            Condition::Compare { column, op, value } => (0..self.outlets.len())
+                .map(|i| compare(&self.value(i, column), *op, value))
+                .collect(),
+            Condition::Active(period) => self
+                .outlets
+                .iter()
+                .map(|(_, outlet)| is_active(outlet, period.as_ref()))
+                .collect(),
+            Condition::Linked {
+                link_types,
+                direction,
+                target,
+            } => {
+                match target {
+                    None => self.linked(link_types, *direction, |_| true),
+                    // Link ends without an outlet declaration still match by ID
+                    Some(Target::Id(id)) => {
+                        self.linked(link_types, *direction, |other| other == *id)
+                    }
+                    Some(Target::Matching(condition)) => {
+                        let targets = self.matching(condition);
+                        self.linked(link_types, *direction, |other| {
+                            self.index_of.get(&other).is_some_and(|&i| targets[i])
+                        })
+                    }
+                }
+            }
+        }
+    }
+
+    /// Outlets with a link of one of the types (any type if empty) in the
+    /// direction to an outlet accepted by `accept`
+    fn linked(
+        &self,
+        link_types: &[String],
+        direction: Direction,
+        accept: impl Fn(u32) -> bool,
+    ) -> Vec<bool> {
+        let mut matched = vec![false; self.outlets.len()];
+        for &(source, target, link_type) in &self.links {
+            if !link_types.is_empty() && !link_types.iter().any(|t| t == link_type) {
+                continue;
+            }
+            let mut ends = Vec::new();
+            if direction != Direction::From {
+                ends.push((source, target));
+            }
+            if direction != Direction::To {
+                ends.push((target, source));
+            }
+            for (own, other) in ends {
+                if let Some(&i) = self.index_of.get(&own) {
+                    if accept(other) {
+                        matched[i] = true;
+                    }
+                }
+            }
+        }
+        matched
+    }
+
+    fn value(&self, index: usize, column: &Column) -> Value {
+        let (family, outlet) = self.outlets[index];
+        match column {
+            Column::Metric { name, year } => outlet
+                .id
+                .and_then(|id| self.metrics.get(&(id, *year, name.as_str())))
+                .map_or(Value::Null, |value| Value::Number(*value)),
+            Column::Field(name) => field(family, outlet, name),
+        }
+    }
+}
+
+fn field(family: &str, outlet: &IROutlet, name: &str) -> Value {
+    let statuses: Vec<&IRLifecycleStatus> = outlet
+        .blocks
+        .iter()
+        .filter_map(|block| match block {
+            IROutletBlock::Lifecycle(statuses) => Some(statuses),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+    let text = |value: Option<&String>| value.map_or(Value::Null, |v| Value::Text(v.clone()));
+
+    match name {
+        "id" => outlet.id.map_or(Value::Null, |id| Value::Number(id as f64)),
+        "name" => Value::Text(outlet.name.clone()),
+        "family" => Value::Text(family.to_string()),
+        "status" => text(statuses.last().map(|status| &status.status)),
+        "start" => text(
+            statuses
+                .first()
+                .and_then(|status| status.start_date.as_ref()),
+        ),
+        "end" => text(statuses.last().and_then(|status| status.end_date.as_ref())),
+        _ => {
+            let (block, path) = match name.split_once('.') {
+                Some((block @ ("identity" | "characteristics" | "metadata"), path)) => {
+                    (Some(block), path)
+                }
+                _ => (None, name),
+            };
+            outlet
+                .blocks
+                .iter()
+                .filter_map(|b| {
+                    let (kind, fields): (&str, Vec<(&String, &IRExpression)>) = match b {
+                        IROutletBlock::Identity(fields) => (
+                            "identity",
+                            fields.iter().map(|f| (&f.name, &f.value)).collect(),
+                        ),
+                        IROutletBlock::Characteristics(fields) => (
+                            "characteristics",
+                            fields.iter().map(|f| (&f.name, &f.value)).collect(),
+                        ),
+                        IROutletBlock::Metadata(fields) => (
+                            "metadata",
+                            fields.iter().map(|f| (&f.name, &f.value)).collect(),
+                        ),
+                        IROutletBlock::Lifecycle(_) => return None,
+                    };
+                    if block.is_some_and(|block| block != kind) {
+                        return None;
+                    }
+                    lookup(&fields, path)
+                })
+                .next()
+                .unwrap_or(Value::Null)
+        }
+    }
+}
+
+/// Value at a dotted path among named fields, descending into objects
+fn lookup(fields: &[(&String, &IRExpression)], path: &str) -> Option<Value> {
+    let (name, rest) = match path.split_once('.') {
+        Some((name, rest)) => (name, Some(rest)),
+        None => (path, None),
+    };
+    let (_, value) = fields.iter().find(|(field, _)| field.as_str() == name)?;
+    match (value, rest) {
+        (IRExpression::Object(members), Some(rest)) => {
+            let members: Vec<(&String, &IRExpression)> =
+                members.iter().map(|m| (&m.name, &m.value)).collect();
+            lookup(&members, rest)
+        }
+        (_, Some(_)) => None,
+        (IRExpression::String(s), None) => Some(Value::Text(s.clone())),
+        (IRExpression::Number(n), None) => Some(Value::Number(*n)),
+        (IRExpression::Boolean(b), None) => Some(Value::Bool(*b)),
+        (IRExpression::Variable(v), None) => Some(Value::Text(format!("${}", v))),
+        (IRExpression::Object(_) | IRExpression::Array(_), None) => None,
+    }
+}
+
+fn compare(actual: &Value, op: CompareOp, expected: &Value) -> bool {
+    if *actual == Value::Null {
+        return false;
+    }
+    if op == CompareOp::Contains {
+        return actual
+            .to_string()
+            .to_lowercase()
+            .contains(&expected.to_string().to_lowercase());
+    }
+    let ordering = compare_values(actual, expected);
+    match op {
+        CompareOp::Eq => ordering == Ordering::Equal,
+        CompareOp::Ne => ordering != Ordering::Equal,
+        CompareOp::Lt => ordering == Ordering::Less,
+        CompareOp::Le => ordering != Ordering::Greater,
+        CompareOp::Gt => ordering == Ordering::Greater,
+        CompareOp::Ge => ordering != Ordering::Less,
+        CompareOp::Contains => unreachable!(),
+    }
+}
+
+/// Numbers compare numerically (also when given as text), everything else
+/// as case-insensitive text
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a.as_number(), b.as_number()) {
+        (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => a
+            .to_string()
+            .to_lowercase()
+            .cmp(&b.to_string().to_lowercase()),
+    }
+}
+
+/// Whether an `active` lifecycle status overlaps the period, or is open now
+fn is_active(outlet: &IROutlet, period: Option<&(String, String)>) -> bool {
+    outlet.blocks.iter().any(|block| {
+        let IROutletBlock::Lifecycle(statuses) = block else {
+            return false;
+        };
+        statuses
+            .iter()
+            .filter(|status| status.status.eq_ignore_ascii_case("active"))
+            .any(|status| {
+                let end = status.end_date.as_deref().filter(|end| *end != "CURRENT");
+                let Some((from, to)) = period else {
+                    return end.is_none();
+                };
+                let starts_before_end = status
+                    .start_date
+                    .as_deref()
+                    .is_none_or(|start| start == "CURRENT" || first_day(start) <= *to);
+                let ends_after_start = end.is_none_or(|end| last_day(end) >= *from);
+                starts_before_end && ends_after_start
+            })
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Word(String),
+    Number(f64),
+    Text(String),
+    Op(CompareOp),
+    LeftParen,
+    RightParen,
+    Comma,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    offset: usize,
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some(&(offset, c)) = chars.peek() {
+        let kind = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            '(' | ')' | ',' => {
+                chars.next();
+                match c {
+                    '(' => TokenKind::LeftParen,
+                    ')' => TokenKind::RightParen,
+                    _ => TokenKind::Comma,
+                }
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '\\')) => {
+                            if let Some((_, escaped)) = chars.next() {
+                                value.push(escaped);
+                            }
+                        }
+                        Some((_, q)) if q == c => break,
+                        Some((_, other)) => value.push(other),
+                        None => return Err(syntax_error(text, offset, "unterminated string")),
+                    }
+                }
+                TokenKind::Text(value)
+            }
+            '=' | '!' | '<' | '>' | '~' => {
+                chars.next();
+                let next = chars.peek().map(|&(_, next)| next);
+                let (op, two) = match (c, next) {
+                    ('=', Some('=')) => (CompareOp::Eq, true),
+                    ('=', _) => (CompareOp::Eq, false),
+                    ('!', Some('=')) => (CompareOp::Ne, true),
+                    ('<', Some('>')) => (CompareOp::Ne, true),
+                    ('<', Some('=')) => (CompareOp::Le, true),
+                    ('<', _) => (CompareOp::Lt, false),
+                    ('>', Some('=')) => (CompareOp::Ge, true),
+                    ('>', _) => (CompareOp::Gt, false),
+                    ('~', _) => (CompareOp::Contains, false),
+                    _ => return Err(syntax_error(text, offset, "expected '!='")),
+                };
+                if two {
+                    chars.next();
+                }
+                TokenKind::Op(op)
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '-' || c == '.' => {
+                let mut end = offset;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_' || c == '-' || c == '.') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let word = &text[offset..end];
+                // Dates such as 1985-06-01 are words that do not parse as numbers
+                match word.parse::<f64>() {
+                    Ok(number) => TokenKind::Number(number),
+                    Err(_) => TokenKind::Word(word.to_string()),
+                }
+            }
+            other => {
+                return Err(syntax_error(
+                    text,
+                    offset,
+                    &format!("unexpected character '{}'", other),
+                ))
+            }
+        };
+        tokens.push(Token { kind, offset });
+    }
+    Ok(tokens)
+}
+
+fn syntax_error(text: &str, offset: usize, message: &str) -> Error {
+    Error::Parser(ParserError::InvalidSyntax {
+        message: format!("query: {}", message),
+        position: SourcePosition::new(1, text[..offset].chars().count() + 1, offset),
+    })
+}
+
+struct QueryParser {
+    tokens: Vec<Token>,
+    current: usize,
+    /// Length of the query text, the offset reported at its end
+    end: usize,
+}
+
+impl QueryParser {
+    fn query(&mut self) -> Result<Query> {
+        let mut query = Query {
+            condition: None,
+            columns: Vec::new(),
+            order: None,
+            limit: None,
+        };
+        if !self.at_end() && !self.at_keyword(&["select", "order", "limit"]) {
+            query.condition = Some(self.or()?);
+        }
+        if self.eat_keyword("select") {
+            query.columns.push(self.column()?);
+            while self.eat(&TokenKind::Comma) {
+                query.columns.push(self.column()?);
+            }
+        }
+        if self.eat_keyword("order") {
+            self.expect_keyword("by")?;
+            let column = self.column()?;
+            let descending = self.eat_keyword("desc");
+            if !descending {
+                self.eat_keyword("asc");
+            }
+            query.order = Some((column, descending));
+        }
+        if self.eat_keyword("limit") {
+            query.limit = Some(self.number()? as usize);
+        }
+        if !self.at_end() {
+            return Err(self.error("expected 'and', 'or', 'select', 'order by' or 'limit'"));
+        }
+        Ok(query)
+    }
+
+    fn or(&mut self) -> Result<Condition> {
+        let mut condition = self.and()?;
+        while self.eat_keyword("or") {
+            condition = Condition::Or(Box::new(condition), Box::new(self.and()?));
+        }
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<Condition> {
+        let mut condition = self.unary()?;
+        while self.eat_keyword("and") {
+            condition = Condition::And(Box::new(condition), Box::new(self.unary()?));
+        }
+        Ok(condition)
+    }
+
+    fn unary(&mut self) -> Result<Condition> {
+        if self.eat_keyword("not") {
+            return Ok(Condition::Not(Box::new(self.unary()?)));
+        }
+        if self.eat(&TokenKind::LeftParen) {
+            let condition = self.or()?;
+            self.expect(&TokenKind::RightParen, "')'")?;
+            return Ok(condition);
+        }
+        if self.eat_keyword("active") {
+            return self.active();
+        }
+        if self.eat_keyword("linked") {
+            return self.linked();
+        }
+
+        let mut column = self.column()?;
+        let op = match self.peek() {
+            Some(TokenKind::Op(op)) => *op,
+            _ => return Err(self.error("expected a comparison operator")),
+        };
+        self.current += 1;
+        let value = self.value()?;
+        // The year of a metric may also follow the value
+        if let Column::Field(name) = &column {
+            if self.eat_keyword("in") {
+                column = Column::Metric {
+                    name: name.clone(),
+                    year: self.number()? as u32,
+                };
+            }
+        }
+        Ok(Condition::Compare { column, op, value })
+    }
+
+    fn active(&mut self) -> Result<Condition> {
+        // A year covers all of its days, a date just the one
+        let period = if self.eat_keyword("in") || self.eat_keyword("on") {
+            let date = self.date()?;
+            Some((first_day(&date), last_day(&date)))
+        } else if self.eat_keyword("between") {
+            let from = self.date()?;
+            self.expect_keyword("and")?;
+            let to = self.date()?;
+            Some((first_day(&from), last_day(&to)))
+        } else {
+            None
+        };
+        Ok(Condition::Active(period))
+    }
+
+    fn linked(&mut self) -> Result<Condition> {
+        let mut link_types = Vec::new();
+        if self.eat_keyword("via") {
+            link_types.push(self.word()?);
+            while self.eat(&TokenKind::Comma) {
+                link_types.push(self.word()?);
+            }
+        }
+        let direction = if self.eat_keyword("to") {
+            Direction::To
+        } else if self.eat_keyword("from") {
+            Direction::From
+        } else {
+            self.eat_keyword("with");
+            Direction::With
+        };
+        let target = match self.peek() {
+            Some(TokenKind::Number(_)) => Some(Target::Id(self.number()? as u32)),
+            Some(TokenKind::LeftParen) => {
+                self.current += 1;
+                let condition = self.or()?;
+                self.expect(&TokenKind::RightParen, "')'")?;
+                Some(Target::Matching(Box::new(condition)))
+            }
+            _ => None,
+        };
+        Ok(Condition::Linked {
+            link_types,
+            direction,
+            target,
+        })
+    }
+
+    /// A field, or a metric when followed by `in YEAR`
+    fn column(&mut self) -> Result<Column> {
+        let name = self.word()?;
+        if self.at_keyword(&["in"]) && matches!(self.peek_at(1), Some(TokenKind::Number(_))) {
+            self.current += 1;
+            let year = self.number()? as u32;
+            return Ok(Column::Metric { name, year });
+        }
+        Ok(Column::Field(name))
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        let value = match self.peek() {
+            Some(TokenKind::Number(n)) => Value::Number(*n),
+            Some(TokenKind::Text(text)) => Value::Text(text.clone()),
+            Some(TokenKind::Word(word)) if word == "true" || word == "false" => {
+                Value::Bool(word == "true")
+            }
+            Some(TokenKind::Word(word)) => Value::Text(word.clone()),
+            _ => return Err(self.error("expected a value")),
+        };
+        self.current += 1;
+        Ok(value)
+    }
+
+    /// A year or date, as text
+    fn date(&mut self) -> Result<String> {
+        match self.value()? {
+            Value::Number(n) => Ok(n.to_string()),
+            Value::Text(text) => Ok(text),
+            _ => Err(self.error("expected a year or date")),
+        }
+    }
+
+    fn word(&mut self) -> Result<String> {
+        match self.peek() {
+            Some(TokenKind::Word(word)) => {
+                let word = word.clone();
+                self.current += 1;
+                Ok(word)
+            }
+            _ => Err(self.error("expected a name")),
+        }
+    }
+
+    fn number(&mut self) -> Result<f64> {
+        match self.peek() {
+            Some(TokenKind::Number(n)) => {
+                let n = *n;
+                self.current += 1;
+                Ok(n)
+            }
+            _ => Err(self.error("expected a number")),
+        }
+    }
+
+    fn peek(&self) -> Option<&TokenKind> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, ahead: usize) -> Option<&TokenKind> {
+        self.tokens
+            .get(self.current + ahead)
+            .map(|token| &token.kind)
+    }
+
+    fn at_end(&self) -> bool {
+        self.current >= self.tokens.len()
+    }
+
+    fn at_keyword(&self, keywords: &[&str]) -> bool {
+        matches!(self.peek(), Some(TokenKind::Word(word)) if keywords.iter().any(|k| word.eq_ignore_ascii_case(k)))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.at_keyword(&[keyword]);
+        if found {
+            self.current += 1;
+        }
+        found
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", keyword)))
+        }
+    }
+
+    fn eat(&mut self, kind: &TokenKind) -> bool {
+        let found = self.peek() == Some(kind);
+        if found {
+            self.current += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, kind: &TokenKind, description: &str) -> Result<()> {
+        if self.eat(kind) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {}", description)))
+        }
+    }
+
+    fn error(&self, message: &str) -> Error {
+        let offset = self
+            .tokens
+            .get(self.current)
+            .map_or(self.end, |token| token.offset);
+        Error::Parser(ParserError::InvalidSyntax {
+            message: format!("query: {}", message),
+            position: SourcePosition::new(1, offset + 1, offset),
+        })
+    }
+}
@@ -3,6 +3,10 @@
 use clap::{Arg, Command};
 use mdsl_rs::error::Error;
 use mdsl_rs::import::connection::DatabaseConnection;
+use mdsl_rs::import::families::FamilyOptions;
+use mdsl_rs::import::northdata::{CrawlFailurePolicy, NorthDataImport};
+use mdsl_rs::import::selection::Selection;
+use mdsl_rs::import::sync::ChangeKind;
 use mdsl_rs::import::{DatabaseConfig, DatabaseType, MdslEntityType, SqlImporter};
 use std::fs;
 
@@ -31,7 +35,7 @@ async fn main() -> Result<(), Error> {
                 .short('t')
                 .long("type")
                 .value_name("TYPE")
-                .help("Database type (postgresql, mysql, sqlite, sqlserver)")
+                .help("Database type (postgresql, mysql, mariadb, sqlite, sqlserver, csv)")
                 .default_value("postgresql"),
         )
         .arg(
@@ -56,7 +60,7 @@ async fn main() -> Result<(), Error> {
         )
         .subcommand(
             Command::new("manual")
-                .about("Import using predefined mappings")
+                .about("Import using table mappings from a TOML file")
                 .arg(
                     Arg::new("connection")
                         .short('c')
@@ -64,6 +68,22 @@ async fn main() -> Result<(), Error> {
                         .value_name("CONNECTION_STRING")
                         .help("Database connection string")
                         .required(true),
+                )
+                .arg(
+                    Arg::new("mappings")
+                        .short('m')
+                        .long("mappings")
+                        .value_name("FILE")
+                        .help("TOML file describing the table mappings")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("family-links")
+                        .long("family-links")
+                        .value_name("TYPES")
+                        .value_delimiter(',')
+                        .action(clap::ArgAction::Append)
+                        .help("Relationship types that group outlets into families (default: main_media_outlet,umbrella,combination)"),
                 ),
         )
         .subcommand(
@@ -159,9 +179,9 @@ async fn main() -> Result<(), Error> {
                         .default_value("5"),
                 ),
         )
-        .subcommand(
+        .subcommand(selection_args(
             Command::new("generate")
-                .about("Generate complete MDSL from ANMI database")
+                .about("Generate complete MDSL from ANMI database, or an excerpt of selected outlets")
                 .arg(
                     Arg::new("connection")
                         .short('c')
@@ -176,8 +196,50 @@ async fn main() -> Result<(), Error> {
                         .long("output")
                         .value_name("FILE")
                         .help("Output file (default: stdout)"),
+                )
+                .arg(
+                    Arg::new("family-links")
+                        .long("family-links")
+                        .value_name("TYPES")
+                        .value_delimiter(',')
+                        .action(clap::ArgAction::Append)
+                        .help("Relationship types that group outlets into families (default: main_media_outlet,umbrella,combination)"),
                 ),
-        )
+        ))
+        .subcommand(selection_args(
+            Command::new("sync")
+                .about("Update an MDSL file in place with the outlets, links and market data that changed in the database")
+                .arg(
+                    Arg::new("connection")
+                        .short('c')
+                        .long("connection")
+                        .value_name("CONNECTION_STRING")
+                        .help("Database connection string")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("file")
+                        .short('f')
+                        .long("file")
+                        .value_name("FILE")
+                        .help("MDSL file from an earlier export")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .help("Only report the changes, leave the file as it is")
+                        .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("family-links")
+                        .long("family-links")
+                        .value_name("TYPES")
+                        .value_delimiter(',')
+                        .action(clap::ArgAction::Append)
+                        .help("Relationship types that group outlets into families (default: main_media_outlet,umbrella,combination)"),
+                ),
+        ))
         .subcommand(
             Command::new("validate")
                 .about("Generate small sample and validate MDSL syntax")
@@ -205,6 +267,31 @@ async fn main() -> Result<(), Error> {
                         .help("Output file for sample (default: validation_sample.mdsl)"),
                 ),
         )
+        .subcommand(
+            Command::new("northdata")
+                .about("Import crawled company-register CSV tables (nd_company, nd_person, nd_ownership)")
+                .arg(
+                    Arg::new("dir")
+                        .short('d')
+                        .long("dir")
+                        .value_name("DIR")
+                        .help("Directory containing the nd_*.csv tables")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("keep-failed")
+                        .long("keep-failed")
+                        .help("Keep rows whose crawl failed, marked with a comment")
+                        .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("output")
+                        .short('o')
+                        .long("output")
+                        .value_name("FILE")
+                        .help("Output file (default: stdout)"),
+                ),
+        )
         .get_matches();
 
     let result = match matches.subcommand() {
@@ -214,7 +301,9 @@ async fn main() -> Result<(), Error> {
         }
         Some(("manual", sub_matches)) => {
             let connection_string = sub_matches.get_one::<String>("connection").unwrap();
-            manual_import(connection_string).await
+            let mappings = sub_matches.get_one::<String>("mappings").unwrap();
+            let family_options = family_options(sub_matches);
+            manual_import(connection_string, mappings, family_options).await
         }
         Some(("analyze", sub_matches)) => {
             let connection_string = sub_matches.get_one::<String>("connection").unwrap();
@@ -250,7 +339,18 @@ async fn main() -> Result<(), Error> {
         Some(("generate", sub_matches)) => {
             let connection_string = sub_matches.get_one::<String>("connection").unwrap();
             let output_file = sub_matches.get_one::<String>("output");
-            generate_complete_mdsl(connection_string, output_file).await
+            let selection = selection(sub_matches);
+            let family_options = family_options(sub_matches);
+            generate_complete_mdsl(connection_string, output_file, &selection, family_options)
+                .await
+        }
+        Some(("sync", sub_matches)) => {
+            let connection_string = sub_matches.get_one::<String>("connection").unwrap();
+            let file = sub_matches.get_one::<String>("file").unwrap();
+            let dry_run = sub_matches.get_flag("dry-run");
+            let selection = selection(sub_matches);
+            let family_options = family_options(sub_matches);
+            sync_mdsl(connection_string, file, &selection, family_options, dry_run).await
         }
         Some(("validate", sub_matches)) => {
             let connection_string = sub_matches.get_one::<String>("connection").unwrap();
@@ -262,6 +362,16 @@ async fn main() -> Result<(), Error> {
             let output_file = sub_matches.get_one::<String>("output");
             validate_mdsl(connection_string, limit, output_file).await
         }
+        Some(("northdata", sub_matches)) => {
+            let dir = sub_matches.get_one::<String>("dir").unwrap();
+            let policy = if sub_matches.get_flag("keep-failed") {
+                CrawlFailurePolicy::Report
+            } else {
+                CrawlFailurePolicy::Skip
+            };
+            let output_file = sub_matches.get_one::<String>("output");
+            import_northdata(dir, policy, output_file)
+        }
         _ => {
             // Default behavior
             let connection_string = matches.get_one::<String>("connection");
@@ -299,7 +409,7 @@ async fn auto_import(connection_string: &str) -> Result<(), Error> {
         schema: Some("graphv3".to_string()), // Default to graphv3 schema for ANMI
     };
 
-    let importer = SqlImporter::new(config);
+    let mut importer = SqlImporter::new(config);
 
     // Auto-detect patterns
     let patterns = importer.auto_detect_anmi_patterns().await?;
@@ -320,7 +430,8 @@ async fn auto_import(connection_string: &str) -> Result<(), Error> {
         );
     }
 
-    // For now, just perform basic import
+    importer.register_anmi_mappings().await?;
+
     match importer.import_and_generate().await {
         Ok(mdsl_code) => {
             println!("\n🎉 Generated MDSL code:");
@@ -335,9 +446,83 @@ async fn auto_import(connection_string: &str) -> Result<(), Error> {
     }
 }
 
-/// Manual import using predefined mappings
-async fn manual_import(connection_string: &str) -> Result<(), Error> {
-    println!("Manual import with predefined mappings...");
+/// Add the outlet selection arguments to a subcommand
+fn selection_args(command: Command) -> Command {
+    command
+        .arg(
+            Arg::new("id")
+                .long("id")
+                .value_name("ID")
+                .value_delimiter(',')
+                .action(clap::ArgAction::Append)
+                .help("Select outlets by ID"),
+        )
+        .arg(
+            Arg::new("title")
+                .long("title")
+                .value_name("TEXT")
+                .action(clap::ArgAction::Append)
+                .help("Select outlets whose title contains TEXT (case-insensitive)"),
+        )
+        .arg(
+            Arg::new("sector")
+                .long("sector")
+                .value_name("CODE")
+                .value_delimiter(',')
+                .action(clap::ArgAction::Append)
+                .help("Select outlets of a sector"),
+        )
+        .arg(
+            Arg::new("family")
+                .long("family")
+                .value_name("ID_OR_TITLE")
+                .action(clap::ArgAction::Append)
+                .help("Select a media group by outlet ID or exact title: the outlet and everything under it via main media outlet or umbrella links"),
+        )
+        .arg(
+            Arg::new("hops")
+                .long("hops")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("1")
+                .help("Relationship hops to follow from the selected outlets"),
+        )
+}
+
+/// Outlet selection from the arguments added by `selection_args`
+fn selection(matches: &clap::ArgMatches) -> Selection {
+    let values = |name: &str| -> Vec<String> {
+        matches
+            .get_many::<String>(name)
+            .map(|values| values.cloned().collect())
+            .unwrap_or_default()
+    };
+    Selection {
+        ids: values("id"),
+        title_patterns: values("title"),
+        sectors: values("sector"),
+        families: values("family"),
+        hops: *matches.get_one::<usize>("hops").unwrap(),
+    }
+}
+
+/// Family clustering options from the `--family-links` argument
+fn family_options(matches: &clap::ArgMatches) -> FamilyOptions {
+    match matches.get_many::<String>("family-links") {
+        Some(link_types) => FamilyOptions {
+            link_types: link_types.cloned().collect(),
+        },
+        None => FamilyOptions::default(),
+    }
+}
+
+/// Manual import using the table mappings of a TOML file
+async fn manual_import(
+    connection_string: &str,
+    mappings: &str,
+    family_options: FamilyOptions,
+) -> Result<(), Error> {
+    println!("Manual import with mappings from {}...", mappings);
 
     let db_type = detect_database_type(connection_string)?;
     let config = DatabaseConfig {
@@ -346,11 +531,10 @@ async fn manual_import(connection_string: &str) -> Result<(), Error> {
         schema: None,
     };
 
-    let importer = SqlImporter::new(config);
-
-    // Add basic mappings - these would normally be configurable
-    // For now, just create empty mappings as example
-    println!("Using default mappings...");
+    let mut importer = SqlImporter::new(config);
+    importer.family_options = family_options;
+    let count = importer.load_mapping_file(std::path::Path::new(mappings))?;
+    println!("Using {} table mappings...", count);
 
     match importer.import_and_generate().await {
         Ok(mdsl_code) => {
@@ -509,9 +693,10 @@ async fn default_import(
 fn parse_database_type(type_str: &str) -> Result<DatabaseType, Error> {
     match type_str.to_lowercase().as_str() {
         "postgresql" | "postgres" | "pg" => Ok(DatabaseType::PostgreSQL),
-        "mysql" => Ok(DatabaseType::MySQL),
+        "mysql" | "mariadb" => Ok(DatabaseType::MySQL),
         "sqlite" => Ok(DatabaseType::SQLite),
         "sqlserver" | "mssql" => Ok(DatabaseType::SqlServer),
+        "csv" => Ok(DatabaseType::Csv),
         _ => Err(Error::InvalidConnectionString(format!(
             "Unsupported database type: {}",
             type_str
@@ -530,12 +715,17 @@ fn detect_database_type(connection_string: &str) -> Result<DatabaseType, Error>
     } else if connection_string.starts_with("sqlite://")
         || connection_string.ends_with(".db")
         || connection_string.ends_with(".sqlite")
+        || connection_string.ends_with(".sqlite3")
     {
         Ok(DatabaseType::SQLite)
     } else if connection_string.starts_with("sqlserver://")
         || connection_string.starts_with("mssql://")
     {
         Ok(DatabaseType::SqlServer)
+    } else if connection_string.starts_with("csv://")
+        || std::path::Path::new(connection_string).is_dir()
+    {
+        Ok(DatabaseType::Csv)
     } else {
         // Default to PostgreSQL for backward compatibility
         Ok(DatabaseType::PostgreSQL)
@@ -790,6 +980,8 @@ async fn sample_data(
 async fn generate_complete_mdsl(
     connection_string: &str,
     output_file: Option<&String>,
+    selection: &Selection,
+    family_options: FamilyOptions,
 ) -> Result<(), Error> {
     println!("🚀 Starting complete MDSL generation from ANMI database...");
 
@@ -800,9 +992,10 @@ async fn generate_complete_mdsl(
         schema: Some("graphv3".to_string()),
     };
 
-    let importer = SqlImporter::new(config);
+    let mut importer = SqlImporter::new(config);
+    importer.family_options = family_options;
 
-    match importer.generate_complete_mdsl().await {
+    match importer.generate_selected_mdsl(selection).await {
         Ok(mdsl_content) => {
             if let Some(output_path) = output_file {
                 // Write to file
@@ -824,6 +1017,93 @@ async fn generate_complete_mdsl(
     }
 }
 
+/// Update an MDSL file in place with what changed in the ANMI database
+async fn sync_mdsl(
+    connection_string: &str,
+    file: &str,
+    selection: &Selection,
+    family_options: FamilyOptions,
+    dry_run: bool,
+) -> Result<(), Error> {
+    let existing = fs::read_to_string(file)
+        .map_err(|e| Error::Io(format!("Failed to read '{}': {}", file, e)))?;
+
+    let db_type = detect_database_type(connection_string)?;
+    let config = DatabaseConfig {
+        db_type,
+        connection_string: connection_string.to_string(),
+        schema: Some("graphv3".to_string()),
+    };
+    let mut importer = SqlImporter::new(config);
+    importer.family_options = family_options;
+
+    let patch = importer.sync_mdsl(&existing, selection).await?;
+    if patch.is_empty() {
+        println!("✅ {} is up to date", file);
+        return Ok(());
+    }
+
+    for change in &patch.changes {
+        println!("{}", change);
+    }
+    println!(
+        "📊 {} added, {} changed, {} removed",
+        patch.count(ChangeKind::Added),
+        patch.count(ChangeKind::Changed),
+        patch.count(ChangeKind::Removed)
+    );
+
+    if dry_run {
+        println!("ℹ️ Dry run, {} left unchanged", file);
+    } else {
+        fs::write(file, patch.apply(&existing))
+            .map_err(|e| Error::Io(format!("Failed to write '{}': {}", file, e)))?;
+        println!("✅ Updated {}", file);
+    }
+    Ok(())
+}
+
+/// Import crawled company-register tables from CSV files
+fn import_northdata(
+    dir: &str,
+    policy: CrawlFailurePolicy,
+    output_file: Option<&String>,
+) -> Result<(), Error> {
+    let import = NorthDataImport::from_dir(std::path::Path::new(dir), policy)?;
+    let mdsl_content = import.to_mdsl()?;
+
+    eprintln!(
+        "📊 {} companies, {} persons, {} ownership relations",
+        import.companies.len(),
+        import.persons.len(),
+        import.ownerships.len()
+    );
+    if !import.failed_crawls.is_empty() {
+        eprintln!("⚠️  {} failed crawls:", import.failed_crawls.len());
+        for crawl in &import.failed_crawls {
+            eprintln!(
+                "   - {}: {}",
+                crawl.url,
+                crawl.error_msg.as_deref().unwrap_or("no error message")
+            );
+        }
+    }
+    if !import.skipped.is_empty() {
+        eprintln!("⚠️  {} rows skipped", import.skipped.len());
+    }
+
+    if let Some(output_path) = output_file {
+        fs::write(output_path, &mdsl_content).map_err(|e| {
+            Error::Io(format!("Failed to write to file '{}': {}", output_path, e))
+        })?;
+        eprintln!("✅ Generated MDSL saved to: {}", output_path);
+    } else {
+        println!("{}", mdsl_content);
+    }
+
+    Ok(())
+}
+
 /// Validate MDSL syntax by generating a small sample
 async fn validate_mdsl(
     connection_string: &str,
@@ -0,0 +1,387 @@
+//! ANMI CSV table generator
+//!
+//! Writes the IR in the layout of the ANMI table dump (`anmi-media-v1/02_csv_tables`):
+//! `mo_constant.csv`, `mo_year.csv`, `sources_names.csv` and the numbered
+//! relationship tables. The dump has no empty cells; unknown values use the ANMI
+//! sentinels: `99` for codes and counts, `99.0` for percentages, `KA` (keine
+//! Angabe) for text and unknown start dates and `9999-01-01` for open end dates.
+
+use crate::codegen::common::{anmi_source_id, cited_sources};
+use crate::error::Result;
+use crate::ir::nodes::*;
+use crate::utils::csv::write_record;
+use std::collections::HashMap;
+
+/// Sentinel for unknown codes and counts
+pub const UNKNOWN_NUMBER: &str = "99";
+/// Sentinel for unknown percentages
+pub const UNKNOWN_DECIMAL: &str = "99.0";
+/// Sentinel for unknown text ("keine Angabe")
+pub const UNKNOWN_TEXT: &str = "KA";
+/// End date of outlets and relationships that still exist
+pub const OPEN_END_DATE: &str = "9999-01-01";
+/// Date precision code of exactly known dates (`fake_date` columns)
+pub const KNOWN_DATE: &str = "9";
+
+/// Diachronic relationship tables of the dump, by relationship type
+pub const DIACHRONIC_TABLES: &[(&str, &str)] = &[
+    ("succession", "11_succession"),
+    ("amalgamation", "12_amalgamation"),
+    ("new_distribution_area", "13_new_distribution_area"),
+    ("new_sector", "14_new_sector"),
+    ("interruption", "19_interruption"),
+    ("split_off", "21_split_off"),
+    ("offshoot", "22_offshoot"),
+    ("merger", "23_merger"),
+];
+
+/// Synchronous relationship tables of the dump, by relationship type
+pub const SYNCHRONOUS_TABLES: &[(&str, &str)] = &[
+    ("main_media_outlet", "31_main_media_outlet"),
+    ("umbrella", "33_umbrella"),
+    ("collaboration", "34_collaboration"),
+];
+
+const MO_CONSTANT_COLUMNS: &[&str] = &[
+    "id_mo",
+    "mo_title",
+    "id_sector",
+    "mandate",
+    "location",
+    "primary_distr_area",
+    "local",
+    "language",
+    "start_date",
+    "start_fake_date",
+    "end_date",
+    "end_fake_date",
+    "editorial_line_s",
+    "editorial_line_e",
+    "comments",
+];
+
+const MO_YEAR_COLUMNS: &[&str] = &[
+    "id_mo",
+    "year",
+    "mo_year",
+    "calc",
+    "circulation",
+    "circulation_source",
+    "unique_users",
+    "unique_users_source",
+    "reach_nat",
+    "reach_nat_source",
+    "reach_reg",
+    "reach_reg_source",
+    "market_share",
+    "market_share_source",
+    "comments",
+];
+
+/// ANMI CSV table generator
+pub struct AnmiCsvGenerator;
+
+impl AnmiCsvGenerator {
+    /// Create a new ANMI CSV generator
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Generate every table of the dump as `(file name, CSV content)` pairs
+    ///
+    /// Tables without rows are still written with their header.
+    pub fn generate(&self, ir: &IRProgram) -> Result<Vec<(String, String)>> {
+        let mut files = vec![
+            ("mo_constant.csv".to_string(), self.generate_mo_constant(ir)),
+            ("mo_year.csv".to_string(), self.generate_mo_year(ir)),
+            ("sources_names.csv".to_string(), self.generate_sources_names(ir)),
+        ];
+
+        for (relationship_type, table_name) in DIACHRONIC_TABLES {
+            files.push((
+                format!("{}.csv", table_name),
+                self.generate_diachronic_table(ir, relationship_type),
+            ));
+        }
+        for (relationship_type, table_name) in SYNCHRONOUS_TABLES {
+            files.push((
+                format!("{}.csv", table_name),
+                self.generate_synchronous_table(ir, relationship_type),
+            ));
+        }
+
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(files)
+    }
+
+    /// Generate `mo_constant.csv`, one row per outlet with an ID, ordered by ID
+    pub fn generate_mo_constant(&self, ir: &IRProgram) -> String {
+        let mut rows = Vec::new();
+
+        for outlet in ir.families.iter().flat_map(|family| &family.outlets) {
+            let fields = OutletFields::collect(outlet);
+            let Some(id_mo) = outlet.id.or_else(|| fields.number(&["id"]).map(|id| id as u32))
+            else {
+                continue;
+            };
+
+            let statuses: Vec<&IRLifecycleStatus> = outlet
+                .blocks
+                .iter()
+                .filter_map(|block| match block {
+                    IROutletBlock::Lifecycle(statuses) => Some(statuses),
+                    _ => None,
+                })
+                .flatten()
+                .collect();
+            let first = statuses.first();
+            let last = statuses.last();
+
+            let row = write_record(&[
+                id_mo.to_string(),
+                fields
+                    .text(&["title", "name", "mo_title"])
+                    .unwrap_or_else(|| outlet.name.clone()),
+                fields.code(&["sector", "id_sector"]),
+                fields.code(&["mandate"]),
+                fields.text_or_ka(&["location"]),
+                fields.code(&[
+                    "primary_distribution_area",
+                    "primary_distr_area",
+                    "distribution.primary_area",
+                ]),
+                fields.code(&["local", "distribution.local"]),
+                fields.text_or_ka(&["language"]),
+                start_date(first.and_then(|status| status.start_date.as_deref())),
+                date_precision(first.and_then(|status| status.precision_start.as_deref())),
+                end_date(last.and_then(|status| status.end_date.as_deref())),
+                date_precision(last.and_then(|status| status.precision_end.as_deref())),
+                fields.text_or_ka(&["editorial_line_s", "editorial_line_self", "editorial_line"]),
+                fields.text_or_ka(&["editorial_line_e", "editorial_line_external"]),
+                fields.text_or_ka(&["comments", "comment"]),
+            ]);
+            rows.push((id_mo, row));
+        }
+
+        rows.sort_by_key(|(id_mo, _)| *id_mo);
+        let mut csv = write_record(MO_CONSTANT_COLUMNS);
+        for (_, row) in rows {
+            csv.push_str(&row);
+        }
+        csv
+    }
+
+    /// Generate `mo_year.csv`, one row per outlet and data year
+    pub fn generate_mo_year(&self, ir: &IRProgram) -> String {
+        let mut csv = write_record(MO_YEAR_COLUMNS);
+
+        for data_block in ir.families.iter().flat_map(|family| &family.data_blocks) {
+            for year in &data_block.years {
+                let metric = |names: &[&str]| {
+                    year.metrics
+                        .iter()
+                        .find(|metric| names.contains(&metric.name.as_str()))
+                };
+                let source = |metric: Option<&IRDataMetric>| {
+                    metric
+                        .and_then(|metric| anmi_source_id(ir, &metric.source, year.year))
+                        .map_or(UNKNOWN_NUMBER.to_string(), |id| id.to_string())
+                };
+                let count = |names: &[&str]| {
+                    let metric = metric(names);
+                    let value = metric
+                        .map_or(UNKNOWN_NUMBER.to_string(), |metric| format!("{}", metric.value.round()));
+                    (value, source(metric))
+                };
+                let percentage = |names: &[&str]| {
+                    let metric = metric(names);
+                    let value = metric
+                        .map_or(UNKNOWN_DECIMAL.to_string(), |metric| format!("{:.1}", metric.value));
+                    (value, source(metric))
+                };
+
+                // Additive calculation type, given as a metric or as a block aggregation
+                let calc = metric(&["calc"])
+                    .map(|metric| format!("{}", metric.value.round()))
+                    .or_else(|| {
+                        data_block
+                            .aggregation
+                            .iter()
+                            .find(|aggregation| aggregation.name == "calc")
+                            .map(|aggregation| aggregation.value.clone())
+                    })
+                    .unwrap_or_else(|| UNKNOWN_NUMBER.to_string());
+
+                let (circulation, circulation_source) = count(&["circulation"]);
+                let (unique_users, unique_users_source) = count(&["unique_users"]);
+                let (reach_nat, reach_nat_source) = percentage(&["reach_national", "reach_nat"]);
+                let (reach_reg, reach_reg_source) = percentage(&["reach_regional", "reach_reg"]);
+                let (market_share, market_share_source) = percentage(&["market_share"]);
+
+                csv.push_str(&write_record(&[
+                    data_block.outlet_id.to_string(),
+                    format!("{}-12-01", year.year),
+                    // Composite key: outlet ID followed by the four-digit year
+                    (data_block.outlet_id as u64 * 10_000 + year.year as u64).to_string(),
+                    calc,
+                    circulation,
+                    circulation_source,
+                    unique_users,
+                    unique_users_source,
+                    reach_nat,
+                    reach_nat_source,
+                    reach_reg,
+                    reach_reg_source,
+                    market_share,
+                    market_share_source,
+                    year.comment.clone().unwrap_or_else(|| UNKNOWN_TEXT.to_string()),
+                ]));
+            }
+        }
+
+        csv
+    }
+
+    /// Generate `sources_names.csv` from the sources cited by market data
+    pub fn generate_sources_names(&self, ir: &IRProgram) -> String {
+        let mut csv = write_record(&["id_source", "source_name"]);
+        for (id, name) in cited_sources(ir) {
+            csv.push_str(&write_record(&[id.to_string(), name]));
+        }
+        csv
+    }
+
+    /// Generate a diachronic relationship table (`id_mo` is the predecessor)
+    pub fn generate_diachronic_table(&self, ir: &IRProgram, relationship_type: &str) -> String {
+        let mut csv = write_record(&["id_mo", relationship_type]);
+        for relationship in ir.families.iter().flat_map(|family| &family.relationships) {
+            if let IRRelationship::Diachronic(link) = relationship {
+                if link.relationship_type == relationship_type {
+                    csv.push_str(&write_record(&[
+                        link.predecessor.to_string(),
+                        link.successor.to_string(),
+                    ]));
+                }
+            }
+        }
+        csv
+    }
+
+    /// Generate a synchronous relationship table with its period columns
+    pub fn generate_synchronous_table(&self, ir: &IRProgram, relationship_type: &str) -> String {
+        let mut csv = write_record(&["id_mo", relationship_type, "start_rel", "end_rel"]);
+        for relationship in ir.families.iter().flat_map(|family| &family.relationships) {
+            if let IRRelationship::Synchronous(link) = relationship {
+                if link.relationship_type == relationship_type {
+                    csv.push_str(&write_record(&[
+                        link.outlet_1.id.to_string(),
+                        link.outlet_2.id.to_string(),
+                        start_date(link.period_start.as_deref()),
+                        end_date(link.period_end.as_deref()),
+                    ]));
+                }
+            }
+        }
+        csv
+    }
+}
+
+impl Default for AnmiCsvGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Identity, characteristics and metadata fields of an outlet by name; fields
+/// of nested objects are named by their path (`distribution.primary_area`)
+struct OutletFields<'a> {
+    fields: HashMap<String, &'a IRExpression>,
+}
+
+impl<'a> OutletFields<'a> {
+    fn collect(outlet: &'a IROutlet) -> Self {
+        let mut fields = HashMap::new();
+        for block in &outlet.blocks {
+            let named: Vec<(&String, &IRExpression)> = match block {
+                IROutletBlock::Identity(items) => items.iter().map(|f| (&f.name, &f.value)).collect(),
+                IROutletBlock::Characteristics(items) => {
+                    items.iter().map(|f| (&f.name, &f.value)).collect()
+                }
+                IROutletBlock::Metadata(items) => items.iter().map(|f| (&f.name, &f.value)).collect(),
+                IROutletBlock::Lifecycle(_) => Vec::new(),
+            };
+            for (name, value) in named {
+                Self::insert(&mut fields, name.clone(), value);
+            }
+        }
+        Self { fields }
+    }
+
+    fn insert(
+        fields: &mut HashMap<String, &'a IRExpression>,
+        name: String,
+        value: &'a IRExpression,
+    ) {
+        if let IRExpression::Object(nested) = value {
+            for field in nested {
+                Self::insert(fields, format!("{}.{}", name, field.name), &field.value);
+            }
+        }
+        fields.entry(name).or_insert(value);
+    }
+
+    fn find(&self, names: &[&str]) -> Option<&'a IRExpression> {
+        names.iter().find_map(|name| self.fields.get(*name).copied())
+    }
+
+    fn number(&self, names: &[&str]) -> Option<f64> {
+        match self.find(names)? {
+            IRExpression::Number(n) => Some(*n),
+            IRExpression::String(s) => s.trim().parse().ok(),
+            IRExpression::Boolean(b) => Some(if *b { 1.0 } else { 0.0 }),
+            _ => None,
+        }
+    }
+
+    fn text(&self, names: &[&str]) -> Option<String> {
+        match self.find(names)? {
+            IRExpression::String(s) => Some(s.clone()),
+            IRExpression::Number(n) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Numeric code, `99` when unknown
+    fn code(&self, names: &[&str]) -> String {
+        self.number(names)
+            .map_or(UNKNOWN_NUMBER.to_string(), |n| format!("{}", n.round()))
+    }
+
+    /// Text, `KA` when unknown
+    fn text_or_ka(&self, names: &[&str]) -> String {
+        self.text(names)
+            .filter(|text| !text.is_empty())
+            .unwrap_or_else(|| UNKNOWN_TEXT.to_string())
+    }
+}
+
+/// Start date column: `KA` when the start is unknown
+fn start_date(date: Option<&str>) -> String {
+    date.unwrap_or(UNKNOWN_TEXT).to_string()
+}
+
+/// End date column: open periods end on `9999-01-01`
+fn end_date(date: Option<&str>) -> String {
+    match date {
+        None | Some("CURRENT") => OPEN_END_DATE.to_string(),
+        Some(date) => date.to_string(),
+    }
+}
+
+/// Date precision (`fake_date`) code; numeric precisions are taken as ANMI codes
+fn date_precision(precision: Option<&str>) -> String {
+    precision
+        .filter(|code| !code.is_empty() && code.chars().all(|c| c.is_ascii_digit()))
+        .unwrap_or(KNOWN_DATE)
+        .to_string()
+}
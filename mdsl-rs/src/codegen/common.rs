@@ -1 +1,164 @@
-//! Common code generation utilities (stub)
+//! Common code generation utilities
+
+use crate::ir::nodes::*;
+
+/// A provenance record with the kind and key of the construct it belongs to
+pub(crate) struct ProvenanceEntry<'a> {
+    /// Construct kind (outlet, company, ownership, ...)
+    pub kind: &'static str,
+    /// ID or name of the construct
+    pub key: String,
+    /// The record itself
+    pub record: &'a IRProvenance,
+}
+
+/// Collect the provenance records of all constructs in a program
+pub(crate) fn provenance_entries(ir: &IRProgram) -> Vec<ProvenanceEntry<'_>> {
+    fn push<'a>(
+        entries: &mut Vec<ProvenanceEntry<'a>>,
+        kind: &'static str,
+        key: String,
+        records: &'a [IRProvenance],
+    ) {
+        for record in records {
+            entries.push(ProvenanceEntry {
+                kind,
+                key: key.clone(),
+                record,
+            });
+        }
+    }
+
+    let mut entries = Vec::new();
+
+    for family in &ir.families {
+        push(&mut entries, "family", family.name.clone(), &family.provenance);
+        for outlet in &family.outlets {
+            push(&mut entries, "outlet", outlet.id.unwrap_or(0).to_string(), &outlet.provenance);
+        }
+        for data_block in &family.data_blocks {
+            push(&mut entries, "market_data", data_block.outlet_id.to_string(), &data_block.provenance);
+        }
+        for relationship in &family.relationships {
+            match relationship {
+                IRRelationship::Diachronic(link) => {
+                    push(&mut entries, "diachronic_link", link.name.clone(), &link.provenance)
+                }
+                IRRelationship::Synchronous(link) => {
+                    push(&mut entries, "synchronous_link", link.name.clone(), &link.provenance)
+                }
+            }
+        }
+    }
+    for event in &ir.events {
+        push(&mut entries, "event", event.name.clone(), &event.provenance);
+    }
+    for company in &ir.companies {
+        push(&mut entries, "company", company.id.to_string(), &company.provenance);
+    }
+    for link in &ir.outlet_party_links {
+        push(&mut entries, "outlet_party_link", link.name.clone(), &link.provenance);
+    }
+    for person in &ir.persons {
+        push(&mut entries, "person", person.id.to_string(), &person.provenance);
+    }
+    for ownership in &ir.ownerships {
+        push(&mut entries, "ownership", ownership.name.clone(), &ownership.provenance);
+    }
+    for link in &ir.identifier_links {
+        push(&mut entries, "identifier_link", link.name.clone(), &link.provenance);
+    }
+
+    entries
+}
+
+/// Composite ANMI source ID of a metric source for a data year
+///
+/// Numeric sources are taken to be composite IDs already. Catalog sources are
+/// looked up by their type code in the source table, a `VOCABULARY
+/// sources_names` keyed by ANMI source ID: the ID whose type code and year
+/// match (`3042008`, ÖWA 4th quarter 2008, for type code 30 and 2008). Without
+/// a source table the ID is the type code followed by the five-digit year
+/// (ÖAK 2021 -> 1502021).
+pub(crate) fn anmi_source_id(ir: &IRProgram, source: &str, year: u32) -> Option<u64> {
+    if !source.is_empty() && source.chars().all(|c| c.is_ascii_digit()) {
+        return source.parse().ok();
+    }
+
+    let type_code = ir
+        .catalogs
+        .iter()
+        .flat_map(|catalog| &catalog.sources)
+        .find(|declared| declared.name == source)
+        .and_then(|declared| declared.anmi_type_code)? as u64;
+
+    match source_table(ir) {
+        Some(table) => table
+            .entries
+            .iter()
+            .filter_map(|entry| match entry.key {
+                IRVocabularyKey::Number(id) if id >= 0.0 => Some(id as u64),
+                _ => None,
+            })
+            .find(|id| id / 100_000 == type_code && id % 10_000 == year as u64),
+        None => Some(type_code * 100_000 + year as u64),
+    }
+}
+
+/// Name of a source in the source table
+fn source_table_name(ir: &IRProgram, id: u64) -> Option<String> {
+    source_table(ir)?
+        .entries
+        .iter()
+        .find(|entry| matches!(entry.key, IRVocabularyKey::Number(key) if key as u64 == id))
+        .map(|entry| entry.value.clone())
+}
+
+/// The ANMI source table (`VOCABULARY sources_names`), if the program declares it
+fn source_table(ir: &IRProgram) -> Option<&IRVocabulary> {
+    ir.vocabularies
+        .iter()
+        .find(|vocabulary| vocabulary.name == "sources_names")
+}
+
+/// Composite IDs and titles of the sources cited by market data, in citation order
+pub(crate) fn cited_sources(ir: &IRProgram) -> Vec<(u64, String)> {
+    let mut sources: Vec<(u64, String)> = Vec::new();
+
+    for family in &ir.families {
+        for data_block in &family.data_blocks {
+            for year in &data_block.years {
+                for metric in &year.metrics {
+                    let Some(id) = anmi_source_id(ir, &metric.source, year.year) else {
+                        continue;
+                    };
+                    if sources.iter().any(|(existing, _)| *existing == id) {
+                        continue;
+                    }
+
+                    // ANMI names sources as in the source table, or by their title key
+                    let title = source_table_name(ir, id).unwrap_or_else(|| {
+                        ir.catalogs
+                            .iter()
+                            .flat_map(|catalog| &catalog.sources)
+                            .find(|declared| declared.name == metric.source)
+                            .and_then(|declared| {
+                                declared.fields.iter().find_map(|field| match &field.value {
+                                    IRExpression::String(title)
+                                        if field.name == "source_title_key" =>
+                                    {
+                                        Some(title.clone())
+                                    }
+                                    _ => None,
+                                })
+                            })
+                            .unwrap_or_else(|| metric.source.clone())
+                    });
+                    sources.push((id, title));
+                }
+            }
+        }
+    }
+
+    sources
+}
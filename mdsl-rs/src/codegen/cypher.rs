@@ -1,5 +1,6 @@
 //! Cypher code generator for Neo4j graph database
 
+use crate::codegen::common::provenance_entries;
 use crate::error::Result;
 use crate::ir::nodes::*;
 
@@ -168,6 +169,42 @@ impl CypherGenerator {
         }
     }
 
+    /// Get the company label based on prefix
+    fn company_label(&self) -> String {
+        if self.prefix.is_empty() {
+            "Company".to_string()
+        } else {
+            format!("{}_Company", self.prefix)
+        }
+    }
+
+    /// Get the person label based on prefix
+    fn person_label(&self) -> String {
+        if self.prefix.is_empty() {
+            "Person".to_string()
+        } else {
+            format!("{}_Person", self.prefix)
+        }
+    }
+
+    /// Get the identifier label based on prefix
+    fn identifier_label(&self) -> String {
+        if self.prefix.is_empty() {
+            "Identifier".to_string()
+        } else {
+            format!("{}_Identifier", self.prefix)
+        }
+    }
+
+    /// Get the source label based on prefix
+    fn source_label(&self) -> String {
+        if self.prefix.is_empty() {
+            "Source".to_string()
+        } else {
+            format!("{}_Source", self.prefix)
+        }
+    }
+
     /// Get the data aggregation label based on prefix
     fn data_aggregation_label(&self) -> String {
         if self.prefix.is_empty() {
@@ -261,6 +298,15 @@ impl CypherGenerator {
         // Generate event nodes
         cypher.push_str(&self.generate_event_nodes(ir)?);
 
+        // Generate company nodes and outlet-party links
+        cypher.push_str(&self.generate_company_graph(ir)?);
+
+        // Generate source nodes and citations
+        cypher.push_str(&self.generate_source_graph(ir)?);
+
+        // Generate provenance edges to source nodes
+        cypher.push_str(&self.generate_provenance_graph(ir)?);
+
         Ok(cypher)
     }
 
@@ -327,6 +373,15 @@ impl CypherGenerator {
         // Generate event nodes
         cypher.push_str(&self.generate_event_nodes(ir)?);
 
+        // Generate company nodes and outlet-party links
+        cypher.push_str(&self.generate_company_graph(ir)?);
+
+        // Generate source nodes and citations
+        cypher.push_str(&self.generate_source_graph(ir)?);
+
+        // Generate provenance edges to source nodes
+        cypher.push_str(&self.generate_provenance_graph(ir)?);
+
         Ok(cypher)
     }
 
@@ -341,7 +396,9 @@ impl CypherGenerator {
         cypher.push_str(&format!("CREATE CONSTRAINT {}media_outlet_id_unique IF NOT EXISTS FOR (o:{}) REQUIRE o.id_mo IS UNIQUE;\n", self.constraint_prefix(), self.media_outlet_label()));
         cypher.push_str(&format!("CREATE CONSTRAINT {}family_name_unique IF NOT EXISTS FOR (f:{}) REQUIRE f.name IS UNIQUE;\n", self.constraint_prefix(), self.family_label()));
         cypher.push_str(&format!("CREATE CONSTRAINT {}template_name_unique IF NOT EXISTS FOR (t:{}) REQUIRE t.name IS UNIQUE;\n", self.constraint_prefix(), self.template_label()));
-        cypher.push_str(&format!("CREATE CONSTRAINT {}vocab_name_unique IF NOT EXISTS FOR (v:{}) REQUIRE v.name IS UNIQUE;\n\n", self.constraint_prefix(), self.vocabulary_label()));
+        cypher.push_str(&format!("CREATE CONSTRAINT {}vocab_name_unique IF NOT EXISTS FOR (v:{}) REQUIRE v.name IS UNIQUE;\n", self.constraint_prefix(), self.vocabulary_label()));
+        cypher.push_str(&format!("CREATE CONSTRAINT {}company_id_unique IF NOT EXISTS FOR (c:{}) REQUIRE c.id_company IS UNIQUE;\n", self.constraint_prefix(), self.company_label()));
+        cypher.push_str(&format!("CREATE CONSTRAINT {}person_id_unique IF NOT EXISTS FOR (p:{}) REQUIRE p.id_person IS UNIQUE;\n\n", self.constraint_prefix(), self.person_label()));
 
         // Indexes - updated for media_outlet schema with configurable prefix
         cypher.push_str(&format!(
@@ -755,13 +812,14 @@ impl CypherGenerator {
                                 .replace("-", "_")
                         };
                         cypher.push_str(&format!(
-                            "MATCH (pred:{} {{id_mo: {}}}), (succ:{} {{id_mo: {}}}) MERGE (pred)-[r:{}]->(succ) SET r.event_rel = datetime('{}');\n",
+                            "MATCH (pred:{} {{id_mo: {}}}), (succ:{} {{id_mo: {}}}) MERGE (pred)-[r:{}]->(succ) SET r.event_rel = datetime('{}'){};\n",
                             self.media_outlet_label(),
                             diachronic.predecessor,
                             self.media_outlet_label(),
                             diachronic.successor,
                             self.relationship_type(&rel_type),
-                            diachronic.event_start_date.as_ref().unwrap_or(&"1900-01-01".to_string())
+                            diachronic.event_start_date.as_ref().unwrap_or(&"1900-01-01".to_string()),
+                            self.provenance_properties(&diachronic.provenance)
                         ));
                     }
                     IRRelationship::Synchronous(sync) => {
@@ -772,15 +830,32 @@ impl CypherGenerator {
                         } else {
                             sync.relationship_type.replace("'", "").replace("-", "_")
                         };
+                        // Directed types point from source to target, symmetric
+                        // types get an undirected edge in canonical order
+                        let (outlet_1, outlet_2, arrow) =
+                            match ir.relationship_type(&sync.relationship_type) {
+                                Some(semantics) => {
+                                    let (outlet_1, outlet_2) =
+                                        semantics.endpoints(&sync.outlet_1, &sync.outlet_2);
+                                    let arrow = match semantics.direction {
+                                        LinkDirection::Directed => "->",
+                                        LinkDirection::Symmetric => "-",
+                                    };
+                                    (outlet_1, outlet_2, arrow)
+                                }
+                                None => (sync.outlet_1.id, sync.outlet_2.id, "->"),
+                            };
                         cypher.push_str(&format!(
-                            "MATCH (o1:{} {{id_mo: {}}}), (o2:{} {{id_mo: {}}}) MERGE (o1)-[r:{}]->(o2) SET r.start_rel = datetime('{}'), r.end_rel = datetime('{}');\n",
+                            "MATCH (o1:{} {{id_mo: {}}}), (o2:{} {{id_mo: {}}}) MERGE (o1)-[r:{}]{}(o2) SET r.start_rel = datetime('{}'), r.end_rel = datetime('{}'){};\n",
                             self.media_outlet_label(),
-                            sync.outlet_1.id,
+                            outlet_1,
                             self.media_outlet_label(),
-                            sync.outlet_2.id,
+                            outlet_2,
                             self.relationship_type(&rel_type),
+                            arrow,
                             sync.period_start.as_ref().unwrap_or(&"1900-01-01".to_string()),
-                            sync.period_end.as_ref().unwrap_or(&"9999-01-01".to_string())
+                            sync.period_end.as_ref().unwrap_or(&"9999-01-01".to_string()),
+                            self.provenance_properties(&sync.provenance)
                         ));
                     }
                 }
@@ -1027,4 +1102,366 @@ impl CypherGenerator {
 
         Ok(cypher)
     }
+
+    /// Generate company nodes and their links to media outlets
+    fn generate_company_graph(&self, ir: &IRProgram) -> Result<String> {
+        let mut cypher = String::new();
+
+        if ir.companies.is_empty()
+            && ir.outlet_party_links.is_empty()
+            && ir.persons.is_empty()
+            && ir.ownerships.is_empty()
+            && ir.identifier_links.is_empty()
+        {
+            return Ok(cypher);
+        }
+
+        cypher.push_str("// COMPANIES, PERSONS AND OWNERSHIP\n");
+
+        for company in &ir.companies {
+            cypher.push_str(&format!("// Company: {}\n", company.name));
+            cypher.push_str(&format!(
+                "MERGE (c:{} {{id_company: {}}})\n",
+                self.company_label(),
+                company.id
+            ));
+            cypher.push_str(&format!(
+                "ON CREATE SET c.name = '{}', c.comment = {}, c.created_at = datetime()\n",
+                company.name.replace('\'', "\\'"),
+                self.optional_string_to_cypher(&company.comment)
+            ));
+            cypher.push_str(&format!(
+                "ON MATCH SET c.name = '{}', c.comment = {};\n",
+                company.name.replace('\'', "\\'"),
+                self.optional_string_to_cypher(&company.comment)
+            ));
+
+            for field in &company.fields {
+                cypher.push_str(&format!(
+                    "MATCH (c:{} {{id_company: {}}}) SET c.{} = '{}';\n",
+                    self.company_label(),
+                    company.id,
+                    field.name,
+                    self.expression_to_cypher_value(&field.value)
+                        .replace('\'', "\\'")
+                ));
+            }
+        }
+
+        for link in &ir.outlet_party_links {
+            cypher.push_str(&format!("// Outlet-party link: {}\n", link.name));
+            let rel_type = match link.role.as_str() {
+                "publisher" => "PUBLISHES".to_string(),
+                "broadcaster" => "BROADCASTS".to_string(),
+                "licence_holder" => "HOLDS_LICENCE_FOR".to_string(),
+                "operator" => "OPERATES".to_string(),
+                "owner" => "OWNS".to_string(),
+                "" => "RUNS".to_string(),
+                other => other.to_uppercase().replace(['-', ' ', '\''], "_"),
+            };
+            // Open-ended periods use the same far-future sentinel as synchronous relationships
+            let period_end = match link.period_end.as_deref() {
+                None | Some("CURRENT") => "9999-01-01".to_string(),
+                Some(date) => date.replace('\'', "\\'"),
+            };
+            cypher.push_str(&format!(
+                "MATCH (c:{} {{id_company: {}}}), (o:{} {{id_mo: {}}}) MERGE (c)-[r:{} {{name: '{}'}}]->(o) SET r.role = '{}', r.start_rel = datetime('{}'), r.end_rel = datetime('{}'), r.source = {}{};\n",
+                self.company_label(),
+                link.party_id,
+                self.media_outlet_label(),
+                link.outlet_id,
+                self.relationship_type(&rel_type),
+                link.name.replace('\'', "\\'"),
+                link.role.replace('\'', "\\'"),
+                link.period_start.as_deref().unwrap_or("1900-01-01").replace('\'', "\\'"),
+                period_end,
+                self.optional_string_to_cypher(&link.source),
+                self.provenance_properties(&link.provenance)
+            ));
+        }
+
+        for person in &ir.persons {
+            cypher.push_str(&format!("// Person: {}\n", person.name));
+            cypher.push_str(&format!(
+                "MERGE (p:{} {{id_person: {}}})\n",
+                self.person_label(),
+                person.id
+            ));
+            cypher.push_str(&format!(
+                "ON CREATE SET p.name = '{}', p.comment = {}, p.created_at = datetime()\n",
+                person.name.replace('\'', "\\'"),
+                self.optional_string_to_cypher(&person.comment)
+            ));
+            cypher.push_str(&format!(
+                "ON MATCH SET p.name = '{}', p.comment = {};\n",
+                person.name.replace('\'', "\\'"),
+                self.optional_string_to_cypher(&person.comment)
+            ));
+
+            for field in &person.fields {
+                cypher.push_str(&format!(
+                    "MATCH (p:{} {{id_person: {}}}) SET p.{} = '{}';\n",
+                    self.person_label(),
+                    person.id,
+                    field.name,
+                    self.expression_to_cypher_value(&field.value)
+                        .replace('\'', "\\'")
+                ));
+            }
+        }
+
+        for ownership in &ir.ownerships {
+            let owner = match ownership.owner {
+                Some(IROwner::Company(id)) => {
+                    format!("(h:{} {{id_company: {}}})", self.company_label(), id)
+                }
+                Some(IROwner::Person(id)) => {
+                    format!("(h:{} {{id_person: {}}})", self.person_label(), id)
+                }
+                None => continue,
+            };
+            cypher.push_str(&format!("// Ownership: {}\n", ownership.name));
+            let period_end = match ownership.period_end.as_deref() {
+                None | Some("CURRENT") => "9999-01-01".to_string(),
+                Some(date) => date.replace('\'', "\\'"),
+            };
+            cypher.push_str(&format!(
+                "MATCH {}, (c:{} {{id_company: {}}}) MERGE (h)-[r:{} {{name: '{}'}}]->(c) SET r.share_pct = {}, r.role = {}, r.start_rel = datetime('{}'), r.end_rel = datetime('{}'), r.source_url = {}, r.scraped_at = {}{};\n",
+                owner,
+                self.company_label(),
+                ownership.owned_company_id,
+                self.relationship_type("HOLDS_SHARE_IN"),
+                ownership.name.replace('\'', "\\'"),
+                ownership
+                    .share_pct
+                    .map_or("null".to_string(), |share| share.to_string()),
+                self.optional_string_to_cypher(&ownership.role),
+                ownership
+                    .period_start
+                    .as_deref()
+                    .unwrap_or("1900-01-01")
+                    .replace('\'', "\\'"),
+                period_end,
+                self.optional_string_to_cypher(&ownership.source_url),
+                self.optional_string_to_cypher(&ownership.scraped_at),
+                self.provenance_properties(&ownership.provenance)
+            ));
+        }
+
+        for link in &ir.identifier_links {
+            cypher.push_str(&format!("// Identifier link: {}\n", link.name));
+            cypher.push_str(&format!(
+                "MATCH (c:{} {{id_company: {}}}) MERGE (i:{} {{scheme: '{}', value: '{}'}}) MERGE (c)-[r:{} {{name: '{}'}}]->(i) SET r.status = {}, r.score = {}, r.issued_by = {}{};\n",
+                self.company_label(),
+                link.company_id,
+                self.identifier_label(),
+                link.scheme.replace('\'', "\\'"),
+                link.value.replace('\'', "\\'"),
+                self.relationship_type("HAS_IDENTIFIER"),
+                link.name.replace('\'', "\\'"),
+                self.optional_string_to_cypher(&link.status),
+                link.score.map_or("null".to_string(), |score| score.to_string()),
+                self.optional_string_to_cypher(&link.issued_by),
+                self.provenance_properties(&link.provenance)
+            ));
+        }
+
+        cypher.push('\n');
+
+        Ok(cypher)
+    }
+    /// Generate catalog source nodes and CITES edges from metrics and events
+    fn generate_source_graph(&self, ir: &IRProgram) -> Result<String> {
+        let mut cypher = String::new();
+
+        let has_citations = ir.events.iter().any(|event| !event.provenance.is_empty())
+            || ir.families.iter().any(|family| {
+                family.data_blocks.iter().any(|data_block| {
+                    data_block
+                        .years
+                        .iter()
+                        .any(|year| year.metrics.iter().any(|metric| !metric.source.is_empty()))
+                })
+            });
+        if ir.catalogs.is_empty() && !has_citations {
+            return Ok(cypher);
+        }
+
+        cypher.push_str("// SOURCES\n");
+
+        for catalog in &ir.catalogs {
+            for source in &catalog.sources {
+                cypher.push_str(&format!("// Source: {}\n", source.name));
+                cypher.push_str(&format!(
+                    "MERGE (s:{} {{name: '{}'}})\n",
+                    self.source_label(),
+                    source.name.replace('\'', "\\'")
+                ));
+                cypher.push_str(&format!(
+                    "ON CREATE SET s.catalog = '{}', s.quality_tier = {}, s.maps_to = {}, s.created_at = datetime()\n",
+                    catalog.name.replace('\'', "\\'"),
+                    self.optional_string_to_cypher(&source.quality_tier),
+                    self.optional_string_to_cypher(&source.maps_to)
+                ));
+                cypher.push_str(&format!(
+                    "ON MATCH SET s.catalog = '{}', s.quality_tier = {}, s.maps_to = {};\n",
+                    catalog.name.replace('\'', "\\'"),
+                    self.optional_string_to_cypher(&source.quality_tier),
+                    self.optional_string_to_cypher(&source.maps_to)
+                ));
+
+                for field in &source.fields {
+                    cypher.push_str(&format!(
+                        "MATCH (s:{} {{name: '{}'}}) SET s.{} = '{}';\n",
+                        self.source_label(),
+                        source.name.replace('\'', "\\'"),
+                        field.name.replace('.', "_"),
+                        self.expression_to_cypher_value(&field.value)
+                            .replace('\'', "\\'")
+                    ));
+                }
+            }
+        }
+
+        for family in &ir.families {
+            for data_block in &family.data_blocks {
+                for year in &data_block.years {
+                    for metric in &year.metrics {
+                        if metric.source.is_empty() {
+                            continue;
+                        }
+                        cypher.push_str(&format!(
+                            "MATCH (o:{} {{id_mo: {}}}) MERGE (s:{} {{name: '{}'}}) MERGE (o)-[c:{} {{metric: '{}', year: {}}}]->(s);\n",
+                            self.media_outlet_label(),
+                            data_block.outlet_id,
+                            self.source_label(),
+                            metric.source.replace('\'', "\\'"),
+                            self.relationship_type("CITES"),
+                            metric.name.replace('\'', "\\'"),
+                            year.year
+                        ));
+                    }
+                }
+            }
+        }
+
+        for event in &ir.events {
+            let mut cited: Vec<&str> = Vec::new();
+            for record in &event.provenance {
+                if record.source.is_empty() || cited.contains(&record.source.as_str()) {
+                    continue;
+                }
+                cited.push(&record.source);
+                cypher.push_str(&format!(
+                    "MATCH (e:{} {{name: '{}'}}) MERGE (s:{} {{name: '{}'}}) MERGE (e)-[c:{}]->(s);\n",
+                    self.event_label(),
+                    event.name.replace('\'', "\\'"),
+                    self.source_label(),
+                    record.source.replace('\'', "\\'"),
+                    self.relationship_type("CITES")
+                ));
+            }
+        }
+
+        cypher.push('\n');
+
+        Ok(cypher)
+    }
+
+    /// Generate ASSERTED_BY edges from node subjects to their sources
+    ///
+    /// Relationship subjects cannot carry edges, so their provenance is set
+    /// as relationship properties instead (see `provenance_properties`).
+    fn generate_provenance_graph(&self, ir: &IRProgram) -> Result<String> {
+        let mut cypher = String::new();
+
+        let entries: Vec<_> = provenance_entries(ir)
+            .into_iter()
+            .filter(|entry| {
+                matches!(
+                    entry.kind,
+                    "family" | "outlet" | "market_data" | "event" | "company" | "person"
+                )
+            })
+            .collect();
+
+        if entries.is_empty() {
+            return Ok(cypher);
+        }
+
+        cypher.push_str("// PROVENANCE\n");
+
+        for entry in entries {
+            let record = entry.record;
+            let subject = match entry.kind {
+                "family" => format!(
+                    "(n:{} {{name: '{}'}})",
+                    self.family_label(),
+                    entry.key.replace('\'', "\\'")
+                ),
+                "outlet" | "market_data" => {
+                    format!("(n:{} {{id_mo: {}}})", self.media_outlet_label(), entry.key)
+                }
+                "event" => format!(
+                    "(n:{} {{name: '{}'}})",
+                    self.event_label(),
+                    entry.key.replace('\'', "\\'")
+                ),
+                "company" => format!("(n:{} {{id_company: {}}})", self.company_label(), entry.key),
+                _ => format!("(n:{} {{id_person: {}}})", self.person_label(), entry.key),
+            };
+            // Market data records hang off the outlet node, so qualify their field
+            let field = match (entry.kind, &record.target) {
+                ("market_data", Some(target)) => format!("data.{}", target),
+                ("market_data", None) => "data".to_string(),
+                (_, target) => target.clone().unwrap_or_default(),
+            };
+            cypher.push_str(&format!(
+                "MATCH {} MERGE (s:{} {{name: '{}'}}) MERGE (n)-[p:{} {{field: '{}'}}]->(s) SET p.quality_tier = {}, p.confidence = {}, p.verification = {}, p.verified_at = {}, p.note = {};\n",
+                subject,
+                self.source_label(),
+                record.source.replace('\'', "\\'"),
+                self.relationship_type("ASSERTED_BY"),
+                field.replace('\'', "\\'"),
+                self.optional_string_to_cypher(&record.quality_tier),
+                record.confidence.map_or("null".to_string(), |confidence| confidence.to_string()),
+                self.optional_string_to_cypher(&record.verification),
+                self.optional_string_to_cypher(&record.verified_at),
+                self.optional_string_to_cypher(&record.note)
+            ));
+        }
+
+        cypher.push('\n');
+
+        Ok(cypher)
+    }
+
+    /// Render provenance records as additional SET items on a relationship `r`
+    ///
+    /// Field-level records are prefixed with the field name so that several
+    /// records on one relationship do not overwrite each other.
+    fn provenance_properties(&self, records: &[IRProvenance]) -> String {
+        let mut properties = String::new();
+
+        for record in records {
+            let prefix = match &record.target {
+                Some(target) => format!(
+                    "{}_provenance",
+                    target.replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+                ),
+                None => "provenance".to_string(),
+            };
+            properties.push_str(&format!(
+                ", r.{prefix}_source = '{}', r.{prefix}_quality_tier = {}, r.{prefix}_confidence = {}, r.{prefix}_verification = {}, r.{prefix}_verified_at = {}, r.{prefix}_note = {}",
+                record.source.replace('\'', "\\'"),
+                self.optional_string_to_cypher(&record.quality_tier),
+                record.confidence.map_or("null".to_string(), |confidence| confidence.to_string()),
+                self.optional_string_to_cypher(&record.verification),
+                self.optional_string_to_cypher(&record.verified_at),
+                self.optional_string_to_cypher(&record.note)
+            ));
+        }
+
+        properties
+    }
 }
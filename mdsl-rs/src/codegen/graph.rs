@@ -0,0 +1,562 @@
+//! Graph exports for network analysis tools (GraphML, GEXF, Graphviz DOT)
+//!
+//! Outlets, families, companies and persons become nodes; family membership,
+//! synchronous and diachronic links, outlet-party links and ownership
+//! relations become edges. Dates are padded to full days so that Gephi can
+//! place nodes and edges on its timeline: an outlet lives during its `active`
+//! lifecycle statuses, a link during its period (a diachronic link on its
+//! event date). Endpoints that are referenced but not declared are exported
+//! as nodes labelled with their ID.
+
+use crate::error::Result;
+use crate::ir::nodes::*;
+use crate::utils::dates::{first_day, last_day};
+use crate::utils::dot::quote as dot_quote;
+use crate::utils::xml::escape as xml_escape;
+use std::collections::HashSet;
+
+/// A node of the exported graph
+struct Node {
+    id: String,
+    label: String,
+    kind: &'static str,
+    family: Option<String>,
+    /// Periods the node exists in; empty if always
+    periods: Vec<Period>,
+}
+
+/// An edge of the exported graph
+struct Edge {
+    source: String,
+    target: String,
+    kind: &'static str,
+    relation: String,
+    directed: bool,
+    period: Period,
+    weight: Option<f64>,
+}
+
+/// Start and end day, `None` for an open bound
+#[derive(Clone, Default)]
+struct Period {
+    start: Option<String>,
+    end: Option<String>,
+}
+
+impl Period {
+    fn new(start: Option<&str>, end: Option<&str>) -> Self {
+        fn bound(date: Option<&str>) -> Option<&str> {
+            date.map(str::trim)
+                .filter(|date| !date.is_empty() && *date != "CURRENT")
+        }
+        Period {
+            start: bound(start).map(first_day),
+            end: bound(end).map(last_day),
+        }
+    }
+
+    /// Hull of several periods; open if any of them is open
+    fn hull(periods: &[Period]) -> Period {
+        if periods.is_empty() {
+            return Period::default();
+        }
+        let start = periods
+            .iter()
+            .map(|p| p.start.clone())
+            .collect::<Option<Vec<_>>>()
+            .and_then(|starts| starts.into_iter().min());
+        let end = periods
+            .iter()
+            .map(|p| p.end.clone())
+            .collect::<Option<Vec<_>>>()
+            .and_then(|ends| ends.into_iter().max());
+        Period { start, end }
+    }
+
+    /// Human-readable form for DOT labels; empty if fully open
+    fn describe(&self) -> String {
+        match (&self.start, &self.end) {
+            (Some(start), Some(end)) if start == end => start.clone(),
+            (Some(start), Some(end)) => format!("{} to {}", start, end),
+            (Some(start), None) => format!("since {}", start),
+            (None, Some(end)) => format!("until {}", end),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+/// Graph built from an IR program, shared by all exporters
+struct Graph {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+fn outlet_node(id: u32) -> String {
+    format!("outlet_{}", id)
+}
+
+fn company_node(id: u32) -> String {
+    format!("company_{}", id)
+}
+
+fn person_node(id: u32) -> String {
+    format!("person_{}", id)
+}
+
+impl Graph {
+    fn build(ir: &IRProgram) -> Self {
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        let mut declared = HashSet::new();
+
+        for (index, family) in ir.families.iter().enumerate() {
+            let family_id = format!("family_{}", index);
+            declared.insert(family_id.clone());
+            nodes.push(Node {
+                id: family_id.clone(),
+                label: family.name.clone(),
+                kind: "family",
+                family: None,
+                periods: Vec::new(),
+            });
+            for outlet in &family.outlets {
+                let Some(id) = outlet.id else { continue };
+                let node_id = outlet_node(id);
+                // First declaration wins, as in the other generators
+                if !declared.insert(node_id.clone()) {
+                    continue;
+                }
+                let periods = outlet
+                    .blocks
+                    .iter()
+                    .filter_map(|block| match block {
+                        IROutletBlock::Lifecycle(statuses) => Some(statuses),
+                        _ => None,
+                    })
+                    .flatten()
+                    .filter(|status| status.status.eq_ignore_ascii_case("active"))
+                    .map(|status| {
+                        Period::new(status.start_date.as_deref(), status.end_date.as_deref())
+                    })
+                    .collect();
+                nodes.push(Node {
+                    id: node_id.clone(),
+                    label: outlet.name.clone(),
+                    kind: "outlet",
+                    family: Some(family.name.clone()),
+                    periods,
+                });
+                edges.push(Edge {
+                    source: family_id.clone(),
+                    target: node_id,
+                    kind: "family",
+                    relation: "member".to_string(),
+                    directed: true,
+                    period: Period::default(),
+                    weight: None,
+                });
+            }
+        }
+
+        for relationship in ir.families.iter().flat_map(|f| &f.relationships) {
+            edges.push(match relationship {
+                IRRelationship::Synchronous(link) => Edge {
+                    source: outlet_node(link.outlet_1.id),
+                    target: outlet_node(link.outlet_2.id),
+                    kind: "synchronous",
+                    relation: link.relationship_type.clone(),
+                    directed: false,
+                    period: Period::new(link.period_start.as_deref(), link.period_end.as_deref()),
+                    weight: None,
+                },
+                IRRelationship::Diachronic(link) => Edge {
+                    source: outlet_node(link.predecessor),
+                    target: outlet_node(link.successor),
+                    kind: "diachronic",
+                    relation: link.relationship_type.clone(),
+                    directed: true,
+                    period: Period::new(
+                        link.event_start_date.as_deref(),
+                        link.event_end_date
+                            .as_deref()
+                            .or(link.event_start_date.as_deref()),
+                    ),
+                    weight: None,
+                },
+            });
+        }
+
+        for company in &ir.companies {
+            if declared.insert(company_node(company.id)) {
+                nodes.push(Node {
+                    id: company_node(company.id),
+                    label: company.name.clone(),
+                    kind: "company",
+                    family: None,
+                    periods: Vec::new(),
+                });
+            }
+        }
+        for person in &ir.persons {
+            if declared.insert(person_node(person.id)) {
+                nodes.push(Node {
+                    id: person_node(person.id),
+                    label: person.name.clone(),
+                    kind: "person",
+                    family: None,
+                    periods: Vec::new(),
+                });
+            }
+        }
+
+        for link in &ir.outlet_party_links {
+            edges.push(Edge {
+                source: company_node(link.party_id),
+                target: outlet_node(link.outlet_id),
+                kind: "outlet_party",
+                relation: link.role.clone(),
+                directed: true,
+                period: Period::new(link.period_start.as_deref(), link.period_end.as_deref()),
+                weight: None,
+            });
+        }
+        for ownership in &ir.ownerships {
+            let Some(owner) = &ownership.owner else {
+                continue;
+            };
+            edges.push(Edge {
+                source: match owner {
+                    IROwner::Company(id) => company_node(*id),
+                    IROwner::Person(id) => person_node(*id),
+                },
+                target: company_node(ownership.owned_company_id),
+                kind: "ownership",
+                relation: ownership
+                    .role
+                    .clone()
+                    .unwrap_or_else(|| "owner".to_string()),
+                directed: true,
+                period: Period::new(
+                    ownership.period_start.as_deref(),
+                    ownership.period_end.as_deref(),
+                ),
+                weight: ownership.share_pct,
+            });
+        }
+
+        // Referenced but undeclared endpoints
+        for edge in &edges {
+            for endpoint in [&edge.source, &edge.target] {
+                if declared.insert(endpoint.clone()) {
+                    let (kind, id) = endpoint.split_once('_').unwrap_or(("outlet", endpoint));
+                    nodes.push(Node {
+                        id: endpoint.clone(),
+                        label: id.to_string(),
+                        kind: match kind {
+                            "company" => "company",
+                            "person" => "person",
+                            _ => "outlet",
+                        },
+                        family: None,
+                        periods: Vec::new(),
+                    });
+                }
+            }
+        }
+
+        Graph { nodes, edges }
+    }
+}
+
+/// GraphML generator
+#[derive(Default)]
+pub struct GraphMlGenerator;
+
+impl GraphMlGenerator {
+    /// Create a new GraphML generator
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Generate a GraphML document from IR
+    pub fn generate(&self, ir: &IRProgram) -> Result<String> {
+        let graph = Graph::build(ir);
+        let mut out = String::new();
+        out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        for (id, domain, kind) in [
+            ("label", "node", "string"),
+            ("kind", "all", "string"),
+            ("family", "node", "string"),
+            ("relation", "edge", "string"),
+            ("start", "all", "string"),
+            ("end", "all", "string"),
+            ("weight", "edge", "double"),
+        ] {
+            out.push_str(&format!(
+                "  <key id=\"{0}\" for=\"{1}\" attr.name=\"{0}\" attr.type=\"{2}\"/>\n",
+                id, domain, kind
+            ));
+        }
+        out.push_str("  <graph id=\"mdsl\" edgedefault=\"directed\">\n");
+
+        let data = |key: &str, value: &str| {
+            format!("      <data key=\"{}\">{}</data>\n", key, xml_escape(value))
+        };
+        for node in &graph.nodes {
+            out.push_str(&format!("    <node id=\"{}\">\n", xml_escape(&node.id)));
+            out.push_str(&data("label", &node.label));
+            out.push_str(&data("kind", node.kind));
+            if let Some(family) = &node.family {
+                out.push_str(&data("family", family));
+            }
+            let period = Period::hull(&node.periods);
+            if let Some(start) = &period.start {
+                out.push_str(&data("start", start));
+            }
+            if let Some(end) = &period.end {
+                out.push_str(&data("end", end));
+            }
+            out.push_str("    </node>\n");
+        }
+        for (index, edge) in graph.edges.iter().enumerate() {
+            out.push_str(&format!(
+                "    <edge id=\"e{}\" source=\"{}\" target=\"{}\"{}>\n",
+                index,
+                xml_escape(&edge.source),
+                xml_escape(&edge.target),
+                if edge.directed {
+                    ""
+                } else {
+                    " directed=\"false\""
+                }
+            ));
+            out.push_str(&data("kind", edge.kind));
+            out.push_str(&data("relation", &edge.relation));
+            if let Some(start) = &edge.period.start {
+                out.push_str(&data("start", start));
+            }
+            if let Some(end) = &edge.period.end {
+                out.push_str(&data("end", end));
+            }
+            if let Some(weight) = edge.weight {
+                out.push_str(&data("weight", &weight.to_string()));
+            }
+            out.push_str("    </edge>\n");
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        Ok(out)
+    }
+}
+
+/// GEXF generator for Gephi, with a dynamic (timeline) graph
+#[derive(Default)]
+pub struct GexfGenerator;
+
+impl GexfGenerator {
+    /// Create a new GEXF generator
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Generate a GEXF 1.3 document from IR
+    pub fn generate(&self, ir: &IRProgram) -> Result<String> {
+        let graph = Graph::build(ir);
+        let mut out = String::new();
+        out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n");
+        out.push_str("  <meta>\n    <creator>mdsl-rs</creator>\n  </meta>\n");
+        out.push_str(
+            "  <graph mode=\"dynamic\" defaultedgetype=\"directed\" timeformat=\"date\">\n",
+        );
+        out.push_str("    <attributes class=\"node\">\n");
+        out.push_str("      <attribute id=\"kind\" title=\"kind\" type=\"string\"/>\n");
+        out.push_str("      <attribute id=\"family\" title=\"family\" type=\"string\"/>\n");
+        out.push_str("    </attributes>\n");
+        out.push_str("    <attributes class=\"edge\">\n");
+        out.push_str("      <attribute id=\"kind\" title=\"kind\" type=\"string\"/>\n");
+        out.push_str("    </attributes>\n");
+
+        let bounds = |period: &Period| {
+            let mut attrs = String::new();
+            if let Some(start) = &period.start {
+                attrs.push_str(&format!(" start=\"{}\"", xml_escape(start)));
+            }
+            if let Some(end) = &period.end {
+                attrs.push_str(&format!(" end=\"{}\"", xml_escape(end)));
+            }
+            attrs
+        };
+        let attvalue = |key: &str, value: &str| {
+            format!(
+                "          <attvalue for=\"{}\" value=\"{}\"/>\n",
+                key,
+                xml_escape(value)
+            )
+        };
+
+        out.push_str("    <nodes>\n");
+        for node in &graph.nodes {
+            // A single period goes on the node itself, several as spells
+            let single = match node.periods.as_slice() {
+                [period] => bounds(period),
+                _ => String::new(),
+            };
+            out.push_str(&format!(
+                "      <node id=\"{}\" label=\"{}\"{}>\n",
+                xml_escape(&node.id),
+                xml_escape(&node.label),
+                single
+            ));
+            out.push_str("        <attvalues>\n");
+            out.push_str(&attvalue("kind", node.kind));
+            if let Some(family) = &node.family {
+                out.push_str(&attvalue("family", family));
+            }
+            out.push_str("        </attvalues>\n");
+            if node.periods.len() > 1 {
+                out.push_str("        <spells>\n");
+                for period in &node.periods {
+                    out.push_str(&format!("          <spell{}/>\n", bounds(period)));
+                }
+                out.push_str("        </spells>\n");
+            }
+            out.push_str("      </node>\n");
+        }
+        out.push_str("    </nodes>\n");
+
+        out.push_str("    <edges>\n");
+        for (index, edge) in graph.edges.iter().enumerate() {
+            out.push_str(&format!(
+                "      <edge id=\"e{}\" source=\"{}\" target=\"{}\" label=\"{}\"{}{}{}>\n",
+                index,
+                xml_escape(&edge.source),
+                xml_escape(&edge.target),
+                xml_escape(&edge.relation),
+                if edge.directed {
+                    ""
+                } else {
+                    " type=\"undirected\""
+                },
+                edge.weight
+                    .map_or(String::new(), |weight| format!(" weight=\"{}\"", weight)),
+                bounds(&edge.period)
+            ));
+            out.push_str("        <attvalues>\n");
+            out.push_str(&attvalue("kind", edge.kind));
+            out.push_str("        </attvalues>\n");
+            out.push_str("      </edge>\n");
+        }
+        out.push_str("    </edges>\n");
+        out.push_str("  </graph>\n</gexf>\n");
+        Ok(out)
+    }
+}
+
+/// Graphviz DOT generator; families are drawn as clusters
+#[derive(Default)]
+pub struct DotGenerator;
+
+impl DotGenerator {
+    /// Create a new DOT generator
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Generate a DOT digraph from IR
+    pub fn generate(&self, ir: &IRProgram) -> Result<String> {
+        let graph = Graph::build(ir);
+        let mut out = String::new();
+        out.push_str("digraph mdsl {\n");
+        out.push_str("  rankdir=LR;\n");
+        out.push_str("  node [shape=box];\n");
+
+        let node_line = |node: &Node, indent: &str| {
+            let shape = match node.kind {
+                "company" => ", shape=ellipse",
+                "person" => ", shape=diamond",
+                _ => "",
+            };
+            format!(
+                "{}{} [label={}{}];\n",
+                indent,
+                dot_quote(&node.id),
+                dot_quote(&node.label),
+                shape
+            )
+        };
+
+        // Outlets are drawn inside the cluster of their family
+        for (index, family) in graph
+            .nodes
+            .iter()
+            .filter(|n| n.kind == "family")
+            .enumerate()
+        {
+            out.push_str(&format!(
+                "\n  subgraph {} {{\n",
+                dot_quote(&format!("cluster_{}", index))
+            ));
+            out.push_str(&format!("    label={};\n", dot_quote(&family.label)));
+            for edge in graph
+                .edges
+                .iter()
+                .filter(|e| e.kind == "family" && e.source == family.id)
+            {
+                if let Some(outlet) = graph.nodes.iter().find(|n| n.id == edge.target) {
+                    out.push_str(&node_line(outlet, "    "));
+                }
+            }
+            out.push_str("  }\n");
+        }
+
+        let members: HashSet<&str> = graph
+            .edges
+            .iter()
+            .filter(|e| e.kind == "family")
+            .map(|e| e.target.as_str())
+            .collect();
+        let others: Vec<&Node> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.kind != "family" && !members.contains(n.id.as_str()))
+            .collect();
+        if !others.is_empty() {
+            out.push('\n');
+        }
+        for node in others {
+            out.push_str(&node_line(node, "  "));
+        }
+
+        let links: Vec<&Edge> = graph.edges.iter().filter(|e| e.kind != "family").collect();
+        if !links.is_empty() {
+            out.push('\n');
+        }
+        for edge in links {
+            let mut label = edge.relation.clone();
+            if let Some(weight) = edge.weight {
+                label.push_str(&format!(" {}%", weight));
+            }
+            let period = edge.period.describe();
+            if !period.is_empty() {
+                label.push('\n');
+                label.push_str(&period);
+            }
+            let style = match edge.kind {
+                "synchronous" => ", dir=none, style=dashed",
+                "diachronic" => ", style=bold",
+                "outlet_party" => ", color=gray40",
+                _ => "",
+            };
+            out.push_str(&format!(
+                "  {} -> {} [label={}{}];\n",
+                dot_quote(&edge.source),
+                dot_quote(&edge.target),
+                dot_quote(&label),
+                style
+            ));
+        }
+        out.push_str("}\n");
+        Ok(out)
+    }
+}
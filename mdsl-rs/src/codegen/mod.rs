@@ -1,10 +1,16 @@
 //! Code generation (stub)
 
+pub mod anmi_csv;
 pub mod common;
 pub mod cypher;
+#[cfg(feature = "visualization")]
+pub mod graph;
 pub mod sql;
 pub mod sql_anmi;
 
+pub use anmi_csv::AnmiCsvGenerator;
 pub use cypher::CypherGenerator;
+#[cfg(feature = "visualization")]
+pub use graph::{DotGenerator, GexfGenerator, GraphMlGenerator};
 pub use sql::SqlGenerator;
 pub use sql_anmi::AnmiSqlGenerator;
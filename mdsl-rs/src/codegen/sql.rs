@@ -1,7 +1,10 @@
 //! SQL code generator
 
+use crate::analysis::ownership::OwnershipReport;
+use crate::codegen::common::provenance_entries;
 use crate::error::Result;
 use crate::ir::nodes::*;
+use std::collections::HashSet;
 
 /// SQL code generator
 pub struct SqlGenerator;
@@ -75,12 +78,97 @@ impl SqlGenerator {
         // Generate relationship tables
         sql.push_str(&self.generate_relationship_tables(ir)?);
 
+        // Generate source catalog insertion statements
+        sql.push_str(&self.generate_source_inserts(ir)?);
+
         // Generate data insertion statements
         sql.push_str(&self.generate_data_inserts(ir)?);
 
         // Generate event insertion statements
         sql.push_str(&self.generate_event_inserts(ir)?);
 
+        // Generate company, outlet-party link, person, ownership and identifier insertion statements
+        sql.push_str(&self.generate_company_inserts(ir)?);
+
+        // Generate provenance insertion statements
+        sql.push_str(&self.generate_provenance_inserts(ir)?);
+
+        Ok(sql)
+    }
+
+    /// Generate the beneficial ownership / ultimate controlling entity mart tables
+    pub fn generate_ownership_marts(&self, reports: &[OwnershipReport]) -> Result<String> {
+        let mut sql = String::new();
+
+        sql.push_str("-- OWNERSHIP MARTS\n");
+        sql.push_str(
+            "-- Snapshots computed from ownership stakes by the MDSL ownership analysis\n\n",
+        );
+
+        sql.push_str("CREATE TABLE IF NOT EXISTS beneficial_owner_snapshot (\n");
+        sql.push_str("    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,\n");
+        sql.push_str("    owned_kind VARCHAR(20) NOT NULL,\n");
+        sql.push_str("    owned_id BIGINT NOT NULL,\n");
+        sql.push_str("    bo_person_id BIGINT,\n");
+        sql.push_str("    as_of_date DATE NOT NULL,\n");
+        sql.push_str("    bo_cashflow_pct DECIMAL(6,3),\n");
+        sql.push_str("    bo_votes_pct DECIMAL(6,3),\n");
+        sql.push_str("    bo_basis SMALLINT, -- 1=Equity 2=Control 3=Both\n");
+        sql.push_str("    path_count INTEGER,\n");
+        sql.push_str("    calc_version TEXT,\n");
+        sql.push_str("    fallback_reason TEXT,\n");
+        sql.push_str("    explanation TEXT\n");
+        sql.push_str(");\n\n");
+
+        sql.push_str("CREATE TABLE IF NOT EXISTS ultimate_entity_snapshot (\n");
+        sql.push_str("    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,\n");
+        sql.push_str("    owned_kind VARCHAR(20) NOT NULL,\n");
+        sql.push_str("    owned_id BIGINT NOT NULL,\n");
+        sql.push_str("    uce_entity_id BIGINT NOT NULL,\n");
+        sql.push_str("    as_of_date DATE NOT NULL,\n");
+        sql.push_str("    control_basis SMALLINT, -- 1=Equity 2=Control 3=Both\n");
+        sql.push_str("    path_count INTEGER,\n");
+        sql.push_str("    calc_version TEXT\n");
+        sql.push_str(");\n\n");
+
+        for report in reports {
+            let as_of_date = if report.as_of_date.eq_ignore_ascii_case("CURRENT") {
+                "CURRENT_DATE".to_string()
+            } else {
+                format!("'{}'", report.as_of_date.replace('\'', "''"))
+            };
+
+            for bo in &report.beneficial_owners {
+                sql.push_str(&format!(
+                    "INSERT INTO beneficial_owner_snapshot (owned_kind, owned_id, bo_person_id, as_of_date, bo_cashflow_pct, bo_votes_pct, bo_basis, path_count, calc_version, fallback_reason, explanation) VALUES ('{}', {}, {}, {}, {:.3}, {:.3}, {}, {}, '{}', {}, {});\n",
+                    bo.owned.kind,
+                    bo.owned.id,
+                    bo.bo_person_id.map(|id| id.to_string()).unwrap_or("NULL".to_string()),
+                    as_of_date,
+                    bo.bo_cashflow_pct,
+                    bo.bo_votes_pct,
+                    bo.bo_basis.map(|basis| basis.code().to_string()).unwrap_or("NULL".to_string()),
+                    bo.path_count,
+                    bo.calc_version.replace('\'', "''"),
+                    self.optional_string_to_sql(&bo.fallback_reason),
+                    self.optional_string_to_sql(&Some(bo.explanation.clone()))
+                ));
+            }
+
+            for uce in &report.ultimate_entities {
+                sql.push_str(&format!(
+                    "INSERT INTO ultimate_entity_snapshot (owned_kind, owned_id, uce_entity_id, as_of_date, control_basis, path_count, calc_version) VALUES ('{}', {}, {}, {}, {}, {}, '{}');\n",
+                    uce.owned.kind,
+                    uce.owned.id,
+                    uce.uce_entity_id,
+                    as_of_date,
+                    uce.control_basis.code(),
+                    uce.path_count,
+                    uce.calc_version.replace('\'', "''")
+                ));
+            }
+        }
+
         Ok(sql)
     }
 
@@ -234,6 +322,24 @@ impl SqlGenerator {
         sql.push_str("    FOREIGN KEY (outlet_id) REFERENCES media_outlets(id)\n");
         sql.push_str(");\n\n");
 
+        // Sources table (market_data.data_source and provenance.source_name refer to name)
+        sql.push_str("CREATE TABLE sources (\n");
+        sql.push_str("    name VARCHAR(255) PRIMARY KEY,\n");
+        sql.push_str("    catalog_name VARCHAR(255) NOT NULL,\n");
+        sql.push_str("    quality_tier VARCHAR(20),\n");
+        sql.push_str("    maps_to VARCHAR(255),\n");
+        sql.push_str("    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP\n");
+        sql.push_str(");\n\n");
+
+        // Source attributes table
+        sql.push_str("CREATE TABLE source_attributes (\n");
+        sql.push_str("    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,\n");
+        sql.push_str("    source_name VARCHAR(255) NOT NULL,\n");
+        sql.push_str("    field_name VARCHAR(100) NOT NULL,\n");
+        sql.push_str("    field_value TEXT,\n");
+        sql.push_str("    FOREIGN KEY (source_name) REFERENCES sources(name)\n");
+        sql.push_str(");\n\n");
+
         // Events table
         sql.push_str("CREATE TABLE events (\n");
         sql.push_str("    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,\n");
@@ -277,6 +383,102 @@ impl SqlGenerator {
         sql.push_str("    FOREIGN KEY (event_id) REFERENCES events(id)\n");
         sql.push_str(");\n\n");
 
+        // Companies table
+        sql.push_str("CREATE TABLE companies (\n");
+        sql.push_str("    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,\n");
+        sql.push_str("    name VARCHAR(255) NOT NULL,\n");
+        sql.push_str("    comment TEXT,\n");
+        sql.push_str("    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP\n");
+        sql.push_str(");\n\n");
+
+        // Company attributes table
+        sql.push_str("CREATE TABLE company_attributes (\n");
+        sql.push_str("    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,\n");
+        sql.push_str("    company_id INTEGER NOT NULL,\n");
+        sql.push_str("    field_name VARCHAR(100) NOT NULL,\n");
+        sql.push_str("    field_value TEXT,\n");
+        sql.push_str("    FOREIGN KEY (company_id) REFERENCES companies(id)\n");
+        sql.push_str(");\n\n");
+
+        // Outlet-party links table (period_end NULL = still active)
+        sql.push_str("CREATE TABLE outlet_party_links (\n");
+        sql.push_str("    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,\n");
+        sql.push_str("    link_name VARCHAR(255) NOT NULL,\n");
+        sql.push_str("    outlet_id INTEGER NOT NULL,\n");
+        sql.push_str("    company_id INTEGER NOT NULL,\n");
+        sql.push_str("    party_role VARCHAR(100),\n");
+        sql.push_str("    period_start DATE,\n");
+        sql.push_str("    period_end DATE,\n");
+        sql.push_str("    data_source VARCHAR(255),\n");
+        sql.push_str("    comment TEXT,\n");
+        sql.push_str("    FOREIGN KEY (outlet_id) REFERENCES media_outlets(id),\n");
+        sql.push_str("    FOREIGN KEY (company_id) REFERENCES companies(id)\n");
+        sql.push_str(");\n\n");
+
+        // Persons table
+        sql.push_str("CREATE TABLE persons (\n");
+        sql.push_str("    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,\n");
+        sql.push_str("    name VARCHAR(255) NOT NULL,\n");
+        sql.push_str("    comment TEXT,\n");
+        sql.push_str("    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP\n");
+        sql.push_str(");\n\n");
+
+        // Person attributes table
+        sql.push_str("CREATE TABLE person_attributes (\n");
+        sql.push_str("    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,\n");
+        sql.push_str("    person_id INTEGER NOT NULL,\n");
+        sql.push_str("    field_name VARCHAR(100) NOT NULL,\n");
+        sql.push_str("    field_value TEXT,\n");
+        sql.push_str("    FOREIGN KEY (person_id) REFERENCES persons(id)\n");
+        sql.push_str(");\n\n");
+
+        // Ownership relations table (exactly one owner column is set)
+        sql.push_str("CREATE TABLE ownerships (\n");
+        sql.push_str("    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,\n");
+        sql.push_str("    relation_name VARCHAR(255) NOT NULL,\n");
+        sql.push_str("    owner_company_id INTEGER,\n");
+        sql.push_str("    owner_person_id INTEGER,\n");
+        sql.push_str("    owned_company_id INTEGER NOT NULL,\n");
+        sql.push_str("    share_pct DECIMAL(6,3),\n");
+        sql.push_str("    owner_role VARCHAR(100),\n");
+        sql.push_str("    period_start DATE,\n");
+        sql.push_str("    period_end DATE,\n");
+        sql.push_str("    source_url TEXT,\n");
+        sql.push_str("    scraped_at TIMESTAMP,\n");
+        sql.push_str("    comment TEXT,\n");
+        sql.push_str("    FOREIGN KEY (owner_company_id) REFERENCES companies(id),\n");
+        sql.push_str("    FOREIGN KEY (owner_person_id) REFERENCES persons(id),\n");
+        sql.push_str("    FOREIGN KEY (owned_company_id) REFERENCES companies(id)\n");
+        sql.push_str(");\n\n");
+
+        // Company identifiers in external schemes (register numbers, crawled IDs, ...)
+        sql.push_str("CREATE TABLE company_identifiers (\n");
+        sql.push_str("    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,\n");
+        sql.push_str("    link_name VARCHAR(255) NOT NULL,\n");
+        sql.push_str("    company_id INTEGER NOT NULL,\n");
+        sql.push_str("    scheme VARCHAR(100) NOT NULL,\n");
+        sql.push_str("    id_value VARCHAR(255) NOT NULL,\n");
+        sql.push_str("    status VARCHAR(20),\n");
+        sql.push_str("    score DECIMAL(4,3),\n");
+        sql.push_str("    issued_by VARCHAR(255),\n");
+        sql.push_str("    comment TEXT,\n");
+        sql.push_str("    FOREIGN KEY (company_id) REFERENCES companies(id)\n");
+        sql.push_str(");\n\n");
+
+        // Provenance of declarations and fields (subject_key is an ID or a name, depending on the subject type)
+        sql.push_str("CREATE TABLE provenance (\n");
+        sql.push_str("    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,\n");
+        sql.push_str("    subject_type VARCHAR(50) NOT NULL,\n");
+        sql.push_str("    subject_key VARCHAR(255) NOT NULL,\n");
+        sql.push_str("    field_name VARCHAR(255),\n");
+        sql.push_str("    source_name VARCHAR(255) NOT NULL,\n");
+        sql.push_str("    quality_tier VARCHAR(20),\n");
+        sql.push_str("    confidence DECIMAL(4,3),\n");
+        sql.push_str("    verification_status VARCHAR(20),\n");
+        sql.push_str("    verified_at DATE,\n");
+        sql.push_str("    note TEXT\n");
+        sql.push_str(");\n\n");
+
         Ok(sql)
     }
 
@@ -592,6 +794,48 @@ impl SqlGenerator {
         Ok(sql)
     }
 
+    /// Generate source catalog insert statements
+    fn generate_source_inserts(&self, ir: &IRProgram) -> Result<String> {
+        let mut sql = String::new();
+
+        if ir.catalogs.is_empty() {
+            return Ok(sql);
+        }
+
+        sql.push_str("-- SOURCES\n");
+
+        // Sources are keyed by name; a source redeclared in another catalog
+        // (reported by the validator) keeps its first declaration
+        let mut inserted = HashSet::new();
+        for catalog in &ir.catalogs {
+            for source in &catalog.sources {
+                if !inserted.insert(source.name.as_str()) {
+                    continue;
+                }
+                sql.push_str(&format!(
+                    "INSERT INTO sources (name, catalog_name, quality_tier, maps_to) VALUES ('{}', '{}', {}, {});\n",
+                    source.name.replace('\'', "''"),
+                    catalog.name.replace('\'', "''"),
+                    self.optional_string_to_sql(&source.quality_tier),
+                    self.optional_string_to_sql(&source.maps_to)
+                ));
+
+                for field in &source.fields {
+                    sql.push_str(&format!(
+                        "INSERT INTO source_attributes (source_name, field_name, field_value) VALUES ('{}', '{}', '{}');\n",
+                        source.name.replace('\'', "''"),
+                        field.name.replace('\'', "''"),
+                        self.expression_to_sql_value(&field.value).replace('\'', "''")
+                    ));
+                }
+            }
+        }
+
+        sql.push('\n');
+
+        Ok(sql)
+    }
+
     /// Helper function to convert expression to SQL comment
     fn expression_to_sql_comment(&self, expr: &IRExpression) -> String {
         match expr {
@@ -691,4 +935,165 @@ impl SqlGenerator {
 
         Ok(sql)
     }
+
+    /// Generate company and outlet-party link insertion statements
+    fn generate_company_inserts(&self, ir: &IRProgram) -> Result<String> {
+        let mut sql = String::new();
+
+        if !ir.companies.is_empty() {
+            sql.push_str("-- COMPANIES\n");
+
+            for company in &ir.companies {
+                sql.push_str(&format!(
+                    "INSERT INTO companies (id, name, comment) VALUES ({}, '{}', {});\n",
+                    company.id,
+                    company.name.replace('\'', "''"),
+                    self.optional_string_to_sql(&company.comment)
+                ));
+
+                for field in &company.fields {
+                    sql.push_str(&format!(
+                        "INSERT INTO company_attributes (company_id, field_name, field_value) VALUES ({}, '{}', '{}');\n",
+                        company.id,
+                        field.name.replace('\'', "''"),
+                        self.expression_to_sql_value(&field.value).replace('\'', "''")
+                    ));
+                }
+            }
+
+            sql.push('\n');
+        }
+
+        if !ir.outlet_party_links.is_empty() {
+            sql.push_str("-- OUTLET PARTY LINKS\n");
+
+            for link in &ir.outlet_party_links {
+                // An open-ended period is stored as NULL so that date range queries stay simple
+                let period_end = link.period_end.clone().filter(|date| date != "CURRENT");
+                sql.push_str(&format!(
+                    "INSERT INTO outlet_party_links (link_name, outlet_id, company_id, party_role, period_start, period_end, data_source, comment) VALUES ('{}', {}, {}, {}, {}, {}, {}, {});\n",
+                    link.name.replace('\'', "''"),
+                    link.outlet_id,
+                    link.party_id,
+                    self.optional_string_to_sql(&Some(link.role.clone()).filter(|role| !role.is_empty())),
+                    self.optional_date_to_sql(&link.period_start),
+                    self.optional_date_to_sql(&period_end),
+                    self.optional_string_to_sql(&link.source),
+                    self.optional_string_to_sql(&link.comment)
+                ));
+            }
+
+            sql.push('\n');
+        }
+
+        if !ir.persons.is_empty() {
+            sql.push_str("-- PERSONS\n");
+
+            for person in &ir.persons {
+                sql.push_str(&format!(
+                    "INSERT INTO persons (id, name, comment) VALUES ({}, '{}', {});\n",
+                    person.id,
+                    person.name.replace('\'', "''"),
+                    self.optional_string_to_sql(&person.comment)
+                ));
+
+                for field in &person.fields {
+                    sql.push_str(&format!(
+                        "INSERT INTO person_attributes (person_id, field_name, field_value) VALUES ({}, '{}', '{}');\n",
+                        person.id,
+                        field.name.replace('\'', "''"),
+                        self.expression_to_sql_value(&field.value).replace('\'', "''")
+                    ));
+                }
+            }
+
+            sql.push('\n');
+        }
+
+        if !ir.ownerships.is_empty() {
+            sql.push_str("-- OWNERSHIPS\n");
+
+            for ownership in &ir.ownerships {
+                let (owner_company, owner_person) = match ownership.owner {
+                    Some(IROwner::Company(id)) => (id.to_string(), "NULL".to_string()),
+                    Some(IROwner::Person(id)) => ("NULL".to_string(), id.to_string()),
+                    None => ("NULL".to_string(), "NULL".to_string()),
+                };
+                let period_end = ownership
+                    .period_end
+                    .clone()
+                    .filter(|date| date != "CURRENT");
+                sql.push_str(&format!(
+                    "INSERT INTO ownerships (relation_name, owner_company_id, owner_person_id, owned_company_id, share_pct, owner_role, period_start, period_end, source_url, scraped_at, comment) VALUES ('{}', {}, {}, {}, {}, {}, {}, {}, {}, {}, {});\n",
+                    ownership.name.replace('\'', "''"),
+                    owner_company,
+                    owner_person,
+                    ownership.owned_company_id,
+                    ownership
+                        .share_pct
+                        .map_or("NULL".to_string(), |share| share.to_string()),
+                    self.optional_string_to_sql(&ownership.role),
+                    self.optional_date_to_sql(&ownership.period_start),
+                    self.optional_date_to_sql(&period_end),
+                    self.optional_string_to_sql(&ownership.source_url),
+                    self.optional_string_to_sql(&ownership.scraped_at),
+                    self.optional_string_to_sql(&ownership.comment)
+                ));
+            }
+
+            sql.push('\n');
+        }
+
+        if !ir.identifier_links.is_empty() {
+            sql.push_str("-- COMPANY IDENTIFIERS\n");
+
+            for link in &ir.identifier_links {
+                sql.push_str(&format!(
+                    "INSERT INTO company_identifiers (link_name, company_id, scheme, id_value, status, score, issued_by, comment) VALUES ('{}', {}, '{}', '{}', {}, {}, {}, {});\n",
+                    link.name.replace('\'', "''"),
+                    link.company_id,
+                    link.scheme.replace('\'', "''"),
+                    link.value.replace('\'', "''"),
+                    self.optional_string_to_sql(&link.status),
+                    link.score.map_or("NULL".to_string(), |score| score.to_string()),
+                    self.optional_string_to_sql(&link.issued_by),
+                    self.optional_string_to_sql(&link.comment)
+                ));
+            }
+
+            sql.push('\n');
+        }
+
+        Ok(sql)
+    }
+
+    /// Generate provenance insertion statements
+    fn generate_provenance_inserts(&self, ir: &IRProgram) -> Result<String> {
+        let mut sql = String::new();
+
+        let entries = provenance_entries(ir);
+        if !entries.is_empty() {
+            sql.push_str("-- PROVENANCE\n");
+
+            for entry in entries {
+                let record = entry.record;
+                sql.push_str(&format!(
+                    "INSERT INTO provenance (subject_type, subject_key, field_name, source_name, quality_tier, confidence, verification_status, verified_at, note) VALUES ('{}', '{}', {}, '{}', {}, {}, {}, {}, {});\n",
+                    entry.kind,
+                    entry.key.replace('\'', "''"),
+                    self.optional_string_to_sql(&record.target),
+                    record.source.replace('\'', "''"),
+                    self.optional_string_to_sql(&record.quality_tier),
+                    record.confidence.map_or("NULL".to_string(), |confidence| confidence.to_string()),
+                    self.optional_string_to_sql(&record.verification),
+                    self.optional_date_to_sql(&record.verified_at),
+                    self.optional_string_to_sql(&record.note)
+                ));
+            }
+
+            sql.push('\n');
+        }
+
+        Ok(sql)
+    }
 }
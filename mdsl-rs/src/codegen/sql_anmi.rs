@@ -1,8 +1,8 @@
 //! ANMI-compatible SQL code generator
 
+use crate::codegen::common::{anmi_source_id, cited_sources};
 use crate::error::Result;
 use crate::ir::nodes::*;
-use std::collections::HashMap;
 
 /// ANMI-compatible SQL code generator
 pub struct AnmiSqlGenerator;
@@ -41,6 +41,9 @@ impl AnmiSqlGenerator {
         // Generate insert statements for relationships
         sql.push_str(&self.generate_relationship_inserts(ir)?);
 
+        // Generate insert statements for provenance
+        sql.push_str(&self.generate_provenance_inserts(ir)?);
+
         Ok(sql)
     }
 
@@ -105,6 +108,23 @@ impl AnmiSqlGenerator {
         sql.push_str("    area_name VARCHAR(100)\n");
         sql.push_str(");\n\n");
 
+        // provenance table (rows of mo_constant, mo_year or a relationship table;
+        // companies by ID, events by subject_name)
+        sql.push_str("CREATE TABLE IF NOT EXISTS graphv3.provenance (\n");
+        sql.push_str("    id_provenance INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,\n");
+        sql.push_str("    table_name VARCHAR(50),\n");
+        sql.push_str("    id_1 INTEGER,\n");
+        sql.push_str("    id_2 INTEGER,\n");
+        sql.push_str("    subject_name VARCHAR(255),\n");
+        sql.push_str("    field_name VARCHAR(100),\n");
+        sql.push_str("    source_name VARCHAR(100),\n");
+        sql.push_str("    quality_tier VARCHAR(20),\n");
+        sql.push_str("    confidence DECIMAL(4,3),\n");
+        sql.push_str("    verification_status VARCHAR(20),\n");
+        sql.push_str("    verified_at DATE,\n");
+        sql.push_str("    note TEXT\n");
+        sql.push_str(");\n\n");
+
         Ok(sql)
     }
 
@@ -197,25 +217,8 @@ impl AnmiSqlGenerator {
             }
         }
 
-        // Collect all sources used in market data
-        let mut sources: HashMap<i32, String> = HashMap::new();
-        let mut source_id = 1;
-
-        // Scan for sources in data blocks
-        for family in &ir.families {
-            for data_block in &family.data_blocks {
-                for year in &data_block.years {
-                    for metric in &year.metrics {
-                        if !sources.values().any(|s| s == &metric.source) {
-                            sources.insert(source_id, metric.source.clone());
-                            source_id += 1;
-                        }
-                    }
-                }
-            }
-        }
-
-        // Insert sources
+        // Insert the sources cited by market data under their composite ANMI IDs
+        let sources = cited_sources(ir);
         if !sources.is_empty() {
             sql.push_str("-- Populate sources\n");
             for (id, name) in &sources {
@@ -364,10 +367,6 @@ impl AnmiSqlGenerator {
         }
         sql.push_str(&format!("-- Debug: Found {} data blocks across {} families\n", total_data_blocks, ir.families.len()));
 
-        // Build source lookup
-        let mut source_lookup: HashMap<String, i32> = HashMap::new();
-        let mut source_id = 1;
-
         for family in &ir.families {
             for data_block in &family.data_blocks {
                 for year_data in &data_block.years {
@@ -377,14 +376,18 @@ impl AnmiSqlGenerator {
                         let year = year_data.year;
                         let outlet_id = data_block.outlet_id;
 
-                        // Get or create source ID
-                        let source_id_val = if !source_lookup.contains_key(&metric.source) {
-                            source_lookup.insert(metric.source.clone(), source_id);
-                            let id = source_id;
-                            source_id += 1;
-                            id
-                        } else {
-                            *source_lookup.get(&metric.source).unwrap()
+                        // Resolve the composite ANMI source ID
+                        let source_id_val = match anmi_source_id(ir, &metric.source, year) {
+                            Some(id) => id.to_string(),
+                            None => {
+                                if !metric.source.is_empty() {
+                                    sql.push_str(&format!(
+                                        "-- Source '{}' has no ANMI source ID\n",
+                                        metric.source
+                                    ));
+                                }
+                                "NULL".to_string()
+                            }
                         };
 
                         // Generate appropriate INSERT based on metric type
@@ -455,16 +458,8 @@ impl AnmiSqlGenerator {
             for relationship in &family.relationships {
                 match relationship {
                     IRRelationship::Diachronic(diachronic) => {
-                        let table_name = match diachronic.relationship_type.as_str() {
-                            "succession" => "11_succession",
-                            "amalgamation" => "12_amalgamation",
-                            "new_distribution_area" => "13_new_distribution_area",
-                            "new_sector" => "14_new_sector",
-                            "interruption" => "15_interruption",
-                            "split_off" => "16_split_off",
-                            "merger" => "17_merger",
-                            "offshoot" => "18_offshoot",
-                            _ => continue,
+                        let Some(table_name) = self.diachronic_table(&diachronic.relationship_type) else {
+                            continue;
                         };
 
                         sql.push_str(&format!(
@@ -477,11 +472,8 @@ impl AnmiSqlGenerator {
                         ));
                     }
                     IRRelationship::Synchronous(sync) => {
-                        let table_name = match sync.relationship_type.as_str() {
-                            "main_media_outlet" => "21_main_media_outlet",
-                            "umbrella" => "22_umbrella",
-                            "collaboration" => "23_collaboration",
-                            _ => continue,
+                        let Some(table_name) = self.synchronous_table(&sync.relationship_type) else {
+                            continue;
                         };
 
                         sql.push_str(&format!(
@@ -499,4 +491,152 @@ impl AnmiSqlGenerator {
 
         Ok(sql)
     }
-}
\ No newline at end of file
+
+    /// Generate insert statements for provenance
+    fn generate_provenance_inserts(&self, ir: &IRProgram) -> Result<String> {
+        let mut rows = String::new();
+
+        for family in &ir.families {
+            for outlet in &family.outlets {
+                let id_mo = outlet.id.unwrap_or(0);
+                for record in &outlet.provenance {
+                    rows.push_str(&self.provenance_insert(
+                        "mo_constant",
+                        Some(id_mo),
+                        None,
+                        None,
+                        record,
+                    ));
+                }
+            }
+
+            for data_block in &family.data_blocks {
+                for record in &data_block.provenance {
+                    rows.push_str(&self.provenance_insert(
+                        "mo_year",
+                        Some(data_block.outlet_id),
+                        None,
+                        None,
+                        record,
+                    ));
+                }
+            }
+
+            for relationship in &family.relationships {
+                let (table_name, id_1, id_2, records) = match relationship {
+                    IRRelationship::Diachronic(diachronic) => (
+                        self.diachronic_table(&diachronic.relationship_type),
+                        diachronic.predecessor,
+                        diachronic.successor,
+                        &diachronic.provenance,
+                    ),
+                    IRRelationship::Synchronous(sync) => (
+                        self.synchronous_table(&sync.relationship_type),
+                        sync.outlet_1.id,
+                        sync.outlet_2.id,
+                        &sync.provenance,
+                    ),
+                };
+                let Some(table_name) = table_name else {
+                    continue;
+                };
+                for record in records {
+                    rows.push_str(&self.provenance_insert(
+                        table_name,
+                        Some(id_1),
+                        Some(id_2),
+                        None,
+                        record,
+                    ));
+                }
+            }
+        }
+
+        // Companies and events have no ANMI table; rows name the kind instead
+        for company in &ir.companies {
+            for record in &company.provenance {
+                rows.push_str(&self.provenance_insert(
+                    "company",
+                    Some(company.id),
+                    None,
+                    None,
+                    record,
+                ));
+            }
+        }
+
+        for event in &ir.events {
+            for record in &event.provenance {
+                rows.push_str(&self.provenance_insert(
+                    "event",
+                    None,
+                    None,
+                    Some(&event.name),
+                    record,
+                ));
+            }
+        }
+
+        if rows.is_empty() {
+            return Ok(rows);
+        }
+        Ok(format!("\n-- Provenance\n\n{}", rows))
+    }
+
+    /// Build one graphv3.provenance insert statement
+    fn provenance_insert(
+        &self,
+        table_name: &str,
+        id_1: Option<u32>,
+        id_2: Option<u32>,
+        subject_name: Option<&str>,
+        record: &IRProvenance,
+    ) -> String {
+        let text = |value: Option<&str>| {
+            value
+                .map(|v| format!("'{}'", v.replace('\'', "''")))
+                .unwrap_or("NULL".to_string())
+        };
+        let id = |id: Option<u32>| id.map_or("NULL".to_string(), |id| id.to_string());
+
+        format!(
+            "INSERT INTO graphv3.provenance (table_name, id_1, id_2, subject_name, field_name, source_name, quality_tier, confidence, verification_status, verified_at, note) VALUES ('{}', {}, {}, {}, {}, '{}', {}, {}, {}, {}, {});\n",
+            table_name,
+            id(id_1),
+            id(id_2),
+            text(subject_name),
+            text(record.target.as_deref()),
+            record.source.replace('\'', "''"),
+            text(record.quality_tier.as_deref()),
+            record.confidence.map_or("NULL".to_string(), |confidence| confidence.to_string()),
+            text(record.verification.as_deref()),
+            text(record.verified_at.as_deref()),
+            text(record.note.as_deref())
+        )
+    }
+
+    /// ANMI table holding a diachronic relationship type
+    fn diachronic_table(&self, relationship_type: &str) -> Option<&'static str> {
+        match relationship_type {
+            "succession" => Some("11_succession"),
+            "amalgamation" => Some("12_amalgamation"),
+            "new_distribution_area" => Some("13_new_distribution_area"),
+            "new_sector" => Some("14_new_sector"),
+            "interruption" => Some("15_interruption"),
+            "split_off" => Some("16_split_off"),
+            "merger" => Some("17_merger"),
+            "offshoot" => Some("18_offshoot"),
+            _ => None,
+        }
+    }
+
+    /// ANMI table holding a synchronous relationship type
+    fn synchronous_table(&self, relationship_type: &str) -> Option<&'static str> {
+        match relationship_type {
+            "main_media_outlet" => Some("21_main_media_outlet"),
+            "umbrella" => Some("22_umbrella"),
+            "collaboration" => Some("23_collaboration"),
+            _ => None,
+        }
+    }
+}
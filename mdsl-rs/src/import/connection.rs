@@ -1,7 +1,10 @@
 //! Database connection and schema analysis
 
 use crate::error::{Error, Result};
-use crate::import::{DatabaseConfig, DatabaseType};
+use crate::import::csv_source::CsvSource;
+use crate::import::mysql_source::{self, MysqlSource};
+use crate::import::sqlite_source::{self, SqliteSource};
+use crate::import::{DatabaseConfig, DatabaseType, MdslFieldType};
 use std::collections::HashMap;
 
 #[cfg(feature = "tokio-postgres")]
@@ -14,6 +17,9 @@ use rust_decimal;
 pub struct DatabaseConnection {
     config: DatabaseConfig,
     connection_string: String,
+    csv_source: Option<CsvSource>,
+    sqlite_source: Option<SqliteSource>,
+    mysql_source: Option<MysqlSource>,
     #[cfg(feature = "tokio-postgres")]
     postgres_client: Option<Client>,
 }
@@ -57,6 +63,49 @@ pub struct ColumnInfo {
     pub max_length: Option<usize>,
 }
 
+impl ColumnInfo {
+    /// MDSL field type for the column's SQL type
+    ///
+    /// Understands PostgreSQL, MySQL/MariaDB and SQLite type names, including
+    /// MySQL column types with parameters such as `varchar(255)`,
+    /// `tinyint(1)` (boolean) and `enum('a','b')` (category).
+    pub fn mdsl_field_type(&self) -> MdslFieldType {
+        let data_type = self.data_type.trim().to_lowercase();
+        let base = data_type
+            .split(|c: char| c == '(' || c.is_whitespace())
+            .next()
+            .unwrap_or("");
+        let parameters = data_type
+            .split_once('(')
+            .and_then(|(_, rest)| rest.rsplit_once(')'))
+            .map(|(parameters, _)| parameters);
+
+        match base {
+            "bool" | "boolean" | "bit" if parameters.is_none_or(|p| p == "1") => {
+                MdslFieldType::Boolean
+            }
+            "tinyint" if parameters == Some("1") => MdslFieldType::Boolean,
+            "tinyint" | "smallint" | "mediumint" | "int" | "integer" | "bigint" | "int2"
+            | "int4" | "int8" | "serial" | "smallserial" | "bigserial" | "decimal" | "numeric"
+            | "dec" | "float" | "float4" | "float8" | "double" | "real" => MdslFieldType::Number,
+            "enum" => MdslFieldType::Category(
+                parameters
+                    .unwrap_or("")
+                    .split(',')
+                    .map(|value| value.trim().trim_matches('\'').replace("''", "'"))
+                    .filter(|value| !value.is_empty())
+                    .collect(),
+            ),
+            "varchar" | "char" | "character" | "nvarchar" | "nchar" => MdslFieldType::Text(
+                parameters
+                    .and_then(|p| p.trim().parse().ok())
+                    .or(self.max_length),
+            ),
+            _ => MdslFieldType::Text(None),
+        }
+    }
+}
+
 /// Foreign key relationship information
 #[derive(Debug, Clone)]
 pub struct ForeignKeyInfo {
@@ -109,6 +158,9 @@ impl DatabaseConnection {
         Ok(DatabaseConnection {
             config,
             connection_string,
+            csv_source: None,
+            sqlite_source: None,
+            mysql_source: None,
             #[cfg(feature = "tokio-postgres")]
             postgres_client: None,
         })
@@ -118,6 +170,9 @@ impl DatabaseConnection {
     #[cfg(feature = "tokio-postgres")]
     pub async fn connect(&mut self) -> Result<()> {
         match self.config.db_type {
+            DatabaseType::Csv => self.connect_csv(),
+            DatabaseType::SQLite => self.connect_sqlite(),
+            DatabaseType::MySQL => self.connect_mysql().await,
             DatabaseType::PostgreSQL => {
                 let (client, connection) = tokio_postgres::connect(&self.connection_string, NoTls)
                     .await
@@ -142,14 +197,49 @@ impl DatabaseConnection {
 
     #[cfg(not(feature = "tokio-postgres"))]
     pub async fn connect(&mut self) -> Result<()> {
+        match self.config.db_type {
+            DatabaseType::Csv => return self.connect_csv(),
+            DatabaseType::SQLite => return self.connect_sqlite(),
+            DatabaseType::MySQL => return self.connect_mysql().await,
+            _ => {}
+        }
         Err(Error::NotImplemented(
             "Database connections require tokio-postgres feature".to_string(),
         ))
     }
 
+    /// Load the tables of a CSV directory
+    fn connect_csv(&mut self) -> Result<()> {
+        self.csv_source = Some(CsvSource::from_connection_string(&self.connection_string)?);
+        Ok(())
+    }
+
+    /// Open a SQLite database file read-only
+    fn connect_sqlite(&mut self) -> Result<()> {
+        self.sqlite_source = Some(SqliteSource::from_connection_string(
+            &self.connection_string,
+        )?);
+        Ok(())
+    }
+
+    /// Connect to a MySQL or MariaDB server
+    async fn connect_mysql(&mut self) -> Result<()> {
+        self.mysql_source = Some(MysqlSource::connect(&self.connection_string).await?);
+        Ok(())
+    }
+
     /// Get list of tables in the specified schema
     #[cfg(feature = "tokio-postgres")]
     pub async fn get_tables(&self, schema: Option<&str>) -> Result<Vec<String>> {
+        if let Some(source) = &self.csv_source {
+            return Ok(source.table_names());
+        }
+        if let Some(source) = &self.sqlite_source {
+            return source.table_names();
+        }
+        if let Some(source) = &self.mysql_source {
+            return source.table_names(schema).await;
+        }
         if let Some(client) = &self.postgres_client {
             match self.config.db_type {
                 DatabaseType::PostgreSQL => {
@@ -179,7 +269,16 @@ impl DatabaseConnection {
     }
 
     #[cfg(not(feature = "tokio-postgres"))]
-    pub async fn get_tables(&self, _schema: Option<&str>) -> Result<Vec<String>> {
+    pub async fn get_tables(&self, schema: Option<&str>) -> Result<Vec<String>> {
+        if let Some(source) = &self.csv_source {
+            return Ok(source.table_names());
+        }
+        if let Some(source) = &self.sqlite_source {
+            return source.table_names();
+        }
+        if let Some(source) = &self.mysql_source {
+            return source.table_names(schema).await;
+        }
         Err(Error::NotImplemented(
             "Database queries require tokio-postgres feature".to_string(),
         ))
@@ -192,6 +291,15 @@ impl DatabaseConnection {
         table_name: &str,
         schema: Option<&str>,
     ) -> Result<Vec<(String, String)>> {
+        if let Some(source) = &self.csv_source {
+            return source.columns(table_name);
+        }
+        if let Some(source) = &self.sqlite_source {
+            return source.columns(table_name);
+        }
+        if let Some(source) = &self.mysql_source {
+            return source.columns(table_name, schema).await;
+        }
         if let Some(client) = &self.postgres_client {
             match self.config.db_type {
                 DatabaseType::PostgreSQL => {
@@ -225,9 +333,18 @@ impl DatabaseConnection {
     #[cfg(not(feature = "tokio-postgres"))]
     pub async fn get_columns(
         &self,
-        _table_name: &str,
-        _schema: Option<&str>,
+        table_name: &str,
+        schema: Option<&str>,
     ) -> Result<Vec<(String, String)>> {
+        if let Some(source) = &self.csv_source {
+            return source.columns(table_name);
+        }
+        if let Some(source) = &self.sqlite_source {
+            return source.columns(table_name);
+        }
+        if let Some(source) = &self.mysql_source {
+            return source.columns(table_name, schema).await;
+        }
         Err(Error::NotImplemented(
             "Database queries require tokio-postgres feature".to_string(),
         ))
@@ -241,6 +358,15 @@ impl DatabaseConnection {
         schema: Option<&str>,
         limit: Option<usize>,
     ) -> Result<Vec<std::collections::HashMap<String, String>>> {
+        if let Some(source) = &self.csv_source {
+            return source.rows(table_name, limit);
+        }
+        if let Some(source) = &self.sqlite_source {
+            return source.rows(table_name, limit);
+        }
+        if let Some(source) = &self.mysql_source {
+            return source.rows(table_name, schema, limit).await;
+        }
         if let Some(client) = &self.postgres_client {
             match self.config.db_type {
                 DatabaseType::PostgreSQL => {
@@ -313,10 +439,19 @@ impl DatabaseConnection {
     #[cfg(not(feature = "tokio-postgres"))]
     pub async fn query_table_data(
         &self,
-        _table_name: &str,
-        _schema: Option<&str>,
-        _limit: Option<usize>,
+        table_name: &str,
+        schema: Option<&str>,
+        limit: Option<usize>,
     ) -> Result<Vec<std::collections::HashMap<String, String>>> {
+        if let Some(source) = &self.csv_source {
+            return source.rows(table_name, limit);
+        }
+        if let Some(source) = &self.sqlite_source {
+            return source.rows(table_name, limit);
+        }
+        if let Some(source) = &self.mysql_source {
+            return source.rows(table_name, schema, limit).await;
+        }
         Err(Error::NotImplemented(
             "Database queries require tokio-postgres feature".to_string(),
         ))
@@ -330,6 +465,11 @@ impl DatabaseConnection {
         let mut table_info_map = HashMap::new();
 
         for table_name in tables {
+            if let Some(source) = &self.mysql_source {
+                let table_info = source.table_info(&table_name, schema).await?;
+                table_info_map.insert(table_name, table_info);
+                continue;
+            }
             let columns = self.get_columns(&table_name, schema).await?;
             let table_info = TableInfo {
                 name: table_name.clone(),
@@ -357,6 +497,15 @@ impl DatabaseConnection {
     }
 
     async fn get_database_metadata(&self) -> Result<DatabaseMetadata> {
+        if let Some(source) = &self.mysql_source {
+            let (name, version) = source.metadata(self.config.schema.as_deref()).await?;
+            return Ok(DatabaseMetadata {
+                name,
+                version,
+                schema: self.config.schema.clone(),
+            });
+        }
+
         let name = "ANMI Database".to_string();
         let version = "Unknown".to_string();
 
@@ -367,9 +516,60 @@ impl DatabaseConnection {
         })
     }
 
+    /// Select columns from a table, keeping rows where the `not_null` columns are
+    /// set and sorting by the `order_by` columns; NULLs are returned as `NULL`
+    pub async fn select_rows(
+        &self,
+        table_name: &str,
+        columns: &[&str],
+        not_null: &[&str],
+        order_by: &[&str],
+    ) -> Result<Vec<Vec<String>>> {
+        if let Some(source) = &self.csv_source {
+            return source.select(table_name, columns, not_null, order_by);
+        }
+
+        // SQLite has no schemas to qualify the table with, and MySQL quotes
+        // identifiers with backticks and defaults to the database of the URL
+        let table = if self.sqlite_source.is_some() {
+            sqlite_source::quote(table_name)
+        } else if self.mysql_source.is_some() {
+            mysql_source::table_reference(table_name, self.config.schema.as_deref())
+        } else {
+            format!(
+                "{}.\"{}\"",
+                self.config.schema.as_deref().unwrap_or("public"),
+                table_name
+            )
+        };
+        let mut query = format!("SELECT {} FROM {}", columns.join(", "), table);
+        if !not_null.is_empty() {
+            let conditions: Vec<String> = not_null
+                .iter()
+                .map(|column| format!("{} IS NOT NULL", column))
+                .collect();
+            query.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+        }
+        if !order_by.is_empty() {
+            query.push_str(&format!(" ORDER BY {}", order_by.join(", ")));
+        }
+        self.execute_query(&query).await
+    }
+
     /// Execute a raw SQL query and return string results
     #[cfg(feature = "tokio-postgres")]
     pub async fn execute_query(&self, query: &str) -> Result<Vec<Vec<String>>> {
+        if self.csv_source.is_some() {
+            return Err(Error::NotImplemented(
+                "Raw SQL queries are not supported on CSV sources".to_string(),
+            ));
+        }
+        if let Some(source) = &self.sqlite_source {
+            return source.query(query).map(|(_, rows)| rows);
+        }
+        if let Some(source) = &self.mysql_source {
+            return source.query(query).await.map(|(_, rows)| rows);
+        }
         if let Some(client) = &self.postgres_client {
             let rows = client
                 .query(query, &[])
@@ -440,7 +640,13 @@ impl DatabaseConnection {
     }
 
     #[cfg(not(feature = "tokio-postgres"))]
-    pub async fn execute_query(&self, _query: &str) -> Result<Vec<Vec<String>>> {
+    pub async fn execute_query(&self, query: &str) -> Result<Vec<Vec<String>>> {
+        if let Some(source) = &self.sqlite_source {
+            return source.query(query).map(|(_, rows)| rows);
+        }
+        if let Some(source) = &self.mysql_source {
+            return source.query(query).await.map(|(_, rows)| rows);
+        }
         Err(Error::NotImplemented(
             "Database queries require tokio-postgres feature".to_string(),
         ))
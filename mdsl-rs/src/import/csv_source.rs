@@ -0,0 +1,153 @@
+//! CSV table dumps as an import data source
+//!
+//! Reads a directory of `<table>.csv` files (such as
+//! `anmi-media-v1/02_csv_tables`) and answers the queries the import pipeline
+//! otherwise sends to a database server. Every column is text; empty fields
+//! are reported as `NULL`, like SQL NULLs in the PostgreSQL backend.
+
+use crate::error::{Error, Result};
+use crate::utils::csv::CsvTable;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Prefix for CSV connection strings (`csv://<directory>`)
+pub const CSV_SCHEME: &str = "csv://";
+
+/// Tables loaded from a directory of CSV files
+#[derive(Debug, Clone, Default)]
+pub struct CsvSource {
+    tables: HashMap<String, CsvTable>,
+}
+
+impl CsvSource {
+    /// Load every `*.csv` file of a directory; the file stem is the table name
+    pub fn open(dir: &Path) -> Result<Self> {
+        if !dir.is_dir() {
+            return Err(Error::DatabaseConnection(format!(
+                "CSV directory '{}' not found",
+                dir.display()
+            )));
+        }
+
+        let mut tables = HashMap::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("csv") {
+                continue;
+            }
+            if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                let content = std::fs::read_to_string(&path)?;
+                tables.insert(name.to_string(), CsvTable::parse(&content));
+            }
+        }
+
+        Ok(Self { tables })
+    }
+
+    /// Load the directory named by a connection string (`csv://<dir>` or a plain path)
+    pub fn from_connection_string(connection_string: &str) -> Result<Self> {
+        let dir = connection_string
+            .strip_prefix(CSV_SCHEME)
+            .unwrap_or(connection_string);
+        Self::open(Path::new(dir))
+    }
+
+    /// Names of the loaded tables, sorted
+    pub fn table_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.tables.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Columns of a table in header order, all typed as text
+    pub fn columns(&self, table_name: &str) -> Result<Vec<(String, String)>> {
+        Ok(self
+            .table(table_name)?
+            .headers
+            .iter()
+            .map(|header| (header.trim().to_string(), "text".to_string()))
+            .collect())
+    }
+
+    /// Rows of a table as column -> value maps
+    pub fn rows(
+        &self,
+        table_name: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<HashMap<String, String>>> {
+        let table = self.table(table_name)?;
+        let columns = self.columns(table_name)?;
+
+        Ok(table
+            .rows
+            .iter()
+            .take(limit.unwrap_or(usize::MAX))
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|(column, _)| (column.clone(), value(table, row, column)))
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Select columns of the rows where `not_null` columns are set, sorted by
+    /// `order_by` (numbers compare numerically, NULLs sort last)
+    pub fn select(
+        &self,
+        table_name: &str,
+        columns: &[&str],
+        not_null: &[&str],
+        order_by: &[&str],
+    ) -> Result<Vec<Vec<String>>> {
+        let table = self.table(table_name)?;
+
+        let mut rows: Vec<&Vec<String>> = table
+            .rows
+            .iter()
+            .filter(|row| not_null.iter().all(|column| table.get(row, column).is_some()))
+            .collect();
+        rows.sort_by(|a, b| {
+            order_by
+                .iter()
+                .map(|column| compare(table.get(a, column), table.get(b, column)))
+                .find(|ordering| *ordering != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        });
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|column| value(table, row, column))
+                    .collect()
+            })
+            .collect())
+    }
+
+    fn table(&self, table_name: &str) -> Result<&CsvTable> {
+        self.tables
+            .get(table_name)
+            .ok_or_else(|| Error::DatabaseQuery(format!("CSV table '{}' not found", table_name)))
+    }
+}
+
+/// Field of a row, `NULL` when empty or missing
+fn value(table: &CsvTable, row: &[String], column: &str) -> String {
+    table.get(row, column).unwrap_or("NULL").to_string()
+}
+
+/// Order two fields like SQL `ORDER BY ... ASC` does for numeric or text columns
+fn compare(a: Option<&str>, b: Option<&str>) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => match (a.parse::<f64>(), b.parse::<f64>()) {
+            (Ok(x), Ok(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+            _ => a.cmp(b),
+        },
+    }
+}
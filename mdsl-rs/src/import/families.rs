@@ -0,0 +1,175 @@
+//! Media group (FAMILY) derivation from the relationship graph
+//!
+//! Outlets tied together by group links (main media outlet, umbrella and
+//! combination links by default) form one media group: the connected
+//! components of the graph of those links. Each group is named after its
+//! hub, the outlet most other members point to. Outlets without any group
+//! link end up in no family and are reported as unassigned.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Relationship types that cluster outlets into families by default
+pub const FAMILY_RELATIONSHIPS: &[&str] = &["main_media_outlet", "umbrella", "combination"];
+
+/// Name of the family that collects outlets without group links
+pub const UNASSIGNED_FAMILY: &str = "Unassigned Outlets";
+
+/// How outlets are clustered into families
+#[derive(Debug, Clone)]
+pub struct FamilyOptions {
+    /// Relationship types whose links put both outlets into one family
+    pub link_types: Vec<String>,
+}
+
+impl Default for FamilyOptions {
+    fn default() -> Self {
+        Self {
+            link_types: FAMILY_RELATIONSHIPS.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+}
+
+/// A media group derived from the relationship graph
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivedFamily {
+    /// Family name, taken from the hub outlet
+    pub name: String,
+    /// ID of the hub outlet
+    pub hub: String,
+    /// IDs of all members, the hub included, sorted
+    pub members: Vec<String>,
+}
+
+/// Result of clustering outlets into families
+#[derive(Debug, Clone, Default)]
+pub struct FamilyGrouping {
+    /// Derived families, largest first
+    pub families: Vec<DerivedFamily>,
+    /// IDs of outlets without any group link, in input order
+    pub unassigned: Vec<String>,
+}
+
+impl FamilyGrouping {
+    /// Name of the family an outlet belongs to, if any
+    pub fn family_of(&self, id: &str) -> Option<&str> {
+        self.families
+            .iter()
+            .find(|family| family.members.iter().any(|member| member == id))
+            .map(|family| family.name.as_str())
+    }
+}
+
+impl FamilyOptions {
+    /// Cluster outlets, given as `(id, title)`, into families over the
+    /// `(source, target, relationship type)` links of the configured types.
+    /// Outlets only referenced by links join the family of their links.
+    pub fn derive_families(
+        &self,
+        outlets: &[(String, String)],
+        links: &[(String, String, String)],
+    ) -> FamilyGrouping {
+        let unset = |id: &str| id.is_empty() || id == "NULL";
+        let group_links: Vec<(&str, &str)> = links
+            .iter()
+            .filter(|(source, target, link_type)| {
+                self.link_types.contains(link_type) && !unset(source) && !unset(target)
+            })
+            .map(|(source, target, _)| (source.as_str(), target.as_str()))
+            .collect();
+
+        // Union-find over the linked outlets
+        let mut parent: HashMap<&str, &str> = HashMap::new();
+        fn root<'a>(parent: &mut HashMap<&'a str, &'a str>, id: &'a str) -> &'a str {
+            let mut current = id;
+            while let Some(&next) = parent.get(current) {
+                if next == current {
+                    break;
+                }
+                current = next;
+            }
+            parent.insert(id, current);
+            current
+        }
+        for &(source, target) in &group_links {
+            parent.entry(source).or_insert(source);
+            parent.entry(target).or_insert(target);
+            let (a, b) = (root(&mut parent, source), root(&mut parent, target));
+            if a != b {
+                parent.insert(a.max(b), a.min(b));
+            }
+        }
+
+        let mut components: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        let linked: Vec<&str> = parent.keys().copied().collect();
+        for id in linked {
+            let component = root(&mut parent, id);
+            components.entry(component).or_default().push(id);
+        }
+
+        let mut incoming: HashMap<&str, usize> = HashMap::new();
+        let mut degree: HashMap<&str, usize> = HashMap::new();
+        for &(source, target) in &group_links {
+            *incoming.entry(target).or_default() += 1;
+            *degree.entry(source).or_default() += 1;
+            *degree.entry(target).or_default() += 1;
+        }
+
+        let titles: HashMap<&str, &str> = outlets
+            .iter()
+            .map(|(id, title)| (id.as_str(), title.as_str()))
+            .collect();
+        let mut families: Vec<DerivedFamily> = components
+            .into_values()
+            .map(|mut members| {
+                members.sort_unstable();
+                // Most pointed-to member, then best connected, then lowest ID
+                let hub = members
+                    .iter()
+                    .copied()
+                    .max_by_key(|id| {
+                        (
+                            incoming.get(id).copied().unwrap_or(0),
+                            degree.get(id).copied().unwrap_or(0),
+                            std::cmp::Reverse(*id),
+                        )
+                    })
+                    .unwrap_or_default();
+                let name = match titles.get(hub) {
+                    Some(title) if !title.trim().is_empty() => format!("{} Group", title.trim()),
+                    _ => format!("Outlet {} Group", hub),
+                };
+                DerivedFamily {
+                    name,
+                    hub: hub.to_string(),
+                    members: members.into_iter().map(str::to_string).collect(),
+                }
+            })
+            .collect();
+        families.sort_by(|a, b| {
+            b.members
+                .len()
+                .cmp(&a.members.len())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        // Hubs sharing a title would give duplicate FAMILY names
+        let mut seen: HashSet<String> = HashSet::new();
+        for family in &mut families {
+            if !seen.insert(family.name.clone()) {
+                family.name = format!("{} ({})", family.name, family.hub);
+                seen.insert(family.name.clone());
+            }
+        }
+
+        let unassigned = outlets
+            .iter()
+            .filter(|(id, _)| !parent.contains_key(id.as_str()))
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        FamilyGrouping {
+            families,
+            unassigned,
+        }
+    }
+}
@@ -277,7 +277,10 @@ impl MdslGenerator {
         indent: &str,
     ) -> Result<()> {
         let start_date = status.start_date.as_deref().unwrap_or("unknown");
-        let end_date = status.end_date.as_deref().unwrap_or("CURRENT");
+        let end_date = status
+            .end_date
+            .as_ref()
+            .map_or("CURRENT".to_string(), |date| format!("\"{}\"", date));
 
         writeln!(
             output,
@@ -340,13 +343,9 @@ impl MdslGenerator {
             self.escape_string(&link.relationship_type)
         )?;
 
+        // Links have no comment field; keep the text as a source comment
         if let Some(comment) = &link.comment {
-            writeln!(
-                output,
-                "{}    @comment \"{}\";",
-                indent,
-                self.escape_string(comment)
-            )?;
+            writeln!(output, "{}    // {}", indent, comment.replace('\n', " "))?;
         }
 
         if let Some(maps_to) = &link.maps_to {
@@ -402,12 +401,10 @@ impl MdslGenerator {
         )?;
 
         if let Some(period_start) = &link.period_start {
-            let period_end = link.period_end.as_deref().unwrap_or("CURRENT");
-            writeln!(
-                output,
-                "{}    period = \"{}\" TO \"{}\";",
-                indent, period_start, period_end
-            )?;
+            writeln!(output, "{}    period_start = \"{}\";", indent, period_start)?;
+        }
+        if let Some(period_end) = &link.period_end {
+            writeln!(output, "{}    period_end = \"{}\";", indent, period_end)?;
         }
 
         if let Some(details) = &link.details {
@@ -1,10 +1,16 @@
 //! Data mapping from SQL database to MDSL structures
 
-use crate::error::Result;
+use crate::error::{Error, ImportError, Result};
 use crate::import::connection::{DatabaseConnection, SchemaInfo, TableInfo};
-use crate::import::{FieldTransform, MdslEntityType, MdslFieldType, TableMapping};
+use crate::import::families::{FamilyOptions, UNASSIGNED_FAMILY};
+use crate::import::{
+    FieldTransform, MdslEntityType, MdslFieldType, RelationshipMapping, TableMapping,
+};
 use std::collections::HashMap;
 
+/// Lookup tables used by field transforms: table -> (key -> value)
+type Lookups = HashMap<String, HashMap<String, String>>;
+
 /// MDSL entity enum for unified handling
 #[derive(Debug, Clone)]
 pub enum MdslEntity {
@@ -41,6 +47,8 @@ pub struct MdslData {
     pub synchronous_links: Vec<MdslSynchronousLink>,
     /// Data blocks (market data)
     pub data_blocks: Vec<MdslDataBlock>,
+    /// Names of outlets that ended up in no derived family
+    pub unassigned_outlets: Vec<String>,
 }
 
 impl MdslData {
@@ -83,6 +91,7 @@ impl MdslData {
 /// Data mapper that converts SQL database content to MDSL structures
 pub struct DataMapper<'a> {
     table_mappings: &'a HashMap<String, TableMapping>,
+    family_options: FamilyOptions,
 }
 
 /// MDSL Unit representation
@@ -271,7 +280,16 @@ pub struct MdslMetric {
 impl<'a> DataMapper<'a> {
     /// Create a new data mapper
     pub fn new(table_mappings: &'a HashMap<String, TableMapping>) -> Self {
-        Self { table_mappings }
+        Self {
+            table_mappings,
+            family_options: FamilyOptions::default(),
+        }
+    }
+
+    /// Cluster outlets into families with these options instead of the defaults
+    pub fn with_family_options(mut self, family_options: FamilyOptions) -> Self {
+        self.family_options = family_options;
+        self
     }
 
     /// Map database content to MDSL structures
@@ -288,7 +306,9 @@ impl<'a> DataMapper<'a> {
             diachronic_links: Vec::new(),
             synchronous_links: Vec::new(),
             data_blocks: Vec::new(),
+            unassigned_outlets: Vec::new(),
         };
+        let lookups = self.load_lookups(connection).await?;
 
         // Process each table according to its mapping
         for (table_name, table_info) in &schema_info.tables {
@@ -312,7 +332,7 @@ impl<'a> DataMapper<'a> {
                     }
                     MdslEntityType::Outlet => {
                         let outlets = self
-                            .map_table_to_outlets(connection, table_info, mapping)
+                            .map_table_to_outlets(connection, table_info, mapping, &lookups)
                             .await?;
                         mdsl_data.outlets.extend(outlets);
                     }
@@ -344,6 +364,38 @@ impl<'a> DataMapper<'a> {
         Ok(mdsl_data)
     }
 
+    /// Load the tables referenced by lookup transforms; the first column of a
+    /// lookup table is the key and the second the replacement value
+    async fn load_lookups(&self, connection: &DatabaseConnection) -> Result<Lookups> {
+        let mut lookups = Lookups::new();
+        for mapping in self.table_mappings.values() {
+            for field_mapping in mapping.field_mappings.values() {
+                let Some(FieldTransform::Lookup(table)) = &field_mapping.transform else {
+                    continue;
+                };
+                if lookups.contains_key(table) {
+                    continue;
+                }
+
+                let columns = connection.get_columns(table, None).await?;
+                let (Some((key, _)), Some((value, _))) = (columns.first(), columns.get(1)) else {
+                    return Err(Error::Import(ImportError::MappingFailed {
+                        table: table.clone(),
+                        message: "lookup tables need a key and a value column".to_string(),
+                    }));
+                };
+                let entries = connection
+                    .query_table_data(table, None, None)
+                    .await?
+                    .into_iter()
+                    .filter_map(|row| Some((row.get(key)?.clone(), row.get(value)?.clone())))
+                    .collect();
+                lookups.insert(table.clone(), entries);
+            }
+        }
+        Ok(lookups)
+    }
+
     /// Map a table to a UNIT declaration
     async fn map_table_to_unit(
         &self,
@@ -354,10 +406,15 @@ impl<'a> DataMapper<'a> {
 
         for column in &table_info.columns {
             if let Some(field_mapping) = mapping.field_mappings.get(&column.name) {
+                let is_primary_key = table_info.primary_keys.contains(&column.name);
                 let field = MdslUnitField {
                     name: field_mapping.mdsl_field.clone(),
-                    field_type: MdslFieldType::Text(None), // Default type, should be configured
-                    is_primary_key: table_info.primary_keys.contains(&column.name),
+                    field_type: if is_primary_key {
+                        MdslFieldType::Id
+                    } else {
+                        column.mdsl_field_type()
+                    },
+                    is_primary_key,
                     comment: None,
                 };
                 fields.push(field);
@@ -424,6 +481,7 @@ impl<'a> DataMapper<'a> {
         connection: &DatabaseConnection,
         table_info: &TableInfo,
         mapping: &TableMapping,
+        lookups: &Lookups,
     ) -> Result<Vec<MdslOutlet>> {
         let data = connection
             .query_table_data(&table_info.name, None, None)
@@ -432,19 +490,25 @@ impl<'a> DataMapper<'a> {
         let mut outlets = Vec::new();
 
         for row in data {
-            let outlet = self.map_row_to_outlet(&row, mapping)?;
-            outlets.push(outlet);
+            if let Some(outlet) =
+                self.map_row_to_outlet(&table_info.name, &row, mapping, lookups)?
+            {
+                outlets.push(outlet);
+            }
         }
 
         Ok(outlets)
     }
 
-    /// Map a database row to an OUTLET
+    /// Map a database row to an OUTLET; rows without a valid value for a
+    /// required field are skipped
     fn map_row_to_outlet(
         &self,
+        table: &str,
         row: &std::collections::HashMap<String, String>,
         mapping: &TableMapping,
-    ) -> Result<MdslOutlet> {
+        lookups: &Lookups,
+    ) -> Result<Option<MdslOutlet>> {
         let mut outlet = MdslOutlet {
             id: None,
             name: "Unknown".to_string(),
@@ -457,42 +521,64 @@ impl<'a> DataMapper<'a> {
         };
 
         // Map fields according to mapping configuration
-        for (column_name, value) in row {
-            if let Some(field_mapping) = mapping.field_mappings.get(column_name) {
-                if !value.is_empty() && value != "NULL" {
-                    let transformed_value =
-                        self.apply_field_transform(value, &field_mapping.transform)?;
-
-                    match field_mapping.mdsl_field.as_str() {
-                        "id" => {
-                            outlet.id = transformed_value.parse().ok();
-                        }
-                        "title" | "name" => {
-                            outlet.name = transformed_value;
-                        }
-                        field_name if field_name.starts_with("identity.") => {
-                            let key = field_name.strip_prefix("identity.").unwrap_or(field_name);
-                            outlet.identity.insert(key.to_string(), transformed_value);
-                        }
-                        field_name if field_name.starts_with("characteristics.") => {
-                            let key = field_name
-                                .strip_prefix("characteristics.")
-                                .unwrap_or(field_name);
-                            outlet
-                                .characteristics
-                                .insert(key.to_string(), transformed_value);
-                        }
-                        field_name if field_name.starts_with("metadata.") => {
-                            let key = field_name.strip_prefix("metadata.").unwrap_or(field_name);
-                            outlet.metadata.insert(key.to_string(), transformed_value);
-                        }
-                        _ => {
-                            // Default to characteristics
-                            outlet
-                                .characteristics
-                                .insert(field_mapping.mdsl_field.clone(), transformed_value);
-                        }
+        for field_mapping in mapping.field_mappings.values() {
+            let value = row
+                .get(&field_mapping.sql_column)
+                .filter(|value| !value.is_empty() && *value != "NULL");
+            let typed_value = match value {
+                Some(value) => {
+                    let transformed =
+                        self.apply_field_transform(value, &field_mapping.transform, row, lookups)?;
+                    let typed = typed_value(transformed, &field_mapping.field_type);
+                    if typed.is_none() {
+                        println!(
+                            "⚠️ {}: value '{}' of column '{}' does not fit the type of '{}'",
+                            table, value, field_mapping.sql_column, field_mapping.mdsl_field
+                        );
                     }
+                    typed
+                }
+                None => None,
+            };
+            if typed_value.is_none() && field_mapping.required {
+                println!(
+                    "⚠️ {}: skipping a row without a valid value for required field '{}' (column '{}')",
+                    table, field_mapping.mdsl_field, field_mapping.sql_column
+                );
+                return Ok(None);
+            }
+
+            let Some(transformed_value) = typed_value else {
+                continue;
+            };
+            match field_mapping.mdsl_field.as_str() {
+                "id" => {
+                    outlet.id = transformed_value.parse().ok();
+                }
+                "title" | "name" => {
+                    outlet.name = transformed_value;
+                }
+                field_name if field_name.starts_with("identity.") => {
+                    let key = field_name.strip_prefix("identity.").unwrap_or(field_name);
+                    outlet.identity.insert(key.to_string(), transformed_value);
+                }
+                field_name if field_name.starts_with("characteristics.") => {
+                    let key = field_name
+                        .strip_prefix("characteristics.")
+                        .unwrap_or(field_name);
+                    outlet
+                        .characteristics
+                        .insert(key.to_string(), transformed_value);
+                }
+                field_name if field_name.starts_with("metadata.") => {
+                    let key = field_name.strip_prefix("metadata.").unwrap_or(field_name);
+                    outlet.metadata.insert(key.to_string(), transformed_value);
+                }
+                _ => {
+                    // Default to characteristics
+                    outlet
+                        .characteristics
+                        .insert(field_mapping.mdsl_field.clone(), transformed_value);
                 }
             }
         }
@@ -500,29 +586,57 @@ impl<'a> DataMapper<'a> {
         // Create lifecycle status from date fields
         outlet.lifecycle = self.extract_lifecycle_from_row(row, mapping)?;
 
-        Ok(outlet)
+        Ok(Some(outlet))
     }
 
     /// Extract lifecycle information from database row
     fn extract_lifecycle_from_row(
         &self,
         row: &std::collections::HashMap<String, String>,
-        _mapping: &TableMapping,
+        mapping: &TableMapping,
     ) -> Result<Vec<MdslLifecycleStatus>> {
-        // Look for common date patterns
         let mut start_date = None;
         let mut end_date = None;
         let mut status = "active".to_string();
 
+        // Configured temporal columns take precedence over the common date patterns
+        if let Some(temporal) = &mapping.temporal_fields {
+            let column = |name: &Option<String>| {
+                name.as_ref()
+                    .and_then(|name| row.get(name))
+                    .filter(|value| !value.is_empty() && *value != "NULL")
+            };
+            start_date = column(&temporal.start_date).map(|value| self.normalize_date(value));
+            if let Some(value) = column(&temporal.end_date) {
+                if value != "9999" && value != "current" {
+                    end_date = Some(self.normalize_date(value));
+                    status = "inactive".to_string();
+                }
+            }
+            if let Some(value) = column(&temporal.status_field) {
+                status = value.clone();
+            }
+
+            return Ok(vec![MdslLifecycleStatus {
+                status,
+                start_date,
+                end_date: end_date.clone(),
+                precision_start: Some("known".to_string()),
+                precision_end: end_date.as_ref().map(|_| "known".to_string()),
+                comment: None,
+            }]);
+        }
+
+        // Look for common date patterns
         for (column_name, value) in row {
             if !value.is_empty() && value != "NULL" {
                 match column_name.to_lowercase().as_str() {
                     "start_year" | "start_date" | "founded" | "launch_date" => {
-                        start_date = Some(self.format_date_from_parts(value, None, None));
+                        start_date = Some(self.normalize_date(value));
                     }
                     "end_year" | "end_date" | "closed" | "shutdown_date" => {
                         if value != "9999" && value != "current" && !value.is_empty() {
-                            end_date = Some(self.format_date_from_parts(value, None, None));
+                            end_date = Some(self.normalize_date(value));
                             status = "inactive".to_string();
                         }
                     }
@@ -550,6 +664,15 @@ impl<'a> DataMapper<'a> {
         Ok(vec![lifecycle_status])
     }
 
+    /// Turn a bare year into a date and keep full dates as they are
+    fn normalize_date(&self, value: &str) -> String {
+        if value.len() == 4 && value.chars().all(|c| c.is_ascii_digit()) {
+            self.format_date_from_parts(value, None, None)
+        } else {
+            value.to_string()
+        }
+    }
+
     /// Format date from individual parts
     fn format_date_from_parts(&self, year: &str, month: Option<&str>, day: Option<&str>) -> String {
         match (month, day) {
@@ -578,20 +701,22 @@ impl<'a> DataMapper<'a> {
         let mut links = Vec::new();
 
         for (index, row) in data.iter().enumerate() {
-            let link = self.map_row_to_diachronic_link(row, mapping, index)?;
-            links.push(link);
+            if let Some(link) = self.map_row_to_diachronic_link(row, mapping, index)? {
+                links.push(link);
+            }
         }
 
         Ok(links)
     }
 
-    /// Map a database row to a diachronic link
+    /// Map a database row to a diachronic link; rows missing a configured
+    /// source or target are skipped
     fn map_row_to_diachronic_link(
         &self,
         row: &std::collections::HashMap<String, String>,
-        _mapping: &TableMapping,
+        mapping: &TableMapping,
         index: usize,
-    ) -> Result<MdslDiachronicLink> {
+    ) -> Result<Option<MdslDiachronicLink>> {
         let mut predecessor = 0;
         let mut successor = 0;
         let mut relationship_type = "Unknown".to_string();
@@ -624,7 +749,30 @@ impl<'a> DataMapper<'a> {
             }
         }
 
-        Ok(MdslDiachronicLink {
+        if let Some(relationship) = &mapping.relationship_mapping {
+            let (Some(source), Some(target)) = (
+                id_column(row, &relationship.source_field),
+                id_column(row, &relationship.target_field),
+            ) else {
+                return Ok(None);
+            };
+            predecessor = source;
+            successor = target;
+            if let Some(link_type) = &relationship.link_type {
+                relationship_type = link_type.clone();
+            }
+            if let Some(value) = metadata_column(row, relationship, "relationship_type") {
+                relationship_type = value;
+            }
+            if let Some(value) = metadata_column(row, relationship, "event_date") {
+                event_date = Some(self.normalize_date(&value));
+            }
+            if let Some(value) = metadata_column(row, relationship, "comment") {
+                comment = Some(value);
+            }
+        }
+
+        Ok(Some(MdslDiachronicLink {
             name: format!("diachronic_link_{}", index),
             predecessor,
             successor,
@@ -632,7 +780,7 @@ impl<'a> DataMapper<'a> {
             relationship_type,
             comment,
             maps_to: None,
-        })
+        }))
     }
 
     /// Map a table to synchronous links
@@ -649,20 +797,22 @@ impl<'a> DataMapper<'a> {
         let mut links = Vec::new();
 
         for (index, row) in data.iter().enumerate() {
-            let link = self.map_row_to_synchronous_link(row, mapping, index)?;
-            links.push(link);
+            if let Some(link) = self.map_row_to_synchronous_link(row, mapping, index)? {
+                links.push(link);
+            }
         }
 
         Ok(links)
     }
 
-    /// Map a database row to a synchronous link
+    /// Map a database row to a synchronous link; rows missing a configured
+    /// source or target are skipped
     fn map_row_to_synchronous_link(
         &self,
         row: &std::collections::HashMap<String, String>,
-        _mapping: &TableMapping,
+        mapping: &TableMapping,
         index: usize,
-    ) -> Result<MdslSynchronousLink> {
+    ) -> Result<Option<MdslSynchronousLink>> {
         let mut outlet_1_id = 0;
         let mut outlet_2_id = 0;
         let mut relationship_type = "Unknown".to_string();
@@ -698,7 +848,33 @@ impl<'a> DataMapper<'a> {
             }
         }
 
-        Ok(MdslSynchronousLink {
+        if let Some(relationship) = &mapping.relationship_mapping {
+            let (Some(source), Some(target)) = (
+                id_column(row, &relationship.source_field),
+                id_column(row, &relationship.target_field),
+            ) else {
+                return Ok(None);
+            };
+            outlet_1_id = source;
+            outlet_2_id = target;
+            if let Some(link_type) = &relationship.link_type {
+                relationship_type = link_type.clone();
+            }
+            if let Some(value) = metadata_column(row, relationship, "relationship_type") {
+                relationship_type = value;
+            }
+            if let Some(value) = metadata_column(row, relationship, "period_start") {
+                period_start = Some(self.normalize_date(&value));
+            }
+            if let Some(value) = metadata_column(row, relationship, "period_end") {
+                period_end = Some(self.normalize_date(&value));
+            }
+            if let Some(value) = metadata_column(row, relationship, "details") {
+                details = Some(value);
+            }
+        }
+
+        Ok(Some(MdslSynchronousLink {
             name: format!("synchronous_link_{}", index),
             outlet_1: MdslOutletRef {
                 id: outlet_1_id,
@@ -713,7 +889,7 @@ impl<'a> DataMapper<'a> {
             period_end,
             details,
             maps_to: None,
-        })
+        }))
     }
 
     /// Map a table to data blocks
@@ -733,6 +909,8 @@ impl<'a> DataMapper<'a> {
         &self,
         value: &str,
         transform: &Option<FieldTransform>,
+        row: &HashMap<String, String>,
+        lookups: &Lookups,
     ) -> Result<String> {
         match transform {
             Some(FieldTransform::Direct) | None => Ok(value.to_string()),
@@ -744,36 +922,193 @@ impl<'a> DataMapper<'a> {
                     Ok(format_str.replace("{}", value))
                 }
             }
-            Some(FieldTransform::Lookup(_table)) => {
-                // Lookup transformation would require database access
-                Ok(value.to_string())
-            }
-            Some(FieldTransform::Concat(_fields)) => {
-                // Concatenation would require multiple field values
-                Ok(value.to_string())
+            Some(FieldTransform::Lookup(table)) => {
+                // Unknown keys keep their original value
+                Ok(lookups
+                    .get(table)
+                    .and_then(|entries| entries.get(value))
+                    .cloned()
+                    .unwrap_or_else(|| value.to_string()))
             }
+            Some(FieldTransform::Concat(fields)) => Ok(fields
+                .iter()
+                .filter_map(|field| row.get(field))
+                .filter(|part| !part.is_empty() && *part != "NULL")
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" ")),
         }
     }
 
     /// Organize standalone outlets into families based on relationships
     fn organize_outlets_into_families(&self, mdsl_data: &mut MdslData) -> Result<()> {
-        // This is a complex process that would analyze relationships
-        // to group outlets into logical families
-        // For now, we'll create a simple grouping
-
-        if !mdsl_data.outlets.is_empty() {
-            let default_family = MdslFamily {
-                name: "Default Media Group".to_string(),
-                comment: Some("Auto-generated family for imported outlets".to_string()),
-                outlets: std::mem::take(&mut mdsl_data.outlets),
-                diachronic_links: std::mem::take(&mut mdsl_data.diachronic_links),
-                synchronous_links: std::mem::take(&mut mdsl_data.synchronous_links),
-                data_blocks: std::mem::take(&mut mdsl_data.data_blocks),
-            };
+        if mdsl_data.outlets.is_empty() {
+            return Ok(());
+        }
 
-            mdsl_data.families.push(default_family);
+        // Outlets without an ID cannot be linked and stay unassigned
+        let outlets: Vec<(String, String)> = mdsl_data
+            .outlets
+            .iter()
+            .enumerate()
+            .map(|(i, outlet)| match outlet.id {
+                Some(id) => (id.to_string(), outlet.name.clone()),
+                None => (format!("#{}", i), outlet.name.clone()),
+            })
+            .collect();
+        let links: Vec<(String, String, String)> = mdsl_data
+            .synchronous_links
+            .iter()
+            .map(|link| {
+                (
+                    link.outlet_1.id.to_string(),
+                    link.outlet_2.id.to_string(),
+                    link.relationship_type.clone(),
+                )
+            })
+            .chain(mdsl_data.diachronic_links.iter().map(|link| {
+                (
+                    link.predecessor.to_string(),
+                    link.successor.to_string(),
+                    link.relationship_type.clone(),
+                )
+            }))
+            .collect();
+        let grouping = self.family_options.derive_families(&outlets, &links);
+
+        let mut families: Vec<MdslFamily> = grouping
+            .families
+            .iter()
+            .map(|family| MdslFamily {
+                name: family.name.clone(),
+                comment: Some(format!(
+                    "Derived from {} links around outlet {}",
+                    self.family_options.link_types.join(", "),
+                    family.hub
+                )),
+                outlets: Vec::new(),
+                diachronic_links: Vec::new(),
+                synchronous_links: Vec::new(),
+                data_blocks: Vec::new(),
+            })
+            .collect();
+        let unassigned = families.len();
+        families.push(MdslFamily {
+            name: UNASSIGNED_FAMILY.to_string(),
+            comment: Some(format!(
+                "Outlets without {} links",
+                self.family_options.link_types.join(", ")
+            )),
+            outlets: Vec::new(),
+            diachronic_links: Vec::new(),
+            synchronous_links: Vec::new(),
+            data_blocks: Vec::new(),
+        });
+
+        // Everything an outlet owns goes into that outlet's family
+        let family_of = |id: &str| {
+            grouping
+                .family_of(id)
+                .and_then(|name| families.iter().position(|family| family.name == name))
+                .unwrap_or(unassigned)
+        };
+        let outlet_family: Vec<usize> = outlets.iter().map(|(id, _)| family_of(id)).collect();
+        let diachronic_family: Vec<usize> = mdsl_data
+            .diachronic_links
+            .iter()
+            .map(|link| family_of(&link.predecessor.to_string()))
+            .collect();
+        let synchronous_family: Vec<usize> = mdsl_data
+            .synchronous_links
+            .iter()
+            .map(|link| family_of(&link.outlet_1.id.to_string()))
+            .collect();
+        let data_block_family: Vec<usize> = mdsl_data
+            .data_blocks
+            .iter()
+            .map(|block| family_of(&block.outlet_id.to_string()))
+            .collect();
+
+        for (outlet, family) in std::mem::take(&mut mdsl_data.outlets)
+            .into_iter()
+            .zip(outlet_family)
+        {
+            if family == unassigned {
+                mdsl_data.unassigned_outlets.push(outlet.name.clone());
+            }
+            families[family].outlets.push(outlet);
+        }
+        for (link, family) in std::mem::take(&mut mdsl_data.diachronic_links)
+            .into_iter()
+            .zip(diachronic_family)
+        {
+            families[family].diachronic_links.push(link);
+        }
+        for (link, family) in std::mem::take(&mut mdsl_data.synchronous_links)
+            .into_iter()
+            .zip(synchronous_family)
+        {
+            families[family].synchronous_links.push(link);
         }
+        for (block, family) in std::mem::take(&mut mdsl_data.data_blocks)
+            .into_iter()
+            .zip(data_block_family)
+        {
+            families[family].data_blocks.push(block);
+        }
+
+        // A family is only written if something ended up in it
+        mdsl_data.families.extend(families.into_iter().filter(|family| {
+            !family.outlets.is_empty()
+                || !family.diachronic_links.is_empty()
+                || !family.synchronous_links.is_empty()
+                || !family.data_blocks.is_empty()
+        }));
 
         Ok(())
     }
 }
+
+/// A value in the form of its declared type, or `None` when it does not fit the type
+fn typed_value(value: String, field_type: &Option<MdslFieldType>) -> Option<String> {
+    match field_type {
+        None | Some(MdslFieldType::Text(None)) => Some(value),
+        Some(MdslFieldType::Text(Some(max_length))) => {
+            (value.chars().count() <= *max_length).then_some(value)
+        }
+        Some(MdslFieldType::Category(values)) => {
+            (values.is_empty() || values.contains(&value)).then_some(value)
+        }
+        Some(MdslFieldType::Id) => value.trim().parse::<i64>().ok().map(|id| id.to_string()),
+        Some(MdslFieldType::Number) => value
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|number| number.is_finite())
+            .map(|_| value.trim().to_string()),
+        Some(MdslFieldType::Boolean) => match value.trim().to_lowercase().as_str() {
+            "1" | "true" | "yes" | "ja" => Some("true".to_string()),
+            "0" | "false" | "no" | "nein" => Some("false".to_string()),
+            _ => None,
+        },
+    }
+}
+
+/// Numeric outlet ID of a column, if set
+fn id_column(row: &HashMap<String, String>, column: &str) -> Option<i64> {
+    row.get(column).and_then(|value| value.parse().ok())
+}
+
+/// Value of the column configured for a relationship metadata field, if set
+fn metadata_column(
+    row: &HashMap<String, String>,
+    relationship: &RelationshipMapping,
+    field: &str,
+) -> Option<String> {
+    relationship
+        .metadata_fields
+        .get(field)
+        .and_then(|column| row.get(column))
+        .filter(|value| !value.is_empty() && *value != "NULL")
+        .cloned()
+}
@@ -0,0 +1,275 @@
+//! Declarative table mappings loaded from TOML
+//!
+//! Describes how the tables of a source database map to MDSL entities, so new
+//! databases can be imported without writing Rust:
+//!
+//! ```toml
+//! [[table]]
+//! name = "outlets"
+//! mdsl_type = "Outlet"
+//!
+//! [table.fields]
+//! outlet_id = { mdsl_field = "id", type = "Id", required = true }
+//! outlet_name = { mdsl_field = "title" }
+//! sector_code = { mdsl_field = "characteristics.sector", transform = "lookup", table = "sectors" }
+//! city = { mdsl_field = "characteristics.location", transform = "concat", columns = ["city", "region"] }
+//!
+//! [table.temporal]
+//! start_date = "launch_date"
+//! end_date = "closing_date"
+//!
+//! [[table]]
+//! name = "successions"
+//! mdsl_type = "DiachronicLink"
+//!
+//! [table.relationship]
+//! source_field = "old_id"
+//! target_field = "new_id"
+//! link_type = "succession"
+//! metadata = { event_date = "changed_on", comment = "note" }
+//! ```
+//!
+//! Field keys name the SQL column unless a `column` is given. Transforms are
+//! `direct` (default), `date` (with an optional `format` such as `"{}-01-01"`),
+//! `concat` (joins `columns` with spaces) and `lookup` (replaces the value with
+//! the second column of the `table` row whose first column matches). A field
+//! of `type = "Date"` gets the `date` transform unless another is set.
+//!
+//! Values are checked against their `type`: `Id` must be an integer, `Number`
+//! a number and `Boolean` one of `1`/`0`, `true`/`false`, `yes`/`no` or
+//! `ja`/`nein` (written as `true` or `false`); `Text`, `Category` and `Date`
+//! take any value. A value that does not fit its type is left out, and a row
+//! without a valid value for a `required` field is skipped with a message.
+
+use crate::error::{Error, ImportError, Result};
+use crate::import::{
+    FieldMapping, FieldTransform, MdslEntityType, MdslFieldType, RelationshipMapping,
+    RelationshipType, TableMapping, TemporalFields,
+};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Field types accepted in the `type` key of a field
+const FIELD_TYPES: &[&str] = &["Id", "Text", "Number", "Boolean", "Date", "Category"];
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MappingFile {
+    #[serde(default, rename = "table")]
+    tables: Vec<TableConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TableConfig {
+    name: String,
+    mdsl_type: String,
+    #[serde(default)]
+    fields: BTreeMap<String, FieldConfig>,
+    relationship: Option<RelationshipConfig>,
+    temporal: Option<TemporalConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FieldConfig {
+    mdsl_field: String,
+    column: Option<String>,
+    #[serde(rename = "type")]
+    field_type: Option<String>,
+    #[serde(default)]
+    required: bool,
+    transform: Option<String>,
+    format: Option<String>,
+    #[serde(default)]
+    columns: Vec<String>,
+    table: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RelationshipConfig {
+    source_field: String,
+    target_field: String,
+    link_type: Option<String>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemporalConfig {
+    start_date: Option<String>,
+    end_date: Option<String>,
+    status_field: Option<String>,
+}
+
+/// Load the table mappings of a TOML file, keyed by table name
+pub fn load_mappings(path: &Path) -> Result<HashMap<String, TableMapping>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| Error::Io(format!("{}: {}", path.display(), e)))?;
+    parse_mappings(&content).map_err(|error| match error {
+        Error::Import(ImportError::MappingFailed { table, message }) if table.is_empty() => {
+            Error::Import(ImportError::MappingFailed {
+                table: path.display().to_string(),
+                message,
+            })
+        }
+        other => other,
+    })
+}
+
+/// Parse TOML table mappings, keyed by table name
+pub fn parse_mappings(content: &str) -> Result<HashMap<String, TableMapping>> {
+    let file: MappingFile = toml::from_str(content).map_err(|e| {
+        Error::Import(ImportError::MappingFailed {
+            table: String::new(),
+            message: e.to_string(),
+        })
+    })?;
+
+    let mut mappings = HashMap::new();
+    for table in file.tables {
+        let name = table.name.clone();
+        if mappings.contains_key(&name) {
+            return Err(mapping_error(&name, "table is mapped more than once".to_string()));
+        }
+        mappings.insert(name, table_mapping(table)?);
+    }
+    Ok(mappings)
+}
+
+fn table_mapping(table: TableConfig) -> Result<TableMapping> {
+    let entity_type = entity_type(&table.name, &table.mdsl_type)?;
+
+    let mut field_mappings = HashMap::new();
+    for (key, field) in table.fields {
+        field_mappings.insert(key.clone(), field_mapping(&table.name, key, field)?);
+    }
+
+    let relationship_type = match entity_type {
+        MdslEntityType::DiachronicLink => Some(RelationshipType::Diachronic),
+        MdslEntityType::SynchronousLink => Some(RelationshipType::Synchronous),
+        _ => None,
+    };
+    let relationship_mapping = match (table.relationship, relationship_type) {
+        (Some(relationship), Some(relationship_type)) => Some(RelationshipMapping {
+            relationship_type,
+            source_field: relationship.source_field,
+            target_field: relationship.target_field,
+            link_type: relationship.link_type,
+            metadata_fields: relationship.metadata,
+        }),
+        (Some(_), None) => {
+            return Err(mapping_error(
+                &table.name,
+                format!("{} tables cannot have a relationship", table.mdsl_type),
+            ))
+        }
+        (None, _) => None,
+    };
+
+    Ok(TableMapping {
+        entity_type,
+        field_mappings,
+        relationship_mapping,
+        temporal_fields: table.temporal.map(|temporal| TemporalFields {
+            start_date: temporal.start_date,
+            end_date: temporal.end_date,
+            status_field: temporal.status_field,
+        }),
+        is_anmi_schema: false,
+    })
+}
+
+fn field_mapping(table: &str, key: String, field: FieldConfig) -> Result<FieldMapping> {
+    // Dates are checked by their transform, not as a value type
+    let field_type = match field.field_type.as_deref() {
+        None | Some("Date") => None,
+        Some("Id") => Some(MdslFieldType::Id),
+        Some("Text") => Some(MdslFieldType::Text(None)),
+        Some("Number") => Some(MdslFieldType::Number),
+        Some("Boolean") => Some(MdslFieldType::Boolean),
+        Some("Category") => Some(MdslFieldType::Category(Vec::new())),
+        Some(other) => {
+            return Err(mapping_error(
+                table,
+                format!(
+                    "field '{}' has unknown type '{}' (expected one of {})",
+                    key,
+                    other,
+                    FIELD_TYPES.join(", ")
+                ),
+            ))
+        }
+    };
+
+    let transform = match field.transform.as_deref() {
+        None if field.field_type.as_deref() == Some("Date") => {
+            FieldTransform::Date(field.format.unwrap_or_default())
+        }
+        None | Some("direct") => FieldTransform::Direct,
+        Some("date") => FieldTransform::Date(field.format.unwrap_or_default()),
+        Some("concat") if !field.columns.is_empty() => FieldTransform::Concat(field.columns),
+        Some("lookup") if field.table.is_some() => {
+            FieldTransform::Lookup(field.table.unwrap_or_default())
+        }
+        Some("concat") => {
+            return Err(mapping_error(
+                table,
+                format!("field '{}' needs `columns` for the concat transform", key),
+            ))
+        }
+        Some("lookup") => {
+            return Err(mapping_error(
+                table,
+                format!("field '{}' needs a `table` for the lookup transform", key),
+            ))
+        }
+        Some(other) => {
+            return Err(mapping_error(
+                table,
+                format!(
+                    "field '{}' has unknown transform '{}' (expected direct, date, concat or lookup)",
+                    key, other
+                ),
+            ))
+        }
+    };
+
+    Ok(FieldMapping {
+        sql_column: field.column.unwrap_or_else(|| key.clone()),
+        mdsl_field: field.mdsl_field,
+        transform: Some(transform),
+        required: field.required,
+        field_type,
+    })
+}
+
+fn entity_type(table: &str, mdsl_type: &str) -> Result<MdslEntityType> {
+    match mdsl_type {
+        "Unit" => Ok(MdslEntityType::Unit),
+        "Vocabulary" => Ok(MdslEntityType::Vocabulary),
+        "Family" => Ok(MdslEntityType::Family),
+        "Outlet" => Ok(MdslEntityType::Outlet),
+        "DiachronicLink" => Ok(MdslEntityType::DiachronicLink),
+        "SynchronousLink" => Ok(MdslEntityType::SynchronousLink),
+        "DataBlock" => Ok(MdslEntityType::DataBlock),
+        other => Err(mapping_error(
+            table,
+            format!(
+                "unknown mdsl_type '{}' (expected Unit, Vocabulary, Family, Outlet, \
+                 DiachronicLink, SynchronousLink or DataBlock)",
+                other
+            ),
+        )),
+    }
+}
+
+fn mapping_error(table: &str, message: String) -> Error {
+    Error::Import(ImportError::MappingFailed {
+        table: table.to_string(),
+        message,
+    })
+}
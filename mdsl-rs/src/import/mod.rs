@@ -8,8 +8,16 @@ use crate::error::{Error, Result};
 use std::collections::HashMap;
 
 pub mod connection;
+pub mod csv_source;
+pub mod families;
 pub mod generator;
 pub mod mapper;
+pub mod mapping_file;
+pub mod mysql_source;
+pub mod northdata;
+pub mod selection;
+pub mod sqlite_source;
+pub mod sync;
 
 /// Database configuration for SQL import
 #[derive(Debug, Clone)]
@@ -33,6 +41,8 @@ pub enum DatabaseType {
     SQLite,
     /// Microsoft SQL Server database
     SqlServer,
+    /// Directory of CSV table dumps (one `<table>.csv` per table)
+    Csv,
 }
 
 /// Table mapping configuration for SQL to MDSL conversion
@@ -78,8 +88,10 @@ pub struct FieldMapping {
     pub mdsl_field: String,
     /// Optional field transformation
     pub transform: Option<FieldTransform>,
-    /// Whether this field is required
+    /// Whether this field is required; rows without a valid value are skipped
     pub required: bool,
+    /// Declared value type; values that do not fit it are left out
+    pub field_type: Option<MdslFieldType>,
 }
 
 /// Field transformation types
@@ -130,7 +142,9 @@ pub struct RelationshipMapping {
     pub source_field: String,
     /// Target entity field
     pub target_field: String,
-    /// Optional relationship metadata fields
+    /// Fixed relationship type for tables holding a single kind of link
+    pub link_type: Option<String>,
+    /// Optional relationship metadata fields (MDSL field -> SQL column)
     pub metadata_fields: HashMap<String, String>,
 }
 
@@ -154,12 +168,33 @@ pub struct TemporalFields {
     pub status_field: Option<String>,
 }
 
+/// ANMI tables of contemporary relationships (with temporal bounds) and their types
+const CONTEMPORARY_RELATIONSHIPS: &[(&str, &str)] = &[
+    ("31_main_media_outlet", "main_media_outlet"),
+    ("33_umbrella", "umbrella"),
+    ("34_collaboration", "collaboration"),
+];
+
+/// ANMI tables of historical relationships (without temporal bounds) and their types
+const HISTORICAL_RELATIONSHIPS: &[(&str, &str)] = &[
+    ("11_succession", "succession"),
+    ("12_amalgamation", "amalgamation"),
+    ("13_new_distribution_area", "new_distribution_area"),
+    ("14_new_sector", "new_sector"),
+    ("19_interruption", "interruption"),
+    ("21_split_off", "split_off"),
+    ("22_offshoot", "offshoot"),
+    ("23_merger", "merger"),
+];
+
 /// Main SQL importer
 pub struct SqlImporter {
     /// Database configuration
     pub config: DatabaseConfig,
     /// Table mappings for conversion
     pub table_mappings: HashMap<String, TableMapping>,
+    /// How imported outlets are clustered into families
+    pub family_options: families::FamilyOptions,
 }
 
 impl SqlImporter {
@@ -168,6 +203,7 @@ impl SqlImporter {
         Self {
             config,
             table_mappings: HashMap::new(),
+            family_options: families::FamilyOptions::default(),
         }
     }
 
@@ -199,13 +235,15 @@ impl SqlImporter {
         let schema_info = connection.analyze_schema(schema).await?;
 
         // Map data to MDSL structures
-        let mapper = mapper::DataMapper::new(&self.table_mappings);
+        let mapper = mapper::DataMapper::new(&self.table_mappings)
+            .with_family_options(self.family_options.clone());
         mapper.map_database_to_mdsl(&connection, &schema_info).await
     }
 
     /// Import database content and generate MDSL code in one step
     pub async fn import_and_generate(&self) -> Result<String> {
         let mdsl_data = self.import_database().await?;
+        report_unassigned(&mdsl_data.unassigned_outlets);
         let generator = generator::MdslGenerator::new();
         generator.generate(&mdsl_data.to_entities())
     }
@@ -231,7 +269,8 @@ impl SqlImporter {
 
         let schema_info = connection.analyze_schema(schema).await?;
 
-        let mapper = mapper::DataMapper::new(&self.table_mappings);
+        let mapper = mapper::DataMapper::new(&self.table_mappings)
+            .with_family_options(self.family_options.clone());
         let mdsl_data = mapper
             .map_database_to_mdsl(&connection, &schema_info)
             .await?;
@@ -279,6 +318,32 @@ impl SqlImporter {
         Ok(mappings)
     }
 
+    /// Register the table mappings of a TOML mapping file, returning how many were added
+    pub fn load_mapping_file(&mut self, path: &std::path::Path) -> Result<usize> {
+        let mappings = mapping_file::load_mappings(path)?;
+        let count = mappings.len();
+        self.table_mappings.extend(mappings);
+        Ok(count)
+    }
+
+    /// Detect ANMI tables and register their mappings, returning how many were added
+    pub async fn register_anmi_mappings(&mut self) -> Result<usize> {
+        let mut connection = connection::DatabaseConnection::new(self.config.clone())?;
+        connection.connect().await?;
+
+        let tables = connection.get_tables(self.config.schema.as_deref()).await?;
+        let mut registered = 0;
+        for table_name in tables {
+            if self.is_anmi_table(&table_name) {
+                let mapping = self.create_anmi_mapping(&table_name)?;
+                self.add_table_mapping(table_name, mapping);
+                registered += 1;
+            }
+        }
+
+        Ok(registered)
+    }
+
     /// Check if a table follows ANMI naming patterns (updated for real schema)
     fn is_anmi_table(&self, table_name: &str) -> bool {
         // Real ANMI table patterns from graphv3 schema
@@ -316,6 +381,7 @@ impl SqlImporter {
                         mdsl_field: "id".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: true,
+                        field_type: None,
                     },
                 );
 
@@ -326,6 +392,7 @@ impl SqlImporter {
                         mdsl_field: "identity.title".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: true,
+                        field_type: None,
                     },
                 );
 
@@ -336,6 +403,7 @@ impl SqlImporter {
                         mdsl_field: "characteristics.sector".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: false,
+                        field_type: None,
                     },
                 );
 
@@ -346,6 +414,7 @@ impl SqlImporter {
                         mdsl_field: "characteristics.mandate".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: false,
+                        field_type: None,
                     },
                 );
 
@@ -356,6 +425,7 @@ impl SqlImporter {
                         mdsl_field: "characteristics.location".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: false,
+                        field_type: None,
                     },
                 );
 
@@ -366,6 +436,7 @@ impl SqlImporter {
                         mdsl_field: "lifecycle.start_date".to_string(),
                         transform: Some(FieldTransform::Date("".to_string())),
                         required: false,
+                        field_type: None,
                     },
                 );
 
@@ -376,6 +447,7 @@ impl SqlImporter {
                         mdsl_field: "lifecycle.end_date".to_string(),
                         transform: Some(FieldTransform::Date("".to_string())),
                         required: false,
+                        field_type: None,
                     },
                 );
 
@@ -386,6 +458,7 @@ impl SqlImporter {
                         mdsl_field: "characteristics.editorial_line_self".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: false,
+                        field_type: None,
                     },
                 );
 
@@ -396,6 +469,7 @@ impl SqlImporter {
                         mdsl_field: "characteristics.editorial_line_external".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: false,
+                        field_type: None,
                     },
                 );
 
@@ -412,6 +486,7 @@ impl SqlImporter {
                         mdsl_field: "outlet_id".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: true,
+                        field_type: None,
                     },
                 );
 
@@ -422,6 +497,7 @@ impl SqlImporter {
                         mdsl_field: "year".to_string(),
                         transform: Some(FieldTransform::Date("".to_string())),
                         required: true,
+                        field_type: None,
                     },
                 );
 
@@ -432,6 +508,7 @@ impl SqlImporter {
                         mdsl_field: "metrics.circulation".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: false,
+                        field_type: None,
                     },
                 );
 
@@ -442,6 +519,7 @@ impl SqlImporter {
                         mdsl_field: "metrics.reach_national".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: false,
+                        field_type: None,
                     },
                 );
 
@@ -452,6 +530,7 @@ impl SqlImporter {
                         mdsl_field: "metrics.market_share".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: false,
+                        field_type: None,
                     },
                 );
 
@@ -468,6 +547,7 @@ impl SqlImporter {
                         mdsl_field: "id".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: true,
+                        field_type: None,
                     },
                 );
 
@@ -478,6 +558,7 @@ impl SqlImporter {
                         mdsl_field: "name".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: true,
+                        field_type: None,
                     },
                 );
 
@@ -503,6 +584,7 @@ impl SqlImporter {
                         mdsl_field: "source_outlet".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: true,
+                        field_type: None,
                     },
                 );
 
@@ -515,6 +597,7 @@ impl SqlImporter {
                         mdsl_field: "target_outlet".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: true,
+                        field_type: None,
                     },
                 );
 
@@ -535,6 +618,7 @@ impl SqlImporter {
                         mdsl_field: "source_outlet".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: true,
+                        field_type: None,
                     },
                 );
 
@@ -547,6 +631,7 @@ impl SqlImporter {
                         mdsl_field: "target_outlet".to_string(),
                         transform: Some(FieldTransform::Direct),
                         required: true,
+                        field_type: None,
                     },
                 );
 
@@ -557,6 +642,7 @@ impl SqlImporter {
                         mdsl_field: "start_date".to_string(),
                         transform: Some(FieldTransform::Date("".to_string())),
                         required: false,
+                        field_type: None,
                     },
                 );
 
@@ -567,6 +653,7 @@ impl SqlImporter {
                         mdsl_field: "end_date".to_string(),
                         transform: Some(FieldTransform::Date("".to_string())),
                         required: false,
+                        field_type: None,
                     },
                 );
 
@@ -592,8 +679,76 @@ impl SqlImporter {
 
     /// Generate complete MDSL from ANMI database with proper entity relationships
     pub async fn generate_complete_mdsl(&self) -> Result<String> {
+        self.generate_selected_mdsl(&selection::Selection::default()).await
+    }
+
+    /// Generate MDSL for the outlets picked by a selection, with the links
+    /// between them, their market data and the sources that data cites
+    pub async fn generate_selected_mdsl(&self, selection: &selection::Selection) -> Result<String> {
         println!("🔄 Starting comprehensive MDSL generation from ANMI database...");
 
+        let AnmiExtract {
+            outlets: mo_constant_data,
+            market_data: mo_year_data,
+            sources: sources_data,
+            relationships: relationships_data,
+            ..
+        } = self.extract_selected(selection).await?;
+
+        // Generate MDSL structures
+        let mut output = String::new();
+
+        // Generate header and imports
+        self.generate_mdsl_header(&mut output)?;
+
+        // Generate vocabulary from sources
+        self.generate_vocabulary_from_sources(&mut output, &sources_data)?;
+
+        // Generate sector vocabulary from actual outlet data
+        self.generate_sector_vocabulary(&mut output, &mo_constant_data)?;
+
+        // Generate media outlet units and families (now includes stubs for referenced-only IDs)
+        self.generate_media_outlets_mdsl(&mut output, &mo_constant_data, &relationships_data)?;
+
+        // Generate relationships
+        self.generate_relationships_mdsl(&mut output, &relationships_data)?;
+
+        // Generate yearly market data as DATA blocks, as sync_mdsl renders them
+        self.generate_data_blocks_mdsl(&mut output, &mo_year_data, &mo_constant_data)?;
+
+        println!("🎉 Generated complete MDSL file");
+        Ok(output)
+    }
+
+    /// Compare an existing MDSL file with the outlets picked by a selection
+    /// and return the edits that bring it up to date: added, changed and
+    /// removed outlets, links and yearly market data
+    pub async fn sync_mdsl(
+        &self,
+        existing: &str,
+        selection: &selection::Selection,
+    ) -> Result<sync::SyncPatch> {
+        let extract = self.extract_selected(selection).await?;
+
+        let mut generated = String::new();
+        self.generate_media_outlets_mdsl(&mut generated, &extract.outlets, &extract.relationships)?;
+        self.generate_relationships_mdsl(&mut generated, &extract.relationships)?;
+        self.generate_data_blocks_mdsl(&mut generated, &extract.market_data, &extract.outlets)?;
+
+        let scope = sync::SyncScope {
+            outlet_ids: extract.database_ids,
+            link_types: CONTEMPORARY_RELATIONSHIPS
+                .iter()
+                .chain(HISTORICAL_RELATIONSHIPS)
+                .map(|(_, relationship_type)| relationship_type.to_string())
+                .collect(),
+        };
+        sync::diff(existing, &generated, &scope)
+    }
+
+    /// Extract the outlets picked by a selection with their links, market
+    /// data and sources, adding stub outlets for IDs only referenced in links
+    async fn extract_selected(&self, selection: &selection::Selection) -> Result<AnmiExtract> {
         // Connect to database
         let mut connection = connection::DatabaseConnection::new(self.config.clone())?;
         connection.connect().await?;
@@ -602,9 +757,35 @@ impl SqlImporter {
 
         // Extract and process all data
         let mut mo_constant_data = self.extract_media_outlets_full(&connection).await?;
-        let mo_year_data = self.extract_market_data(&connection).await?;
-        let sources_data = self.extract_sources(&connection).await?;
-        let relationships_data = self.extract_relationships_full(&connection).await?;
+        let mut mo_year_data = self.extract_market_data(&connection).await?;
+        let mut sources_data = self.extract_sources(&connection).await?;
+        let mut relationships_data = self.extract_relationships_full(&connection).await?;
+
+        let database_ids: std::collections::HashSet<String> = mo_constant_data
+            .iter()
+            .map(|outlet| outlet.id_mo.clone())
+            .chain(
+                relationships_data
+                    .iter()
+                    .flat_map(|r| [r.source_id.clone(), r.target_id.clone()]),
+            )
+            .collect();
+
+        if !selection.is_empty() {
+            let ids = selection.select_outlets(&mo_constant_data, &relationships_data);
+            selection::retain(
+                &ids,
+                &mut mo_constant_data,
+                &mut mo_year_data,
+                &mut sources_data,
+                &mut relationships_data,
+            );
+            println!(
+                "✅ Selected {} outlets within {} hops of the seeds",
+                ids.len(),
+                selection.hops
+            );
+        }
 
         println!(
             "✅ Extracted {} media outlets, {} market data records, {} sources, {} relationships",
@@ -659,160 +840,13 @@ impl SqlImporter {
             }
         }
 
-        // Generate MDSL structures
-        let mut output = String::new();
-
-        // Generate header and imports
-        self.generate_mdsl_header(&mut output)?;
-
-        // Generate vocabulary from sources
-        self.generate_vocabulary_from_sources(&mut output, &sources_data)?;
-
-        // Generate sector vocabulary from actual outlet data
-        self.generate_sector_vocabulary(&mut output, &mo_constant_data)?;
-
-        // Generate media outlet units and families (now includes stubs for referenced-only IDs)
-        self.generate_media_outlets_mdsl(&mut output, &mo_constant_data)?;
-
-        // Generate relationships
-        self.generate_relationships_mdsl(&mut output, &relationships_data)?;
-
-        // RESTORED: Generate time series data integrated into outlet definitions
-        // This restores the missing ~5,356 lines of time series data from MDSL-003
-        self.generate_time_series_data(&mut output, &mo_year_data, &mo_constant_data)?;
-
-        println!("🎉 Generated complete MDSL file");
-        Ok(output)
-    }
-
-    /// Extract media outlet data from mo_constant table - ORF focused (for GUI)
-    async fn extract_media_outlets_orf_focused(
-        &self,
-        connection: &connection::DatabaseConnection,
-    ) -> Result<Vec<MediaOutletData>> {
-        // Query to find all outlets connected to ORF outlets through any relationship
-        let query = r#"
-        WITH orf_outlets AS (
-            SELECT id_mo
-            FROM graphv3.mo_constant
-            WHERE mo_title ILIKE '%orf%'
-        ),
-        connected_outlets AS (
-            -- Include direct ORF outlets
-            SELECT id_mo as outlet_id FROM orf_outlets
-            
-            UNION
-            
-            -- From contemporary relationships (bilateral)
-            SELECT DISTINCT unnest(ARRAY[id_mo, main_media_outlet]) as outlet_id
-            FROM graphv3."31_main_media_outlet" 
-            WHERE id_mo IN (SELECT id_mo FROM orf_outlets) 
-               OR main_media_outlet IN (SELECT id_mo FROM orf_outlets)
-            
-            UNION
-            
-            SELECT DISTINCT unnest(ARRAY[id_mo, umbrella]) as outlet_id
-            FROM graphv3."33_umbrella"
-            WHERE id_mo IN (SELECT id_mo FROM orf_outlets)
-               OR umbrella IN (SELECT id_mo FROM orf_outlets)
-               
-            UNION
-            
-            SELECT DISTINCT unnest(ARRAY[id_mo, collaboration]) as outlet_id
-            FROM graphv3."34_collaboration"
-            WHERE id_mo IN (SELECT id_mo FROM orf_outlets)
-               OR collaboration IN (SELECT id_mo FROM orf_outlets)
-               
-            UNION
-            
-            -- From historical relationships (bilateral)
-            SELECT DISTINCT unnest(ARRAY[id_mo, succession]) as outlet_id
-            FROM graphv3."11_succession"
-            WHERE id_mo IN (SELECT id_mo FROM orf_outlets)
-               OR succession IN (SELECT id_mo FROM orf_outlets)
-               
-            UNION
-            
-            SELECT DISTINCT unnest(ARRAY[id_mo, amalgamation]) as outlet_id
-            FROM graphv3."12_amalgamation"
-            WHERE id_mo IN (SELECT id_mo FROM orf_outlets)
-               OR amalgamation IN (SELECT id_mo FROM orf_outlets)
-               
-            UNION
-            
-            SELECT DISTINCT unnest(ARRAY[id_mo, new_distribution_area]) as outlet_id
-            FROM graphv3."13_new_distribution_area"
-            WHERE id_mo IN (SELECT id_mo FROM orf_outlets)
-               OR new_distribution_area IN (SELECT id_mo FROM orf_outlets)
-               
-            UNION
-            
-            SELECT DISTINCT unnest(ARRAY[id_mo, new_sector]) as outlet_id
-            FROM graphv3."14_new_sector"
-            WHERE id_mo IN (SELECT id_mo FROM orf_outlets)
-               OR new_sector IN (SELECT id_mo FROM orf_outlets)
-               
-            UNION
-            
-            SELECT DISTINCT unnest(ARRAY[id_mo, interruption]) as outlet_id
-            FROM graphv3."19_interruption"
-            WHERE id_mo IN (SELECT id_mo FROM orf_outlets)
-               OR interruption IN (SELECT id_mo FROM orf_outlets)
-               
-            UNION
-            
-            SELECT DISTINCT unnest(ARRAY[id_mo, split_off]) as outlet_id
-            FROM graphv3."21_split_off"
-            WHERE id_mo IN (SELECT id_mo FROM orf_outlets)
-               OR split_off IN (SELECT id_mo FROM orf_outlets)
-               
-            UNION
-            
-            SELECT DISTINCT unnest(ARRAY[id_mo, offshoot]) as outlet_id
-            FROM graphv3."22_offshoot"
-            WHERE id_mo IN (SELECT id_mo FROM orf_outlets)
-               OR offshoot IN (SELECT id_mo FROM orf_outlets)
-               
-            UNION
-            
-            SELECT DISTINCT unnest(ARRAY[id_mo, merger]) as outlet_id
-            FROM graphv3."23_merger"
-            WHERE id_mo IN (SELECT id_mo FROM orf_outlets)
-               OR merger IN (SELECT id_mo FROM orf_outlets)
-        )
-        SELECT mo.id_mo, mo.mo_title, mo.id_sector, mo.mandate, mo.location, mo.primary_distr_area, mo.local, mo.language, mo.start_date, mo.end_date, mo.editorial_line_s, mo.comments 
-        FROM graphv3.mo_constant mo
-        JOIN connected_outlets co ON mo.id_mo = co.outlet_id
-        WHERE mo.mo_title IS NOT NULL
-        ORDER BY mo.mo_title
-        "#;
-
-        let rows = connection.execute_query(query).await?;
-        let mut outlets = Vec::new();
-
-        for row in rows {
-            if row.len() >= 12 {
-                outlets.push(MediaOutletData {
-                    id_mo: row[0].clone(),
-                    title: row[1].clone(),
-                    sector: row[2].clone(),
-                    mandate: row[3].clone(),
-                    location: row[4].clone(),
-                    distribution_area: row[5].clone(),
-                    local: row[6].clone(),
-                    language: row[7].clone(),
-                    start_date: row[8].clone(),
-                    start_fake_date: "".to_string(),
-                    end_date: row[9].clone(),
-                    end_fake_date: "".to_string(),
-                    editorial_line_start: row[10].clone(),
-                    editorial_line_end: "".to_string(),
-                    comments: row[11].clone(),
-                });
-            }
-        }
-
-        Ok(outlets)
+        Ok(AnmiExtract {
+            outlets: mo_constant_data,
+            market_data: mo_year_data,
+            sources: sources_data,
+            relationships: relationships_data,
+            database_ids,
+        })
     }
 
     /// Extract ALL media outlets from database (full conversion)
@@ -820,30 +854,35 @@ impl SqlImporter {
         &self,
         connection: &connection::DatabaseConnection,
     ) -> Result<Vec<MediaOutletData>> {
-        // Query to extract ALL media outlets from the database
-        let query = r#"
-        SELECT 
-            id_mo,
-            mo_title,
-            id_sector,
-            mandate,
-            location,
-            primary_distr_area,
-            local,
-            language,
-            start_date,
-            end_date,
-            editorial_line_s,
-            comments
-        FROM graphv3.mo_constant 
-        WHERE id_mo IS NOT NULL 
-        ORDER BY id_mo"#;
-
-        let rows = connection.execute_query(query).await?;
+        // Extract ALL media outlets from the database
+        let rows = connection
+            .select_rows(
+                "mo_constant",
+                &[
+                    "id_mo",
+                    "mo_title",
+                    "id_sector",
+                    "mandate",
+                    "location",
+                    "primary_distr_area",
+                    "local",
+                    "language",
+                    "start_date",
+                    "start_fake_date",
+                    "end_date",
+                    "end_fake_date",
+                    "editorial_line_s",
+                    "editorial_line_e",
+                    "comments",
+                ],
+                &["id_mo"],
+                &["id_mo"],
+            )
+            .await?;
         let mut outlets = Vec::new();
 
         for row in rows {
-            if row.len() >= 12 {
+            if row.len() >= 15 {
                 outlets.push(MediaOutletData {
                     id_mo: row[0].clone(),
                     title: row[1].clone(),
@@ -854,12 +893,12 @@ impl SqlImporter {
                     local: row[6].clone(),
                     language: row[7].clone(),
                     start_date: row[8].clone(),
-                    start_fake_date: "".to_string(),
-                    end_date: row[9].clone(),
-                    end_fake_date: "".to_string(),
-                    editorial_line_start: row[10].clone(),
-                    editorial_line_end: "".to_string(),
-                    comments: row[11].clone(),
+                    start_fake_date: row[9].clone(),
+                    end_date: row[10].clone(),
+                    end_fake_date: row[11].clone(),
+                    editorial_line_start: row[12].clone(),
+                    editorial_line_end: row[13].clone(),
+                    comments: row[14].clone(),
                 });
             }
         }
@@ -867,22 +906,35 @@ impl SqlImporter {
         Ok(outlets)
     }
 
-    /// Alias for backward compatibility (GUI uses this)
-    async fn extract_media_outlets(
-        &self,
-        connection: &connection::DatabaseConnection,
-    ) -> Result<Vec<MediaOutletData>> {
-        self.extract_media_outlets_orf_focused(connection).await
-    }
-
     /// Extract market data from mo_year table (include all records to show data patterns)
     async fn extract_market_data(
         &self,
         connection: &connection::DatabaseConnection,
     ) -> Result<Vec<MarketData>> {
-        let query = "SELECT id_mo, year, mo_year, calc, circulation, circulation_source, unique_users, unique_users_source, reach_nat, reach_nat_source, reach_reg, reach_reg_source, market_share, market_share_source, comments FROM graphv3.mo_year ORDER BY year, calc, comments";
-
-        let rows = connection.execute_query(query).await?;
+        let rows = connection
+            .select_rows(
+                "mo_year",
+                &[
+                    "id_mo",
+                    "year",
+                    "mo_year",
+                    "calc",
+                    "circulation",
+                    "circulation_source",
+                    "unique_users",
+                    "unique_users_source",
+                    "reach_nat",
+                    "reach_nat_source",
+                    "reach_reg",
+                    "reach_reg_source",
+                    "market_share",
+                    "market_share_source",
+                    "comments",
+                ],
+                &[],
+                &["year", "calc", "comments"],
+            )
+            .await?;
         let mut market_data = Vec::new();
 
         for row in rows {
@@ -915,9 +967,14 @@ impl SqlImporter {
         &self,
         connection: &connection::DatabaseConnection,
     ) -> Result<Vec<SourceData>> {
-        let query = "SELECT id_source, source_name FROM graphv3.sources_names WHERE source_name IS NOT NULL ORDER BY source_name";
-
-        let rows = connection.execute_query(query).await?;
+        let rows = connection
+            .select_rows(
+                "sources_names",
+                &["id_source", "source_name"],
+                &["source_name"],
+                &["source_name"],
+            )
+            .await?;
         let mut sources = Vec::new();
 
         for row in rows {
@@ -940,150 +997,15 @@ impl SqlImporter {
         let mut relationships = Vec::new();
 
         // Contemporary relationships (with temporal bounds) - ALL outlets
-        let contemporary_tables = vec![
-            ("31_main_media_outlet", "main_media_outlet"),
-            ("33_umbrella", "umbrella"),
-            ("34_collaboration", "collaboration"),
-        ];
-
-        for (table_name, relation_type) in contemporary_tables {
-            let query = format!(
-                r#"SELECT id_mo, {}, start_rel, end_rel 
-                FROM graphv3."{}" 
-                WHERE {} IS NOT NULL AND id_mo IS NOT NULL"#,
-                relation_type, table_name, relation_type
-            );
-            let rows = connection.execute_query(&query).await?;
-
-            for row in rows {
-                if row.len() >= 4 {
-                    relationships.push(RelationshipData {
-                        source_id: row[0].clone(),
-                        target_id: row[1].clone(),
-                        relationship_type: relation_type.to_string(),
-                        start_date: row[2].clone(),
-                        end_date: row[3].clone(),
-                        is_temporal: true,
-                    });
-                }
-            }
-        }
-
-        // Historical relationships (without temporal bounds) - ALL outlets
-        let historical_tables = vec![
-            ("11_succession", "succession"),
-            ("12_amalgamation", "amalgamation"),
-            ("13_new_distribution_area", "new_distribution_area"),
-            ("14_new_sector", "new_sector"),
-            ("19_interruption", "interruption"),
-            ("21_split_off", "split_off"),
-            ("22_offshoot", "offshoot"),
-            ("23_merger", "merger"),
-        ];
-
-        for (table_name, relation_type) in historical_tables {
-            let query = format!(
-                r#"SELECT id_mo, {}, NULL as start_rel, NULL as end_rel 
-                FROM graphv3."{}" 
-                WHERE {} IS NOT NULL AND id_mo IS NOT NULL"#,
-                relation_type, table_name, relation_type
-            );
-            let rows = connection.execute_query(&query).await?;
-
-            for row in rows {
-                if row.len() >= 2 {
-                    relationships.push(RelationshipData {
-                        source_id: row[0].clone(),
-                        target_id: row[1].clone(),
-                        relationship_type: relation_type.to_string(),
-                        start_date: "NULL".to_string(),
-                        end_date: "NULL".to_string(),
-                        is_temporal: false,
-                    });
-                }
-            }
-        }
-
-        Ok(relationships)
-    }
-
-    /// Extract relationship data from all relationship tables (ORF-focused for GUI)
-    async fn extract_relationships_orf_focused(
-        &self,
-        connection: &connection::DatabaseConnection,
-    ) -> Result<Vec<RelationshipData>> {
-        let mut relationships = Vec::new();
-
-        // Contemporary relationships (with temporal bounds)
-        let contemporary_tables = vec![
-            ("31_main_media_outlet", "main_media_outlet"),
-            ("33_umbrella", "umbrella"),
-            ("34_collaboration", "collaboration"),
-        ];
-
-        for (table_name, relation_type) in contemporary_tables {
-            let query = format!(
-                r#"WITH orf_outlets AS (
-                    SELECT id_mo
-                    FROM graphv3.mo_constant
-                    WHERE mo_title ILIKE '%orf%'
-                ),
-                connected_outlets AS (
-                    -- Include direct ORF outlets
-                    SELECT id_mo as outlet_id FROM orf_outlets
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, main_media_outlet]) as outlet_id
-                    FROM graphv3."31_main_media_outlet" 
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR main_media_outlet IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, umbrella]) as outlet_id
-                    FROM graphv3."33_umbrella"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR umbrella IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, collaboration]) as outlet_id
-                    FROM graphv3."34_collaboration"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR collaboration IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, succession]) as outlet_id
-                    FROM graphv3."11_succession"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR succession IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, amalgamation]) as outlet_id
-                    FROM graphv3."12_amalgamation"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR amalgamation IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, new_distribution_area]) as outlet_id
-                    FROM graphv3."13_new_distribution_area"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR new_distribution_area IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, new_sector]) as outlet_id
-                    FROM graphv3."14_new_sector"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR new_sector IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, interruption]) as outlet_id
-                    FROM graphv3."19_interruption"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR interruption IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, split_off]) as outlet_id
-                    FROM graphv3."21_split_off"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR split_off IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, offshoot]) as outlet_id
-                    FROM graphv3."22_offshoot"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR offshoot IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, merger]) as outlet_id
-                    FROM graphv3."23_merger"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR merger IN (SELECT id_mo FROM orf_outlets)
+        for (table_name, relation_type) in CONTEMPORARY_RELATIONSHIPS.iter().copied() {
+            let rows = connection
+                .select_rows(
+                    table_name,
+                    &["id_mo", relation_type, "start_rel", "end_rel"],
+                    &[relation_type, "id_mo"],
+                    &[],
                 )
-                SELECT r.id_mo, r.{}, r.start_rel, r.end_rel 
-                FROM graphv3."{}" r
-                WHERE r.{} IS NOT NULL
-                AND r.id_mo IN (SELECT outlet_id FROM connected_outlets)
-                AND r.{} IN (SELECT outlet_id FROM connected_outlets)"#,
-                relation_type, table_name, relation_type, relation_type
-            );
-            let rows = connection.execute_query(&query).await?;
+                .await?;
 
             for row in rows {
                 if row.len() >= 4 {
@@ -1099,81 +1021,11 @@ impl SqlImporter {
             }
         }
 
-        // Historical relationships (without temporal bounds)
-        let historical_tables = vec![
-            ("11_succession", "succession"),
-            ("12_amalgamation", "amalgamation"),
-            ("13_new_distribution_area", "new_distribution_area"),
-            ("14_new_sector", "new_sector"),
-            ("19_interruption", "interruption"),
-            ("21_split_off", "split_off"),
-            ("22_offshoot", "offshoot"),
-            ("23_merger", "merger"),
-        ];
-
-        for (table_name, relation_type) in historical_tables {
-            let query = format!(
-                r#"WITH orf_outlets AS (
-                    SELECT id_mo
-                    FROM graphv3.mo_constant
-                    WHERE mo_title ILIKE '%orf%'
-                ),
-                connected_outlets AS (
-                    -- Include direct ORF outlets
-                    SELECT id_mo as outlet_id FROM orf_outlets
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, main_media_outlet]) as outlet_id
-                    FROM graphv3."31_main_media_outlet" 
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR main_media_outlet IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, umbrella]) as outlet_id
-                    FROM graphv3."33_umbrella"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR umbrella IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, collaboration]) as outlet_id
-                    FROM graphv3."34_collaboration"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR collaboration IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, succession]) as outlet_id
-                    FROM graphv3."11_succession"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR succession IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, amalgamation]) as outlet_id
-                    FROM graphv3."12_amalgamation"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR amalgamation IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, new_distribution_area]) as outlet_id
-                    FROM graphv3."13_new_distribution_area"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR new_distribution_area IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, new_sector]) as outlet_id
-                    FROM graphv3."14_new_sector"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR new_sector IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, interruption]) as outlet_id
-                    FROM graphv3."19_interruption"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR interruption IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, split_off]) as outlet_id
-                    FROM graphv3."21_split_off"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR split_off IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, offshoot]) as outlet_id
-                    FROM graphv3."22_offshoot"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR offshoot IN (SELECT id_mo FROM orf_outlets)
-                    UNION
-                    SELECT DISTINCT unnest(ARRAY[id_mo, merger]) as outlet_id
-                    FROM graphv3."23_merger"
-                    WHERE id_mo IN (SELECT id_mo FROM orf_outlets) OR merger IN (SELECT id_mo FROM orf_outlets)
-                )
-                SELECT r.id_mo, r.{} 
-                FROM graphv3."{}" r
-                WHERE r.{} IS NOT NULL
-                AND r.id_mo IN (SELECT outlet_id FROM connected_outlets)
-                AND r.{} IN (SELECT outlet_id FROM connected_outlets)"#,
-                relation_type, table_name, relation_type, relation_type
-            );
-            let rows = connection.execute_query(&query).await?;
+        // Historical relationships (without temporal bounds) - ALL outlets
+        for (table_name, relation_type) in HISTORICAL_RELATIONSHIPS.iter().copied() {
+            let rows = connection
+                .select_rows(table_name, &["id_mo", relation_type], &[relation_type, "id_mo"], &[])
+                .await?;
 
             for row in rows {
                 if row.len() >= 2 {
@@ -1192,14 +1044,6 @@ impl SqlImporter {
         Ok(relationships)
     }
 
-    /// Alias for backward compatibility (GUI uses this)
-    async fn extract_relationships(
-        &self,
-        connection: &connection::DatabaseConnection,
-    ) -> Result<Vec<RelationshipData>> {
-        self.extract_relationships_orf_focused(connection).await
-    }
-
     /// Generate MDSL file header and imports
     fn generate_mdsl_header(&self, output: &mut String) -> Result<()> {
         use std::fmt::Write;
@@ -1319,6 +1163,7 @@ impl SqlImporter {
         &self,
         output: &mut String,
         outlets: &[MediaOutletData],
+        relationships: &[RelationshipData],
     ) -> Result<()> {
         use std::fmt::Write;
 
@@ -1363,51 +1208,79 @@ impl SqlImporter {
         )?;
         writeln!(output)?;
 
-        // NEW: Generate actual FAMILY and OUTLET structures for ORF outlets
         writeln!(output, "// Generated Media Outlets")?;
         writeln!(output, "// =======================")?;
         writeln!(output)?;
 
-        // Filter for ORF outlets
-        let orf_outlets: Vec<_> = outlets
+        let grouping = self.family_options.derive_families(
+            &outlets
+                .iter()
+                .map(|outlet| (outlet.id_mo.clone(), outlet.title.clone()))
+                .collect::<Vec<_>>(),
+            &relationships
+                .iter()
+                .map(|r| {
+                    (
+                        r.source_id.clone(),
+                        r.target_id.clone(),
+                        r.relationship_type.clone(),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        );
+        println!(
+            "✅ Grouped outlets into {} families over {} links",
+            grouping.families.len(),
+            self.family_options.link_types.join(", ")
+        );
+
+        let by_id: HashMap<&str, &MediaOutletData> = outlets
             .iter()
-            .filter(|outlet| outlet.title.to_lowercase().contains("orf"))
+            .map(|outlet| (outlet.id_mo.as_str(), outlet))
             .collect();
-
-        if !orf_outlets.is_empty() {
-            writeln!(output, "FAMILY \"ORF Media Group\" {{")?;
+        report_unassigned(
+            &grouping
+                .unassigned
+                .iter()
+                .filter_map(|id| by_id.get(id.as_str()))
+                .map(|outlet| format!("{} ({})", outlet.title, outlet.id_mo))
+                .collect::<Vec<_>>(),
+        );
+        for family in &grouping.families {
+            writeln!(output, "FAMILY \"{}\" {{", family.name)?;
             writeln!(
                 output,
-                "    @comment \"Österreichischer Rundfunk and related outlets\""
+                "    @comment \"Derived from {} links around outlet {}\"",
+                self.family_options.link_types.join(", "),
+                family.hub
             )?;
             writeln!(output)?;
 
-            for outlet in orf_outlets {
-                self.generate_outlet_from_data(output, outlet)?;
-                writeln!(output)?;
+            for id in &family.members {
+                if let Some(outlet) = by_id.get(id.as_str()) {
+                    self.generate_outlet_from_data(output, outlet)?;
+                    writeln!(output)?;
+                }
             }
 
             writeln!(output, "}}")?;
             writeln!(output)?;
         }
 
-        // Generate ALL remaining outlets (non-ORF)
-        let other_outlets: Vec<_> = outlets
-            .iter()
-            .filter(|outlet| !outlet.title.to_lowercase().contains("orf"))
-            .collect();
-
-        if !other_outlets.is_empty() {
-            writeln!(output, "FAMILY \"All Media Outlets\" {{")?;
+        if !grouping.unassigned.is_empty() {
+            writeln!(output, "FAMILY \"{}\" {{", families::UNASSIGNED_FAMILY)?;
             writeln!(
                 output,
-                "    @comment \"Complete media outlets from database (excluding ORF group)\""
+                "    @comment \"Outlets without {} links\"",
+                self.family_options.link_types.join(", ")
             )?;
             writeln!(output)?;
 
-            for outlet in other_outlets {
-                self.generate_outlet_from_data(output, outlet)?;
-                writeln!(output)?;
+            for id in &grouping.unassigned {
+                if let Some(outlet) = by_id.get(id.as_str()) {
+                    self.generate_outlet_from_data(output, outlet)?;
+                    writeln!(output)?;
+                }
             }
 
             writeln!(output, "}}")?;
@@ -1432,6 +1305,24 @@ impl SqlImporter {
             .replace("-", "_")
             .replace(".", "_");
 
+        let escape = |text: &str| {
+            text.replace("\\", "\\\\")
+                .replace("\"", "\\\"")
+                .replace("\n", "\\n")
+                .replace("\r", "\\r")
+                .replace("\t", "\\t")
+        };
+        // Empty, NULL and KA ("keine Angabe") cells carry no value
+        let known = |value: &str| !value.is_empty() && value != "NULL" && value != "KA";
+        // Numeric fake_date codes are kept so that the CSV export can write them back
+        let precision = |code: &str| {
+            if !code.is_empty() && code.chars().all(|c| c.is_ascii_digit()) {
+                code.to_string()
+            } else {
+                "known".to_string()
+            }
+        };
+
         writeln!(output, "    OUTLET \"{}\" {{", outlet.title)?;
 
         writeln!(output, "        identity {{")?;
@@ -1442,13 +1333,7 @@ impl SqlImporter {
         writeln!(
             output,
             "            title = \"{}\";",
-            outlet
-                .title
-                .replace("\\", "\\\\")
-                .replace("\"", "\\\"")
-                .replace("\n", "\\n")
-                .replace("\r", "\\r")
-                .replace("\t", "\\t")
+            escape(&outlet.title)
         )?;
         writeln!(output, "        }};")?;
 
@@ -1470,8 +1355,16 @@ impl SqlImporter {
             "            status \"{}\" FROM \"{}\" TO {} {{",
             status, outlet.start_date, end_date
         )?;
-        writeln!(output, "                precision_start = \"known\";")?;
-        writeln!(output, "                precision_end = \"known\";")?;
+        writeln!(
+            output,
+            "                precision_start = \"{}\";",
+            precision(&outlet.start_fake_date)
+        )?;
+        writeln!(
+            output,
+            "                precision_end = \"{}\";",
+            precision(&outlet.end_fake_date)
+        )?;
         writeln!(output, "            }};")?;
         writeln!(output, "        }};")?;
 
@@ -1488,6 +1381,27 @@ impl SqlImporter {
         writeln!(output, "                local = {};", local_bool)?;
         writeln!(output, "            }};")?;
         writeln!(output, "            language = \"{}\";", outlet.language)?;
+        if known(&outlet.location) {
+            writeln!(
+                output,
+                "            location = \"{}\";",
+                escape(&outlet.location)
+            )?;
+        }
+        if known(&outlet.editorial_line_start) {
+            writeln!(
+                output,
+                "            editorial_line_s = \"{}\";",
+                escape(&outlet.editorial_line_start)
+            )?;
+        }
+        if known(&outlet.editorial_line_end) {
+            writeln!(
+                output,
+                "            editorial_line_e = \"{}\";",
+                escape(&outlet.editorial_line_end)
+            )?;
+        }
         writeln!(output, "        }};")?;
 
         writeln!(output, "        metadata {{")?;
@@ -1497,13 +1411,7 @@ impl SqlImporter {
             writeln!(
                 output,
                 "            comment = \"{}\";",
-                outlet
-                    .comments
-                    .replace("\\", "\\\\")
-                    .replace("\"", "\\\"")
-                    .replace("\n", "\\n")
-                    .replace("\r", "\\r")
-                    .replace("\t", "\\t")
+                escape(&outlet.comments)
             )?;
         }
         writeln!(output, "        }};")?;
@@ -1607,122 +1515,72 @@ impl SqlImporter {
         Ok(())
     }
 
-    /// Generate DATA blocks from market data showing available time series patterns
+    /// Generate a DATA block per outlet with its yearly market data; ANMI's
+    /// unknown markers (99, 99.0) and NULLs are left out
     fn generate_data_blocks_mdsl(
         &self,
         output: &mut String,
         market_data: &[MarketData],
         outlet_data: &[MediaOutletData],
     ) -> Result<()> {
-        use std::collections::HashMap;
+        use std::collections::BTreeMap;
         use std::fmt::Write;
 
-        if market_data.is_empty() {
-            return Ok(());
-        }
-
-        writeln!(output, "// Market Data & Time Series")?;
-        writeln!(output, "// =========================")?;
-        writeln!(
-            output,
-            "// Comprehensive time series data from mo_year table"
-        )?;
-        writeln!(
-            output,
-            "// Shows data availability patterns and calculation methods by year"
-        )?;
-        writeln!(output)?;
-
-        // Group by year for time series analysis
-        let mut data_by_year: HashMap<String, Vec<&MarketData>> = HashMap::new();
-        let mut records_with_outlet_id = 0;
-        let mut records_with_data = 0;
+        let known = |value: &str| !matches!(value, "" | "NULL" | "99" | "99.0");
+        let declared: std::collections::HashSet<&str> =
+            outlet_data.iter().map(|outlet| outlet.id_mo.as_str()).collect();
 
+        // Outlet ID -> year -> record, in ID and year order
+        let mut data_by_outlet: BTreeMap<&str, BTreeMap<&str, &MarketData>> = BTreeMap::new();
         for data in market_data {
-            if data.year == "NULL" || data.year.is_empty() {
+            if !declared.contains(data.id_mo.as_str()) || !known(&data.year) {
                 continue;
             }
-
-            data_by_year
-                .entry(data.year.clone())
-                .or_insert_with(Vec::new)
-                .push(data);
-
-            if data.id_mo != "NULL" && !data.id_mo.is_empty() {
-                records_with_outlet_id += 1;
-            }
-
-            if data.calc != "NULL" || data.comments != "NULL" || data.circulation != "NULL" {
-                records_with_data += 1;
-            }
+            let year = data.year.split('-').next().unwrap_or(&data.year);
+            data_by_outlet
+                .entry(data.id_mo.as_str())
+                .or_default()
+                .insert(year, data);
         }
 
-        // Generate summary first
-        writeln!(output, "// Time Series Data Summary")?;
-        writeln!(output, "// Total records: {}", market_data.len())?;
-        writeln!(
-            output,
-            "// Records with outlet ID: {}",
-            records_with_outlet_id
-        )?;
-        writeln!(output, "// Records with data: {}", records_with_data)?;
-        writeln!(output, "// Years covered: {}", data_by_year.len())?;
-        writeln!(output)?;
-
-        // Create set of valid outlet IDs that are actually generated
-        let valid_outlet_ids: std::collections::HashSet<String> = outlet_data
-            .iter()
-            .map(|outlet| outlet.id_mo.clone())
-            .collect();
-
-        // Group data by outlet_id for proper DATA FOR blocks, filtering for valid outlets only
-        let mut data_by_outlet: HashMap<String, Vec<&MarketData>> = HashMap::new();
-
-        for data in market_data {
-            if data.id_mo != "NULL"
-                && !data.id_mo.is_empty()
-                && valid_outlet_ids.contains(&data.id_mo)
-            {
-                data_by_outlet
-                    .entry(data.id_mo.clone())
-                    .or_insert_with(Vec::new)
-                    .push(data);
-            }
-        }
-
-        // Generate DATA blocks for each outlet with meaningful data
-        for (outlet_id, outlet_data) in data_by_outlet {
-            if outlet_data.is_empty() {
-                continue;
-            }
-
+        for (outlet_id, years) in data_by_outlet {
             writeln!(output, "DATA FOR {} {{", outlet_id)?;
-            writeln!(output, "    // Time series data for outlet {}", outlet_id)?;
-            writeln!(output, "    total_records: {}", outlet_data.len())?;
-
-            // Simple years block with basic metrics
-            writeln!(output, "    years {{")?;
-            for data in outlet_data {
-                if data.year != "NULL" && !data.year.is_empty() {
-                    let clean_year = data.year.split('-').next().unwrap_or(&data.year);
-                    writeln!(output, "        {} {{", clean_year)?;
-
-                    if data.circulation != "NULL" && data.circulation != "99" {
-                        writeln!(output, "            circulation = {};", data.circulation)?;
+            for (year, data) in years {
+                writeln!(output, "    YEAR {} {{", year)?;
+                writeln!(output, "        metrics {{")?;
+                let metrics = [
+                    ("calc", &data.calc, "", ""),
+                    ("circulation", &data.circulation, "copies", &data.circulation_source),
+                    ("unique_users", &data.unique_users, "users", &data.unique_users_source),
+                    ("reach_national", &data.reach_nat, "percent", &data.reach_nat_source),
+                    ("reach_regional", &data.reach_reg, "percent", &data.reach_reg_source),
+                    ("market_share", &data.market_share, "percent", &data.market_share_source),
+                ];
+                for (name, value, unit, source) in metrics {
+                    if !known(value) {
+                        continue;
                     }
-                    if data.reach_nat != "NULL" && data.reach_nat != "99.0" {
-                        writeln!(output, "            reach_national = {};", data.reach_nat)?;
+                    writeln!(output, "            {} = {{", name)?;
+                    writeln!(output, "                value = {};", value)?;
+                    if !unit.is_empty() {
+                        writeln!(output, "                unit = \"{}\";", unit)?;
                     }
-                    if data.market_share != "NULL" && data.market_share != "99.0" {
-                        writeln!(output, "            market_share = {};", data.market_share)?;
+                    if known(source) {
+                        writeln!(output, "                source = \"{}\";", source)?;
                     }
-
-                    writeln!(output, "        }};")?;
+                    writeln!(output, "            }};")?;
+                }
+                writeln!(output, "        }};")?;
+                if known(&data.comments) && data.comments != "KA" {
+                    writeln!(
+                        output,
+                        "        comment = \"{}\";",
+                        data.comments.replace('"', "\\\"")
+                    )?;
                 }
+                writeln!(output, "    }};")?;
             }
-            writeln!(output, "    }};")?;
-
-            writeln!(output, "}}")?;
+            writeln!(output, "}};")?;
             writeln!(output)?;
         }
 
@@ -1904,6 +1762,32 @@ impl SqlImporter {
     }
 }
 
+/// ANMI rows picked by a selection
+struct AnmiExtract {
+    outlets: Vec<MediaOutletData>,
+    market_data: Vec<MarketData>,
+    sources: Vec<SourceData>,
+    relationships: Vec<RelationshipData>,
+    /// IDs of all outlets in the database, selected or not
+    database_ids: std::collections::HashSet<String>,
+}
+
+/// Print the outlets that ended up in no family
+fn report_unassigned(outlets: &[String]) {
+    const SHOWN: usize = 10;
+
+    if outlets.is_empty() {
+        return;
+    }
+    println!("ℹ️ {} outlets are in no family:", outlets.len());
+    for outlet in outlets.iter().take(SHOWN) {
+        println!("   - {}", outlet);
+    }
+    if outlets.len() > SHOWN {
+        println!("   ... and {} more", outlets.len() - SHOWN);
+    }
+}
+
 /// Extracted media outlet data from mo_constant table
 #[derive(Debug, Clone)]
 pub struct MediaOutletData {
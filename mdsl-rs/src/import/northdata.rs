@@ -0,0 +1,537 @@
+//! Import of crawled company-register data
+//!
+//! Converts the `nd_company`, `nd_person` and `nd_ownership` tables of the
+//! ANMI CSV export into COMPANY, PERSON and OWNERSHIP declarations. The
+//! `source_url` and `scraped_at` columns are carried along as provenance
+//! fields, and rows whose page failed to crawl (according to `nd_crawl_log`)
+//! are skipped or flagged depending on the [`CrawlFailurePolicy`].
+
+use crate::error::{Error, ImportError, Result};
+use crate::ir::nodes::IROwner;
+use crate::utils::csv::CsvTable;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::path::Path;
+
+/// What to do with rows whose source page failed to crawl
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CrawlFailurePolicy {
+    /// Leave the rows out of the generated MDSL
+    #[default]
+    Skip,
+    /// Keep the rows but mark them with a comment annotation
+    Report,
+}
+
+/// Company row from `nd_company.csv`
+#[derive(Debug, Clone)]
+pub struct NdCompany {
+    /// Company ID (`nd_id`)
+    pub id: u32,
+    /// Company name
+    pub name: String,
+    /// Legal form (GmbH, AG, ...)
+    pub legal_form: Option<String>,
+    /// Register number
+    pub registry_id: Option<String>,
+    /// Register court or jurisdiction
+    pub jurisdiction: Option<String>,
+    /// Register status (active, liquidated, ...)
+    pub status: Option<String>,
+    /// Whether the company was classified as a media company
+    pub is_media_company: Option<bool>,
+    /// Sector
+    pub sector: Option<String>,
+    /// Region
+    pub region: Option<String>,
+    /// Register page the row was taken from
+    pub source_url: Option<String>,
+    /// Time the page was scraped
+    pub scraped_at: Option<String>,
+    /// Whether the latest crawl of the source page failed
+    pub crawl_failed: bool,
+}
+
+/// Person row from `nd_person.csv`
+#[derive(Debug, Clone)]
+pub struct NdPerson {
+    /// Person ID (`nd_id`)
+    pub id: u32,
+    /// Full name
+    pub full_name: String,
+    /// Register page of the first ownership row naming this person
+    pub source_url: Option<String>,
+    /// Time the person was scraped
+    pub scraped_at: Option<String>,
+}
+
+/// Ownership row from `nd_ownership.csv`
+#[derive(Debug, Clone)]
+pub struct NdOwnership {
+    /// Row ID
+    pub id: u32,
+    /// Owning company or person
+    pub owner: IROwner,
+    /// Owned company ID
+    pub owned_company_id: u32,
+    /// Share held, in percent
+    pub share_pct: Option<f64>,
+    /// Role of the owner
+    pub role: Option<String>,
+    /// Whether the relation was current when scraped
+    pub is_current: bool,
+    /// Register page the row was taken from
+    pub source_url: Option<String>,
+    /// Time the page was scraped
+    pub scraped_at: Option<String>,
+    /// Whether the latest crawl of the source page failed
+    pub crawl_failed: bool,
+}
+
+/// Failed crawl from `nd_crawl_log.csv`
+#[derive(Debug, Clone)]
+pub struct FailedCrawl {
+    /// Crawled URL
+    pub url: String,
+    /// Company name, if the crawler recorded one
+    pub company_name: Option<String>,
+    /// Error message
+    pub error_msg: Option<String>,
+    /// Time of the crawl
+    pub crawled_at: Option<String>,
+}
+
+/// Row that was left out of the import
+#[derive(Debug, Clone)]
+pub struct SkippedRow {
+    /// Source table
+    pub table: String,
+    /// Row ID as found in the table
+    pub row_id: String,
+    /// Why the row was skipped
+    pub reason: String,
+}
+
+/// Result of importing the crawled company-register tables
+#[derive(Debug, Clone, Default)]
+pub struct NorthDataImport {
+    /// Imported companies
+    pub companies: Vec<NdCompany>,
+    /// Imported persons
+    pub persons: Vec<NdPerson>,
+    /// Imported ownership relations
+    pub ownerships: Vec<NdOwnership>,
+    /// Crawls whose latest attempt failed
+    pub failed_crawls: Vec<FailedCrawl>,
+    /// Rows left out of the import
+    pub skipped: Vec<SkippedRow>,
+}
+
+impl NorthDataImport {
+    /// Read `nd_company.csv`, `nd_person.csv`, `nd_ownership.csv` and (if present)
+    /// `nd_crawl_log.csv` from a directory
+    pub fn from_dir(dir: &Path, policy: CrawlFailurePolicy) -> Result<Self> {
+        let read = |name: &str| std::fs::read_to_string(dir.join(name));
+
+        let company = read("nd_company.csv")?;
+        let person = read("nd_person.csv")?;
+        let ownership = read("nd_ownership.csv")?;
+        let crawl_log = read("nd_crawl_log.csv").ok();
+
+        Self::from_csv(&company, &person, &ownership, crawl_log.as_deref(), policy)
+    }
+
+    /// Import the tables from CSV content
+    pub fn from_csv(
+        company_csv: &str,
+        person_csv: &str,
+        ownership_csv: &str,
+        crawl_log_csv: Option<&str>,
+        policy: CrawlFailurePolicy,
+    ) -> Result<Self> {
+        let mut import = Self::default();
+
+        let failed_urls = match crawl_log_csv {
+            Some(content) => import.read_crawl_log(content)?,
+            None => HashSet::new(),
+        };
+
+        import.read_companies(company_csv, &failed_urls, policy)?;
+        import.read_ownerships(ownership_csv, &failed_urls, policy)?;
+        import.read_persons(person_csv)?;
+
+        Ok(import)
+    }
+
+    /// Collect the URLs whose most recent crawl failed
+    fn read_crawl_log(&mut self, content: &str) -> Result<HashSet<String>> {
+        let table = parse_table("nd_crawl_log", content, &["url", "success"])?;
+
+        // Keep only the latest attempt per URL
+        let mut latest: HashMap<&str, &Vec<String>> = HashMap::new();
+        for row in &table.rows {
+            let Some(url) = table.get(row, "url") else {
+                continue;
+            };
+            let newer = latest.get(url).is_none_or(|previous| {
+                table.get(row, "crawled_at") >= table.get(previous, "crawled_at")
+            });
+            if newer {
+                latest.insert(url, row);
+            }
+        }
+
+        let mut failed_urls = HashSet::new();
+        for (url, row) in latest {
+            if parse_flag(table.get(row, "success")) == Some(true) {
+                continue;
+            }
+            failed_urls.insert(url.to_string());
+            self.failed_crawls.push(FailedCrawl {
+                url: url.to_string(),
+                company_name: table.get(row, "company_name").map(str::to_string),
+                error_msg: table.get(row, "error_msg").map(str::to_string),
+                crawled_at: table.get(row, "crawled_at").map(str::to_string),
+            });
+        }
+        self.failed_crawls
+            .sort_by(|a, b| a.crawled_at.cmp(&b.crawled_at).then(a.url.cmp(&b.url)));
+
+        Ok(failed_urls)
+    }
+
+    fn read_companies(
+        &mut self,
+        content: &str,
+        failed_urls: &HashSet<String>,
+        policy: CrawlFailurePolicy,
+    ) -> Result<()> {
+        let table = parse_table("nd_company", content, &["nd_id", "name"])?;
+
+        for row in &table.rows {
+            let raw_id = table.get(row, "nd_id").unwrap_or_default();
+            let Some(id) = parse_id(raw_id) else {
+                self.skip("nd_company", raw_id, "ID is not numeric");
+                continue;
+            };
+            let Some(name) = table.get(row, "name") else {
+                self.skip("nd_company", raw_id, "company has no name");
+                continue;
+            };
+
+            let source_url = table.get(row, "northdata_url").map(str::to_string);
+            let crawl_failed = source_url
+                .as_ref()
+                .is_some_and(|url| failed_urls.contains(url));
+            if crawl_failed && policy == CrawlFailurePolicy::Skip {
+                self.skip("nd_company", raw_id, "crawl of the source page failed");
+                continue;
+            }
+
+            let text = |column: &str| table.get(row, column).map(str::to_string);
+            self.companies.push(NdCompany {
+                id,
+                name: name.to_string(),
+                legal_form: text("legal_form"),
+                registry_id: text("registry_id"),
+                jurisdiction: text("jurisdiction"),
+                status: text("status"),
+                is_media_company: parse_flag(table.get(row, "is_media_company")),
+                sector: text("sector"),
+                region: text("region"),
+                source_url,
+                scraped_at: text("scraped_at"),
+                crawl_failed,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn read_ownerships(
+        &mut self,
+        content: &str,
+        failed_urls: &HashSet<String>,
+        policy: CrawlFailurePolicy,
+    ) -> Result<()> {
+        let table = parse_table(
+            "nd_ownership",
+            content,
+            &["id", "owner_company_id", "owner_person_id", "owned_company_id"],
+        )?;
+        let company_ids: HashSet<u32> = self.companies.iter().map(|c| c.id).collect();
+
+        for row in &table.rows {
+            let raw_id = table.get(row, "id").unwrap_or_default();
+            let Some(id) = parse_id(raw_id) else {
+                self.skip("nd_ownership", raw_id, "ID is not numeric");
+                continue;
+            };
+
+            let owner = match (
+                table.get(row, "owner_company_id").and_then(parse_id),
+                table.get(row, "owner_person_id").and_then(parse_id),
+            ) {
+                (Some(company), _) => IROwner::Company(company),
+                (None, Some(person)) => IROwner::Person(person),
+                (None, None) => {
+                    self.skip("nd_ownership", raw_id, "relation has no owner");
+                    continue;
+                }
+            };
+            let Some(owned_company_id) = table.get(row, "owned_company_id").and_then(parse_id)
+            else {
+                self.skip("nd_ownership", raw_id, "relation has no owned company");
+                continue;
+            };
+
+            let source_url = table.get(row, "source_url").map(str::to_string);
+            let crawl_failed = source_url
+                .as_ref()
+                .is_some_and(|url| failed_urls.contains(url));
+            if crawl_failed && policy == CrawlFailurePolicy::Skip {
+                self.skip("nd_ownership", raw_id, "crawl of the source page failed");
+                continue;
+            }
+
+            // Relations must point at imported companies, or the MDSL would not validate
+            let missing = [Some(owned_company_id), match owner {
+                IROwner::Company(company) => Some(company),
+                IROwner::Person(_) => None,
+            }]
+            .into_iter()
+            .flatten()
+            .find(|company| !company_ids.contains(company));
+            if let Some(company) = missing {
+                self.skip(
+                    "nd_ownership",
+                    raw_id,
+                    &format!("company {} was not imported", company),
+                );
+                continue;
+            }
+
+            self.ownerships.push(NdOwnership {
+                id,
+                owner,
+                owned_company_id,
+                share_pct: table.get(row, "share_pct").and_then(|v| v.parse().ok()),
+                role: table.get(row, "role").map(str::to_string),
+                is_current: parse_flag(table.get(row, "is_current")).unwrap_or(true),
+                source_url,
+                scraped_at: table.get(row, "scraped_at").map(str::to_string),
+                crawl_failed,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn read_persons(&mut self, content: &str) -> Result<()> {
+        let table = parse_table("nd_person", content, &["nd_id", "full_name"])?;
+
+        for row in &table.rows {
+            let raw_id = table.get(row, "nd_id").unwrap_or_default();
+            let Some(id) = parse_id(raw_id) else {
+                self.skip("nd_person", raw_id, "ID is not numeric");
+                continue;
+            };
+            let Some(full_name) = table.get(row, "full_name") else {
+                self.skip("nd_person", raw_id, "person has no name");
+                continue;
+            };
+
+            // Persons have no page of their own; cite the first relation naming them
+            let source_url = self
+                .ownerships
+                .iter()
+                .find(|ownership| ownership.owner == IROwner::Person(id))
+                .and_then(|ownership| ownership.source_url.clone());
+
+            self.persons.push(NdPerson {
+                id,
+                full_name: full_name.to_string(),
+                source_url,
+                scraped_at: table.get(row, "scraped_at").map(str::to_string),
+            });
+        }
+
+        let person_ids: HashSet<u32> = self.persons.iter().map(|p| p.id).collect();
+        let mut skipped = Vec::new();
+        self.ownerships.retain(|ownership| match ownership.owner {
+            IROwner::Person(person) if !person_ids.contains(&person) => {
+                skipped.push(SkippedRow {
+                    table: "nd_ownership".to_string(),
+                    row_id: ownership.id.to_string(),
+                    reason: format!("person {} was not imported", person),
+                });
+                false
+            }
+            _ => true,
+        });
+        self.skipped.extend(skipped);
+
+        Ok(())
+    }
+
+    fn skip(&mut self, table: &str, row_id: &str, reason: &str) {
+        self.skipped.push(SkippedRow {
+            table: table.to_string(),
+            row_id: row_id.to_string(),
+            reason: reason.to_string(),
+        });
+    }
+
+    /// Generate COMPANY, PERSON and OWNERSHIP declarations
+    pub fn to_mdsl(&self) -> Result<String> {
+        let mut output = String::new();
+
+        writeln!(output, "// Generated MDSL from crawled company-register data")?;
+        writeln!(
+            output,
+            "// {} companies, {} persons, {} ownership relations ({} rows skipped, {} failed crawls)",
+            self.companies.len(),
+            self.persons.len(),
+            self.ownerships.len(),
+            self.skipped.len(),
+            self.failed_crawls.len()
+        )?;
+        writeln!(output)?;
+
+        if !self.companies.is_empty() {
+            writeln!(output, "// COMPANIES")?;
+            for company in &self.companies {
+                writeln!(output, "COMPANY \"{}\" {{", escape_string(&company.name))?;
+                if company.crawl_failed {
+                    writeln!(output, "    @comment \"Latest crawl of the source page failed\"")?;
+                }
+                writeln!(output, "    id = {};", company.id)?;
+                write_text_field(&mut output, "legal_form", &company.legal_form)?;
+                write_text_field(&mut output, "registry_id", &company.registry_id)?;
+                write_text_field(&mut output, "jurisdiction", &company.jurisdiction)?;
+                write_text_field(&mut output, "status", &company.status)?;
+                if let Some(flag) = company.is_media_company {
+                    writeln!(output, "    is_media_company = {};", flag)?;
+                }
+                write_text_field(&mut output, "sector", &company.sector)?;
+                write_text_field(&mut output, "region", &company.region)?;
+                write_text_field(&mut output, "source_url", &company.source_url)?;
+                write_text_field(&mut output, "scraped_at", &company.scraped_at)?;
+                writeln!(output, "}}")?;
+                writeln!(output)?;
+            }
+        }
+
+        if !self.persons.is_empty() {
+            writeln!(output, "// PERSONS")?;
+            for person in &self.persons {
+                writeln!(output, "PERSON \"{}\" {{", escape_string(&person.full_name))?;
+                writeln!(output, "    id = {};", person.id)?;
+                write_text_field(&mut output, "source_url", &person.source_url)?;
+                write_text_field(&mut output, "scraped_at", &person.scraped_at)?;
+                writeln!(output, "}}")?;
+                writeln!(output)?;
+            }
+        }
+
+        if !self.ownerships.is_empty() {
+            writeln!(output, "// OWNERSHIP")?;
+            for ownership in &self.ownerships {
+                writeln!(output, "OWNERSHIP nd_ownership_{} {{", ownership.id)?;
+                if ownership.crawl_failed {
+                    writeln!(output, "    @comment \"Latest crawl of the source page failed\"")?;
+                }
+                match ownership.owner {
+                    IROwner::Company(id) => writeln!(output, "    owner_company = {};", id)?,
+                    IROwner::Person(id) => writeln!(output, "    owner_person = {};", id)?,
+                }
+                writeln!(output, "    owned_company = {};", ownership.owned_company_id)?;
+                if let Some(share) = ownership.share_pct {
+                    writeln!(output, "    share_pct = {};", share)?;
+                }
+                write_text_field(&mut output, "role", &ownership.role)?;
+                // A relation that was no longer current ended at the latest when it was scraped
+                if !ownership.is_current {
+                    if let Some(scraped_at) = &ownership.scraped_at {
+                        let date: String = scraped_at.chars().take(10).collect();
+                        writeln!(output, "    period_end = \"{}\";", escape_string(&date))?;
+                    }
+                }
+                write_text_field(&mut output, "source_url", &ownership.source_url)?;
+                write_text_field(&mut output, "scraped_at", &ownership.scraped_at)?;
+                writeln!(output, "}}")?;
+                writeln!(output)?;
+            }
+        }
+
+        if !self.failed_crawls.is_empty() {
+            writeln!(output, "// FAILED CRAWLS")?;
+            for crawl in &self.failed_crawls {
+                writeln!(
+                    output,
+                    "// - {} ({}): {}",
+                    crawl.url,
+                    crawl.crawled_at.as_deref().unwrap_or("unknown time"),
+                    crawl.error_msg.as_deref().unwrap_or("no error message")
+                )?;
+            }
+            writeln!(output)?;
+        }
+
+        if !self.skipped.is_empty() {
+            writeln!(output, "// SKIPPED ROWS")?;
+            for row in &self.skipped {
+                writeln!(output, "// - {} {}: {}", row.table, row.row_id, row.reason)?;
+            }
+            writeln!(output)?;
+        }
+
+        Ok(output)
+    }
+}
+
+/// Parse a table and check that the required columns are present
+fn parse_table(name: &str, content: &str, required: &[&str]) -> Result<CsvTable> {
+    let table = CsvTable::parse(content);
+    if let Some(column) = required.iter().find(|column| !table.has_column(column)) {
+        return Err(Error::Import(ImportError::MappingFailed {
+            table: name.to_string(),
+            message: format!("missing column '{}'", column),
+        }));
+    }
+    Ok(table)
+}
+
+/// Parse a numeric ID (exports may write integers as `123.0`)
+fn parse_id(value: &str) -> Option<u32> {
+    let value = value.trim();
+    value
+        .parse::<u32>()
+        .ok()
+        .or_else(|| value.strip_suffix(".0").and_then(|v| v.parse().ok()))
+}
+
+/// Parse a PostgreSQL-style boolean (`t`/`f`, `true`/`false`, `1`/`0`)
+fn parse_flag(value: Option<&str>) -> Option<bool> {
+    match value?.trim().to_lowercase().as_str() {
+        "t" | "true" | "1" | "yes" => Some(true),
+        "f" | "false" | "0" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+fn write_text_field(output: &mut String, name: &str, value: &Option<String>) -> Result<()> {
+    if let Some(value) = value {
+        writeln!(output, "    {} = \"{}\";", name, escape_string(value))?;
+    }
+    Ok(())
+}
+
+/// Escape a string for use in an MDSL string literal
+fn escape_string(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t")
+}
@@ -0,0 +1,157 @@
+//! Subset selection for ANMI imports
+//!
+//! Seeds outlets by ID, title, sector or media group and follows their
+//! relationships for a number of hops, so an import can produce a
+//! self-contained MDSL excerpt: only links between selected outlets, the
+//! market data of selected outlets and the sources that data cites are kept.
+
+use crate::import::{MarketData, MediaOutletData, RelationshipData, SourceData};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Relationship types that tie outlets into one media group
+pub const GROUP_RELATIONSHIPS: &[&str] = &["main_media_outlet", "umbrella"];
+
+/// Which outlets an import keeps; without seeds everything is kept
+#[derive(Debug, Clone, Default)]
+pub struct Selection {
+    /// Seed outlet IDs
+    pub ids: Vec<String>,
+    /// Seed outlets whose title contains one of these (case-insensitive)
+    pub title_patterns: Vec<String>,
+    /// Seed outlets of these sector codes
+    pub sectors: Vec<String>,
+    /// Seed whole media groups: outlets with one of these IDs or exact titles,
+    /// plus every outlet below them through main media outlet or umbrella links
+    pub families: Vec<String>,
+    /// Relationship hops to follow from the seeds
+    pub hops: usize,
+}
+
+impl Selection {
+    /// Whether no seeds are set, so the whole database is imported
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+            && self.title_patterns.is_empty()
+            && self.sectors.is_empty()
+            && self.families.is_empty()
+    }
+
+    /// IDs of the selected outlets: the seeds, their media groups and every
+    /// outlet within `hops` relationships of them
+    pub fn select_outlets(
+        &self,
+        outlets: &[MediaOutletData],
+        relationships: &[RelationshipData],
+    ) -> HashSet<String> {
+        let mut seeds: HashSet<String> = self.ids.iter().cloned().collect();
+        seeds.extend(
+            outlets
+                .iter()
+                .filter(|outlet| {
+                    matches_title(&outlet.title, &self.title_patterns)
+                        || self.sectors.contains(&outlet.sector)
+                })
+                .map(|outlet| outlet.id_mo.clone()),
+        );
+
+        let group_links: Vec<&RelationshipData> = relationships
+            .iter()
+            .filter(|r| GROUP_RELATIONSHIPS.contains(&r.relationship_type.as_str()))
+            .collect();
+        // Group heads may only appear in links, without an outlet record
+        let family_heads: HashSet<String> = outlets
+            .iter()
+            .filter(|outlet| {
+                self.families.contains(&outlet.id_mo) || self.families.contains(&outlet.title)
+            })
+            .map(|outlet| outlet.id_mo.clone())
+            .chain(
+                self.families
+                    .iter()
+                    .filter(|family| {
+                        group_links
+                            .iter()
+                            .any(|r| &r.source_id == *family || &r.target_id == *family)
+                    })
+                    .cloned(),
+            )
+            .collect();
+        // Members are the link source and their umbrella or main outlet the
+        // target, so a group is followed from its head down, never up to the
+        // head and siblings of a member
+        seeds.extend(neighbourhood(&family_heads, &group_links, usize::MAX, false));
+
+        let all_links: Vec<&RelationshipData> = relationships.iter().collect();
+        neighbourhood(&seeds, &all_links, self.hops, true)
+    }
+}
+
+/// Keep the outlets of `ids`, the relationships between them, their
+/// market data and the sources cited by that data
+pub fn retain(
+    ids: &HashSet<String>,
+    outlets: &mut Vec<MediaOutletData>,
+    market_data: &mut Vec<MarketData>,
+    sources: &mut Vec<SourceData>,
+    relationships: &mut Vec<RelationshipData>,
+) {
+    outlets.retain(|outlet| ids.contains(&outlet.id_mo));
+    relationships.retain(|r| ids.contains(&r.source_id) && ids.contains(&r.target_id));
+    market_data.retain(|data| ids.contains(&data.id_mo));
+
+    let cited: HashSet<&str> = market_data
+        .iter()
+        .flat_map(|data| {
+            [
+                data.circulation_source.as_str(),
+                data.unique_users_source.as_str(),
+                data.reach_nat_source.as_str(),
+                data.reach_reg_source.as_str(),
+                data.market_share_source.as_str(),
+            ]
+        })
+        .collect();
+    sources.retain(|source| cited.contains(source.id_source.as_str()));
+}
+
+fn matches_title(title: &str, patterns: &[String]) -> bool {
+    let title = title.to_lowercase();
+    patterns
+        .iter()
+        .any(|pattern| title.contains(&pattern.to_lowercase()))
+}
+
+/// Outlets reachable from `start` within `hops` links, following links from
+/// target to source, and from source to target as well when `both_directions`
+fn neighbourhood(
+    start: &HashSet<String>,
+    relationships: &[&RelationshipData],
+    hops: usize,
+    both_directions: bool,
+) -> HashSet<String> {
+    let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+    for r in relationships {
+        let unset = |id: &str| id.is_empty() || id == "NULL";
+        if unset(&r.source_id) || unset(&r.target_id) {
+            continue;
+        }
+        adjacency.entry(&r.target_id).or_default().push(&r.source_id);
+        if both_directions {
+            adjacency.entry(&r.source_id).or_default().push(&r.target_id);
+        }
+    }
+
+    let mut reached = start.clone();
+    let mut queue: VecDeque<(String, usize)> = start.iter().map(|id| (id.clone(), 0)).collect();
+    while let Some((id, depth)) = queue.pop_front() {
+        if depth >= hops {
+            continue;
+        }
+        for next in adjacency.get(id.as_str()).into_iter().flatten() {
+            if reached.insert(next.to_string()) {
+                queue.push_back((next.to_string(), depth + 1));
+            }
+        }
+    }
+    reached
+}
@@ -0,0 +1,144 @@
+//! SQLite snapshot files as an import data source
+//!
+//! Opens a local SQLite database read-only and answers the queries the import
+//! pipeline otherwise sends to a PostgreSQL server. Values are returned as
+//! text; SQL NULLs are reported as `NULL`, like in the PostgreSQL backend.
+
+use crate::error::{Error, Result};
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Prefix for SQLite connection strings (`sqlite://<file>`)
+pub const SQLITE_SCHEME: &str = "sqlite://";
+
+/// Read-only connection to a SQLite database file
+#[derive(Debug)]
+pub struct SqliteSource {
+    connection: Connection,
+}
+
+impl SqliteSource {
+    /// Open a SQLite database file; a missing file is an error, not a new database
+    pub fn open(path: &Path) -> Result<Self> {
+        if !path.is_file() {
+            return Err(Error::DatabaseConnection(format!(
+                "SQLite database '{}' not found",
+                path.display()
+            )));
+        }
+
+        let connection = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+        .map_err(|e| Error::DatabaseConnection(e.to_string()))?;
+
+        Ok(Self { connection })
+    }
+
+    /// Open the file named by a connection string (`sqlite://<file>` or a plain path)
+    pub fn from_connection_string(connection_string: &str) -> Result<Self> {
+        let path = connection_string
+            .strip_prefix(SQLITE_SCHEME)
+            .unwrap_or(connection_string);
+        Self::open(Path::new(path))
+    }
+
+    /// Names of the tables and views, sorted
+    pub fn table_names(&self) -> Result<Vec<String>> {
+        let (_, rows) = self.query(
+            "SELECT name FROM sqlite_master WHERE type IN ('table', 'view') \
+             AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        )?;
+        Ok(rows.into_iter().filter_map(|row| row.into_iter().next()).collect())
+    }
+
+    /// Columns of a table in declaration order with their declared types
+    pub fn columns(&self, table_name: &str) -> Result<Vec<(String, String)>> {
+        let (_, rows) = self.query(&format!("PRAGMA table_info({})", quote(table_name)))?;
+        if rows.is_empty() {
+            return Err(Error::DatabaseQuery(format!(
+                "SQLite table '{}' not found",
+                table_name
+            )));
+        }
+
+        // table_info rows: cid, name, type, notnull, dflt_value, pk
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let data_type = match row[2].as_str() {
+                    "NULL" | "" => "text".to_string(),
+                    declared => declared.to_lowercase(),
+                };
+                (row[1].clone(), data_type)
+            })
+            .collect())
+    }
+
+    /// Rows of a table as column -> value maps
+    pub fn rows(
+        &self,
+        table_name: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<HashMap<String, String>>> {
+        let mut query = format!("SELECT * FROM {}", quote(table_name));
+        if let Some(limit) = limit {
+            query.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let (columns, rows) = self.query(&query)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| columns.iter().cloned().zip(row).collect())
+            .collect())
+    }
+
+    /// Run a query and return its column names and rows as text
+    pub fn query(&self, query: &str) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        let mut statement = self
+            .connection
+            .prepare(query)
+            .map_err(|e| Error::DatabaseQuery(e.to_string()))?;
+        let columns: Vec<String> = statement
+            .column_names()
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+
+        let mut rows = statement
+            .query([])
+            .map_err(|e| Error::DatabaseQuery(e.to_string()))?;
+        let mut result = Vec::new();
+        while let Some(row) = rows.next().map_err(|e| Error::DatabaseQuery(e.to_string()))? {
+            let mut values = Vec::with_capacity(columns.len());
+            for i in 0..columns.len() {
+                let value = row
+                    .get_ref(i)
+                    .map_err(|e| Error::DatabaseQuery(e.to_string()))?;
+                values.push(text(value));
+            }
+            result.push(values);
+        }
+
+        Ok((columns, result))
+    }
+}
+
+/// Quote an identifier for use in a SQLite statement
+pub fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Render a SQLite value the way the PostgreSQL backend renders its columns
+fn text(value: ValueRef<'_>) -> String {
+    match value {
+        ValueRef::Null => "NULL".to_string(),
+        ValueRef::Integer(v) => v.to_string(),
+        ValueRef::Real(v) => v.to_string(),
+        ValueRef::Text(v) => String::from_utf8_lossy(v).into_owned(),
+        ValueRef::Blob(v) => format!("(blob, {} bytes)", v.len()),
+    }
+}
@@ -0,0 +1,538 @@
+//! Incremental sync of an existing MDSL file with the database
+//!
+//! Compares the outlets, links and yearly market data of an MDSL file with a
+//! fresh rendering of the database and produces a [`SyncPatch`] that adds,
+//! updates or removes only what changed. Outlets are matched by ID, links by
+//! relationship type and endpoints, market data by outlet and year.
+//!
+//! Changed declarations are updated field by field, so content the database
+//! does not produce (comments, annotations, extra fields, hand-written
+//! declarations) stays as it is. Blocks that only make sense as a whole,
+//! such as `lifecycle`, are replaced as a whole.
+
+use crate::error::Result;
+use crate::utils::source_map::{line_indent, own_line_start, SourceMap, SourceNode};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Range;
+
+/// Blocks compared and replaced as a whole rather than field by field
+const ATOMIC_BLOCKS: &[&str] = &["lifecycle"];
+
+/// Fields that identify an entity; they match by definition, and curated
+/// details such as link roles are kept
+const KEY_FIELDS: &[&str] = &[
+    "id",
+    "outlet_1",
+    "outlet_2",
+    "predecessor",
+    "successor",
+    "relationship_type",
+];
+
+/// What the database covers, to tell removed entities from curated ones
+#[derive(Debug, Clone, Default)]
+pub struct SyncScope {
+    /// IDs of all outlets in the database; outlets of the file with other
+    /// IDs have been deleted from it
+    pub outlet_ids: HashSet<String>,
+    /// Relationship types the database provides; links of other types are
+    /// curated and never removed
+    pub link_types: HashSet<String>,
+}
+
+/// Kind of change found by a sync
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// In the database, not in the file
+    Added,
+    /// In both, with different field values
+    Changed,
+    /// In the file, no longer in the database
+    Removed,
+}
+
+/// One added, changed or removed entity
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncChange {
+    /// Kind of change
+    pub kind: ChangeKind,
+    /// Entity label: `outlet 200001`, `link umbrella 103681 -> 103680`, `data 200001 year 2021`
+    pub entity: String,
+    /// Changed field paths, for changed entities
+    pub fields: Vec<String>,
+}
+
+impl fmt::Display for SyncChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = match self.kind {
+            ChangeKind::Added => '+',
+            ChangeKind::Changed => '~',
+            ChangeKind::Removed => '-',
+        };
+        write!(f, "{} {}", sign, self.entity)?;
+        if !self.fields.is_empty() {
+            write!(f, ": {}", self.fields.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Edits that bring an MDSL file up to date with the database
+#[derive(Debug, Clone, Default)]
+pub struct SyncPatch {
+    /// Changes in file order: changed and removed entities, then additions
+    pub changes: Vec<SyncChange>,
+    edits: Vec<Edit>,
+}
+
+/// Replace a byte range of the file; an empty range inserts
+#[derive(Debug, Clone)]
+struct Edit {
+    range: Range<usize>,
+    text: String,
+}
+
+impl SyncPatch {
+    /// Whether the file is already up to date
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Number of changes of a kind
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.changes.iter().filter(|change| change.kind == kind).count()
+    }
+
+    /// Apply the patch to the source it was computed from
+    pub fn apply(&self, source: &str) -> String {
+        let mut edits: Vec<(usize, &Edit)> = self.edits.iter().enumerate().collect();
+        // Back to front, so earlier offsets stay valid; insertions at the
+        // same offset keep their order
+        edits.sort_by(|(a_seq, a), (b_seq, b)| {
+            (b.range.start, b_seq).cmp(&(a.range.start, a_seq))
+        });
+
+        let mut output = source.to_string();
+        for (_, edit) in edits {
+            output.replace_range(edit.range.clone(), &edit.text);
+        }
+        output
+    }
+}
+
+/// Compare an MDSL file with the database rendered as MDSL
+pub fn diff(existing: &str, generated: &str, scope: &SyncScope) -> Result<SyncPatch> {
+    let existing_map = SourceMap::parse(existing)?;
+    let generated_map = SourceMap::parse(generated)?;
+    let existing_entities = entities(&existing_map);
+    let generated_entities = entities(&generated_map);
+
+    let mut sync = Reconciler {
+        existing,
+        generated,
+        patch: SyncPatch::default(),
+    };
+    let generated_keys: HashMap<&str, &Entity<'_>> = generated_entities
+        .iter()
+        .map(|entity| (entity.key.as_str(), entity))
+        .collect();
+    let generated_outlets: HashSet<&str> = generated_entities
+        .iter()
+        .filter(|entity| entity.kind == EntityKind::Outlet)
+        .map(|entity| entity.id.as_str())
+        .collect();
+
+    let mut matched: HashSet<&str> = HashSet::new();
+    for entity in &existing_entities {
+        match generated_keys.get(entity.key.as_str()) {
+            Some(generated) => {
+                matched.insert(generated.key.as_str());
+                sync.update(entity, generated);
+            }
+            None if entity.is_removed(scope, &generated_outlets) => sync.remove(entity),
+            None => {}
+        }
+    }
+
+    let additions: Vec<&Entity<'_>> = generated_entities
+        .iter()
+        .filter(|entity| !matched.contains(entity.key.as_str()))
+        .collect();
+    sync.add(&existing_entities, &additions);
+
+    Ok(sync.patch)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntityKind {
+    Outlet,
+    Link,
+    Data,
+}
+
+/// An outlet, link or DATA block of a file
+struct Entity<'a> {
+    kind: EntityKind,
+    /// Matching key, also the label in the change report
+    key: String,
+    /// Outlet ID of outlets and DATA blocks
+    id: String,
+    /// Outlet IDs of links
+    endpoints: Vec<String>,
+    /// Relationship type of links
+    link_type: String,
+    node: &'a SourceNode,
+    parent: Option<&'a SourceNode>,
+}
+
+impl Entity<'_> {
+    fn is_removed(&self, scope: &SyncScope, generated_outlets: &HashSet<&str>) -> bool {
+        match self.kind {
+            EntityKind::Outlet => !scope.outlet_ids.contains(&self.id),
+            // A database link the database no longer has: each end was either
+            // rendered, so the link would have been too, or deleted
+            EntityKind::Link => {
+                scope.link_types.contains(&self.link_type)
+                    && self.endpoints.iter().all(|id| {
+                        generated_outlets.contains(id.as_str()) || !scope.outlet_ids.contains(id)
+                    })
+            }
+            EntityKind::Data => !scope.outlet_ids.contains(&self.id),
+        }
+    }
+}
+
+fn entities(map: &SourceMap) -> Vec<Entity<'_>> {
+    let mut entities = Vec::new();
+    for (parent, node) in map.walk() {
+        let keyword = node.keyword();
+        let entity = if keyword.eq_ignore_ascii_case("OUTLET") && node.has_body() {
+            let id = node
+                .field("id")
+                .or_else(|| node.child("identity").and_then(|identity| identity.field("id")));
+            id.map(|id| Entity {
+                kind: EntityKind::Outlet,
+                key: format!("outlet {}", id),
+                id: id.to_string(),
+                endpoints: Vec::new(),
+                link_type: String::new(),
+                node,
+                parent,
+            })
+        } else if keyword.eq_ignore_ascii_case("SYNCHRONOUS_LINK")
+            || keyword.eq_ignore_ascii_case("DIACHRONIC_LINK")
+        {
+            let endpoints = if keyword.eq_ignore_ascii_case("SYNCHRONOUS_LINK") {
+                [
+                    node.child("outlet_1").and_then(object_id),
+                    node.child("outlet_2").and_then(object_id),
+                ]
+            } else {
+                [
+                    node.field("predecessor").map(str::to_string),
+                    node.field("successor").map(str::to_string),
+                ]
+            };
+            match (endpoints, node.field("relationship_type")) {
+                ([Some(a), Some(b)], Some(link_type)) => Some(Entity {
+                    kind: EntityKind::Link,
+                    key: format!("link {} {} -> {}", link_type, a, b),
+                    id: String::new(),
+                    endpoints: vec![a, b],
+                    link_type: link_type.to_string(),
+                    node,
+                    parent,
+                }),
+                _ => None,
+            }
+        } else if keyword.eq_ignore_ascii_case("DATA") && node.has_body() {
+            node.head.split(' ').nth(2).map(|id| Entity {
+                kind: EntityKind::Data,
+                key: format!("data {}", id),
+                id: id.to_string(),
+                endpoints: Vec::new(),
+                link_type: String::new(),
+                node,
+                parent,
+            })
+        } else {
+            None
+        };
+        entities.extend(entity);
+    }
+    entities
+}
+
+/// `id` of an `outlet_1 = { id = ...; ... }` object
+fn object_id(node: &SourceNode) -> Option<String> {
+    let tokens: Vec<&str> = node.value.as_deref()?.split(' ').collect();
+    tokens
+        .windows(3)
+        .find(|window| window[0] == "id" && window[1] == "=")
+        .map(|window| window[2].to_string())
+}
+
+struct Reconciler<'a> {
+    existing: &'a str,
+    generated: &'a str,
+    patch: SyncPatch,
+}
+
+impl Reconciler<'_> {
+    fn update(&mut self, existing: &Entity<'_>, generated: &Entity<'_>) {
+        let mut fields = Vec::new();
+
+        // Outlet titles are the string in the declaration head
+        if existing.kind == EntityKind::Outlet {
+            let title = |source: &str, node: &SourceNode| {
+                string_literal(&source[node.head_span.clone()])
+                    .map(|range| node.head_span.start + range.start..node.head_span.start + range.end)
+            };
+            if let (Some(current), Some(new)) = (
+                title(self.existing, existing.node),
+                title(self.generated, generated.node),
+            ) {
+                if self.existing[current.clone()] != self.generated[new.clone()] {
+                    self.edit(current, self.generated[new].to_string());
+                    fields.push("name".to_string());
+                }
+            }
+        }
+
+        if existing.kind == EntityKind::Data {
+            self.update_years(existing, generated);
+        } else {
+            self.reconcile(existing.node, generated.node, "", &mut fields);
+        }
+
+        if !fields.is_empty() {
+            self.patch.changes.push(SyncChange {
+                kind: ChangeKind::Changed,
+                entity: existing.key.clone(),
+                fields,
+            });
+        }
+    }
+
+    /// Match YEAR blocks of two DATA blocks, reporting each year on its own
+    fn update_years(&mut self, existing: &Entity<'_>, generated: &Entity<'_>) {
+        fn years(node: &SourceNode) -> Vec<&SourceNode> {
+            node.children
+                .iter()
+                .filter(|child| child.keyword().eq_ignore_ascii_case("YEAR"))
+                .collect()
+        }
+        let existing_years = years(existing.node);
+        let generated_years = years(generated.node);
+        let label = |year: &SourceNode| {
+            format!(
+                "data {} year {}",
+                existing.id,
+                year.head.split(' ').nth(1).unwrap_or_default()
+            )
+        };
+
+        for year in generated_years.iter().copied() {
+            match existing_years.iter().find(|e| e.head == year.head) {
+                Some(existing_year) => {
+                    let mut fields = Vec::new();
+                    self.reconcile(existing_year, year, "", &mut fields);
+                    if !fields.is_empty() {
+                        self.patch.changes.push(SyncChange {
+                            kind: ChangeKind::Changed,
+                            entity: label(year),
+                            fields,
+                        });
+                    }
+                }
+                None => {
+                    self.insert_child(existing.node, year);
+                    self.patch.changes.push(SyncChange {
+                        kind: ChangeKind::Added,
+                        entity: label(year),
+                        fields: Vec::new(),
+                    });
+                }
+            }
+        }
+        for year in existing_years.iter().copied() {
+            if !generated_years.iter().any(|g| g.head == year.head) {
+                self.delete(year);
+                self.patch.changes.push(SyncChange {
+                    kind: ChangeKind::Removed,
+                    entity: label(year),
+                    fields: Vec::new(),
+                });
+            }
+        }
+    }
+
+    /// Bring the fields of `existing` in line with `generated`; fields only
+    /// the file has are left alone
+    fn reconcile(
+        &mut self,
+        existing: &SourceNode,
+        generated: &SourceNode,
+        path: &str,
+        fields: &mut Vec<String>,
+    ) {
+        for child in &generated.children {
+            if child.head.starts_with('@') || KEY_FIELDS.contains(&child.head.as_str()) {
+                continue;
+            }
+            let child_path = if path.is_empty() {
+                child.head.clone()
+            } else {
+                format!("{}.{}", path, child.head)
+            };
+
+            match existing.child(&child.head) {
+                None => {
+                    self.insert_child(existing, child);
+                    fields.push(child_path);
+                }
+                Some(current)
+                    if current.has_body()
+                        && child.has_body()
+                        && !ATOMIC_BLOCKS.contains(&child.head.as_str()) =>
+                {
+                    self.reconcile(current, child, &child_path, fields);
+                }
+                Some(current) => {
+                    if current.normalized() != child.normalized() {
+                        let indent = line_indent(self.existing, current.span.start);
+                        let text = child.reindented(self.generated, &indent);
+                        self.edit(current.span.clone(), text);
+                        fields.push(child_path);
+                    }
+                }
+            }
+        }
+    }
+
+    fn remove(&mut self, entity: &Entity<'_>) {
+        self.delete(entity.node);
+        self.patch.changes.push(SyncChange {
+            kind: ChangeKind::Removed,
+            entity: entity.key.clone(),
+            fields: Vec::new(),
+        });
+    }
+
+    /// Add new outlets to the family that holds the rest of their generated
+    /// family, or to a new family; append new links and DATA blocks
+    fn add(&mut self, existing: &[Entity<'_>], additions: &[&Entity<'_>]) {
+        let family_of: HashMap<&str, &SourceNode> = existing
+            .iter()
+            .filter(|entity| entity.kind == EntityKind::Outlet)
+            .filter_map(|entity| entity.parent.map(|parent| (entity.id.as_str(), parent)))
+            .collect();
+
+        let mut new_families: Vec<(&SourceNode, Vec<&SourceNode>)> = Vec::new();
+        let mut appended: Vec<&SourceNode> = Vec::new();
+        for entity in additions {
+            self.patch.changes.push(SyncChange {
+                kind: ChangeKind::Added,
+                entity: entity.key.clone(),
+                fields: Vec::new(),
+            });
+
+            let generated_family = match (entity.kind, entity.parent) {
+                (EntityKind::Outlet, Some(family)) => family,
+                _ => {
+                    appended.push(entity.node);
+                    continue;
+                }
+            };
+            let target = generated_family
+                .children
+                .iter()
+                .filter_map(|sibling| {
+                    sibling
+                        .field("id")
+                        .or_else(|| sibling.child("identity").and_then(|i| i.field("id")))
+                })
+                .find_map(|id| family_of.get(id));
+            match target {
+                Some(family) => self.insert_child(family, entity.node),
+                None => match new_families
+                    .iter_mut()
+                    .find(|(family, _)| std::ptr::eq(*family, generated_family))
+                {
+                    Some((_, outlets)) => outlets.push(entity.node),
+                    None => new_families.push((generated_family, vec![entity.node])),
+                },
+            }
+        }
+
+        if new_families.is_empty() && appended.is_empty() {
+            return;
+        }
+        let mut text = String::new();
+        if !self.existing.is_empty() && !self.existing.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str("\n// Added from the database\n");
+        for (family, outlets) in new_families {
+            text.push('\n');
+            text.push_str(&self.generated[family.head_span.clone()]);
+            text.push_str(" {\n");
+            for outlet in outlets {
+                text.push_str("    ");
+                text.push_str(&outlet.reindented(self.generated, "    "));
+                text.push_str("\n\n");
+            }
+            text.push_str("}\n");
+        }
+        for node in appended {
+            text.push('\n');
+            text.push_str(&node.reindented(self.generated, ""));
+            text.push('\n');
+        }
+        let end = self.existing.len();
+        self.edit(end..end, text);
+    }
+
+    /// Insert a generated statement at the end of an existing block
+    fn insert_child(&mut self, parent: &SourceNode, child: &SourceNode) {
+        let Some(body_end) = parent.body_end else {
+            return;
+        };
+        let closing_indent = line_indent(self.existing, body_end);
+        let indent = format!("{}    ", closing_indent);
+
+        let mut text = format!("{}{}", indent, child.reindented(self.generated, &indent));
+        match own_line_start(self.existing, body_end) {
+            Some(line_start) => {
+                text.push('\n');
+                self.edit(line_start..line_start, text);
+            }
+            None => self.edit(body_end..body_end, format!("\n{}\n{}", text, closing_indent)),
+        }
+    }
+
+    /// Delete a statement together with its lines, if it has them to itself
+    fn delete(&mut self, node: &SourceNode) {
+        let range = node.removal_span(self.existing);
+        self.edit(range, String::new());
+    }
+
+    fn edit(&mut self, range: Range<usize>, text: String) {
+        self.patch.edits.push(Edit { range, text });
+    }
+}
+
+/// Byte range of the first string literal in a text, quotes included
+fn string_literal(text: &str) -> Option<Range<usize>> {
+    let start = text.find('"')?;
+    let mut escaped = false;
+    for (i, c) in text[start + 1..].char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some(start..start + 1 + i + 1),
+            _ => escaped = false,
+        }
+    }
+    None
+}
@@ -1,6 +1,10 @@
 //! Intermediate representation
 
 pub mod nodes;
+pub mod publication;
+pub mod redaction;
+pub mod snapshot;
+pub mod sync_links;
 pub mod transformer;
 
 use crate::error::Result;
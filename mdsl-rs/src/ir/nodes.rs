@@ -17,6 +17,35 @@ pub struct IRProgram {
     pub families: Vec<IRFamily>,
     /// List of events
     pub events: Vec<IREvent>,
+    /// List of companies
+    pub companies: Vec<IRCompany>,
+    /// List of outlet-party links
+    pub outlet_party_links: Vec<IROutletPartyLink>,
+    /// List of natural persons
+    pub persons: Vec<IRPerson>,
+    /// List of ownership relations
+    pub ownerships: Vec<IROwnership>,
+    /// List of identifier links
+    pub identifier_links: Vec<IRIdentifierLink>,
+    /// List of source catalogs
+    pub catalogs: Vec<IRCatalog>,
+    /// List of redaction policies
+    pub redaction_policies: Vec<IRRedactionPolicy>,
+    /// Declared semantics of synchronous relationship types
+    pub relationship_types: Vec<IRRelationshipType>,
+}
+
+impl IRProgram {
+    /// Semantics of a synchronous relationship type: the declared ones, else
+    /// the built-in defaults; `None` for types without known semantics
+    pub fn relationship_type(&self, name: &str) -> Option<IRRelationshipType> {
+        self.relationship_types
+            .iter()
+            .rev()
+            .find(|declared| declared.name == name)
+            .cloned()
+            .or_else(|| IRRelationshipType::builtin(name))
+    }
 }
 
 /// IR import
@@ -150,6 +179,12 @@ pub struct IRFamily {
     pub relationships: Vec<IRRelationship>,
     /// Family data blocks
     pub data_blocks: Vec<IRDataBlock>,
+    /// Overrides of referenced outlets (OUTLET_REF)
+    pub overrides: Vec<IROutletOverride>,
+    /// Provenance records
+    pub provenance: Vec<IRProvenance>,
+    /// Review records
+    pub reviews: Vec<IRReview>,
 }
 
 /// IR outlet
@@ -165,6 +200,25 @@ pub struct IROutlet {
     pub base_ref: Option<u32>,
     /// Outlet blocks
     pub blocks: Vec<IROutletBlock>,
+    /// Provenance records
+    pub provenance: Vec<IRProvenance>,
+    /// Review records
+    pub reviews: Vec<IRReview>,
+}
+
+/// IR override of an outlet for a period
+#[derive(Debug, Clone)]
+pub struct IROutletOverride {
+    /// Overridden outlet ID
+    pub outlet_id: u32,
+    /// Start date (None = open)
+    pub start_date: Option<String>,
+    /// End date (None or CURRENT = open)
+    pub end_date: Option<String>,
+    /// Family the outlet belongs to during the period
+    pub family: Option<String>,
+    /// Blocks whose fields take precedence over the outlet's own
+    pub blocks: Vec<IROutletBlock>,
 }
 
 /// IR outlet block
@@ -217,6 +271,10 @@ pub struct IRDataBlock {
     pub years: Vec<IRDataYear>,
     /// Maps to reference
     pub maps_to: Option<String>,
+    /// Provenance records
+    pub provenance: Vec<IRProvenance>,
+    /// Review records
+    pub reviews: Vec<IRReview>,
 }
 
 /// IR data aggregation
@@ -282,6 +340,10 @@ pub struct IRDiachronicLink {
     pub comment: Option<String>,
     /// Maps to reference
     pub maps_to: Option<String>,
+    /// Provenance records
+    pub provenance: Vec<IRProvenance>,
+    /// Review records
+    pub reviews: Vec<IRReview>,
 }
 
 /// IR synchronous link
@@ -303,6 +365,10 @@ pub struct IRSynchronousLink {
     pub details: Option<String>,
     /// Maps to reference
     pub maps_to: Option<String>,
+    /// Provenance records
+    pub provenance: Vec<IRProvenance>,
+    /// Review records
+    pub reviews: Vec<IRReview>,
 }
 
 /// IR synchronous outlet reference
@@ -314,6 +380,124 @@ pub struct IRSyncOutlet {
     pub role: String,
 }
 
+/// Direction of a synchronous relationship type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkDirection {
+    /// From the outlet in the first role of a pair to the one in the second
+    Directed,
+    /// Both outlets take the same part; their order carries no meaning
+    Symmetric,
+}
+
+impl LinkDirection {
+    /// Names accepted in a RELATIONSHIP_TYPE block
+    pub const NAMES: &'static [&'static str] = &["directed", "symmetric"];
+
+    /// Parse a direction name
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "directed" => Some(Self::Directed),
+            "symmetric" => Some(Self::Symmetric),
+            _ => None,
+        }
+    }
+
+    /// Direction name as written in a RELATIONSHIP_TYPE block
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Directed => "directed",
+            Self::Symmetric => "symmetric",
+        }
+    }
+}
+
+/// How the roles of a link match the role pairs of its type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoleOrder {
+    /// `outlet_1` and `outlet_2` take the roles of a pair in order
+    Declared,
+    /// The roles of a pair, with the outlets swapped
+    Reversed,
+    /// No allowed pair matches
+    NotAllowed,
+}
+
+/// IR semantics of a synchronous relationship type
+#[derive(Debug, Clone, PartialEq)]
+pub struct IRRelationshipType {
+    /// Relationship type name
+    pub name: String,
+    /// Whether links are directed or symmetric
+    pub direction: LinkDirection,
+    /// Allowed (from, to) role pairs; empty if any roles are allowed
+    pub role_pairs: Vec<(String, String)>,
+}
+
+impl IRRelationshipType {
+    /// Built-in semantics of the ANMI synchronous types, used unless a
+    /// RELATIONSHIP_TYPE declaration overrides them. Umbrella and main media
+    /// outlet links point from the member outlet to its umbrella or main
+    /// outlet, as `id_mo` to the related outlet in the ANMI tables: the
+    /// importer writes the member as `source` and the other as `target`,
+    /// curated files the member as `integrated` and the other as `primary`
+    pub fn builtin(name: &str) -> Option<Self> {
+        let pairs = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(from, to)| (from.to_string(), to.to_string()))
+                .collect()
+        };
+        let (direction, role_pairs) = match name {
+            "umbrella" | "main_media_outlet" => (
+                LinkDirection::Directed,
+                pairs(&[("source", "target"), ("integrated", "primary")]),
+            ),
+            "collaboration" => (LinkDirection::Symmetric, Vec::new()),
+            _ => return None,
+        };
+        Some(IRRelationshipType {
+            name: name.to_string(),
+            direction,
+            role_pairs,
+        })
+    }
+
+    /// How the roles of `outlet_1` and `outlet_2` match the allowed pairs
+    pub fn role_order(&self, role_1: &str, role_2: &str) -> RoleOrder {
+        if self.role_pairs.is_empty()
+            || self
+                .role_pairs
+                .iter()
+                .any(|(from, to)| from == role_1 && to == role_2)
+        {
+            RoleOrder::Declared
+        } else if self
+            .role_pairs
+            .iter()
+            .any(|(from, to)| from == role_2 && to == role_1)
+        {
+            RoleOrder::Reversed
+        } else {
+            RoleOrder::NotAllowed
+        }
+    }
+
+    /// Endpoints of a link of this type: (source, target) for a directed
+    /// type, the lower ID first for a symmetric one
+    pub fn endpoints(&self, outlet_1: &IRSyncOutlet, outlet_2: &IRSyncOutlet) -> (u32, u32) {
+        match self.direction {
+            LinkDirection::Symmetric => (
+                outlet_1.id.min(outlet_2.id),
+                outlet_1.id.max(outlet_2.id),
+            ),
+            LinkDirection::Directed => match self.role_order(&outlet_1.role, &outlet_2.role) {
+                RoleOrder::Reversed => (outlet_2.id, outlet_1.id),
+                _ => (outlet_1.id, outlet_2.id),
+            },
+        }
+    }
+}
+
 /// IR outlet field (deprecated, use IRIdentityField)
 #[derive(Debug, Clone)]
 pub struct IROutletField {
@@ -349,6 +533,43 @@ pub struct IRObjectField {
     pub value: IRExpression,
 }
 
+/// IR source catalog
+#[derive(Debug, Clone)]
+pub struct IRCatalog {
+    /// Catalog name
+    pub name: String,
+    /// Sources declared in the catalog
+    pub sources: Vec<IRSource>,
+}
+
+/// IR data source
+#[derive(Debug, Clone)]
+pub struct IRSource {
+    /// Source name, as cited by metrics and provenance clauses
+    pub name: String,
+    /// Name of the declaring catalog
+    pub catalog: String,
+    /// Source fields; nested blocks are flattened to `block.field`
+    pub fields: Vec<IRObjectField>,
+    /// Quality tier
+    pub quality_tier: Option<String>,
+    /// ANMI source type code (`anmi_source_id_components { type_code }`)
+    pub anmi_type_code: Option<u32>,
+    /// Maps to reference (`@maps_to_table`)
+    pub maps_to: Option<String>,
+}
+
+impl IRSource {
+    /// Largest ANMI source type code; composite source IDs keep two digits for it
+    pub const MAX_TYPE_CODE: u32 = 99;
+
+    /// ANMI source type code of a `type_code` value: a non-negative integer of at most two digits
+    pub fn type_code(value: f64) -> Option<u32> {
+        (value.fract() == 0.0 && (0.0..=Self::MAX_TYPE_CODE as f64).contains(&value))
+            .then_some(value as u32)
+    }
+}
+
 /// IR event declaration
 #[derive(Debug, Clone)]
 pub struct IREvent {
@@ -366,6 +587,10 @@ pub struct IREvent {
     pub metadata: Vec<IREventMetadata>,
     /// Event status
     pub status: Option<String>,
+    /// Provenance records
+    pub provenance: Vec<IRProvenance>,
+    /// Review records
+    pub reviews: Vec<IRReview>,
 }
 
 /// IR event entity
@@ -373,8 +598,12 @@ pub struct IREvent {
 pub struct IREventEntity {
     /// Entity name
     pub name: String,
-    /// Entity ID
+    /// Entity ID: the person ID of a person, otherwise an outlet, or a
+    /// company when no outlet has the ID
     pub id: u32,
+    /// Declared person the entity is (`person = <id>`); person IDs are a
+    /// namespace of their own, so only this marks a natural person
+    pub person: Option<u32>,
     /// Entity role
     pub role: String,
     /// Stake before event
@@ -400,3 +629,213 @@ pub struct IREventMetadata {
     /// Metadata value
     pub value: IRExpression,
 }
+
+/// IR company declaration
+#[derive(Debug, Clone)]
+pub struct IRCompany {
+    /// Company name
+    pub name: String,
+    /// Company ID
+    pub id: u32,
+    /// Additional company fields (legal form, registry ID, ...)
+    pub fields: Vec<IRObjectField>,
+    /// Comment
+    pub comment: Option<String>,
+    /// Provenance records
+    pub provenance: Vec<IRProvenance>,
+    /// Review records
+    pub reviews: Vec<IRReview>,
+}
+
+/// IR outlet-party link
+#[derive(Debug, Clone)]
+pub struct IROutletPartyLink {
+    /// Link name
+    pub name: String,
+    /// Outlet ID
+    pub outlet_id: u32,
+    /// Party (company) ID
+    pub party_id: u32,
+    /// Role of the party
+    pub role: String,
+    /// Period start
+    pub period_start: Option<String>,
+    /// Period end
+    pub period_end: Option<String>,
+    /// Source of the information
+    pub source: Option<String>,
+    /// Comment
+    pub comment: Option<String>,
+    /// Provenance records
+    pub provenance: Vec<IRProvenance>,
+    /// Review records
+    pub reviews: Vec<IRReview>,
+}
+
+/// IR natural person declaration
+#[derive(Debug, Clone)]
+pub struct IRPerson {
+    /// Person name
+    pub name: String,
+    /// Person ID
+    pub id: u32,
+    /// Additional person fields (source URL, scrape time, ...)
+    pub fields: Vec<IRObjectField>,
+    /// Comment
+    pub comment: Option<String>,
+    /// Provenance records
+    pub provenance: Vec<IRProvenance>,
+    /// Review records
+    pub reviews: Vec<IRReview>,
+}
+
+/// Holder of an ownership stake
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IROwner {
+    /// A company, by company ID
+    Company(u32),
+    /// A natural person, by person ID
+    Person(u32),
+}
+
+/// IR ownership relation
+#[derive(Debug, Clone)]
+pub struct IROwnership {
+    /// Relation name
+    pub name: String,
+    /// Owner of the stake
+    pub owner: Option<IROwner>,
+    /// Owned company ID
+    pub owned_company_id: u32,
+    /// Share held, in percent
+    pub share_pct: Option<f64>,
+    /// Role of the owner
+    pub role: Option<String>,
+    /// Period start
+    pub period_start: Option<String>,
+    /// Period end
+    pub period_end: Option<String>,
+    /// URL the relation was taken from
+    pub source_url: Option<String>,
+    /// Time the source was scraped
+    pub scraped_at: Option<String>,
+    /// Comment
+    pub comment: Option<String>,
+    /// Provenance records
+    pub provenance: Vec<IRProvenance>,
+    /// Review records
+    pub reviews: Vec<IRReview>,
+}
+
+/// IR identifier link
+#[derive(Debug, Clone)]
+pub struct IRIdentifierLink {
+    /// Link name
+    pub name: String,
+    /// Company ID
+    pub company_id: u32,
+    /// Identifier scheme
+    pub scheme: String,
+    /// Identifier value within the scheme
+    pub value: String,
+    /// Review status
+    pub status: Option<String>,
+    /// Match score
+    pub score: Option<f64>,
+    /// Who or what issued the link
+    pub issued_by: Option<String>,
+    /// Comment
+    pub comment: Option<String>,
+    /// Provenance records
+    pub provenance: Vec<IRProvenance>,
+    /// Review records
+    pub reviews: Vec<IRReview>,
+}
+
+/// IR provenance record, tying a declaration or one of its fields to a catalog source
+#[derive(Debug, Clone, PartialEq)]
+pub struct IRProvenance {
+    /// Field the record applies to (None for the whole declaration)
+    pub target: Option<String>,
+    /// Catalog source name
+    pub source: String,
+    /// Quality tier of the source, as declared in the catalog
+    pub quality_tier: Option<String>,
+    /// Confidence in the assertion (0-1)
+    pub confidence: Option<f64>,
+    /// Verification status
+    pub verification: Option<String>,
+    /// Date of the last verification
+    pub verified_at: Option<String>,
+    /// Free-text note
+    pub note: Option<String>,
+}
+
+/// IR review record, the publication status of a declaration or of one of its assertions
+#[derive(Debug, Clone, PartialEq)]
+pub struct IRReview {
+    /// Field the review applies to (None for the whole declaration)
+    pub target: Option<String>,
+    /// Publication status (draft, in_review, reviewed, published, withdrawn)
+    pub status: String,
+    /// Reviewer
+    pub reviewer: Option<String>,
+    /// Review date
+    pub reviewed_at: Option<String>,
+    /// Free-text note
+    pub note: Option<String>,
+}
+
+/// What a redaction policy does with person-level data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedactionAction {
+    /// Export unchanged
+    Keep,
+    /// Leave out of the export
+    Drop,
+    /// Replace names by stable hashes and leave out identifying fields
+    Pseudonymise,
+    /// Collapse into one aggregate record per company or event
+    Aggregate,
+}
+
+impl RedactionAction {
+    /// Names accepted in a REDACTION block
+    pub const NAMES: &'static [&'static str] = &["keep", "drop", "pseudonymise", "aggregate"];
+
+    /// Parse an action name (`pseudonymize` is accepted as well)
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "keep" => Some(Self::Keep),
+            "drop" => Some(Self::Drop),
+            "pseudonymise" | "pseudonymize" => Some(Self::Pseudonymise),
+            "aggregate" => Some(Self::Aggregate),
+            _ => None,
+        }
+    }
+
+    /// Action name as written in a REDACTION block
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Keep => "keep",
+            Self::Drop => "drop",
+            Self::Pseudonymise => "pseudonymise",
+            Self::Aggregate => "aggregate",
+        }
+    }
+}
+
+/// IR redaction policy
+#[derive(Debug, Clone, PartialEq)]
+pub struct IRRedactionPolicy {
+    /// Policy name
+    pub name: String,
+    /// Action for PERSON declarations and the stakes they hold
+    pub persons: RedactionAction,
+    /// Action for event entities referring to a person
+    pub event_persons: RedactionAction,
+    /// Names of metadata fields holding person names
+    pub person_metadata: Vec<String>,
+    /// Salt mixed into pseudonym hashes
+    pub salt: String,
+}
@@ -0,0 +1,382 @@
+//! Publication filter: reduces an IR program to its approved content
+//!
+//! A declaration is approved when its untargeted REVIEW clause has status
+//! `published`. Outlets, relationships and data blocks without a review of their
+//! own inherit the status of their family. Targeted reviews that are not
+//! `published` withhold the reviewed field and its provenance records. A family
+//! that is not approved is withheld with everything it holds. Links and event
+//! entities that refer to declarations that were filtered out are dropped as
+//! well.
+
+use crate::ir::nodes::*;
+use std::collections::{HashMap, HashSet};
+
+/// Publication status that passes the filter
+pub const PUBLISHED: &str = "published";
+
+/// Return a copy of `ir` that only holds published declarations and assertions
+pub fn publish_only(ir: &IRProgram) -> IRProgram {
+    let mut published = IRProgram {
+        imports: ir.imports.clone(),
+        variables: ir.variables.clone(),
+        templates: ir.templates.clone(),
+        units: ir.units.clone(),
+        vocabularies: ir.vocabularies.clone(),
+        families: Vec::new(),
+        events: Vec::new(),
+        companies: Vec::new(),
+        outlet_party_links: Vec::new(),
+        persons: Vec::new(),
+        ownerships: Vec::new(),
+        identifier_links: Vec::new(),
+        catalogs: ir.catalogs.clone(),
+        redaction_policies: ir.redaction_policies.clone(),
+        relationship_types: ir.relationship_types.clone(),
+    };
+
+    for family in &ir.families {
+        // A family that is not approved is withheld with all of its content,
+        // so its name and reviews cannot leak through an approved outlet
+        if !is_approved(&family.reviews, None) {
+            continue;
+        }
+        let family_status = declaration_status(&family.reviews);
+        let mut filtered = IRFamily {
+            name: family.name.clone(),
+            comment: family.comment.clone(),
+            outlets: Vec::new(),
+            relationships: Vec::new(),
+            data_blocks: Vec::new(),
+            overrides: family.overrides.clone(),
+            provenance: approved_provenance(&family.provenance, &family.reviews),
+            reviews: family.reviews.clone(),
+        };
+
+        for outlet in &family.outlets {
+            if is_approved(&outlet.reviews, family_status) {
+                filtered.outlets.push(filter_outlet(outlet));
+            }
+        }
+        for relationship in &family.relationships {
+            let reviews = match relationship {
+                IRRelationship::Diachronic(link) => &link.reviews,
+                IRRelationship::Synchronous(link) => &link.reviews,
+            };
+            if is_approved(reviews, family_status) {
+                filtered.relationships.push(filter_relationship(relationship));
+            }
+        }
+        for data in &family.data_blocks {
+            if is_approved(&data.reviews, family_status) {
+                filtered.data_blocks.push(filter_data(data));
+            }
+        }
+
+        published.families.push(filtered);
+    }
+
+    for company in &ir.companies {
+        if is_approved(&company.reviews, None) {
+            let withheld = withheld_targets(&company.reviews);
+            published.companies.push(IRCompany {
+                fields: retain_named(&company.fields, &withheld, |field| &field.name),
+                provenance: approved_provenance(&company.provenance, &company.reviews),
+                ..company.clone()
+            });
+        }
+    }
+    for person in &ir.persons {
+        if is_approved(&person.reviews, None) {
+            let withheld = withheld_targets(&person.reviews);
+            published.persons.push(IRPerson {
+                fields: retain_named(&person.fields, &withheld, |field| &field.name),
+                provenance: approved_provenance(&person.provenance, &person.reviews),
+                ..person.clone()
+            });
+        }
+    }
+
+    // Links are only kept while both of their ends are published
+    let outlets: HashSet<u32> = published
+        .families
+        .iter()
+        .flat_map(|family| &family.outlets)
+        .filter_map(|outlet| outlet.id)
+        .collect();
+    let companies: HashSet<u32> = published.companies.iter().map(|company| company.id).collect();
+    let persons: HashSet<u32> = published.persons.iter().map(|person| person.id).collect();
+
+    for family in &mut published.families {
+        family.relationships.retain(|relationship| match relationship {
+            IRRelationship::Diachronic(link) => {
+                outlets.contains(&link.predecessor) && outlets.contains(&link.successor)
+            }
+            IRRelationship::Synchronous(link) => {
+                outlets.contains(&link.outlet_1.id) && outlets.contains(&link.outlet_2.id)
+            }
+        });
+        family.data_blocks.retain(|data| outlets.contains(&data.outlet_id));
+    }
+
+    // Overrides (OUTLET_REF) may point at outlets of other families; they are
+    // kept for published outlets, without the fields the outlet withholds
+    let outlet_reviews: HashMap<u32, &[IRReview]> = ir
+        .families
+        .iter()
+        .flat_map(|family| &family.outlets)
+        .filter_map(|outlet| outlet.id.map(|id| (id, outlet.reviews.as_slice())))
+        .collect();
+    for family in &mut published.families {
+        family
+            .overrides
+            .retain(|overridden| outlets.contains(&overridden.outlet_id));
+        for overridden in &mut family.overrides {
+            let reviews = outlet_reviews
+                .get(&overridden.outlet_id)
+                .copied()
+                .unwrap_or_default();
+            overridden.blocks = filter_blocks(&overridden.blocks, &withheld_targets(reviews));
+        }
+    }
+
+    // Event entities name a person, an outlet, or a company when no outlet
+    // has their ID; entities naming a declaration that was withheld are dropped
+    let declared_outlets: HashSet<u32> = ir
+        .families
+        .iter()
+        .flat_map(|family| &family.outlets)
+        .filter_map(|outlet| outlet.id)
+        .collect();
+    let declared_companies: HashSet<u32> = ir.companies.iter().map(|company| company.id).collect();
+    let declared_persons: HashSet<u32> = ir.persons.iter().map(|person| person.id).collect();
+    let entity_published = |entity: &IREventEntity| {
+        if let Some(person) = entity.person {
+            !declared_persons.contains(&person) || persons.contains(&person)
+        } else if declared_outlets.contains(&entity.id) {
+            outlets.contains(&entity.id)
+        } else {
+            !declared_companies.contains(&entity.id) || companies.contains(&entity.id)
+        }
+    };
+    for event in &ir.events {
+        if is_approved(&event.reviews, None) {
+            let withheld = withheld_targets(&event.reviews);
+            published.events.push(IREvent {
+                date: event.date.clone().filter(|_| !withheld.contains("date")),
+                status: event.status.clone().filter(|_| !withheld.contains("status")),
+                entities: if withheld.contains("entities") {
+                    Vec::new()
+                } else {
+                    retain_named(&event.entities, &withheld, |entity| &entity.name)
+                        .into_iter()
+                        .filter(|entity| entity_published(entity))
+                        .collect()
+                },
+                impact: if withheld.contains("impact") {
+                    Vec::new()
+                } else {
+                    event.impact.clone()
+                },
+                metadata: if withheld.contains("metadata") {
+                    Vec::new()
+                } else {
+                    event.metadata.clone()
+                },
+                provenance: approved_provenance(&event.provenance, &event.reviews),
+                ..event.clone()
+            });
+        }
+    }
+
+    for link in &ir.outlet_party_links {
+        if is_approved(&link.reviews, None)
+            && outlets.contains(&link.outlet_id)
+            && companies.contains(&link.party_id)
+        {
+            let withheld = withheld_targets(&link.reviews);
+            published.outlet_party_links.push(IROutletPartyLink {
+                period_start: withhold(&link.period_start, &withheld, "period_start"),
+                period_end: withhold(&link.period_end, &withheld, "period_end"),
+                source: withhold(&link.source, &withheld, "source"),
+                provenance: approved_provenance(&link.provenance, &link.reviews),
+                ..link.clone()
+            });
+        }
+    }
+    for ownership in &ir.ownerships {
+        let owner_published = match ownership.owner {
+            Some(IROwner::Company(id)) => companies.contains(&id),
+            Some(IROwner::Person(id)) => persons.contains(&id),
+            None => false,
+        };
+        if is_approved(&ownership.reviews, None)
+            && owner_published
+            && companies.contains(&ownership.owned_company_id)
+        {
+            let withheld = withheld_targets(&ownership.reviews);
+            published.ownerships.push(IROwnership {
+                share_pct: ownership.share_pct.filter(|_| !withheld.contains("share_pct")),
+                role: withhold(&ownership.role, &withheld, "role"),
+                period_start: withhold(&ownership.period_start, &withheld, "period_start"),
+                period_end: withhold(&ownership.period_end, &withheld, "period_end"),
+                source_url: withhold(&ownership.source_url, &withheld, "source_url"),
+                scraped_at: withhold(&ownership.scraped_at, &withheld, "scraped_at"),
+                provenance: approved_provenance(&ownership.provenance, &ownership.reviews),
+                ..ownership.clone()
+            });
+        }
+    }
+    for link in &ir.identifier_links {
+        if is_approved(&link.reviews, None) && companies.contains(&link.company_id) {
+            let withheld = withheld_targets(&link.reviews);
+            published.identifier_links.push(IRIdentifierLink {
+                status: withhold(&link.status, &withheld, "status"),
+                score: link.score.filter(|_| !withheld.contains("score")),
+                issued_by: withhold(&link.issued_by, &withheld, "issued_by"),
+                provenance: approved_provenance(&link.provenance, &link.reviews),
+                ..link.clone()
+            });
+        }
+    }
+
+    published
+}
+
+/// Status of the untargeted review of a declaration, if it has one
+fn declaration_status(reviews: &[IRReview]) -> Option<&str> {
+    reviews
+        .iter()
+        .rev()
+        .find(|review| review.target.is_none())
+        .map(|review| review.status.as_str())
+}
+
+/// Whether a declaration is published, falling back to the status of its parent
+fn is_approved(reviews: &[IRReview], inherited: Option<&str>) -> bool {
+    declaration_status(reviews).or(inherited) == Some(PUBLISHED)
+}
+
+/// Targets of the field-level reviews that are not published
+fn withheld_targets(reviews: &[IRReview]) -> HashSet<&str> {
+    reviews
+        .iter()
+        .filter(|review| review.status != PUBLISHED)
+        .filter_map(|review| review.target.as_deref())
+        .collect()
+}
+
+/// Provenance records whose target is not withheld
+fn approved_provenance(provenance: &[IRProvenance], reviews: &[IRReview]) -> Vec<IRProvenance> {
+    let withheld = withheld_targets(reviews);
+    provenance
+        .iter()
+        .filter(|record| {
+            record
+                .target
+                .as_deref()
+                .is_none_or(|target| !withheld.contains(target))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Items whose name is not withheld
+fn retain_named<T: Clone>(items: &[T], withheld: &HashSet<&str>, name: fn(&T) -> &String) -> Vec<T> {
+    items
+        .iter()
+        .filter(|item| !withheld.contains(name(item).as_str()))
+        .cloned()
+        .collect()
+}
+
+/// An optional field, cleared when its review is not published
+fn withhold(value: &Option<String>, withheld: &HashSet<&str>, field: &str) -> Option<String> {
+    value.clone().filter(|_| !withheld.contains(field))
+}
+
+/// Outlet without its withheld fields and blocks
+fn filter_outlet(outlet: &IROutlet) -> IROutlet {
+    IROutlet {
+        blocks: filter_blocks(&outlet.blocks, &withheld_targets(&outlet.reviews)),
+        provenance: approved_provenance(&outlet.provenance, &outlet.reviews),
+        ..outlet.clone()
+    }
+}
+
+/// Outlet blocks without the withheld blocks and fields
+fn filter_blocks(blocks: &[IROutletBlock], withheld: &HashSet<&str>) -> Vec<IROutletBlock> {
+    blocks
+        .iter()
+        .filter_map(|block| match block {
+            IROutletBlock::Identity(fields) if !withheld.contains("identity") => Some(
+                IROutletBlock::Identity(retain_named(fields, withheld, |field| &field.name)),
+            ),
+            IROutletBlock::Lifecycle(statuses) if !withheld.contains("lifecycle") => {
+                Some(IROutletBlock::Lifecycle(statuses.clone()))
+            }
+            IROutletBlock::Characteristics(fields) if !withheld.contains("characteristics") => {
+                Some(IROutletBlock::Characteristics(retain_named(
+                    fields,
+                    withheld,
+                    |field| &field.name,
+                )))
+            }
+            IROutletBlock::Metadata(fields) if !withheld.contains("metadata") => Some(
+                IROutletBlock::Metadata(retain_named(fields, withheld, |field| &field.name)),
+            ),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Relationship without its withheld fields
+fn filter_relationship(relationship: &IRRelationship) -> IRRelationship {
+    match relationship {
+        IRRelationship::Diachronic(link) => {
+            let withheld = withheld_targets(&link.reviews);
+            IRRelationship::Diachronic(IRDiachronicLink {
+                event_start_date: withhold(&link.event_start_date, &withheld, "event_date"),
+                event_end_date: withhold(&link.event_end_date, &withheld, "event_date"),
+                provenance: approved_provenance(&link.provenance, &link.reviews),
+                ..link.clone()
+            })
+        }
+        IRRelationship::Synchronous(link) => {
+            let withheld = withheld_targets(&link.reviews);
+            IRRelationship::Synchronous(IRSynchronousLink {
+                period_start: withhold(&link.period_start, &withheld, "period_start"),
+                period_end: withhold(&link.period_end, &withheld, "period_end"),
+                details: withhold(&link.details, &withheld, "details"),
+                provenance: approved_provenance(&link.provenance, &link.reviews),
+                ..link.clone()
+            })
+        }
+    }
+}
+
+/// Data block without its withheld metrics; year-level reviews target `<year>.<metric>`
+fn filter_data(data: &IRDataBlock) -> IRDataBlock {
+    let withheld = withheld_targets(&data.reviews);
+    let years = data
+        .years
+        .iter()
+        .map(|year| IRDataYear {
+            metrics: year
+                .metrics
+                .iter()
+                .filter(|metric| {
+                    !withheld.contains(metric.name.as_str())
+                        && !withheld.contains(format!("{}.{}", year.year, metric.name).as_str())
+                })
+                .cloned()
+                .collect(),
+            ..year.clone()
+        })
+        .collect();
+
+    IRDataBlock {
+        years,
+        provenance: approved_provenance(&data.provenance, &data.reviews),
+        ..data.clone()
+    }
+}
@@ -0,0 +1,426 @@
+//! Redaction of natural persons for public exports
+//!
+//! A REDACTION policy decides what happens to person-level data before code
+//! generation: PERSON declarations and the stakes they hold, event entities that
+//! name a person (`person = <id>`), and metadata fields listed as holding person
+//! names.
+//! Pseudonyms are stable hashes of the salt and the person ID, so the same person
+//! gets the same pseudonym in every export made with the same salt. Pseudonymised
+//! persons also get a pseudonymous ID, which replaces the register ID in every
+//! stake, event entity and metadata value referring to them.
+
+use crate::ir::nodes::*;
+use std::collections::BTreeMap;
+
+/// Name and role of the records that replace aggregated persons
+pub const AGGREGATE_NAME: &str = "natural_persons";
+
+/// One redacted item
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedactionEntry {
+    /// Kind of item (person, ownership, event entity, metadata)
+    pub kind: &'static str,
+    /// Name of the item
+    pub subject: String,
+    /// Applied action
+    pub action: RedactionAction,
+    /// What was done
+    pub detail: String,
+}
+
+/// Everything a redaction policy changed
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedactionReport {
+    /// Name of the applied policy
+    pub policy: String,
+    /// Redacted items, in declaration order
+    pub entries: Vec<RedactionEntry>,
+}
+
+impl RedactionReport {
+    /// Format the report as human-readable text
+    pub fn format_text(&self) -> String {
+        let mut out = format!(
+            "Redaction policy '{}': {} item(s) redacted\n",
+            self.policy,
+            self.entries.len()
+        );
+        for entry in &self.entries {
+            out.push_str(&format!(
+                "  {:<12} {:<32} {:<13} {}\n",
+                entry.kind,
+                entry.subject,
+                entry.action.as_str(),
+                entry.detail
+            ));
+        }
+        out
+    }
+
+    fn record(&mut self, kind: &'static str, subject: &str, action: RedactionAction, detail: String) {
+        self.entries.push(RedactionEntry {
+            kind,
+            subject: subject.to_string(),
+            action,
+            detail,
+        });
+    }
+}
+
+/// First pseudonymous person ID; pseudonymous IDs have nine digits
+const PSEUDONYM_ID_BASE: u32 = 100_000_000;
+
+/// Number of pseudonymous person IDs
+const PSEUDONYM_ID_RANGE: u64 = 900_000_000;
+
+/// FNV-1a hash of a salted value; identical across builds and platforms
+fn salted_hash(salt: &str, kind: &str, value: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in [salt, "\u{1f}", kind, "\u{1f}", value]
+        .iter()
+        .flat_map(|part| part.bytes())
+    {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Stable pseudonym for a value
+pub fn pseudonym(salt: &str, kind: &str, value: &str) -> String {
+    format!("{}_{:016x}", kind, salted_hash(salt, kind, value))
+}
+
+/// Stable pseudonymous ID for a person ID, before collisions are resolved
+pub fn pseudonym_id(salt: &str, person_id: u32) -> u32 {
+    let hash = salted_hash(salt, "person_id", &person_id.to_string());
+    PSEUDONYM_ID_BASE + (hash % PSEUDONYM_ID_RANGE) as u32
+}
+
+/// Apply a redaction policy, returning the redacted program and a report of the changes
+pub fn redact(ir: &IRProgram, policy: &IRRedactionPolicy) -> (IRProgram, RedactionReport) {
+    let mut redacted = ir.clone();
+    let mut report = RedactionReport {
+        policy: policy.name.clone(),
+        entries: Vec::new(),
+    };
+    let ids = if policy.persons == RedactionAction::Pseudonymise {
+        pseudonymous_ids(ir, &policy.salt)
+    } else {
+        BTreeMap::new()
+    };
+    redact_persons(&mut redacted, policy, &mut report);
+    redact_stakes(&mut redacted, policy, &mut report);
+    redact_event_entities(&mut redacted, policy, &mut report);
+    redact_metadata(&mut redacted, policy, &ids, &mut report);
+    remap_person_ids(&mut redacted, &ids);
+
+    (redacted, report)
+}
+
+/// Pseudonymous IDs of every person ID referenced in the program; a colliding
+/// ID moves to the next free one, in order of the original IDs
+fn pseudonymous_ids(ir: &IRProgram, salt: &str) -> BTreeMap<u32, u32> {
+    let mut referenced: Vec<u32> = ir.persons.iter().map(|person| person.id).collect();
+    referenced.extend(
+        ir.ownerships
+            .iter()
+            .filter_map(|ownership| match ownership.owner {
+                Some(IROwner::Person(id)) => Some(id),
+                _ => None,
+            }),
+    );
+    referenced.extend(
+        ir.events
+            .iter()
+            .flat_map(|event| &event.entities)
+            .filter_map(|entity| entity.person),
+    );
+    referenced.sort_unstable();
+    referenced.dedup();
+
+    let mut ids = BTreeMap::new();
+    let mut taken = std::collections::BTreeSet::new();
+    for id in referenced {
+        let mut pseudonymous = pseudonym_id(salt, id);
+        while !taken.insert(pseudonymous) {
+            let next = (pseudonymous - PSEUDONYM_ID_BASE + 1) as u64 % PSEUDONYM_ID_RANGE;
+            pseudonymous = PSEUDONYM_ID_BASE + next as u32;
+        }
+        ids.insert(id, pseudonymous);
+    }
+    ids
+}
+
+/// Replace person IDs by their pseudonymous IDs wherever they are referenced
+fn remap_person_ids(ir: &mut IRProgram, ids: &BTreeMap<u32, u32>) {
+    let remap = |id: &mut u32| {
+        if let Some(pseudonymous) = ids.get(id) {
+            *id = *pseudonymous;
+        }
+    };
+
+    for person in &mut ir.persons {
+        remap(&mut person.id);
+    }
+    for ownership in &mut ir.ownerships {
+        if let Some(IROwner::Person(id)) = &mut ownership.owner {
+            remap(id);
+        }
+    }
+    for entity in ir.events.iter_mut().flat_map(|event| &mut event.entities) {
+        // The entity ID of a person is the person ID
+        if let Some(person) = &mut entity.person {
+            remap(person);
+            entity.id = *person;
+        }
+    }
+}
+
+/// Redact PERSON declarations
+fn redact_persons(ir: &mut IRProgram, policy: &IRRedactionPolicy, report: &mut RedactionReport) {
+    match policy.persons {
+        RedactionAction::Keep => {}
+        RedactionAction::Drop | RedactionAction::Aggregate => {
+            for person in ir.persons.drain(..) {
+                report.record("person", &person.name, policy.persons, "removed".to_string());
+            }
+        }
+        RedactionAction::Pseudonymise => {
+            for person in &mut ir.persons {
+                let name = pseudonym(&policy.salt, "person", &person.id.to_string());
+                report.record(
+                    "person",
+                    &person.name,
+                    policy.persons,
+                    format!(
+                        "renamed to {}, ID replaced, {} field(s) removed",
+                        name,
+                        person.fields.len()
+                    ),
+                );
+                person.name = name;
+                person.fields.clear();
+                person.comment = None;
+            }
+        }
+    }
+}
+
+/// Redact the ownership stakes held by persons
+fn redact_stakes(ir: &mut IRProgram, policy: &IRRedactionPolicy, report: &mut RedactionReport) {
+    let held_by_person =
+        |ownership: &IROwnership| matches!(ownership.owner, Some(IROwner::Person(_)));
+
+    match policy.persons {
+        RedactionAction::Keep => {}
+        RedactionAction::Drop => {
+            ir.ownerships.retain(|ownership| {
+                if held_by_person(ownership) {
+                    report.record("ownership", &ownership.name, policy.persons, "removed".to_string());
+                }
+                !held_by_person(ownership)
+            });
+        }
+        RedactionAction::Pseudonymise => {
+            // The source URL usually points at the person's register profile
+            for ownership in ir.ownerships.iter_mut().filter(|o| held_by_person(o)) {
+                report.record(
+                    "ownership",
+                    &ownership.name,
+                    policy.persons,
+                    "source URL and comment removed".to_string(),
+                );
+                ownership.source_url = None;
+                ownership.comment = None;
+            }
+        }
+        RedactionAction::Aggregate => {
+            // Current person-held stakes are summed per owned company; historic ones are dropped
+            let mut aggregates: BTreeMap<u32, (usize, Option<f64>)> = BTreeMap::new();
+            let mut kept = Vec::new();
+            for ownership in ir.ownerships.drain(..) {
+                if !held_by_person(&ownership) {
+                    kept.push(ownership);
+                    continue;
+                }
+                let current = matches!(ownership.period_end.as_deref(), None | Some("CURRENT"));
+                if current {
+                    let (count, share) = aggregates.entry(ownership.owned_company_id).or_default();
+                    *count += 1;
+                    if let Some(pct) = ownership.share_pct {
+                        *share = Some(share.unwrap_or(0.0) + pct);
+                    }
+                }
+                report.record(
+                    "ownership",
+                    &ownership.name,
+                    policy.persons,
+                    if current {
+                        format!("merged into {}_{}", AGGREGATE_NAME, ownership.owned_company_id)
+                    } else {
+                        "historic stake removed".to_string()
+                    },
+                );
+            }
+            for (owned_company_id, (count, share_pct)) in aggregates {
+                kept.push(IROwnership {
+                    name: format!("{}_{}", AGGREGATE_NAME, owned_company_id),
+                    owner: None,
+                    owned_company_id,
+                    share_pct,
+                    role: Some(AGGREGATE_NAME.to_string()),
+                    period_start: None,
+                    period_end: None,
+                    source_url: None,
+                    scraped_at: None,
+                    comment: Some(format!("Aggregated stake of {} natural person(s)", count)),
+                    provenance: Vec::new(),
+                    reviews: Vec::new(),
+                });
+            }
+            ir.ownerships = kept;
+        }
+    }
+}
+
+/// Redact event entities that name a person; entity IDs of outlets and
+/// companies may equal a person ID, so only the person reference counts
+fn redact_event_entities(
+    ir: &mut IRProgram,
+    policy: &IRRedactionPolicy,
+    report: &mut RedactionReport,
+) {
+    let action = policy.event_persons;
+    if action == RedactionAction::Keep {
+        return;
+    }
+
+    for event in &mut ir.events {
+        let (persons, others): (Vec<_>, Vec<_>) = event
+            .entities
+            .drain(..)
+            .partition(|entity| entity.person.is_some());
+        event.entities = others;
+        if persons.is_empty() {
+            continue;
+        }
+
+        for entity in &persons {
+            report.record(
+                "event entity",
+                &format!("{}.{}", event.name, entity.name),
+                action,
+                match action {
+                    RedactionAction::Pseudonymise => {
+                        format!("renamed to {}", pseudonym(&policy.salt, "person", &entity.id.to_string()))
+                    }
+                    RedactionAction::Aggregate => format!("merged into {}", AGGREGATE_NAME),
+                    _ => "removed".to_string(),
+                },
+            );
+        }
+
+        match action {
+            RedactionAction::Pseudonymise => {
+                event.entities.extend(persons.into_iter().map(|entity| IREventEntity {
+                    name: pseudonym(&policy.salt, "person", &entity.id.to_string()),
+                    ..entity
+                }));
+            }
+            RedactionAction::Aggregate => {
+                let sum = |stake: fn(&IREventEntity) -> Option<f64>| {
+                    persons.iter().filter_map(stake).reduce(|a, b| a + b)
+                };
+                event.entities.push(IREventEntity {
+                    name: AGGREGATE_NAME.to_string(),
+                    id: 0,
+                    person: None,
+                    role: AGGREGATE_NAME.to_string(),
+                    stake_before: sum(|entity| entity.stake_before),
+                    stake_after: sum(|entity| entity.stake_after),
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Redact the metadata fields listed as holding person names, following the person action
+fn redact_metadata(
+    ir: &mut IRProgram,
+    policy: &IRRedactionPolicy,
+    ids: &BTreeMap<u32, u32>,
+    report: &mut RedactionReport,
+) {
+    if policy.persons == RedactionAction::Keep || policy.person_metadata.is_empty() {
+        return;
+    }
+    let listed = |name: &str| policy.person_metadata.iter().any(|field| field == name);
+
+    // Pseudonymised values keep the field, dropped and aggregated ones lose it
+    let mut redact_fields = |owner: &str, fields: Vec<(String, IRExpression)>| {
+        let mut kept = Vec::new();
+        for (name, value) in fields {
+            if !listed(&name) {
+                kept.push((name, value));
+                continue;
+            }
+            let subject = format!("{}.{}", owner, name);
+            if policy.persons == RedactionAction::Pseudonymise {
+                report.record("metadata", &subject, policy.persons, "values hashed".to_string());
+                let value = pseudonymise_value(&policy.salt, ids, value);
+                kept.push((name, value));
+            } else {
+                report.record("metadata", &subject, policy.persons, "removed".to_string());
+            }
+        }
+        kept
+    };
+
+    for event in &mut ir.events {
+        let fields = event.metadata.drain(..).map(|m| (m.name, m.value)).collect();
+        event.metadata = redact_fields(&event.name, fields)
+            .into_iter()
+            .map(|(name, value)| IREventMetadata { name, value })
+            .collect();
+    }
+    for outlet in ir.families.iter_mut().flat_map(|family| &mut family.outlets) {
+        for block in &mut outlet.blocks {
+            if let IROutletBlock::Metadata(metadata) = block {
+                let fields = metadata.drain(..).map(|m| (m.name, m.value)).collect();
+                *metadata = redact_fields(&outlet.name, fields)
+                    .into_iter()
+                    .map(|(name, value)| IRMetadata { name, value })
+                    .collect();
+            }
+        }
+    }
+    for company in &mut ir.companies {
+        let fields = company.fields.drain(..).map(|f| (f.name, f.value)).collect();
+        company.fields = redact_fields(&company.name, fields)
+            .into_iter()
+            .map(|(name, value)| IRObjectField { name, value })
+            .collect();
+    }
+}
+
+/// Replace the names in a metadata value by pseudonyms and person IDs by pseudonymous IDs
+fn pseudonymise_value(salt: &str, ids: &BTreeMap<u32, u32>, value: IRExpression) -> IRExpression {
+    match value {
+        IRExpression::String(name) => IRExpression::String(pseudonym(salt, "name", &name)),
+        IRExpression::Number(n) if n.fract() == 0.0 && n >= 0.0 && n <= u32::MAX as f64 => {
+            match ids.get(&(n as u32)) {
+                Some(pseudonymous) => IRExpression::Number(*pseudonymous as f64),
+                None => IRExpression::Number(n),
+            }
+        }
+        IRExpression::Array(items) => IRExpression::Array(
+            items
+                .into_iter()
+                .map(|item| pseudonymise_value(salt, ids, item))
+                .collect(),
+        ),
+        other => other,
+    }
+}
@@ -0,0 +1,780 @@
+//! Point-in-time snapshot: reduces an IR program to the landscape at one date
+//!
+//! An outlet is part of the snapshot when an `active` lifecycle status covers
+//! the date. Its lifecycle keeps the statuses covering the date, and the
+//! OUTLET_REF overrides in effect are applied to its fields; an override with
+//! `@family` moves the outlet into that family. Synchronous links are kept when
+//! their period covers the date and every declared outlet they connect is in
+//! the snapshot. Diachronic links describe transitions rather than a state and
+//! are dropped. Market data keeps, per outlet and metric, the latest year up to
+//! the year of the date. Outlet-party links and ownership relations are kept
+//! when their period covers the date, events when they happened on or before
+//! it, so ownership can be evaluated on the snapshot as well.
+
+use crate::ir::nodes::*;
+use crate::utils::dates::{covers, first_day};
+use crate::utils::json;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::mem::discriminant;
+
+/// Latest year and value of each metric of an outlet, by metric name
+type LatestMetrics<'a> = BTreeMap<&'a str, (u32, &'a IRDataMetric)>;
+
+/// Return the state of `ir` at `date` (`YYYY`, `YYYY-MM` or `YYYY-MM-DD`)
+pub fn snapshot_at(ir: &IRProgram, date: &str) -> IRProgram {
+    let date = first_day(date);
+    let mut snapshot = IRProgram {
+        imports: ir.imports.clone(),
+        variables: ir.variables.clone(),
+        templates: ir.templates.clone(),
+        units: ir.units.clone(),
+        vocabularies: ir.vocabularies.clone(),
+        families: Vec::new(),
+        events: Vec::new(),
+        companies: ir.companies.clone(),
+        outlet_party_links: Vec::new(),
+        persons: ir.persons.clone(),
+        ownerships: Vec::new(),
+        identifier_links: ir.identifier_links.clone(),
+        catalogs: ir.catalogs.clone(),
+        redaction_policies: ir.redaction_policies.clone(),
+        relationship_types: ir.relationship_types.clone(),
+    };
+
+    let overrides: Vec<&IROutletOverride> = ir
+        .families
+        .iter()
+        .flat_map(|family| &family.overrides)
+        .filter(|o| covers(o.start_date.as_deref(), o.end_date.as_deref(), &date))
+        .collect();
+
+    // Active outlets, grouped by the family they belong to at the date
+    for family in &ir.families {
+        for outlet in &family.outlets {
+            let Some(mut outlet) = outlet_at(outlet, &date) else {
+                continue;
+            };
+            let id = outlet.id;
+            let mut family_name = family.name.as_str();
+            for o in overrides.iter().filter(|o| Some(o.outlet_id) == id) {
+                apply_override(&mut outlet, &o.blocks);
+                if let Some(name) = &o.family {
+                    family_name = name;
+                }
+            }
+            let index = family_index(&mut snapshot.families, family_name, ir);
+            snapshot.families[index].outlets.push(outlet);
+        }
+    }
+
+    let family_of: HashMap<u32, usize> = snapshot
+        .families
+        .iter()
+        .enumerate()
+        .flat_map(|(i, family)| family.outlets.iter().filter_map(move |o| Some((o.id?, i))))
+        .collect();
+    let declared: HashSet<u32> = ir
+        .families
+        .iter()
+        .flat_map(|family| &family.outlets)
+        .filter_map(|outlet| outlet.id)
+        .collect();
+    let in_snapshot = |id: u32| family_of.contains_key(&id) || !declared.contains(&id);
+
+    // Links live with the family of one of the outlets they connect
+    for relationship in ir.families.iter().flat_map(|family| &family.relationships) {
+        let IRRelationship::Synchronous(link) = relationship else {
+            continue;
+        };
+        if !covers(
+            link.period_start.as_deref(),
+            link.period_end.as_deref(),
+            &date,
+        ) || !in_snapshot(link.outlet_1.id)
+            || !in_snapshot(link.outlet_2.id)
+        {
+            continue;
+        }
+        let index = family_of
+            .get(&link.outlet_1.id)
+            .or_else(|| family_of.get(&link.outlet_2.id));
+        if let Some(&index) = index {
+            snapshot.families[index]
+                .relationships
+                .push(relationship.clone());
+        }
+    }
+
+    // Latest value of every metric up to the year of the date
+    let year: u32 = date
+        .get(..4)
+        .and_then(|y| y.parse().ok())
+        .unwrap_or(u32::MAX);
+    let mut latest: BTreeMap<u32, (&IRDataBlock, LatestMetrics<'_>)> = BTreeMap::new();
+    for block in ir.families.iter().flat_map(|family| &family.data_blocks) {
+        if !family_of.contains_key(&block.outlet_id) {
+            continue;
+        }
+        let (_, metrics) = latest
+            .entry(block.outlet_id)
+            .or_insert((block, BTreeMap::new()));
+        for data_year in block.years.iter().filter(|y| y.year <= year) {
+            for metric in &data_year.metrics {
+                let entry = metrics
+                    .entry(metric.name.as_str())
+                    .or_insert((data_year.year, metric));
+                if data_year.year >= entry.0 {
+                    *entry = (data_year.year, metric);
+                }
+            }
+        }
+    }
+    for (outlet_id, (block, metrics)) in latest {
+        if metrics.is_empty() {
+            continue;
+        }
+        let mut years: BTreeMap<u32, Vec<IRDataMetric>> = BTreeMap::new();
+        for (metric_year, metric) in metrics.into_values() {
+            years.entry(metric_year).or_default().push(metric.clone());
+        }
+        let kept: HashSet<String> = years
+            .iter()
+            .flat_map(|(y, metrics)| metrics.iter().map(move |m| format!("{}.{}", y, m.name)))
+            .collect();
+        snapshot.families[family_of[&outlet_id]]
+            .data_blocks
+            .push(IRDataBlock {
+                outlet_id,
+                aggregation: block.aggregation.clone(),
+                years: years
+                    .into_iter()
+                    .map(|(year, metrics)| IRDataYear {
+                        year,
+                        metrics,
+                        comment: None,
+                    })
+                    .collect(),
+                maps_to: block.maps_to.clone(),
+                provenance: block
+                    .provenance
+                    .iter()
+                    .filter(|p| p.target.as_ref().is_none_or(|t| kept.contains(t)))
+                    .cloned()
+                    .collect(),
+                reviews: block.reviews.clone(),
+            });
+    }
+
+    snapshot.events = ir
+        .events
+        .iter()
+        .filter(|event| event.date.as_deref().is_none_or(|d| first_day(d) <= date))
+        .cloned()
+        .collect();
+    snapshot.outlet_party_links = ir
+        .outlet_party_links
+        .iter()
+        .filter(|link| {
+            in_snapshot(link.outlet_id)
+                && covers(
+                    link.period_start.as_deref(),
+                    link.period_end.as_deref(),
+                    &date,
+                )
+        })
+        .cloned()
+        .collect();
+    snapshot.ownerships = ir
+        .ownerships
+        .iter()
+        .filter(|o| covers(o.period_start.as_deref(), o.period_end.as_deref(), &date))
+        .cloned()
+        .collect();
+
+    snapshot
+}
+
+/// The outlet as of the date, if it is active then
+fn outlet_at(outlet: &IROutlet, date: &str) -> Option<IROutlet> {
+    let mut outlet = outlet.clone();
+    let mut active = false;
+    for block in &mut outlet.blocks {
+        if let IROutletBlock::Lifecycle(statuses) = block {
+            statuses.retain(|status| {
+                covers(
+                    status.start_date.as_deref(),
+                    status.end_date.as_deref(),
+                    date,
+                )
+            });
+            active |= statuses
+                .iter()
+                .any(|status| status.status.eq_ignore_ascii_case("active"));
+        }
+    }
+    active.then_some(outlet)
+}
+
+/// Index of the snapshot family with the given name, added when missing
+fn family_index(families: &mut Vec<IRFamily>, name: &str, ir: &IRProgram) -> usize {
+    if let Some(index) = families.iter().position(|family| family.name == name) {
+        return index;
+    }
+    let source = ir.families.iter().find(|family| family.name == name);
+    families.push(IRFamily {
+        name: name.to_string(),
+        comment: source.and_then(|family| family.comment.clone()),
+        outlets: Vec::new(),
+        relationships: Vec::new(),
+        data_blocks: Vec::new(),
+        overrides: Vec::new(),
+        provenance: source
+            .map(|family| family.provenance.clone())
+            .unwrap_or_default(),
+        reviews: source
+            .map(|family| family.reviews.clone())
+            .unwrap_or_default(),
+    });
+    families.len() - 1
+}
+
+/// Let the fields of override blocks take precedence over the outlet's own
+fn apply_override(outlet: &mut IROutlet, blocks: &[IROutletBlock]) {
+    for block in blocks {
+        let index = match outlet
+            .blocks
+            .iter()
+            .position(|own| discriminant(own) == discriminant(block))
+        {
+            Some(index) => index,
+            None => {
+                outlet.blocks.push(match block {
+                    IROutletBlock::Identity(_) => IROutletBlock::Identity(Vec::new()),
+                    IROutletBlock::Lifecycle(_) => IROutletBlock::Lifecycle(Vec::new()),
+                    IROutletBlock::Characteristics(_) => IROutletBlock::Characteristics(Vec::new()),
+                    IROutletBlock::Metadata(_) => IROutletBlock::Metadata(Vec::new()),
+                });
+                outlet.blocks.len() - 1
+            }
+        };
+        match (&mut outlet.blocks[index], block) {
+            (IROutletBlock::Identity(own), IROutletBlock::Identity(fields)) => {
+                merge_fields(own, fields, |field| &field.name)
+            }
+            (IROutletBlock::Characteristics(own), IROutletBlock::Characteristics(fields)) => {
+                merge_fields(own, fields, |field| &field.name)
+            }
+            (IROutletBlock::Metadata(own), IROutletBlock::Metadata(fields)) => {
+                merge_fields(own, fields, |field| &field.name)
+            }
+            (IROutletBlock::Lifecycle(own), IROutletBlock::Lifecycle(statuses)) => {
+                *own = statuses.clone()
+            }
+            _ => {}
+        }
+    }
+}
+
+fn merge_fields<T: Clone>(own: &mut Vec<T>, fields: &[T], name: impl Fn(&T) -> &String) {
+    for field in fields {
+        match own
+            .iter_mut()
+            .find(|existing| name(existing) == name(field))
+        {
+            Some(existing) => *existing = field.clone(),
+            None => own.push(field.clone()),
+        }
+    }
+}
+
+/// Render a snapshot as MDSL
+pub fn format_mdsl(snapshot: &IRProgram, date: &str) -> String {
+    let mut out = format!("// Snapshot as of {}\n", first_day(date));
+
+    for family in &snapshot.families {
+        out.push_str(&format!("\nFAMILY {} {{\n", mdsl_string(&family.name)));
+        if let Some(comment) = &family.comment {
+            out.push_str(&format!("    @comment {};\n", mdsl_string(comment)));
+        }
+        let mut members = Vec::new();
+        for outlet in &family.outlets {
+            let mut member = String::new();
+            write_outlet(&mut member, outlet);
+            members.push(member);
+        }
+        for relationship in &family.relationships {
+            if let IRRelationship::Synchronous(link) = relationship {
+                let mut member = String::new();
+                write_synchronous_link(&mut member, link);
+                members.push(member);
+            }
+        }
+        out.push_str(&members.join("\n"));
+        out.push_str("}\n");
+        for block in &family.data_blocks {
+            out.push('\n');
+            write_data_block(&mut out, block);
+        }
+    }
+
+    for company in &snapshot.companies {
+        out.push_str(&format!("\nCOMPANY {} {{\n", mdsl_string(&company.name)));
+        out.push_str(&format!("    id = {};\n", company.id));
+        write_fields(&mut out, "    ", &company.fields);
+        out.push_str("}\n");
+    }
+    for person in &snapshot.persons {
+        out.push_str(&format!("\nPERSON {} {{\n", mdsl_string(&person.name)));
+        out.push_str(&format!("    id = {};\n", person.id));
+        write_fields(&mut out, "    ", &person.fields);
+        out.push_str("}\n");
+    }
+    for link in &snapshot.outlet_party_links {
+        out.push_str(&format!("\nOUTLET_PARTY_LINK {} {{\n", link.name));
+        out.push_str(&format!("    outlet = {};\n", link.outlet_id));
+        out.push_str(&format!("    party = {};\n", link.party_id));
+        out.push_str(&format!("    role = {};\n", mdsl_string(&link.role)));
+        write_period(&mut out, "    ", &link.period_start, &link.period_end);
+        if let Some(source) = &link.source {
+            out.push_str(&format!("    source = {};\n", mdsl_string(source)));
+        }
+        out.push_str("}\n");
+    }
+    for ownership in &snapshot.ownerships {
+        out.push_str(&format!("\nOWNERSHIP {} {{\n", ownership.name));
+        match ownership.owner {
+            Some(IROwner::Company(id)) => out.push_str(&format!("    owner_company = {};\n", id)),
+            Some(IROwner::Person(id)) => out.push_str(&format!("    owner_person = {};\n", id)),
+            None => {}
+        }
+        out.push_str(&format!(
+            "    owned_company = {};\n",
+            ownership.owned_company_id
+        ));
+        if let Some(share) = ownership.share_pct {
+            out.push_str(&format!("    share_pct = {};\n", share));
+        }
+        if let Some(role) = &ownership.role {
+            out.push_str(&format!("    role = {};\n", mdsl_string(role)));
+        }
+        write_period(
+            &mut out,
+            "    ",
+            &ownership.period_start,
+            &ownership.period_end,
+        );
+        out.push_str("}\n");
+    }
+    for event in &snapshot.events {
+        write_event(&mut out, event);
+    }
+
+    out
+}
+
+fn write_outlet(out: &mut String, outlet: &IROutlet) {
+    out.push_str(&format!("    OUTLET {} {{\n", mdsl_string(&outlet.name)));
+    for block in &outlet.blocks {
+        match block {
+            IROutletBlock::Identity(fields) => {
+                out.push_str("        identity {\n");
+                for field in fields {
+                    out.push_str(&format!(
+                        "            {} = {};\n",
+                        field.name,
+                        mdsl_value(&field.value)
+                    ));
+                }
+            }
+            IROutletBlock::Lifecycle(statuses) => {
+                out.push_str("        lifecycle {\n");
+                for status in statuses {
+                    let date = |date: &str| match date {
+                        "CURRENT" => date.to_string(),
+                        _ => mdsl_string(date),
+                    };
+                    out.push_str(&format!(
+                        "            status {} FROM {}",
+                        mdsl_string(&status.status),
+                        date(status.start_date.as_deref().unwrap_or("CURRENT"))
+                    ));
+                    if let Some(end) = &status.end_date {
+                        out.push_str(&format!(" TO {}", date(end)));
+                    }
+                    out.push_str(" {\n");
+                    for (name, value) in [
+                        ("precision_start", &status.precision_start),
+                        ("precision_end", &status.precision_end),
+                        ("comment", &status.comment),
+                    ] {
+                        if let Some(value) = value {
+                            out.push_str(&format!(
+                                "                {} = {};\n",
+                                name,
+                                mdsl_string(value)
+                            ));
+                        }
+                    }
+                    out.push_str("            };\n");
+                }
+            }
+            IROutletBlock::Characteristics(fields) => {
+                out.push_str("        characteristics {\n");
+                for field in fields {
+                    out.push_str(&format!(
+                        "            {} = {};\n",
+                        field.name,
+                        mdsl_value(&field.value)
+                    ));
+                }
+            }
+            IROutletBlock::Metadata(fields) => {
+                out.push_str("        metadata {\n");
+                for field in fields {
+                    out.push_str(&format!(
+                        "            {} = {};\n",
+                        field.name,
+                        mdsl_value(&field.value)
+                    ));
+                }
+            }
+        }
+        out.push_str("        };\n");
+    }
+    out.push_str("    };\n");
+}
+
+fn write_synchronous_link(out: &mut String, link: &IRSynchronousLink) {
+    out.push_str(&format!("    SYNCHRONOUS_LINK {} {{\n", link.name));
+    for (field, end) in [("outlet_1", &link.outlet_1), ("outlet_2", &link.outlet_2)] {
+        out.push_str(&format!(
+            "        {} = {{ id = {}; role = {}; }};\n",
+            field,
+            end.id,
+            mdsl_string(&end.role)
+        ));
+    }
+    out.push_str(&format!(
+        "        relationship_type = {};\n",
+        mdsl_string(&link.relationship_type)
+    ));
+    write_period(out, "        ", &link.period_start, &link.period_end);
+    if let Some(details) = &link.details {
+        out.push_str(&format!("        details = {};\n", mdsl_string(details)));
+    }
+    out.push_str("    };\n");
+}
+
+fn write_data_block(out: &mut String, block: &IRDataBlock) {
+    out.push_str(&format!("DATA FOR {} {{\n", block.outlet_id));
+    if !block.aggregation.is_empty() {
+        let fields: Vec<String> = block
+            .aggregation
+            .iter()
+            .map(|a| format!("{} = {};", a.name, mdsl_string(&a.value)))
+            .collect();
+        out.push_str(&format!("    aggregation = {{ {} }};\n", fields.join(" ")));
+    }
+    for year in &block.years {
+        out.push_str(&format!("    YEAR {} {{\n        metrics {{\n", year.year));
+        for metric in &year.metrics {
+            let mut fields = format!(
+                "value = {}; unit = {}; source = {};",
+                metric.value,
+                mdsl_string(&metric.unit),
+                mdsl_string(&metric.source)
+            );
+            if let Some(comment) = &metric.comment {
+                fields.push_str(&format!(" comment = {};", mdsl_string(comment)));
+            }
+            out.push_str(&format!(
+                "            {} = {{ {} }};\n",
+                metric.name, fields
+            ));
+        }
+        out.push_str("        };\n    };\n");
+    }
+    out.push_str("};\n");
+}
+
+fn write_event(out: &mut String, event: &IREvent) {
+    out.push_str(&format!("\nEVENT {} {{\n", event.name));
+    out.push_str(&format!("    type = {};\n", mdsl_string(&event.event_type)));
+    if let Some(date) = &event.date {
+        out.push_str(&format!("    date = {};\n", mdsl_string(date)));
+    }
+    if let Some(status) = &event.status {
+        out.push_str(&format!("    status = {};\n", mdsl_string(status)));
+    }
+    if !event.entities.is_empty() {
+        out.push_str("    entities = {\n");
+        for entity in &event.entities {
+            let mut fields = match entity.person {
+                Some(person) => format!("person = {};", person),
+                None => format!("id = {};", entity.id),
+            };
+            fields.push_str(&format!(" role = {};", mdsl_string(&entity.role)));
+            if let Some(stake) = entity.stake_before {
+                fields.push_str(&format!(" stake_before = {};", stake));
+            }
+            if let Some(stake) = entity.stake_after {
+                fields.push_str(&format!(" stake_after = {};", stake));
+            }
+            out.push_str(&format!("        {} = {{ {} }};\n", entity.name, fields));
+        }
+        out.push_str("    };\n");
+    }
+    for (keyword, fields) in [
+        (
+            "impact",
+            event
+                .impact
+                .iter()
+                .map(|f| (&f.name, &f.value))
+                .collect::<Vec<_>>(),
+        ),
+        (
+            "metadata",
+            event.metadata.iter().map(|f| (&f.name, &f.value)).collect(),
+        ),
+    ] {
+        if fields.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("    {} = {{\n", keyword));
+        for (name, value) in fields {
+            out.push_str(&format!("        {} = {};\n", name, mdsl_value(value)));
+        }
+        out.push_str("    };\n");
+    }
+    out.push_str("}\n");
+}
+
+fn write_fields(out: &mut String, indent: &str, fields: &[IRObjectField]) {
+    for field in fields {
+        out.push_str(&format!(
+            "{}{} = {};\n",
+            indent,
+            field.name,
+            mdsl_value(&field.value)
+        ));
+    }
+}
+
+fn write_period(out: &mut String, indent: &str, start: &Option<String>, end: &Option<String>) {
+    for (name, date) in [("period_start", start), ("period_end", end)] {
+        match date.as_deref() {
+            Some("CURRENT") => out.push_str(&format!("{}{} = CURRENT;\n", indent, name)),
+            Some(date) => out.push_str(&format!("{}{} = {};\n", indent, name, mdsl_string(date))),
+            None => {}
+        }
+    }
+}
+
+fn mdsl_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn mdsl_value(value: &IRExpression) -> String {
+    match value {
+        IRExpression::String(s) => mdsl_string(s),
+        IRExpression::Number(n) => n.to_string(),
+        IRExpression::Boolean(b) => b.to_string(),
+        IRExpression::Variable(name) => format!("${}", name),
+        IRExpression::Object(members) => {
+            let members: Vec<String> = members
+                .iter()
+                .map(|m| format!("{} = {};", m.name, mdsl_value(&m.value)))
+                .collect();
+            format!("{{ {} }}", members.join(" "))
+        }
+        IRExpression::Array(items) => {
+            let items: Vec<String> = items.iter().map(mdsl_value).collect();
+            format!("[{}]", items.join(", "))
+        }
+    }
+}
+
+/// Render a snapshot as JSON: families with their active outlets and latest
+/// market data, active links, outlet-party links and ownership relations
+pub fn format_json(snapshot: &IRProgram, date: &str) -> String {
+    let data: HashMap<u32, &IRDataBlock> = snapshot
+        .families
+        .iter()
+        .flat_map(|family| &family.data_blocks)
+        .map(|block| (block.outlet_id, block))
+        .collect();
+
+    let families: Vec<String> = snapshot
+        .families
+        .iter()
+        .map(|family| {
+            let outlets: Vec<String> = family
+                .outlets
+                .iter()
+                .map(|outlet| format!("\n      {}", outlet_json(outlet, &data)))
+                .collect();
+            format!(
+                "\n    {{ \"name\": {}, \"outlets\": [{}\n    ] }}",
+                json::quote(&family.name),
+                outlets.join(",")
+            )
+        })
+        .collect();
+
+    let links: Vec<String> = snapshot
+        .families
+        .iter()
+        .flat_map(|family| &family.relationships)
+        .filter_map(|relationship| match relationship {
+            IRRelationship::Synchronous(link) => Some(format!(
+                "\n    {{ \"name\": {}, \"type\": {}, \"outlet_1\": {{ \"id\": {}, \"role\": {} }}, \"outlet_2\": {{ \"id\": {}, \"role\": {} }}, \"period_start\": {}, \"period_end\": {} }}",
+                json::quote(&link.name),
+                json::quote(&link.relationship_type),
+                link.outlet_1.id,
+                json::quote(&link.outlet_1.role),
+                link.outlet_2.id,
+                json::quote(&link.outlet_2.role),
+                optional_json(link.period_start.as_deref()),
+                optional_json(link.period_end.as_deref())
+            )),
+            IRRelationship::Diachronic(_) => None,
+        })
+        .collect();
+
+    let parties: Vec<String> = snapshot
+        .outlet_party_links
+        .iter()
+        .map(|link| {
+            format!(
+                "\n    {{ \"name\": {}, \"outlet\": {}, \"party\": {}, \"role\": {} }}",
+                json::quote(&link.name),
+                link.outlet_id,
+                link.party_id,
+                json::quote(&link.role)
+            )
+        })
+        .collect();
+
+    let ownerships: Vec<String> = snapshot
+        .ownerships
+        .iter()
+        .map(|ownership| {
+            let (owner_kind, owner_id) = match ownership.owner {
+                Some(IROwner::Company(id)) => ("company", Some(id)),
+                Some(IROwner::Person(id)) => ("person", Some(id)),
+                None => ("unknown", None),
+            };
+            format!(
+                "\n    {{ \"name\": {}, \"owner\": {{ \"kind\": {}, \"id\": {} }}, \"owned_company\": {}, \"share_pct\": {} }}",
+                json::quote(&ownership.name),
+                json::quote(owner_kind),
+                owner_id.map_or("null".to_string(), |id| id.to_string()),
+                ownership.owned_company_id,
+                ownership.share_pct.map_or("null".to_string(), json::number)
+            )
+        })
+        .collect();
+
+    let list = |items: Vec<String>| {
+        if items.is_empty() {
+            "[]".to_string()
+        } else {
+            format!("[{}\n  ]", items.join(","))
+        }
+    };
+    format!(
+        "{{\n  \"as_of\": {},\n  \"families\": {},\n  \"links\": {},\n  \"outlet_parties\": {},\n  \"ownerships\": {}\n}}\n",
+        json::quote(&first_day(date)),
+        list(families),
+        list(links),
+        list(parties),
+        list(ownerships)
+    )
+}
+
+fn outlet_json(outlet: &IROutlet, data: &HashMap<u32, &IRDataBlock>) -> String {
+    let mut status = None;
+    let mut fields = Vec::new();
+    for block in &outlet.blocks {
+        let (prefix, block_fields): (&str, Vec<(&String, &IRExpression)>) = match block {
+            IROutletBlock::Lifecycle(statuses) => {
+                status = status.or(statuses.first());
+                continue;
+            }
+            IROutletBlock::Identity(f) => {
+                ("identity", f.iter().map(|f| (&f.name, &f.value)).collect())
+            }
+            IROutletBlock::Characteristics(f) => (
+                "characteristics",
+                f.iter().map(|f| (&f.name, &f.value)).collect(),
+            ),
+            IROutletBlock::Metadata(f) => {
+                ("metadata", f.iter().map(|f| (&f.name, &f.value)).collect())
+            }
+        };
+        for (name, value) in block_fields {
+            fields.push(format!(
+                "{}: {}",
+                json::quote(&format!("{}.{}", prefix, name)),
+                json_value(value)
+            ));
+        }
+    }
+
+    let market_data: Vec<String> = outlet
+        .id
+        .and_then(|id| data.get(&id))
+        .map(|block| {
+            block
+                .years
+                .iter()
+                .flat_map(|year| year.metrics.iter().map(move |metric| (year.year, metric)))
+                .map(|(year, metric)| {
+                    format!(
+                        "{{ \"metric\": {}, \"year\": {}, \"value\": {}, \"unit\": {}, \"source\": {} }}",
+                        json::quote(&metric.name),
+                        year,
+                        json::number(metric.value),
+                        json::quote(&metric.unit),
+                        json::quote(&metric.source)
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    format!(
+        "{{ \"id\": {}, \"name\": {}, \"status\": {}, \"since\": {}, \"fields\": {{ {} }}, \"market_data\": [{}] }}",
+        outlet.id.map_or("null".to_string(), |id| id.to_string()),
+        json::quote(&outlet.name),
+        optional_json(status.map(|s| s.status.as_str())),
+        optional_json(status.and_then(|s| s.start_date.as_deref())),
+        fields.join(", "),
+        market_data.join(", ")
+    )
+}
+
+fn optional_json(value: Option<&str>) -> String {
+    value.map_or("null".to_string(), json::quote)
+}
+
+fn json_value(value: &IRExpression) -> String {
+    match value {
+        IRExpression::String(s) => json::quote(s),
+        IRExpression::Number(n) => json::number(*n),
+        IRExpression::Boolean(b) => b.to_string(),
+        IRExpression::Variable(name) => json::quote(&format!("${}", name)),
+        IRExpression::Object(members) => {
+            let members: Vec<String> = members
+                .iter()
+                .map(|m| format!("{}: {}", json::quote(&m.name), json_value(&m.value)))
+                .collect();
+            format!("{{ {} }}", members.join(", "))
+        }
+        IRExpression::Array(items) => {
+            let items: Vec<String> = items.iter().map(json_value).collect();
+            format!("[{}]", items.join(", "))
+        }
+    }
+}
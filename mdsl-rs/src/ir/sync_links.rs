@@ -0,0 +1,93 @@
+//! Direction and symmetry of synchronous links
+//!
+//! A synchronous relationship type is either directed (umbrella: from the
+//! member outlet to its umbrella outlet) or symmetric (collaboration). Its semantics
+//! come from a RELATIONSHIP_TYPE declaration or the built-in defaults, see
+//! [`IRProgram::relationship_type`]. Normalising writes every link of a known
+//! type in one canonical order: a directed link from source to target, a
+//! symmetric one with the lower outlet ID first. Two links of one type
+//! between the same outlets with overlapping periods are duplicates, in
+//! whichever order they were written.
+
+use crate::ir::nodes::*;
+use crate::utils::dates::overlaps;
+use std::mem::swap;
+
+/// Two synchronous links stating the same relationship
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateLink {
+    /// Name of the link declared first
+    pub first: String,
+    /// Name of the link declared later
+    pub second: String,
+    /// Relationship type
+    pub relationship_type: String,
+    /// Whether the second link names the outlets the other way round
+    pub reversed: bool,
+}
+
+/// Return `ir` with every synchronous link of a known type in canonical order
+pub fn normalize(ir: &IRProgram) -> IRProgram {
+    let mut normalized = ir.clone();
+    for family in &mut normalized.families {
+        for relationship in &mut family.relationships {
+            if let IRRelationship::Synchronous(link) = relationship {
+                normalize_link(ir, link);
+            }
+        }
+    }
+    normalized
+}
+
+/// Swap the outlets of a link if its type puts them the other way round
+fn normalize_link(ir: &IRProgram, link: &mut IRSynchronousLink) {
+    let Some(semantics) = ir.relationship_type(&link.relationship_type) else {
+        return;
+    };
+    let (first, _) = semantics.endpoints(&link.outlet_1, &link.outlet_2);
+    if first != link.outlet_1.id {
+        swap(&mut link.outlet_1, &mut link.outlet_2);
+    }
+}
+
+/// Synchronous links that repeat an earlier link of the same type between
+/// the same outlets in an overlapping period
+pub fn duplicate_links(ir: &IRProgram) -> Vec<DuplicateLink> {
+    let links: Vec<&IRSynchronousLink> = ir
+        .families
+        .iter()
+        .flat_map(|family| &family.relationships)
+        .filter_map(|relationship| match relationship {
+            IRRelationship::Synchronous(link) => Some(link),
+            _ => None,
+        })
+        .collect();
+
+    let mut duplicates = Vec::new();
+    for (index, second) in links.iter().enumerate() {
+        let semantics = ir.relationship_type(&second.relationship_type);
+        let key = |link: &IRSynchronousLink| match &semantics {
+            Some(semantics) => semantics.endpoints(&link.outlet_1, &link.outlet_2),
+            None => (link.outlet_1.id, link.outlet_2.id),
+        };
+        for first in &links[..index] {
+            if first.relationship_type == second.relationship_type
+                && key(first) == key(second)
+                && overlaps(
+                    first.period_start.as_deref(),
+                    first.period_end.as_deref(),
+                    second.period_start.as_deref(),
+                    second.period_end.as_deref(),
+                )
+            {
+                duplicates.push(DuplicateLink {
+                    first: first.name.clone(),
+                    second: second.name.clone(),
+                    relationship_type: second.relationship_type.clone(),
+                    reversed: first.outlet_1.id != second.outlet_1.id,
+                });
+            }
+        }
+    }
+    duplicates
+}
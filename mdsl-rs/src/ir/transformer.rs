@@ -1,8 +1,18 @@
 //! AST to IR transformer
 
-use crate::error::Result;
+use crate::error::{Error, Result, SemanticError, SourcePosition};
 use crate::ir::nodes::*;
 use crate::parser::ast::*;
+use std::collections::HashMap;
+
+/// ID, fields, comment, provenance and reviews shared by company and person declarations
+type PartyFields = (
+    u32,
+    Vec<IRObjectField>,
+    Option<String>,
+    Vec<IRProvenance>,
+    Vec<IRReview>,
+);
 
 /// Transform AST to IR
 pub fn transform(ast: &Program) -> Result<IRProgram> {
@@ -11,16 +21,28 @@ pub fn transform(ast: &Program) -> Result<IRProgram> {
 }
 
 /// AST to IR transformer
-pub struct Transformer;
+pub struct Transformer {
+    /// Quality tiers of catalog sources, by source name
+    source_tiers: HashMap<String, String>,
+}
 
 impl Transformer {
     /// Create a new transformer
     pub fn new() -> Self {
-        Self
+        Self {
+            source_tiers: HashMap::new(),
+        }
     }
 
     /// Transform a program
     pub fn transform_program(&mut self, program: &Program) -> Result<IRProgram> {
+        // Catalogs may be declared after the statements citing them
+        for statement in &program.statements {
+            if let Statement::Catalog(catalog) = statement {
+                self.collect_source_tiers(catalog);
+            }
+        }
+
         let mut imports = Vec::new();
         let mut variables = Vec::new();
         let mut templates = Vec::new();
@@ -28,6 +50,14 @@ impl Transformer {
         let mut vocabularies = Vec::new();
         let mut families = Vec::new();
         let mut events = Vec::new();
+        let mut companies = Vec::new();
+        let mut outlet_party_links = Vec::new();
+        let mut persons = Vec::new();
+        let mut ownerships = Vec::new();
+        let mut identifier_links = Vec::new();
+        let mut catalogs = Vec::new();
+        let mut redaction_policies = Vec::new();
+        let mut relationship_types = Vec::new();
         let mut top_level_relationships = Vec::new();
         let mut top_level_data_blocks = Vec::new();
 
@@ -61,6 +91,30 @@ impl Transformer {
                 Statement::Data(data) => {
                     top_level_data_blocks.push(self.transform_data_declaration(data)?);
                 }
+                Statement::Company(company) => {
+                    companies.push(self.transform_company(company)?);
+                }
+                Statement::OutletPartyLink(link) => {
+                    outlet_party_links.push(self.transform_outlet_party_link(link)?);
+                }
+                Statement::Person(person) => {
+                    persons.push(self.transform_person(person)?);
+                }
+                Statement::Ownership(ownership) => {
+                    ownerships.push(self.transform_ownership(ownership)?);
+                }
+                Statement::IdentifierLink(link) => {
+                    identifier_links.push(self.transform_identifier_link(link)?);
+                }
+                Statement::Catalog(catalog) => {
+                    catalogs.push(self.transform_catalog(catalog)?);
+                }
+                Statement::Redaction(policy) => {
+                    redaction_policies.push(self.transform_redaction(policy)?);
+                }
+                Statement::RelationshipType(declaration) => {
+                    relationship_types.push(self.transform_relationship_type(declaration)?);
+                }
                 _ => {
                     // Skip other statements for now
                 }
@@ -79,7 +133,10 @@ impl Transformer {
                     outlets: Vec::new(),
                     relationships: top_level_relationships,
                     data_blocks: Vec::new(),
+                    overrides: Vec::new(),
                     comment: Some("Auto-generated family for top-level relationships".to_string()),
+                    provenance: Vec::new(),
+                    reviews: Vec::new(),
                 });
             }
         }
@@ -106,7 +163,10 @@ impl Transformer {
                     outlets: Vec::new(),
                     relationships: Vec::new(),
                     data_blocks: vec![data_block],
+                    overrides: Vec::new(),
                     comment: Some("Auto-generated family for top-level data blocks".to_string()),
+                    provenance: Vec::new(),
+                    reviews: Vec::new(),
                 });
             }
         }
@@ -119,6 +179,14 @@ impl Transformer {
             vocabularies,
             families,
             events,
+            companies,
+            outlet_party_links,
+            persons,
+            ownerships,
+            identifier_links,
+            catalogs,
+            redaction_policies,
+            relationship_types,
         })
     }
 
@@ -246,6 +314,9 @@ impl Transformer {
         let mut outlets = Vec::new();
         let mut relationships = Vec::new();
         let mut data_blocks = Vec::new();
+        let mut overrides = Vec::new();
+        let mut provenance = Vec::new();
+        let mut reviews = Vec::new();
 
         // Extract comment from family members
         let mut comment = None;
@@ -267,6 +338,22 @@ impl Transformer {
                 FamilyMember::Data(data) => {
                     data_blocks.push(self.transform_data_declaration(data)?);
                 }
+                FamilyMember::OutletReference(reference) => {
+                    for declaration in &reference.overrides {
+                        self.transform_override(
+                            reference.id as u32,
+                            declaration,
+                            (None, None, None),
+                            &mut overrides,
+                        )?;
+                    }
+                }
+                FamilyMember::Provenance(clause) => {
+                    provenance.push(self.transform_provenance(clause));
+                }
+                FamilyMember::Review(clause) => {
+                    reviews.push(self.transform_review(clause));
+                }
                 _ => {
                     // Skip other members for now
                 }
@@ -279,12 +366,63 @@ impl Transformer {
             outlets,
             relationships,
             data_blocks,
+            overrides,
+            provenance,
+            reviews,
         })
     }
 
+    /// Transform an override and its nested periods, which inherit the
+    /// period bounds and family they do not set themselves
+    fn transform_override(
+        &mut self,
+        outlet_id: u32,
+        declaration: &OverrideDeclaration,
+        inherited: (Option<String>, Option<String>, Option<String>),
+        overrides: &mut Vec<IROutletOverride>,
+    ) -> Result<()> {
+        let date = |date: &DateExpression| match date {
+            DateExpression::Literal(date) => date.clone(),
+            DateExpression::Current => "CURRENT".to_string(),
+        };
+        let start_date = declaration.from.as_ref().map(date).or(inherited.0);
+        let end_date = declaration.to.as_ref().map(date).or(inherited.1);
+        let family = declaration.family.clone().or(inherited.2);
+
+        if !declaration.blocks.is_empty() || family.is_some() {
+            // Outlet blocks transform like those of a declared outlet
+            let outlet = self.transform_outlet(&OutletDeclaration {
+                name: String::new(),
+                inheritance: None,
+                blocks: declaration.blocks.clone(),
+                position: declaration.position,
+            })?;
+            overrides.push(IROutletOverride {
+                outlet_id,
+                start_date: start_date.clone(),
+                end_date: end_date.clone(),
+                family: family.clone(),
+                blocks: outlet.blocks,
+            });
+        }
+        for period in &declaration.periods {
+            self.transform_override(
+                outlet_id,
+                period,
+                (start_date.clone(), end_date.clone(), family.clone()),
+                overrides,
+            )?;
+        }
+        Ok(())
+    }
+
     /// Transform an outlet declaration
     fn transform_outlet(&mut self, outlet: &OutletDeclaration) -> Result<IROutlet> {
         let mut blocks = Vec::new();
+        let mut provenance = Vec::new();
+        let mut reviews = Vec::new();
+        let mut legacy_verified = None;
+        let mut legacy_steward = None;
 
         // Extract ID and inheritance information
         let mut id = None;
@@ -394,6 +532,15 @@ impl Transformer {
                     let mut metadata = Vec::new();
                     for field in &meta.fields {
                         if let MetadataField::Assignment { name, value, .. } = field {
+                            match (name.as_str(), value) {
+                                ("verified", Expression::String(date)) => {
+                                    legacy_verified = Some(date.clone())
+                                }
+                                ("steward", Expression::String(steward)) => {
+                                    legacy_steward = Some(steward.clone())
+                                }
+                                _ => {}
+                            }
                             metadata.push(IRMetadata {
                                 name: name.clone(),
                                 value: self.transform_expression(value)?,
@@ -402,18 +549,39 @@ impl Transformer {
                     }
                     blocks.push(IROutletBlock::Metadata(metadata));
                 }
+                OutletBlock::Provenance(clause) => {
+                    provenance.push(self.transform_provenance(clause));
+                }
+                OutletBlock::Review(clause) => {
+                    reviews.push(self.transform_review(clause));
+                }
                 _ => {
                     // Skip other blocks for now
                 }
             }
         }
 
+        // `metadata { verified = ... }` predates PROVENANCE clauses: it marks the
+        // outlet's own records as verified, or becomes a record of its steward
+        if let Some(date) = legacy_verified {
+            if !provenance.iter().any(|record| record.target.is_none()) {
+                let source = legacy_steward.as_deref().unwrap_or("metadata");
+                provenance.push(self.provenance_record(None, source));
+            }
+            for record in provenance.iter_mut().filter(|record| record.target.is_none()) {
+                record.verification.get_or_insert_with(|| "verified".to_string());
+                record.verified_at.get_or_insert_with(|| date.clone());
+            }
+        }
+
         Ok(IROutlet {
             name: outlet.name.clone(),
             id,
             template_ref,
             base_ref,
             blocks,
+            provenance,
+            reviews,
         })
     }
 
@@ -422,6 +590,8 @@ impl Transformer {
         let mut aggregation = Vec::new();
         let mut years = Vec::new();
         let mut maps_to = None;
+        let mut provenance = Vec::new();
+        let mut reviews = Vec::new();
 
         for block in &data.blocks {
             match block {
@@ -477,6 +647,13 @@ impl Transformer {
                                         }
                                     }
 
+                                    if !source.is_empty() {
+                                        provenance.push(self.provenance_record(
+                                            Some(format!("{}.{}", year.year, metric.name)),
+                                            &source,
+                                        ));
+                                    }
+
                                     metrics.push(IRDataMetric {
                                         name: metric.name.clone(),
                                         value,
@@ -489,6 +666,23 @@ impl Transformer {
                             YearBlock::CommentAssignment { value, .. } => {
                                 year_comment = Some(value.clone());
                             }
+                            YearBlock::Provenance(clause) => {
+                                // Targets inside a year block are relative to that year
+                                let mut record = self.transform_provenance(clause);
+                                record.target = Some(match record.target {
+                                    Some(metric) => format!("{}.{}", year.year, metric),
+                                    None => year.year.to_string(),
+                                });
+                                provenance.push(record);
+                            }
+                            YearBlock::Review(clause) => {
+                                let mut review = self.transform_review(clause);
+                                review.target = Some(match review.target {
+                                    Some(metric) => format!("{}.{}", year.year, metric),
+                                    None => year.year.to_string(),
+                                });
+                                reviews.push(review);
+                            }
                             _ => {}
                         }
                     }
@@ -499,6 +693,12 @@ impl Transformer {
                         comment: year_comment,
                     });
                 }
+                DataBlock::Provenance(clause) => {
+                    provenance.push(self.transform_provenance(clause));
+                }
+                DataBlock::Review(clause) => {
+                    reviews.push(self.transform_review(clause));
+                }
                 _ => {}
             }
         }
@@ -508,6 +708,8 @@ impl Transformer {
             aggregation,
             years,
             maps_to,
+            provenance,
+            reviews,
         })
     }
 
@@ -522,6 +724,8 @@ impl Transformer {
                 let mut relationship_type = String::new();
                 let mut comment = None;
                 let mut maps_to = None;
+                let mut provenance = Vec::new();
+                let mut reviews = Vec::new();
 
                 for field in &diachronic.fields {
                     match field {
@@ -532,7 +736,7 @@ impl Transformer {
                             successor = *value as u32;
                         }
                         DiachronicField::EventDate { value, .. } => {
-                            event_start_date = Some(match &value.from {
+                            event_start_date = value.from.as_ref().map(|from| match from {
                                 DateExpression::Literal(date) => date.clone(),
                                 DateExpression::Current => "CURRENT".to_string(),
                             });
@@ -551,6 +755,12 @@ impl Transformer {
                                 comment = annotation.value.clone();
                             }
                         }
+                        DiachronicField::Provenance(clause) => {
+                            provenance.push(self.transform_provenance(clause));
+                        }
+                        DiachronicField::Review(clause) => {
+                            reviews.push(self.transform_review(clause));
+                        }
                         _ => {}
                     }
                 }
@@ -564,6 +774,8 @@ impl Transformer {
                     relationship_type,
                     comment,
                     maps_to,
+                    provenance,
+                    reviews,
                 }))
             }
             RelationshipDeclaration::Synchronous(sync) => {
@@ -576,6 +788,8 @@ impl Transformer {
                 let mut period_end = None;
                 let mut details = None;
                 let mut maps_to = None;
+                let mut provenance = Vec::new();
+                let mut reviews = Vec::new();
 
                 for field in &sync.fields {
                     match field {
@@ -591,7 +805,7 @@ impl Transformer {
                             relationship_type = value.clone();
                         }
                         SynchronousField::Period { value, .. } => {
-                            period_start = Some(match &value.from {
+                            period_start = value.from.as_ref().map(|from| match from {
                                 DateExpression::Literal(date) => date.clone(),
                                 DateExpression::Current => "CURRENT".to_string(),
                             });
@@ -608,6 +822,12 @@ impl Transformer {
                                 maps_to = annotation.value.clone();
                             }
                         }
+                        SynchronousField::Provenance(clause) => {
+                            provenance.push(self.transform_provenance(clause));
+                        }
+                        SynchronousField::Review(clause) => {
+                            reviews.push(self.transform_review(clause));
+                        }
                         _ => {}
                     }
                 }
@@ -627,6 +847,8 @@ impl Transformer {
                     period_end,
                     details,
                     maps_to,
+                    provenance,
+                    reviews,
                 }))
             }
         }
@@ -667,6 +889,10 @@ impl Transformer {
         let mut impact = Vec::new();
         let mut metadata = Vec::new();
         let mut status = None;
+        let mut provenance = Vec::new();
+        let mut reviews = Vec::new();
+        let mut legacy_source = None;
+        let mut legacy_confidence = None;
 
         for field in &event.fields {
             match field {
@@ -684,6 +910,7 @@ impl Transformer {
                         let mut ir_entity = IREventEntity {
                             name: entity.name.clone(),
                             id: 0,
+                            person: None,
                             role: String::new(),
                             stake_before: None,
                             stake_after: None,
@@ -694,6 +921,10 @@ impl Transformer {
                                 EntityRole::Id { value, .. } => {
                                     ir_entity.id = *value as u32;
                                 }
+                                EntityRole::Person { value, .. } => {
+                                    ir_entity.id = *value as u32;
+                                    ir_entity.person = Some(*value as u32);
+                                }
                                 EntityRole::Role { value, .. } => {
                                     ir_entity.role = value.clone();
                                 }
@@ -730,12 +961,35 @@ impl Transformer {
                 EventField::Status { value, .. } => {
                     status = Some(value.clone());
                 }
+                EventField::Provenance(clause) => {
+                    provenance.push(self.transform_provenance(clause));
+                }
+                EventField::Review(clause) => {
+                    reviews.push(self.transform_review(clause));
+                }
+                EventField::Annotation { name, value, .. } => match name.as_str() {
+                    "source" => legacy_source = value.clone(),
+                    "confidence" => legacy_confidence = value.clone(),
+                    _ => {}
+                },
                 _ => {
                     // Skip other field types for now
                 }
             }
         }
 
+        // Free-form @source/@confidence annotations predate PROVENANCE clauses
+        if let Some(source) = legacy_source {
+            let mut record = self.provenance_record(None, &source);
+            if let Some(confidence) = legacy_confidence {
+                match confidence.parse::<f64>() {
+                    Ok(value) => record.confidence = Some(value),
+                    Err(_) => record.note = Some(format!("confidence: {}", confidence)),
+                }
+            }
+            provenance.push(record);
+        }
+
         Ok(IREvent {
             name: event.name.clone(),
             event_type,
@@ -744,6 +998,484 @@ impl Transformer {
             impact,
             metadata,
             status,
+            provenance,
+            reviews,
+        })
+    }
+
+    /// Transform a company declaration
+    fn transform_company(&mut self, company: &CompanyDeclaration) -> Result<IRCompany> {
+        let (id, fields, comment, provenance, reviews) = self.transform_company_fields(&company.fields)?;
+
+        Ok(IRCompany {
+            name: company.name.clone(),
+            id,
+            fields,
+            comment,
+            provenance,
+            reviews,
+        })
+    }
+
+    /// Transform a person declaration
+    fn transform_person(&mut self, person: &PersonDeclaration) -> Result<IRPerson> {
+        let (id, fields, comment, provenance, reviews) = self.transform_company_fields(&person.fields)?;
+
+        Ok(IRPerson {
+            name: person.name.clone(),
+            id,
+            fields,
+            comment,
+            provenance,
+            reviews,
+        })
+    }
+
+    /// Split company/person fields into ID, remaining fields, @comment, provenance and reviews
+    fn transform_company_fields(
+        &mut self,
+        company_fields: &[CompanyField],
+    ) -> Result<PartyFields> {
+        let mut id = 0;
+        let mut fields = Vec::new();
+        let mut comment = None;
+        let mut provenance = Vec::new();
+        let mut reviews = Vec::new();
+
+        for field in company_fields {
+            match field {
+                CompanyField::Assignment { name, value, .. } => {
+                    if name == "id" {
+                        if let Expression::Number(n) = value {
+                            id = *n as u32;
+                        }
+                    } else {
+                        fields.push(IRObjectField {
+                            name: name.clone(),
+                            value: self.transform_expression(value)?,
+                        });
+                    }
+                }
+                CompanyField::Annotation(annotation) => {
+                    if annotation.name == "comment" {
+                        comment = annotation.value.clone();
+                    }
+                }
+                CompanyField::Provenance(clause) => {
+                    provenance.push(self.transform_provenance(clause));
+                }
+                CompanyField::Review(clause) => {
+                    reviews.push(self.transform_review(clause));
+                }
+                CompanyField::Comment(_) => {}
+            }
+        }
+
+        Ok((id, fields, comment, provenance, reviews))
+    }
+
+    /// Transform an ownership relation
+    fn transform_ownership(&mut self, ownership: &OwnershipDeclaration) -> Result<IROwnership> {
+        let mut ir_ownership = IROwnership {
+            name: ownership.name.clone(),
+            owner: None,
+            owned_company_id: 0,
+            share_pct: None,
+            role: None,
+            period_start: None,
+            period_end: None,
+            source_url: None,
+            scraped_at: None,
+            comment: None,
+            provenance: Vec::new(),
+            reviews: Vec::new(),
+        };
+
+        for field in &ownership.fields {
+            match field {
+                OwnershipField::OwnerCompany { value, .. } => {
+                    ir_ownership.owner = Some(IROwner::Company(*value as u32));
+                }
+                OwnershipField::OwnerPerson { value, .. } => {
+                    ir_ownership.owner = Some(IROwner::Person(*value as u32));
+                }
+                OwnershipField::OwnedCompany { value, .. } => {
+                    ir_ownership.owned_company_id = *value as u32;
+                }
+                OwnershipField::SharePct { value, .. } => {
+                    ir_ownership.share_pct = Some(*value);
+                }
+                OwnershipField::Role { value, .. } => {
+                    ir_ownership.role = Some(value.clone());
+                }
+                OwnershipField::PeriodStart { value, .. } => {
+                    ir_ownership.period_start = Some(match value {
+                        DateExpression::Literal(date) => date.clone(),
+                        DateExpression::Current => "CURRENT".to_string(),
+                    });
+                }
+                OwnershipField::PeriodEnd { value, .. } => {
+                    ir_ownership.period_end = Some(match value {
+                        DateExpression::Literal(date) => date.clone(),
+                        DateExpression::Current => "CURRENT".to_string(),
+                    });
+                }
+                OwnershipField::SourceUrl { value, .. } => {
+                    ir_ownership.source_url = Some(value.clone());
+                }
+                OwnershipField::ScrapedAt { value, .. } => {
+                    ir_ownership.scraped_at = Some(value.clone());
+                }
+                OwnershipField::Annotation(annotation) => {
+                    if annotation.name == "comment" {
+                        ir_ownership.comment = annotation.value.clone();
+                    }
+                }
+                OwnershipField::Provenance(clause) => {
+                    ir_ownership.provenance.push(self.transform_provenance(clause));
+                }
+                OwnershipField::Review(clause) => {
+                    ir_ownership.reviews.push(self.transform_review(clause));
+                }
+                OwnershipField::Comment(_) => {}
+            }
+        }
+
+        Ok(ir_ownership)
+    }
+
+    /// Transform an identifier link
+    fn transform_identifier_link(&mut self, link: &IdentifierLink) -> Result<IRIdentifierLink> {
+        let mut ir_link = IRIdentifierLink {
+            name: link.name.clone(),
+            company_id: 0,
+            scheme: String::new(),
+            value: String::new(),
+            status: None,
+            score: None,
+            issued_by: None,
+            comment: None,
+            provenance: Vec::new(),
+            reviews: Vec::new(),
+        };
+
+        for field in &link.fields {
+            match field {
+                IdentifierLinkField::Company { value, .. } => {
+                    ir_link.company_id = *value as u32;
+                }
+                IdentifierLinkField::Scheme { value, .. } => {
+                    ir_link.scheme = value.clone();
+                }
+                IdentifierLinkField::Value { value, .. } => {
+                    ir_link.value = value.clone();
+                }
+                IdentifierLinkField::Status { value, .. } => {
+                    ir_link.status = Some(value.clone());
+                }
+                IdentifierLinkField::Score { value, .. } => {
+                    ir_link.score = Some(*value);
+                }
+                IdentifierLinkField::IssuedBy { value, .. } => {
+                    ir_link.issued_by = Some(value.clone());
+                }
+                IdentifierLinkField::Annotation(annotation) => {
+                    if annotation.name == "comment" {
+                        ir_link.comment = annotation.value.clone();
+                    }
+                }
+                IdentifierLinkField::Provenance(clause) => {
+                    ir_link.provenance.push(self.transform_provenance(clause));
+                }
+                IdentifierLinkField::Review(clause) => {
+                    ir_link.reviews.push(self.transform_review(clause));
+                }
+                IdentifierLinkField::Comment(_) => {}
+            }
+        }
+
+        Ok(ir_link)
+    }
+
+    /// Transform an outlet-party link
+    fn transform_outlet_party_link(&mut self, link: &OutletPartyLink) -> Result<IROutletPartyLink> {
+        let mut ir_link = IROutletPartyLink {
+            name: link.name.clone(),
+            outlet_id: 0,
+            party_id: 0,
+            role: String::new(),
+            period_start: None,
+            period_end: None,
+            source: None,
+            comment: None,
+            provenance: Vec::new(),
+            reviews: Vec::new(),
+        };
+
+        for field in &link.fields {
+            match field {
+                OutletPartyField::Outlet { value, .. } => {
+                    ir_link.outlet_id = *value as u32;
+                }
+                OutletPartyField::Party { value, .. } => {
+                    ir_link.party_id = *value as u32;
+                }
+                OutletPartyField::Role { value, .. } => {
+                    ir_link.role = value.clone();
+                }
+                OutletPartyField::PeriodStart { value, .. } => {
+                    ir_link.period_start = Some(match value {
+                        DateExpression::Literal(date) => date.clone(),
+                        DateExpression::Current => "CURRENT".to_string(),
+                    });
+                }
+                OutletPartyField::PeriodEnd { value, .. } => {
+                    ir_link.period_end = Some(match value {
+                        DateExpression::Literal(date) => date.clone(),
+                        DateExpression::Current => "CURRENT".to_string(),
+                    });
+                }
+                OutletPartyField::Source { value, .. } => {
+                    ir_link.source = Some(value.clone());
+                }
+                OutletPartyField::Annotation(annotation) => {
+                    if annotation.name == "comment" {
+                        ir_link.comment = annotation.value.clone();
+                    }
+                }
+                OutletPartyField::Provenance(clause) => {
+                    ir_link.provenance.push(self.transform_provenance(clause));
+                }
+                OutletPartyField::Review(clause) => {
+                    ir_link.reviews.push(self.transform_review(clause));
+                }
+                OutletPartyField::Comment(_) => {}
+            }
+        }
+
+        Ok(ir_link)
+    }
+
+    /// Remember the quality tier declared for each catalog source
+    fn collect_source_tiers(&mut self, catalog: &CatalogDeclaration) {
+        for source in &catalog.sources {
+            for field in &source.fields {
+                if let SourceField::Assignment { name, value, .. } = field {
+                    if name == "quality_tier" {
+                        let tier = match value {
+                            Expression::String(s) => s.clone(),
+                            Expression::Number(n) => n.to_string(),
+                            _ => continue,
+                        };
+                        self.source_tiers.entry(source.name.clone()).or_insert(tier);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Transform a redaction policy; event persons follow the person action unless set
+    fn transform_redaction(&mut self, policy: &RedactionPolicy) -> Result<IRRedactionPolicy> {
+        let action = |value: &str, position: SourcePosition| {
+            RedactionAction::parse(value).ok_or_else(|| {
+                Error::Semantic(SemanticError::TypeMismatch {
+                    expected: format!("redaction action ({})", RedactionAction::NAMES.join(", ")),
+                    found: value.to_string(),
+                    position,
+                })
+            })
+        };
+
+        let mut persons = RedactionAction::Keep;
+        let mut event_persons = None;
+        let mut person_metadata = Vec::new();
+        let mut salt = String::new();
+
+        for field in &policy.fields {
+            match field {
+                RedactionField::Persons { value, position } => persons = action(value, *position)?,
+                RedactionField::EventPersons { value, position } => {
+                    event_persons = Some(action(value, *position)?)
+                }
+                RedactionField::PersonMetadata { value, .. } => {
+                    person_metadata.extend(value.iter().cloned())
+                }
+                RedactionField::Salt { value, .. } => salt = value.clone(),
+                RedactionField::Comment(_) => {}
+            }
+        }
+
+        Ok(IRRedactionPolicy {
+            name: policy.name.clone(),
+            persons,
+            event_persons: event_persons.unwrap_or(persons),
+            person_metadata,
+            salt,
         })
     }
+
+    /// Transform a relationship type declaration; types are directed unless declared otherwise
+    fn transform_relationship_type(
+        &mut self,
+        declaration: &RelationshipTypeDeclaration,
+    ) -> Result<IRRelationshipType> {
+        let mut direction = LinkDirection::Directed;
+        let mut role_pairs = Vec::new();
+
+        for field in &declaration.fields {
+            match field {
+                RelationshipTypeField::Direction { value, position } => {
+                    direction = LinkDirection::parse(value).ok_or_else(|| {
+                        Error::Semantic(SemanticError::TypeMismatch {
+                            expected: format!("direction ({})", LinkDirection::NAMES.join(", ")),
+                            found: value.clone(),
+                            position: *position,
+                        })
+                    })?;
+                }
+                RelationshipTypeField::Roles { value, .. } => {
+                    role_pairs.extend(value.iter().cloned())
+                }
+                RelationshipTypeField::Comment(_) => {}
+            }
+        }
+
+        Ok(IRRelationshipType {
+            name: declaration.name.clone(),
+            direction,
+            role_pairs,
+        })
+    }
+
+    /// Transform a catalog declaration
+    fn transform_catalog(&mut self, catalog: &CatalogDeclaration) -> Result<IRCatalog> {
+        let mut sources = Vec::new();
+
+        for source in &catalog.sources {
+            let mut fields = Vec::new();
+            let mut maps_to = None;
+
+            for field in &source.fields {
+                match field {
+                    SourceField::Assignment { name, value, .. } => {
+                        fields.push(IRObjectField {
+                            name: name.clone(),
+                            value: self.transform_expression(value)?,
+                        });
+                    }
+                    SourceField::NestedAssignment {
+                        name,
+                        fields: nested,
+                        ..
+                    } => {
+                        for nested_field in nested {
+                            if let NestedSourceField::Assignment {
+                                name: nested_name,
+                                value,
+                                ..
+                            } = nested_field
+                            {
+                                fields.push(IRObjectField {
+                                    name: format!("{}.{}", name, nested_name),
+                                    value: self.transform_expression(value)?,
+                                });
+                            }
+                        }
+                    }
+                    SourceField::Annotation(annotation) => {
+                        if annotation.name == "maps_to_table" || annotation.name == "maps_to" {
+                            maps_to = annotation.value.clone();
+                        }
+                    }
+                    SourceField::Comment(_) => {}
+                }
+            }
+
+            let anmi_type_code = fields.iter().find_map(|field| match &field.value {
+                IRExpression::Number(n) if field.name == "anmi_source_id_components.type_code" => {
+                    IRSource::type_code(*n)
+                }
+                _ => None,
+            });
+
+            sources.push(IRSource {
+                name: source.name.clone(),
+                catalog: catalog.name.clone(),
+                fields,
+                quality_tier: self.source_tiers.get(&source.name).cloned(),
+                anmi_type_code,
+                maps_to,
+            });
+        }
+
+        Ok(IRCatalog {
+            name: catalog.name.clone(),
+            sources,
+        })
+    }
+
+    /// Transform a review clause
+    fn transform_review(&self, clause: &ReviewClause) -> IRReview {
+        let mut review = IRReview {
+            target: clause.target.clone(),
+            status: String::new(),
+            reviewer: None,
+            reviewed_at: None,
+            note: None,
+        };
+
+        for field in &clause.fields {
+            match field {
+                ReviewField::Status { value, .. } => review.status = value.clone(),
+                ReviewField::Reviewer { value, .. } => review.reviewer = Some(value.clone()),
+                ReviewField::ReviewedAt { value, .. } => review.reviewed_at = Some(value.clone()),
+                ReviewField::Note { value, .. } => review.note = Some(value.clone()),
+                ReviewField::Comment(_) => {}
+            }
+        }
+
+        review
+    }
+
+    /// Create a provenance record for a source, resolving its quality tier
+    fn provenance_record(&self, target: Option<String>, source: &str) -> IRProvenance {
+        IRProvenance {
+            target,
+            source: source.to_string(),
+            quality_tier: self.source_tiers.get(source).cloned(),
+            confidence: None,
+            verification: None,
+            verified_at: None,
+            note: None,
+        }
+    }
+
+    /// Transform a provenance clause
+    fn transform_provenance(&self, clause: &ProvenanceClause) -> IRProvenance {
+        let mut record = self.provenance_record(clause.target.clone(), "");
+
+        for field in &clause.fields {
+            match field {
+                ProvenanceField::Source { value, .. } => {
+                    record.source = value.clone();
+                    record.quality_tier = self.source_tiers.get(value).cloned();
+                }
+                ProvenanceField::Confidence { value, .. } => {
+                    record.confidence = Some(*value);
+                }
+                ProvenanceField::Verification { value, .. } => {
+                    record.verification = Some(value.clone());
+                }
+                ProvenanceField::VerifiedAt { value, .. } => {
+                    record.verified_at = Some(value.clone());
+                }
+                ProvenanceField::Note { value, .. } => {
+                    record.note = Some(value.clone());
+                }
+                ProvenanceField::Comment(_) => {}
+            }
+        }
+
+        record
+    }
 }
@@ -252,6 +252,30 @@ pub enum Keyword {
     Catalog,
     /// source or SOURCE
     Source,
+
+    // Companies and outlet-party links
+    /// company or COMPANY
+    Company,
+    /// outlet_party_link or OUTLET_PARTY_LINK
+    OutletPartyLink,
+
+    // Persons and ownership relations
+    /// person or PERSON
+    Person,
+    /// ownership or OWNERSHIP
+    Ownership,
+    /// identifier_link or IDENTIFIER_LINK
+    IdentifierLink,
+
+    // Provenance
+    /// provenance or PROVENANCE
+    Provenance,
+    /// review or REVIEW
+    Review,
+
+    // Privacy
+    /// redaction or REDACTION
+    Redaction,
 }
 
 /// Literal values
@@ -364,6 +388,14 @@ impl fmt::Display for Keyword {
             Keyword::Until => "until",
             Keyword::Catalog => "catalog",
             Keyword::Source => "source",
+            Keyword::Company => "company",
+            Keyword::OutletPartyLink => "outlet_party_link",
+            Keyword::Person => "person",
+            Keyword::Ownership => "ownership",
+            Keyword::IdentifierLink => "identifier_link",
+            Keyword::Provenance => "provenance",
+            Keyword::Review => "review",
+            Keyword::Redaction => "redaction",
         };
         write!(f, "{}", keyword_str)
     }
@@ -434,6 +466,14 @@ impl Keyword {
             "until" => Some(Keyword::Until),
             "catalog" => Some(Keyword::Catalog),
             "source" => Some(Keyword::Source),
+            "company" => Some(Keyword::Company),
+            "outlet_party_link" => Some(Keyword::OutletPartyLink),
+            "person" => Some(Keyword::Person),
+            "ownership" => Some(Keyword::Ownership),
+            "identifier_link" => Some(Keyword::IdentifierLink),
+            "provenance" => Some(Keyword::Provenance),
+            "review" => Some(Keyword::Review),
+            "redaction" => Some(Keyword::Redaction),
             _ => None,
         }
     }
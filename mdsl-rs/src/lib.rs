@@ -64,12 +64,15 @@ pub mod ir;
 /// Code generation - generates SQL and Cypher from IR
 pub mod codegen;
 
+/// Analyses over the IR (beneficial ownership, entity resolution)
+pub mod analysis;
+
 /// SQL to MDSL import functionality
 #[cfg(feature = "import")]
 pub mod import;
 
-/// Interactive REPL for testing and development
-#[cfg(feature = "repl")]
+// Interactive REPL for testing and development
+// #[cfg(feature = "repl")]
 // pub mod repl;
 
 /// Utility functions and types
@@ -23,11 +23,31 @@ fn main() {
         );
         eprintln!("  lex <file>                                            - Tokenize a MediaLanguage file");
         eprintln!("  parse <file>                                          - Parse a MediaLanguage file to AST");
-        eprintln!("  sql <file>                                            - Generate SQL from MediaLanguage file");
-        eprintln!("  sql-anmi <file>                                      - Generate ANMI-compatible SQL from MediaLanguage file");
-        eprintln!("  cypher <file>                                         - Generate Cypher from MediaLanguage file");
-        eprintln!("  cypher-split <file>                                  - Generate split Cypher (schema + data) files");
+        eprintln!("  sql <file> [generator options]                        - Generate SQL from MediaLanguage file");
+        eprintln!("  sql-anmi <file> [generator options]                  - Generate ANMI-compatible SQL from MediaLanguage file");
+        eprintln!("  csv-anmi <file> <dir> [generator options]             - Write the ANMI CSV tables (mo_constant, mo_year, ...) to a directory");
+        eprintln!("  cypher <file> [generator options]                     - Generate Cypher from MediaLanguage file");
+        eprintln!("  cypher-split <file> [generator options]              - Generate split Cypher (schema + data) files");
         eprintln!("  neo4j-test <file> [--url=URL]                        - Test Cypher generation against Neo4j");
+        eprintln!("  ownership <file> [--outlet=ID|--company=ID] [--date=YYYY-MM-DD] [--threshold=PCT]");
+        eprintln!("            [--nd-ownership=FILE] [--format=text|sql]   - Compute beneficial owners and ultimate controlling entities");
+        eprintln!("  match <curated> <crawled> [--threshold=0.6] [--max=3] [--scheme=NAME]");
+        eprintln!("        [--format=csv|mdsl] [--output=FILE]              - Propose matches between curated and crawled companies");
+        eprintln!("  match-accept <review.csv|review.mdsl> [--scheme=NAME] [--into=FILE]");
+        eprintln!("                                                        - Write accepted matches as IDENTIFIER_LINK declarations");
+        eprintln!("  diff <old> <new> [--format=text|json] [--output=FILE] [generator options]");
+        eprintln!("                                                        - Compare two datasets by outlet, link, metric and event");
+        eprintln!("  merge <base> <ours> <theirs> [--output=FILE]         - Three-way merge of edited copies of a file");
+        eprintln!("  history <file> <outlet-id>... [--format=text|markdown|json] [--output=FILE] [generator options]");
+        eprintln!("                                                        - Chronological timeline of outlets");
+        eprintln!("  lineage <file> [<outlet-id>...] [--format=dot|svg|json] [--output=FILE]");
+        eprintln!("                                                        - Check diachronic links, or export genealogy trees");
+        eprintln!("  snapshot <file> --at=DATE [--format=mdsl|json|sql] [--output=FILE] [generator options]");
+        eprintln!("                                                        - State of the landscape at a date");
+        eprintln!("  graph <file> [--format=graphml|gexf|dot] [--output=FILE] [generator options]");
+        eprintln!("                                                        - Export the network for Gephi or Graphviz");
+        eprintln!("  query <file> <query> [--format=table|json|csv] [--output=FILE] [generator options]");
+        eprintln!("                                                        - Select outlets by field, lifecycle, metric and link");
         eprintln!(
             "  test                                                  - Run tests on sample input"
         );
@@ -35,6 +55,11 @@ fn main() {
         eprintln!("Options:");
         eprintln!("  --format=FORMAT   Output format for validation (text, json, csv)");
         eprintln!("  --no-color        Disable colored output");
+        eprintln!();
+        eprintln!("Generator options:");
+        eprintln!("  --publish-only             Only generate declarations and assertions reviewed as published");
+        eprintln!("  --redact[=POLICY]          Apply a REDACTION policy to natural persons (report on stderr)");
+        eprintln!("  --redaction-file=FILE      Take the REDACTION policy from another MDSL file");
         process::exit(1);
     }
 
@@ -67,28 +92,35 @@ fn main() {
                 eprintln!("Error: sql command requires a file argument");
                 process::exit(1);
             }
-            generate_sql(&args[2]);
+            generate_sql(&args[2], &args[3..]);
         }
         "sql-anmi" => {
             if args.len() < 3 {
                 eprintln!("Error: sql-anmi command requires a file argument");
                 process::exit(1);
             }
-            generate_sql_anmi(&args[2]);
+            generate_sql_anmi(&args[2], &args[3..]);
+        }
+        "csv-anmi" => {
+            if args.len() < 4 {
+                eprintln!("Error: csv-anmi command requires a file and an output directory");
+                process::exit(1);
+            }
+            generate_csv_anmi(&args[2], &args[3], &args[4..]);
         }
         "cypher" => {
             if args.len() < 3 {
                 eprintln!("Error: cypher command requires a file argument");
                 process::exit(1);
             }
-            generate_cypher(&args[2]);
+            generate_cypher(&args[2], &args[3..]);
         }
         "cypher-split" => {
             if args.len() < 3 {
                 eprintln!("Error: cypher-split command requires a file argument");
                 process::exit(1);
             }
-            generate_cypher_split(&args[2]);
+            generate_cypher_split(&args[2], &args[3..]);
         }
         "neo4j-test" => {
             if args.len() < 3 {
@@ -97,6 +129,76 @@ fn main() {
             }
             test_neo4j(&args[2], &args[3..]);
         }
+        "ownership" => {
+            if args.len() < 3 {
+                eprintln!("Error: ownership command requires a file argument");
+                process::exit(1);
+            }
+            analyze_ownership(&args[2], &args[3..]);
+        }
+        "match" => {
+            if args.len() < 4 {
+                eprintln!("Error: match command requires a curated and a crawled file");
+                process::exit(1);
+            }
+            match_companies(&args[2], &args[3], &args[4..]);
+        }
+        "match-accept" => {
+            if args.len() < 3 {
+                eprintln!("Error: match-accept command requires a review file");
+                process::exit(1);
+            }
+            accept_matches(&args[2], &args[3..]);
+        }
+        "diff" => {
+            if args.len() < 4 {
+                eprintln!("Error: diff command requires an old and a new file");
+                process::exit(1);
+            }
+            diff_files(&args[2], &args[3], &args[4..]);
+        }
+        "merge" => {
+            if args.len() < 5 {
+                eprintln!("Error: merge command requires a base, our and their file");
+                process::exit(1);
+            }
+            merge_files(&args[2], &args[3], &args[4], &args[5..]);
+        }
+        "graph" => {
+            if args.len() < 3 {
+                eprintln!("Error: graph command requires a file argument");
+                process::exit(1);
+            }
+            graph_file(&args[2], &args[3..]);
+        }
+        "history" => {
+            if args.len() < 4 {
+                eprintln!("Error: history command requires a file and an outlet ID");
+                process::exit(1);
+            }
+            history_file(&args[2], &args[3..]);
+        }
+        "lineage" => {
+            if args.len() < 3 {
+                eprintln!("Error: lineage command requires a file argument");
+                process::exit(1);
+            }
+            lineage_file(&args[2], &args[3..]);
+        }
+        "snapshot" => {
+            if args.len() < 3 {
+                eprintln!("Error: snapshot command requires a file");
+                process::exit(1);
+            }
+            snapshot_file(&args[2], &args[3..]);
+        }
+        "query" => {
+            if args.len() < 4 {
+                eprintln!("Error: query command requires a file and a query");
+                process::exit(1);
+            }
+            query_file(&args[2], &args[3], &args[4..]);
+        }
         "test" => {
             run_tests();
         }
@@ -245,7 +347,8 @@ fn parse_file(filename: &str) {
 }
 
 /// Generate SQL from a file
-fn generate_sql(filename: &str) {
+fn generate_sql(filename: &str, options: &[String]) {
+    let options = GeneratorOptions::parse(options);
     let source = match fs::read_to_string(filename) {
         Ok(content) => content,
         Err(err) => {
@@ -286,6 +389,7 @@ fn generate_sql(filename: &str) {
                 process::exit(1);
             }
         };
+        let ir = options.prepare(ir);
 
         let generator = SqlGenerator::new();
         match generator.generate(&ir) {
@@ -307,7 +411,8 @@ fn generate_sql(filename: &str) {
 }
 
 /// Generate ANMI-compatible SQL from a file
-fn generate_sql_anmi(filename: &str) {
+fn generate_sql_anmi(filename: &str, options: &[String]) {
+    let options = GeneratorOptions::parse(options);
     let source = match fs::read_to_string(filename) {
         Ok(content) => content,
         Err(err) => {
@@ -348,6 +453,7 @@ fn generate_sql_anmi(filename: &str) {
                 process::exit(1);
             }
         };
+        let ir = options.prepare(ir);
 
         let generator = AnmiSqlGenerator::new();
         match generator.generate(&ir) {
@@ -368,8 +474,40 @@ fn generate_sql_anmi(filename: &str) {
     }
 }
 
+/// Write the ANMI CSV tables of a file to a directory
+fn generate_csv_anmi(filename: &str, dir: &str, options: &[String]) {
+    use mdsl_rs::codegen::AnmiCsvGenerator;
+
+    let options = GeneratorOptions::parse(options);
+    let ir = options.prepare(load_ir(filename));
+
+    let files = match AnmiCsvGenerator::new().generate(&ir) {
+        Ok(files) => files,
+        Err(err) => {
+            eprintln!("ANMI CSV generation error: {}", err);
+            process::exit(1);
+        }
+    };
+
+    if let Err(err) = fs::create_dir_all(dir) {
+        eprintln!("Error creating directory '{}': {}", dir, err);
+        process::exit(1);
+    }
+    for (name, content) in files {
+        let path = std::path::Path::new(dir).join(&name);
+        match fs::write(&path, content) {
+            Ok(_) => println!("✓ {} written", path.display()),
+            Err(err) => {
+                eprintln!("Error writing '{}': {}", path.display(), err);
+                process::exit(1);
+            }
+        }
+    }
+}
+
 /// Generate Cypher from a file
-fn generate_cypher(filename: &str) {
+fn generate_cypher(filename: &str, options: &[String]) {
+    let options = GeneratorOptions::parse(options);
     let source = match fs::read_to_string(filename) {
         Ok(content) => content,
         Err(err) => {
@@ -410,6 +548,7 @@ fn generate_cypher(filename: &str) {
                 process::exit(1);
             }
         };
+        let ir = options.prepare(ir);
 
         let generator = CypherGenerator::with_prefix(""); // Use empty prefix so labels are just "media_outlet" to match existing Neo4j schema
         match generator.generate(&ir) {
@@ -431,7 +570,8 @@ fn generate_cypher(filename: &str) {
 }
 
 /// Generate split Cypher (schema and data) from a file
-fn generate_cypher_split(filename: &str) {
+fn generate_cypher_split(filename: &str, options: &[String]) {
+    let options = GeneratorOptions::parse(options);
     let source = match fs::read_to_string(filename) {
         Ok(content) => content,
         Err(err) => {
@@ -469,6 +609,7 @@ fn generate_cypher_split(filename: &str) {
                 process::exit(1);
             }
         };
+        let ir = options.prepare(ir);
 
         let generator = CypherGenerator::with_prefix(""); // Use empty prefix so labels are just "media_outlet" to match existing Neo4j schema
         match generator.generate_split(&ir) {
@@ -514,6 +655,83 @@ fn generate_cypher_split(filename: &str) {
     }
 }
 
+/// Options shared by the code generators and JSON exporters
+#[derive(Default)]
+struct GeneratorOptions {
+    /// Only keep published declarations and assertions
+    publish_only: bool,
+    /// Apply a redaction policy
+    redact: bool,
+    /// Name of the redaction policy (the first declared one if unset)
+    policy_name: Option<String>,
+    /// MDSL file declaring the redaction policy (the input file if unset)
+    policy_file: Option<String>,
+}
+
+impl GeneratorOptions {
+    /// Parse generator options, exiting on an unknown option
+    fn parse(options: &[String]) -> Self {
+        let mut parsed = Self::default();
+        for option in options {
+            if !parsed.accept(option) {
+                eprintln!("Error: invalid option '{}'", option);
+                process::exit(1);
+            }
+        }
+        parsed
+    }
+
+    /// Take a generator option, returning false if it is not one
+    fn accept(&mut self, option: &str) -> bool {
+        if option == "--publish-only" {
+            self.publish_only = true;
+        } else if option == "--redact" {
+            self.redact = true;
+        } else if let Some(name) = option.strip_prefix("--redact=") {
+            self.redact = true;
+            self.policy_name = Some(name.to_string());
+        } else if let Some(path) = option.strip_prefix("--redaction-file=") {
+            self.redact = true;
+            self.policy_file = Some(path.to_string());
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// Filter and redact the IR before generation; the redaction report goes to stderr
+    fn prepare(&self, ir: mdsl_rs::ir::nodes::IRProgram) -> mdsl_rs::ir::nodes::IRProgram {
+        let ir = if self.publish_only {
+            mdsl_rs::ir::publication::publish_only(&ir)
+        } else {
+            ir
+        };
+        if !self.redact {
+            return ir;
+        }
+
+        let policies = match &self.policy_file {
+            Some(path) => load_ir(path).redaction_policies,
+            None => ir.redaction_policies.clone(),
+        };
+        let policy = match &self.policy_name {
+            Some(name) => policies.iter().find(|policy| &policy.name == name),
+            None => policies.first(),
+        };
+        let Some(policy) = policy else {
+            eprintln!(
+                "Error: redaction policy '{}' is not declared",
+                self.policy_name.as_deref().unwrap_or("(any)")
+            );
+            process::exit(1);
+        };
+
+        let (redacted, report) = mdsl_rs::ir::redaction::redact(&ir, policy);
+        eprint!("{}", report.format_text());
+        redacted
+    }
+}
+
 /// Run tests on sample input
 fn run_tests() {
     println!("Running lexer tests...");
@@ -563,6 +781,622 @@ UNIT MediaOutlet {
     }
 }
 
+/// Read, parse and transform a MediaLanguage file into IR, exiting on error
+fn load_ir(filename: &str) -> mdsl_rs::ir::nodes::IRProgram {
+    let source = match fs::read_to_string(filename) {
+        Ok(content) => content,
+        Err(err) => {
+            eprintln!("Error reading file '{}': {}", filename, err);
+            process::exit(1);
+        }
+    };
+
+    let ast = match mdsl_rs::parse(&source) {
+        Ok(ast) => ast,
+        Err(err) => {
+            eprintln!("Parse error in '{}': {}", filename, err);
+            process::exit(1);
+        }
+    };
+
+    match mdsl_rs::ir::transform(&ast) {
+        Ok(ir) => ir,
+        Err(err) => {
+            eprintln!("IR transformation error: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Compute beneficial owners and ultimate controlling entities
+fn analyze_ownership(filename: &str, options: &[String]) {
+    use mdsl_rs::analysis::ownership::{OwnershipGraph, PartyRef, DEFAULT_THRESHOLD_PCT};
+
+    let mut targets = Vec::new();
+    let mut date = "CURRENT".to_string();
+    let mut threshold = DEFAULT_THRESHOLD_PCT;
+    let mut nd_ownership = None;
+    let mut format = "text";
+
+    for option in options {
+        let parsed = if let Some(id) = option.strip_prefix("--outlet=") {
+            id.parse().map(|id| targets.push(PartyRef::outlet(id))).is_ok()
+        } else if let Some(id) = option.strip_prefix("--company=") {
+            id.parse().map(|id| targets.push(PartyRef::company(id))).is_ok()
+        } else if let Some(value) = option.strip_prefix("--date=") {
+            date = value.to_string();
+            true
+        } else if let Some(value) = option.strip_prefix("--threshold=") {
+            value.parse().map(|value| threshold = value).is_ok()
+        } else if let Some(path) = option.strip_prefix("--nd-ownership=") {
+            nd_ownership = Some(path.to_string());
+            true
+        } else if let Some(value) = option.strip_prefix("--format=") {
+            format = value;
+            true
+        } else {
+            false
+        };
+        if !parsed {
+            eprintln!("Error: invalid option '{}'", option);
+            process::exit(1);
+        }
+    }
+
+    let ir = load_ir(filename);
+    let mut graph = OwnershipGraph::from_ir(&ir);
+
+    if let Some(path) = nd_ownership {
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) => {
+                eprintln!("Error reading file '{}': {}", path, err);
+                process::exit(1);
+            }
+        };
+        if let Err(err) = graph.load_nd_ownership_csv(&content) {
+            eprintln!("Error loading '{}': {}", path, err);
+            process::exit(1);
+        }
+    }
+
+    // Without an explicit target, analyse every declared outlet and company
+    if targets.is_empty() {
+        for family in &ir.families {
+            for outlet in &family.outlets {
+                if let Some(id) = outlet.id {
+                    targets.push(PartyRef::outlet(id as u64));
+                }
+            }
+        }
+        for company in &ir.companies {
+            targets.push(PartyRef::company(company.id as u64));
+        }
+    }
+
+    let reports: Vec<_> = targets
+        .into_iter()
+        .map(|target| graph.analyze(target, &date, threshold))
+        .collect();
+
+    match format {
+        "sql" => {
+            let generator = mdsl_rs::codegen::SqlGenerator::new();
+            match generator.generate_ownership_marts(&reports) {
+                Ok(sql) => println!("{}", sql),
+                Err(err) => {
+                    eprintln!("SQL generation error: {}", err);
+                    process::exit(1);
+                }
+            }
+        }
+        "text" => {
+            for report in &reports {
+                println!("{}", report.format_text(&graph));
+            }
+        }
+        _ => {
+            eprintln!("Error: unknown format '{}' (expected text or sql)", format);
+            process::exit(1);
+        }
+    }
+}
+
+/// Propose matches between curated and crawled company declarations
+fn match_companies(curated_file: &str, crawled_file: &str, options: &[String]) {
+    use mdsl_rs::analysis::entity_resolution::{EntityMatcher, MatchRecord};
+
+    let mut matcher = EntityMatcher::new();
+    let mut format = "csv";
+    let mut output = None;
+
+    for option in options {
+        let parsed = if let Some(value) = option.strip_prefix("--threshold=") {
+            value.parse().map(|value| matcher.threshold = value).is_ok()
+        } else if let Some(value) = option.strip_prefix("--max=") {
+            value.parse().map(|value| matcher.max_candidates = value).is_ok()
+        } else if let Some(value) = option.strip_prefix("--scheme=") {
+            matcher.scheme = value.to_string();
+            true
+        } else if let Some(value) = option.strip_prefix("--format=") {
+            format = value;
+            true
+        } else if let Some(path) = option.strip_prefix("--output=") {
+            output = Some(path.to_string());
+            true
+        } else {
+            false
+        };
+        if !parsed {
+            eprintln!("Error: invalid option '{}'", option);
+            process::exit(1);
+        }
+    }
+
+    let curated = MatchRecord::from_source(&load_ir(curated_file), curated_file);
+    let crawled = MatchRecord::from_source(&load_ir(crawled_file), crawled_file);
+    let candidates = matcher.propose(&curated, &crawled);
+
+    let content = match format {
+        "csv" => matcher.to_csv(&candidates),
+        "mdsl" => matcher.to_mdsl(&candidates),
+        _ => {
+            eprintln!("Error: unknown format '{}' (expected csv or mdsl)", format);
+            process::exit(1);
+        }
+    };
+
+    eprintln!(
+        "{} candidate pairs for {} curated and {} crawled companies",
+        candidates.len(),
+        curated.len(),
+        crawled.len()
+    );
+    write_output(output.as_deref(), &content, false);
+}
+
+/// Turn a reviewed match file into IDENTIFIER_LINK declarations
+fn accept_matches(review_file: &str, options: &[String]) {
+    use mdsl_rs::analysis::entity_resolution::{
+        accepted_from_program, render_identifier_link, EntityMatcher,
+    };
+
+    let mut matcher = EntityMatcher::new();
+    let mut into = None;
+
+    for option in options {
+        if let Some(value) = option.strip_prefix("--scheme=") {
+            matcher.scheme = value.to_string();
+        } else if let Some(path) = option.strip_prefix("--into=") {
+            into = Some(path.to_string());
+        } else {
+            eprintln!("Error: invalid option '{}'", option);
+            process::exit(1);
+        }
+    }
+
+    let links = if review_file.ends_with(".csv") {
+        let content = match fs::read_to_string(review_file) {
+            Ok(content) => content,
+            Err(err) => {
+                eprintln!("Error reading file '{}': {}", review_file, err);
+                process::exit(1);
+            }
+        };
+        match matcher.accepted_from_csv(&content) {
+            Ok(links) => links,
+            Err(err) => {
+                eprintln!("Error in '{}': {}", review_file, err);
+                process::exit(1);
+            }
+        }
+    } else {
+        accepted_from_program(&load_ir(review_file))
+    };
+
+    let mut content = String::from("// Accepted matches between curated and crawled companies\n\n");
+    for link in &links {
+        content.push_str(&render_identifier_link(link));
+    }
+
+    eprintln!("{} accepted matches", links.len());
+    write_output(into.as_deref(), &content, true);
+}
+
+/// Report entity-level differences between two MediaLanguage files
+fn diff_files(old_file: &str, new_file: &str, options: &[String]) {
+    use mdsl_rs::analysis::diff::ProgramDiff;
+
+    let mut format = "text";
+    let mut output = None;
+    let mut generator = GeneratorOptions::default();
+
+    for option in options {
+        if let Some(value) = option.strip_prefix("--format=") {
+            format = value;
+        } else if let Some(path) = option.strip_prefix("--output=") {
+            output = Some(path.to_string());
+        } else if !generator.accept(option) {
+            eprintln!("Error: invalid option '{}'", option);
+            process::exit(1);
+        }
+    }
+
+    let diff = ProgramDiff::between(
+        &generator.prepare(load_ir(old_file)),
+        &generator.prepare(load_ir(new_file)),
+    );
+    let content = match format {
+        "text" => diff.format_text(),
+        "json" => diff.format_json(),
+        _ => {
+            eprintln!("Error: unknown format '{}' (expected text or json)", format);
+            process::exit(1);
+        }
+    };
+
+    write_output(output.as_deref(), &content, false);
+}
+
+/// Merge two edited copies of a MediaLanguage file against their common base
+fn merge_files(base_file: &str, ours_file: &str, theirs_file: &str, options: &[String]) {
+    let mut output = None;
+    for option in options {
+        if let Some(path) = option.strip_prefix("--output=") {
+            output = Some(path.to_string());
+        } else {
+            eprintln!("Error: invalid option '{}'", option);
+            process::exit(1);
+        }
+    }
+
+    let read = |filename: &str| match fs::read_to_string(filename) {
+        Ok(content) => content,
+        Err(err) => {
+            eprintln!("Error reading file '{}': {}", filename, err);
+            process::exit(1);
+        }
+    };
+    let (base, ours, theirs) = (read(base_file), read(ours_file), read(theirs_file));
+
+    let result = match mdsl_rs::utils::merge::merge(&base, &ours, &theirs) {
+        Ok(result) => result,
+        Err(err) => {
+            eprintln!("Merge error: {}", err);
+            process::exit(1);
+        }
+    };
+
+    write_output(output.as_deref(), &result.text, false);
+    if result.is_clean() {
+        if let Err(err) = mdsl_rs::parse(&result.text) {
+            eprintln!("Warning: the merged file does not parse: {}", err);
+        }
+        return;
+    }
+    eprintln!("{} conflict(s):", result.conflicts.len());
+    for conflict in &result.conflicts {
+        eprintln!("  line {}: {}", conflict.line, conflict.path);
+    }
+    process::exit(1);
+}
+
+/// Report the chronological history of outlets in a MediaLanguage file
+fn history_file(file: &str, args: &[String]) {
+    use mdsl_rs::analysis::history::OutletHistory;
+
+    let mut ids = Vec::new();
+    let mut format = "text";
+    let mut output = None;
+    let mut generator = GeneratorOptions::default();
+
+    for arg in args {
+        if let Some(value) = arg.strip_prefix("--format=") {
+            format = value;
+        } else if let Some(path) = arg.strip_prefix("--output=") {
+            output = Some(path.to_string());
+        } else if let Ok(id) = arg.parse::<u32>() {
+            ids.push(id);
+        } else if !generator.accept(arg) {
+            eprintln!("Error: invalid option '{}'", arg);
+            process::exit(1);
+        }
+    }
+    if ids.is_empty() {
+        eprintln!("Error: history command requires an outlet ID");
+        process::exit(1);
+    }
+
+    let ir = generator.prepare(load_ir(file));
+    let histories: Vec<OutletHistory> = ids
+        .iter()
+        .map(|&id| match OutletHistory::build(&ir, id) {
+            Some(history) => history,
+            None => {
+                eprintln!("Error: outlet {} does not occur in '{}'", id, file);
+                process::exit(1);
+            }
+        })
+        .collect();
+
+    let content = match format {
+        "text" => histories
+            .iter()
+            .map(OutletHistory::format_text)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "markdown" => histories
+            .iter()
+            .map(OutletHistory::format_markdown)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "json" => {
+            let histories: Vec<String> = histories.iter().map(|h| h.format_json()).collect();
+            format!("[{}]\n", histories.join(",\n"))
+        }
+        _ => {
+            eprintln!(
+                "Error: unknown format '{}' (expected text, markdown or json)",
+                format
+            );
+            process::exit(1);
+        }
+    };
+
+    write_output(output.as_deref(), &content, false);
+}
+
+/// Reduce a MediaLanguage file to the state of the landscape at a date
+fn snapshot_file(file: &str, options: &[String]) {
+    use mdsl_rs::ir::snapshot;
+
+    let mut date = None;
+    let mut format = "mdsl";
+    let mut output = None;
+    let mut generator = GeneratorOptions::default();
+
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        if let Some(value) = option.strip_prefix("--at=") {
+            date = Some(value.to_string());
+        } else if option == "--at" {
+            date = options.next().cloned();
+        } else if let Some(value) = option.strip_prefix("--format=") {
+            format = value;
+        } else if let Some(path) = option.strip_prefix("--output=") {
+            output = Some(path.to_string());
+        } else if !generator.accept(option) {
+            eprintln!("Error: invalid option '{}'", option);
+            process::exit(1);
+        }
+    }
+    let Some(date) = date else {
+        eprintln!("Error: snapshot command requires --at=DATE");
+        process::exit(1);
+    };
+
+    let state = snapshot::snapshot_at(&generator.prepare(load_ir(file)), &date);
+    let content = match format {
+        "mdsl" => snapshot::format_mdsl(&state, &date),
+        "json" => snapshot::format_json(&state, &date),
+        "sql" => match mdsl_rs::codegen::SqlGenerator::new().generate(&state) {
+            Ok(sql) => sql,
+            Err(err) => {
+                eprintln!("SQL generation error: {}", err);
+                process::exit(1);
+            }
+        },
+        _ => {
+            eprintln!(
+                "Error: unknown format '{}' (expected mdsl, json or sql)",
+                format
+            );
+            process::exit(1);
+        }
+    };
+
+    write_output(output.as_deref(), &content, false);
+}
+
+/// Select outlets of a MediaLanguage file with a query
+fn query_file(file: &str, query: &str, options: &[String]) {
+    use mdsl_rs::analysis::query::Query;
+
+    let mut format = "table";
+    let mut output = None;
+    let mut generator = GeneratorOptions::default();
+
+    for option in options {
+        if let Some(value) = option.strip_prefix("--format=") {
+            format = value;
+        } else if let Some(path) = option.strip_prefix("--output=") {
+            output = Some(path.to_string());
+        } else if !generator.accept(option) {
+            eprintln!("Error: invalid option '{}'", option);
+            process::exit(1);
+        }
+    }
+
+    let query = match Query::parse(query) {
+        Ok(query) => query,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+    let result = query.run(&generator.prepare(load_ir(file)));
+    let content = match format {
+        "table" => result.format_table(),
+        "json" => result.format_json(),
+        "csv" => result.format_csv(),
+        _ => {
+            eprintln!(
+                "Error: unknown format '{}' (expected table, json or csv)",
+                format
+            );
+            process::exit(1);
+        }
+    };
+
+    write_output(output.as_deref(), &content, false);
+}
+
+/// Check the lineage graph, or export the genealogy trees of outlets
+fn lineage_file(file: &str, args: &[String]) {
+    use mdsl_rs::analysis::lineage::{LineageGraph, LineageTree};
+
+    let mut ids = Vec::new();
+    let mut format = "dot";
+    let mut output = None;
+
+    for arg in args {
+        if let Some(value) = arg.strip_prefix("--format=") {
+            format = value;
+        } else if let Some(path) = arg.strip_prefix("--output=") {
+            output = Some(path.to_string());
+        } else if let Ok(id) = arg.parse::<u32>() {
+            ids.push(id);
+        } else {
+            eprintln!("Error: invalid option '{}'", arg);
+            process::exit(1);
+        }
+    }
+
+    let graph = LineageGraph::from_ir(&load_ir(file));
+
+    // Without outlets, report the problems of the whole graph
+    if ids.is_empty() {
+        let issues = graph.check();
+        let mut content = format!(
+            "{} diachronic links, {} issues\n",
+            graph.links().len(),
+            issues.len()
+        );
+        for issue in &issues {
+            content.push_str(&format!("  {}\n", issue));
+        }
+        write_output(output.as_deref(), &content, false);
+        if !issues.is_empty() {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let trees: Vec<LineageTree> = ids
+        .iter()
+        .map(|&id| match graph.tree(id) {
+            Some(tree) => tree,
+            None => {
+                eprintln!("Error: outlet {} does not occur in '{}'", id, file);
+                process::exit(1);
+            }
+        })
+        .collect();
+    for issue in trees.iter().flat_map(|tree| &tree.issues) {
+        eprintln!("Warning: {}", issue);
+    }
+
+    let content = match format {
+        "dot" => trees
+            .iter()
+            .map(LineageTree::format_dot)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "svg" if trees.len() == 1 => trees[0].format_svg(),
+        "svg" => {
+            eprintln!("Error: SVG output takes a single outlet");
+            process::exit(1);
+        }
+        "json" => {
+            let trees: Vec<String> = trees.iter().map(|t| t.format_json()).collect();
+            format!("[{}]\n", trees.join(",\n"))
+        }
+        _ => {
+            eprintln!(
+                "Error: unknown format '{}' (expected dot, svg or json)",
+                format
+            );
+            process::exit(1);
+        }
+    };
+
+    write_output(output.as_deref(), &content, false);
+}
+
+/// Export the outlet network as GraphML, GEXF or DOT
+#[cfg(feature = "visualization")]
+fn graph_file(file: &str, options: &[String]) {
+    use mdsl_rs::codegen::{DotGenerator, GexfGenerator, GraphMlGenerator};
+
+    let mut format = "graphml";
+    let mut output = None;
+    let mut generator = GeneratorOptions::default();
+
+    for option in options {
+        if let Some(value) = option.strip_prefix("--format=") {
+            format = value;
+        } else if let Some(path) = option.strip_prefix("--output=") {
+            output = Some(path.to_string());
+        } else if !generator.accept(option) {
+            eprintln!("Error: invalid option '{}'", option);
+            process::exit(1);
+        }
+    }
+
+    let ir = generator.prepare(load_ir(file));
+    let result = match format {
+        "graphml" => GraphMlGenerator::new().generate(&ir),
+        "gexf" => GexfGenerator::new().generate(&ir),
+        "dot" => DotGenerator::new().generate(&ir),
+        _ => {
+            eprintln!(
+                "Error: unknown format '{}' (expected graphml, gexf or dot)",
+                format
+            );
+            process::exit(1);
+        }
+    };
+    match result {
+        Ok(content) => write_output(output.as_deref(), &content, false),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(feature = "visualization"))]
+fn graph_file(_file: &str, _options: &[String]) {
+    eprintln!("Graph export not enabled (requires 'visualization' feature)");
+    eprintln!("Run with: cargo run --features visualization --bin mdsl -- graph <file>");
+    process::exit(1);
+}
+
+/// Write command output to a file (optionally appending) or stdout
+fn write_output(path: Option<&str>, content: &str, append: bool) {
+    let Some(path) = path else {
+        print!("{}", content);
+        return;
+    };
+
+    let result = if append {
+        use std::io::Write;
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| write!(file, "\n{}", content))
+    } else {
+        fs::write(path, content)
+    };
+
+    if let Err(err) = result {
+        eprintln!("Error writing file '{}': {}", path, err);
+        process::exit(1);
+    }
+    eprintln!("Written to {}", path);
+}
+
 /// Test MDSL Cypher generation against Neo4j database
 #[cfg(feature = "neo4j")]
 fn test_neo4j(filename: &str, options: &[String]) {
@@ -36,6 +36,20 @@ pub enum Statement {
     Event(EventDeclaration),
     /// Catalog declaration
     Catalog(CatalogDeclaration),
+    /// Company declaration
+    Company(CompanyDeclaration),
+    /// Outlet-party link
+    OutletPartyLink(OutletPartyLink),
+    /// Person declaration
+    Person(PersonDeclaration),
+    /// Ownership relation
+    Ownership(OwnershipDeclaration),
+    /// Identifier link
+    IdentifierLink(IdentifierLink),
+    /// Redaction policy
+    Redaction(RedactionPolicy),
+    /// Semantics of a synchronous relationship type
+    RelationshipType(RelationshipTypeDeclaration),
     /// Comment
     Comment(CommentStatement),
 }
@@ -163,6 +177,10 @@ pub enum FamilyMember {
     Data(DataDeclaration),
     /// Relationship declaration
     Relationship(RelationshipDeclaration),
+    /// Provenance clause
+    Provenance(ProvenanceClause),
+    /// Review clause
+    Review(ReviewClause),
     /// Comment
     Comment(CommentStatement),
 }
@@ -200,6 +218,10 @@ pub enum OutletBlock {
     Characteristics(CharacteristicsBlock),
     /// Metadata block
     Metadata(MetadataBlock),
+    /// Provenance clause
+    Provenance(ProvenanceClause),
+    /// Review clause
+    Review(ReviewClause),
     /// Comment
     Comment(CommentStatement),
 }
@@ -380,6 +402,26 @@ pub struct OutletReference {
     pub id: f64,
     /// Reference name
     pub name: String,
+    /// Overrides of the referenced outlet
+    pub overrides: Vec<OverrideDeclaration>,
+    /// Source position
+    pub position: SourcePosition,
+}
+
+/// Override of a referenced outlet for a period
+/// (`OVERRIDE FROM "1971-01-01" { ... }` or `FOR_PERIOD "1972-01-01" TO "1980-12-31" { ... }`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverrideDeclaration {
+    /// Start of the period
+    pub from: Option<DateExpression>,
+    /// End of the period
+    pub to: Option<DateExpression>,
+    /// Family the outlet belongs to during the period (`@family`)
+    pub family: Option<String>,
+    /// Identity, characteristics and metadata blocks taking precedence over the outlet's own
+    pub blocks: Vec<OutletBlock>,
+    /// Nested `FOR_PERIOD` overrides
+    pub periods: Vec<OverrideDeclaration>,
     /// Source position
     pub position: SourcePosition,
 }
@@ -404,6 +446,10 @@ pub enum DataBlock {
     Aggregation(AggregationDeclaration),
     /// Year declaration
     Year(YearDeclaration),
+    /// Provenance clause
+    Provenance(ProvenanceClause),
+    /// Review clause
+    Review(ReviewClause),
     /// Comment
     Comment(CommentStatement),
 }
@@ -451,6 +497,10 @@ pub enum YearBlock {
         /// Source position
         position: SourcePosition,
     },
+    /// Provenance clause
+    Provenance(ProvenanceClause),
+    /// Review clause
+    Review(ReviewClause),
     /// Comment
     Comment(CommentStatement),
 }
@@ -560,6 +610,10 @@ pub enum EventField {
         /// Source position
         position: SourcePosition,
     },
+    /// Provenance clause
+    Provenance(ProvenanceClause),
+    /// Review clause
+    Review(ReviewClause),
     /// Comment
     Comment {
         /// Comment text
@@ -590,6 +644,13 @@ pub enum EntityRole {
         /// Source position
         position: SourcePosition,
     },
+    /// Declared person the entity is, for natural persons
+    Person {
+        /// Person ID
+        value: f64,
+        /// Source position
+        position: SourcePosition,
+    },
     /// Entity role
     Role {
         /// Role value
@@ -676,6 +737,10 @@ pub enum DiachronicField {
     },
     /// Annotation
     Annotation(AnnotationStatement),
+    /// Provenance clause
+    Provenance(ProvenanceClause),
+    /// Review clause
+    Review(ReviewClause),
     /// Comment
     Comment(CommentStatement),
 }
@@ -738,6 +803,10 @@ pub enum SynchronousField {
     },
     /// Annotation
     Annotation(AnnotationStatement),
+    /// Provenance clause
+    Provenance(ProvenanceClause),
+    /// Review clause
+    Review(ReviewClause),
     /// Comment
     Comment(CommentStatement),
 }
@@ -756,8 +825,8 @@ pub struct OutletSpec {
 /// Date range
 #[derive(Debug, Clone, PartialEq)]
 pub struct DateRange {
-    /// Start date
-    pub from: DateExpression,
+    /// Start date (None for a period given only by its end)
+    pub from: Option<DateExpression>,
     /// End date (optional)
     pub to: Option<DateExpression>,
     /// Source position
@@ -831,6 +900,111 @@ pub struct AnnotationStatement {
     pub position: SourcePosition,
 }
 
+/// Provenance clause: `PROVENANCE [target] { source = "oeak"; confidence = 0.9; }`
+///
+/// Ties the enclosing declaration, or one of its fields when a target is
+/// given, to a CATALOG source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProvenanceClause {
+    /// Field the clause applies to (None for the whole declaration)
+    pub target: Option<String>,
+    /// Provenance fields
+    pub fields: Vec<ProvenanceField>,
+    /// Source position
+    pub position: SourcePosition,
+}
+
+/// Provenance field
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProvenanceField {
+    /// Catalog source name
+    Source {
+        /// Source name
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Confidence in the assertion (0-1)
+    Confidence {
+        /// Confidence value
+        value: f64,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Verification status (unverified, verified, disputed, retracted)
+    Verification {
+        /// Status value
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Date of the last verification
+    VerifiedAt {
+        /// Date value
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Free-text note (page, table, retrieval details, ...)
+    Note {
+        /// Note text
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Comment
+    Comment(CommentStatement),
+}
+
+/// Review clause: `REVIEW [target] { status = "published"; reviewer = "mk"; }`
+///
+/// Without a target the clause sets the publication status of the enclosing
+/// declaration; with a target it reviews the assertion about that field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewClause {
+    /// Field the review applies to
+    pub target: Option<String>,
+    /// Clause fields
+    pub fields: Vec<ReviewField>,
+    /// Source position
+    pub position: SourcePosition,
+}
+
+/// Review clause field
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReviewField {
+    /// Publication status (draft, in_review, reviewed, published, withdrawn)
+    Status {
+        /// Status value
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Reviewer
+    Reviewer {
+        /// Reviewer name or handle
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Review date
+    ReviewedAt {
+        /// Review date
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Free-text review note
+    Note {
+        /// Note text
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Comment
+    Comment(CommentStatement),
+}
+
 /// Catalog declaration
 #[derive(Debug, Clone, PartialEq)]
 pub struct CatalogDeclaration {
@@ -896,6 +1070,355 @@ pub enum NestedSourceField {
     Comment(CommentStatement),
 }
 
+/// Company declaration (publisher, broadcaster, licence holder, ...)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompanyDeclaration {
+    /// Company name
+    pub name: String,
+    /// Company fields
+    pub fields: Vec<CompanyField>,
+    /// Source position
+    pub position: SourcePosition,
+}
+
+/// Company field
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompanyField {
+    /// Simple field assignment
+    Assignment {
+        /// Field name
+        name: String,
+        /// Field value
+        value: Expression,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Annotation
+    Annotation(AnnotationStatement),
+    /// Provenance clause
+    Provenance(ProvenanceClause),
+    /// Review clause
+    Review(ReviewClause),
+    /// Comment
+    Comment(CommentStatement),
+}
+
+/// Link between a media outlet and a company running it
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutletPartyLink {
+    /// Link name
+    pub name: String,
+    /// Link fields
+    pub fields: Vec<OutletPartyField>,
+    /// Source position
+    pub position: SourcePosition,
+}
+
+/// Outlet-party link field
+#[derive(Debug, Clone, PartialEq)]
+pub enum OutletPartyField {
+    /// Outlet ID
+    Outlet {
+        /// Outlet ID value
+        value: f64,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Party (company) ID
+    Party {
+        /// Company ID value
+        value: f64,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Role of the party (publisher, broadcaster, licence_holder, ...)
+    Role {
+        /// Role value
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Start of the period
+    PeriodStart {
+        /// Start date
+        value: DateExpression,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// End of the period
+    PeriodEnd {
+        /// End date
+        value: DateExpression,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Source of the information
+    Source {
+        /// Source value
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Annotation
+    Annotation(AnnotationStatement),
+    /// Provenance clause
+    Provenance(ProvenanceClause),
+    /// Review clause
+    Review(ReviewClause),
+    /// Comment
+    Comment(CommentStatement),
+}
+
+/// Natural person declaration (shareholder, beneficial owner, ...)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersonDeclaration {
+    /// Person name
+    pub name: String,
+    /// Person fields (same shape as company fields)
+    pub fields: Vec<CompanyField>,
+    /// Source position
+    pub position: SourcePosition,
+}
+
+/// Ownership relation: a company or person holding a share of a company
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnershipDeclaration {
+    /// Relation name
+    pub name: String,
+    /// Relation fields
+    pub fields: Vec<OwnershipField>,
+    /// Source position
+    pub position: SourcePosition,
+}
+
+/// Ownership relation field
+#[derive(Debug, Clone, PartialEq)]
+pub enum OwnershipField {
+    /// Owning company ID
+    OwnerCompany {
+        /// Company ID value
+        value: f64,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Owning person ID
+    OwnerPerson {
+        /// Person ID value
+        value: f64,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Owned company ID
+    OwnedCompany {
+        /// Company ID value
+        value: f64,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Share held, in percent
+    SharePct {
+        /// Share value
+        value: f64,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Role of the owner (shareholder, general_partner, ...)
+    Role {
+        /// Role value
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Start of the period
+    PeriodStart {
+        /// Start date
+        value: DateExpression,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// End of the period
+    PeriodEnd {
+        /// End date
+        value: DateExpression,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// URL the relation was taken from
+    SourceUrl {
+        /// URL value
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Time the source was scraped
+    ScrapedAt {
+        /// Timestamp value
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Annotation
+    Annotation(AnnotationStatement),
+    /// Provenance clause
+    Provenance(ProvenanceClause),
+    /// Review clause
+    Review(ReviewClause),
+    /// Comment
+    Comment(CommentStatement),
+}
+
+/// Link between a company and its identifier in an external scheme
+/// (register number, crawled company ID, ...)
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdentifierLink {
+    /// Link name
+    pub name: String,
+    /// Link fields
+    pub fields: Vec<IdentifierLinkField>,
+    /// Source position
+    pub position: SourcePosition,
+}
+
+/// Identifier link field
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdentifierLinkField {
+    /// Company ID
+    Company {
+        /// Company ID value
+        value: f64,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Identifier scheme (northdata, firmenbuch, ...)
+    Scheme {
+        /// Scheme name
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Identifier value within the scheme
+    Value {
+        /// Identifier value
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Review status (proposed, accepted, rejected)
+    Status {
+        /// Status value
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Match score between 0 and 1
+    Score {
+        /// Score value
+        value: f64,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Who or what issued the link
+    IssuedBy {
+        /// Issuer value
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Annotation
+    Annotation(AnnotationStatement),
+    /// Provenance clause
+    Provenance(ProvenanceClause),
+    /// Review clause
+    Review(ReviewClause),
+    /// Comment
+    Comment(CommentStatement),
+}
+
+/// Redaction policy for public exports
+///
+/// Decides what happens to natural persons (PERSON declarations, the stakes they
+/// hold, event entities referring to them and person-valued metadata) when an
+/// export is generated with the policy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedactionPolicy {
+    /// Policy name
+    pub name: String,
+    /// Policy fields
+    pub fields: Vec<RedactionField>,
+    /// Source position
+    pub position: SourcePosition,
+}
+
+/// Redaction policy field
+#[derive(Debug, Clone, PartialEq)]
+pub enum RedactionField {
+    /// Action for PERSON declarations and their stakes (keep, drop, pseudonymise, aggregate)
+    Persons {
+        /// Action name
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Action for event entities that refer to a person
+    EventPersons {
+        /// Action name
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Names of metadata fields that hold person names (advisors, editors, ...)
+    PersonMetadata {
+        /// Field names
+        value: Vec<String>,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Salt mixed into pseudonym hashes
+    Salt {
+        /// Salt value
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Comment
+    Comment(CommentStatement),
+}
+
+/// Semantics of a synchronous relationship type
+///
+/// Declares whether links of the type are directed or symmetric and which
+/// `outlet_1`/`outlet_2` role pairs they may use.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelationshipTypeDeclaration {
+    /// Relationship type name, as used in `relationship_type`
+    pub name: String,
+    /// Declaration fields
+    pub fields: Vec<RelationshipTypeField>,
+    /// Source position
+    pub position: SourcePosition,
+}
+
+/// Relationship type field
+#[derive(Debug, Clone, PartialEq)]
+pub enum RelationshipTypeField {
+    /// Direction (directed, symmetric)
+    Direction {
+        /// Direction name
+        value: String,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Allowed role pairs, from outlet to target outlet
+    Roles {
+        /// Role pairs
+        value: Vec<(String, String)>,
+        /// Source position
+        position: SourcePosition,
+    },
+    /// Comment
+    Comment(CommentStatement),
+}
+
 // Convenience implementations
 impl Program {
     /// Create a new program
@@ -924,6 +1447,13 @@ impl Statement {
             },
             Statement::Event(s) => s.position,
             Statement::Catalog(s) => s.position,
+            Statement::Company(s) => s.position,
+            Statement::OutletPartyLink(s) => s.position,
+            Statement::Person(s) => s.position,
+            Statement::Ownership(s) => s.position,
+            Statement::IdentifierLink(s) => s.position,
+            Statement::Redaction(s) => s.position,
+            Statement::RelationshipType(s) => s.position,
             Statement::Comment(s) => s.position,
         }
     }
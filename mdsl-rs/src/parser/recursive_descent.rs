@@ -66,6 +66,23 @@ impl Parser {
             TokenKind::Keyword(Keyword::Data) => self.parse_data().map(Statement::Data),
             TokenKind::Keyword(Keyword::Event) => self.parse_event().map(Statement::Event),
             TokenKind::Keyword(Keyword::Catalog) => self.parse_catalog().map(Statement::Catalog),
+            TokenKind::Keyword(Keyword::Company) => self.parse_company().map(Statement::Company),
+            TokenKind::Keyword(Keyword::OutletPartyLink) => self
+                .parse_outlet_party_link()
+                .map(Statement::OutletPartyLink),
+            TokenKind::Keyword(Keyword::Person) => self.parse_person().map(Statement::Person),
+            TokenKind::Keyword(Keyword::Ownership) => {
+                self.parse_ownership().map(Statement::Ownership)
+            }
+            TokenKind::Keyword(Keyword::IdentifierLink) => self
+                .parse_identifier_link()
+                .map(Statement::IdentifierLink),
+            TokenKind::Keyword(Keyword::Redaction) => {
+                self.parse_redaction().map(Statement::Redaction)
+            }
+            TokenKind::Keyword(Keyword::RelationshipType) => self
+                .parse_relationship_type()
+                .map(Statement::RelationshipType),
             TokenKind::Keyword(Keyword::DiachronicLink) => self
                 .parse_diachronic_link()
                 .map(|link| Statement::Relationship(RelationshipDeclaration::Diachronic(link))),
@@ -442,6 +459,12 @@ impl Parser {
                 TokenKind::Keyword(Keyword::Data) => {
                     members.push(FamilyMember::Data(self.parse_data()?));
                 }
+                TokenKind::Keyword(Keyword::Provenance) => {
+                    members.push(FamilyMember::Provenance(self.parse_provenance()?));
+                }
+                TokenKind::Keyword(Keyword::Review) => {
+                    members.push(FamilyMember::Review(self.parse_review()?));
+                }
                 TokenKind::Keyword(Keyword::DiachronicLink) => {
                     members.push(FamilyMember::Relationship(
                         RelationshipDeclaration::Diachronic(self.parse_diachronic_link()?),
@@ -566,6 +589,12 @@ impl Parser {
                 TokenKind::Keyword(Keyword::Metadata) => {
                     blocks.push(OutletBlock::Metadata(self.parse_metadata_block()?));
                 }
+                TokenKind::Keyword(Keyword::Provenance) => {
+                    blocks.push(OutletBlock::Provenance(self.parse_provenance()?));
+                }
+                TokenKind::Keyword(Keyword::Review) => {
+                    blocks.push(OutletBlock::Review(self.parse_review()?));
+                }
                 TokenKind::Comment(_) | TokenKind::MultiLineComment(_) => {
                     blocks.push(OutletBlock::Comment(self.parse_comment()?));
                 }
@@ -730,7 +759,7 @@ impl Parser {
                     };
 
                     let date_range = DateRange {
-                        from: from_expr,
+                        from: Some(from_expr),
                         to: to_expr,
                         position,
                     };
@@ -926,22 +955,30 @@ impl Parser {
                     position: field_position,
                 });
             } else if self.check(&TokenKind::LeftBrace) {
-                // Skip nested object literals for now
-                let mut depth = 1;
-                self.advance(); // consume '{'
-                while depth > 0 && !self.is_at_end() {
-                    match &self.current_token().kind {
-                        TokenKind::LeftBrace => depth += 1,
-                        TokenKind::RightBrace => depth -= 1,
-                        _ => {}
+                // Nested objects such as `distribution = { primary_area = "15"; }`
+                // keep their fields; objects the literal parser rejects are skipped
+                let start = self.current;
+                let value = match self.parse_object_literal() {
+                    Ok(object) => Expression::Object(object),
+                    Err(_) => {
+                        self.current = start;
+                        let mut depth = 1;
+                        self.advance(); // consume '{'
+                        while depth > 0 && !self.is_at_end() {
+                            match &self.current_token().kind {
+                                TokenKind::LeftBrace => depth += 1,
+                                TokenKind::RightBrace => depth -= 1,
+                                _ => {}
+                            }
+                            self.advance();
+                        }
+                        Expression::String("complex_object".to_string())
                     }
-                    self.advance();
-                }
+                };
 
-                // Add a placeholder assignment
                 fields.push(CharacteristicField::Assignment {
                     name,
-                    value: Expression::String("complex_object".to_string()),
+                    value,
                     position: field_position,
                 });
             } else {
@@ -1008,7 +1045,6 @@ impl Parser {
     }
 
     fn parse_outlet_reference(&mut self) -> Result<OutletReference> {
-        // TODO: Implement outlet reference parsing
         let position = self.current_position();
         self.consume_keyword(Keyword::OutletRef, "Expected 'outlet_ref'")?;
         let id = self.consume_number("Expected outlet ID")?;
@@ -1023,23 +1059,125 @@ impl Parser {
             self.consume_string("Expected outlet name")?
         };
 
-        // Parse the block if present
-        if self.check(&TokenKind::LeftBrace) {
-            self.consume_token(TokenKind::LeftBrace, "Expected '{'")?;
+        let mut overrides = Vec::new();
+        if self.match_token(&TokenKind::LeftBrace) {
+            while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+                if self.match_token(&TokenKind::Newline)
+                    || self.match_comment()
+                    || self.match_token(&TokenKind::Semicolon)
+                {
+                    continue;
+                }
 
-            // Skip to end of block for now
-            let mut depth = 1;
-            while depth > 0 && !self.is_at_end() {
-                match &self.current_token().kind {
-                    TokenKind::LeftBrace => depth += 1,
-                    TokenKind::RightBrace => depth -= 1,
-                    _ => {}
+                if self.match_keyword(Keyword::Override) {
+                    // OVERRIDE FROM "date" { ... } or OVERRIDE FOR_PERIOD "date" TO "date" { ... }
+                    let override_position = self.current_position();
+                    if !self.match_keyword(Keyword::From) {
+                        self.match_keyword(Keyword::ForPeriod);
+                    }
+                    overrides.push(self.parse_override(override_position)?);
+                } else {
+                    // Annotations, INHERITS_FROM and blocks outside overrides are not modelled
+                    self.skip_statement();
+                }
+            }
+            self.consume_token(TokenKind::RightBrace, "Expected '}' after outlet reference")?;
+        }
+
+        Ok(OutletReference {
+            id,
+            name,
+            overrides,
+            position,
+        })
+    }
+
+    /// Parse the period and body of an override, after its keyword
+    fn parse_override(&mut self, position: SourcePosition) -> Result<OverrideDeclaration> {
+        let from = if self.check(&TokenKind::LeftBrace) {
+            None
+        } else {
+            Some(self.parse_date_expression()?)
+        };
+        let to = if self.match_keyword(Keyword::To) {
+            Some(self.parse_date_expression()?)
+        } else {
+            None
+        };
+        self.consume_token(TokenKind::LeftBrace, "Expected '{' after override period")?;
+
+        let mut family = None;
+        let mut blocks = Vec::new();
+        let mut periods = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            if self.match_token(&TokenKind::Newline)
+                || self.match_comment()
+                || self.match_token(&TokenKind::Semicolon)
+            {
+                continue;
+            }
+
+            match &self.current_token().kind {
+                TokenKind::Keyword(Keyword::ForPeriod) => {
+                    let period_position = self.current_position();
+                    self.advance();
+                    periods.push(self.parse_override(period_position)?);
+                }
+                TokenKind::Keyword(Keyword::Identity) => {
+                    blocks.push(OutletBlock::Identity(self.parse_identity_block()?));
+                }
+                TokenKind::Keyword(Keyword::Characteristics) => {
+                    blocks.push(OutletBlock::Characteristics(
+                        self.parse_characteristics_block()?,
+                    ));
+                }
+                TokenKind::Keyword(Keyword::Metadata) => {
+                    blocks.push(OutletBlock::Metadata(self.parse_metadata_block()?));
+                }
+                TokenKind::Annotation(name) if name == "family" => {
+                    family = self.parse_annotation()?.value;
+                }
+                _ => {
+                    // Lifecycle overrides use free-form assignments and are not modelled
+                    self.skip_statement();
                 }
-                self.advance();
             }
         }
+        self.consume_token(TokenKind::RightBrace, "Expected '}' after override")?;
 
-        Ok(OutletReference { id, name, position })
+        Ok(OverrideDeclaration {
+            from,
+            to,
+            family,
+            blocks,
+            periods,
+            position,
+        })
+    }
+
+    /// Skip a statement up to its ';', or past its block
+    fn skip_statement(&mut self) {
+        let mut depth = 0;
+        while !self.is_at_end() {
+            match &self.current_token().kind {
+                TokenKind::RightBrace if depth == 0 => return,
+                TokenKind::Semicolon if depth == 0 => {
+                    self.advance();
+                    return;
+                }
+                TokenKind::LeftBrace => depth += 1,
+                TokenKind::RightBrace => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.advance();
+                        self.consume_optional_semicolon();
+                        return;
+                    }
+                }
+                _ => {}
+            }
+            self.advance();
+        }
     }
 
     fn parse_data(&mut self) -> Result<DataDeclaration> {
@@ -1062,6 +1200,22 @@ impl Parser {
                 TokenKind::Comment(_) => {
                     blocks.push(DataBlock::Comment(self.parse_comment()?));
                 }
+                TokenKind::Keyword(Keyword::Provenance) => {
+                    blocks.push(DataBlock::Provenance(self.parse_provenance()?));
+                }
+                TokenKind::Keyword(Keyword::Review) => {
+                    blocks.push(DataBlock::Review(self.parse_review()?));
+                }
+                TokenKind::Annotation(_) => {
+                    blocks.push(DataBlock::Annotation(self.parse_annotation()?));
+                    self.consume_optional_semicolon();
+                }
+                TokenKind::Keyword(Keyword::Aggregation) => {
+                    blocks.push(DataBlock::Aggregation(self.parse_aggregation()?));
+                }
+                TokenKind::Keyword(Keyword::Year) => {
+                    blocks.push(DataBlock::Year(self.parse_year_declaration()?));
+                }
                 TokenKind::Identifier(_) => {
                     // Parse aggregation or other identifier-based blocks
                     let identifier = self.consume_identifier("Expected identifier")?;
@@ -1103,6 +1257,16 @@ impl Parser {
                                         continue;
                                     }
                                     
+                                    if self.check(&TokenKind::Keyword(Keyword::Provenance)) {
+                                        year_blocks.push(YearBlock::Provenance(self.parse_provenance()?));
+                                        continue;
+                                    }
+
+                                    if self.check(&TokenKind::Keyword(Keyword::Review)) {
+                                        year_blocks.push(YearBlock::Review(self.parse_review()?));
+                                        continue;
+                                    }
+
                                     if let TokenKind::Identifier(metric_name) = &self.current_token().kind {
                                         let name = metric_name.clone();
                                         self.advance();
@@ -1161,6 +1325,148 @@ impl Parser {
         })
     }
 
+    /// Parse aggregation declaration: aggregation = { circulation = "national"; };
+    fn parse_aggregation(&mut self) -> Result<AggregationDeclaration> {
+        let position = self.current_position();
+        self.consume_keyword(Keyword::Aggregation, "Expected 'aggregation'")?;
+        self.consume_token(TokenKind::Assign, "Expected '=' after 'aggregation'")?;
+        self.consume_token(TokenKind::LeftBrace, "Expected '{' after 'aggregation ='")?;
+
+        let mut fields = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            if self.match_token(&TokenKind::Newline) || self.match_comment() {
+                continue;
+            }
+
+            let field_pos = self.current_position();
+            let name = self.consume_identifier("Expected aggregation field name")?;
+            self.consume_token(TokenKind::Assign, "Expected '=' after aggregation field name")?;
+            let value = self.consume_string("Expected aggregation value")?;
+            fields.push(AggregationField {
+                name,
+                value,
+                position: field_pos,
+            });
+
+            self.consume_optional_semicolon();
+            self.consume_optional_comma();
+        }
+
+        self.consume_token(TokenKind::RightBrace, "Expected '}' after aggregation fields")?;
+        self.consume_optional_semicolon();
+
+        Ok(AggregationDeclaration { fields, position })
+    }
+
+    /// Parse year declaration: YEAR 2021 { metrics { circulation = { value = 1; source = "oeak"; }; } }
+    fn parse_year_declaration(&mut self) -> Result<YearDeclaration> {
+        let position = self.current_position();
+        self.consume_keyword(Keyword::Year, "Expected 'year'")?;
+        let year = self.consume_number("Expected year")?;
+        self.consume_token(TokenKind::LeftBrace, "Expected '{' after year")?;
+
+        let mut blocks = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            if matches!(self.current_token().kind, TokenKind::Newline) {
+                self.advance();
+                continue;
+            }
+
+            if self.is_comment_token(&self.current_token().kind) {
+                blocks.push(YearBlock::Comment(self.parse_comment()?));
+                continue;
+            }
+
+            match &self.current_token().kind {
+                TokenKind::Keyword(Keyword::Metrics) => {
+                    blocks.push(YearBlock::Metrics(self.parse_metrics_block()?));
+                }
+                TokenKind::Keyword(Keyword::Provenance) => {
+                    blocks.push(YearBlock::Provenance(self.parse_provenance()?));
+                }
+                TokenKind::Keyword(Keyword::Review) => {
+                    blocks.push(YearBlock::Review(self.parse_review()?));
+                }
+                _ => {
+                    let field_pos = self.current_position();
+                    let name = self.consume_identifier("Expected 'metrics' or 'comment' in year block")?;
+                    if name != "comment" {
+                        return Err(self.error(format!("Unknown year block field: {}", name)));
+                    }
+                    self.consume_token(TokenKind::Assign, "Expected '=' after 'comment'")?;
+                    let value = self.consume_string("Expected comment text")?;
+                    blocks.push(YearBlock::CommentAssignment {
+                        value,
+                        position: field_pos,
+                    });
+                    self.consume_optional_semicolon();
+                }
+            }
+        }
+
+        self.consume_token(TokenKind::RightBrace, "Expected '}' after year block")?;
+        self.consume_optional_semicolon();
+
+        Ok(YearDeclaration {
+            year,
+            blocks,
+            position,
+        })
+    }
+
+    /// Parse metrics block: metrics { circulation = { value = 1; unit = "copies"; }; }
+    fn parse_metrics_block(&mut self) -> Result<MetricsBlock> {
+        let position = self.current_position();
+        self.consume_keyword(Keyword::Metrics, "Expected 'metrics'")?;
+        self.consume_token(TokenKind::LeftBrace, "Expected '{' after 'metrics'")?;
+
+        let mut fields = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            if self.match_token(&TokenKind::Newline) || self.match_comment() {
+                continue;
+            }
+
+            let field_pos = self.current_position();
+            let name = self.consume_identifier("Expected metric name")?;
+            self.consume_token(TokenKind::Assign, "Expected '=' after metric name")?;
+            self.consume_token(TokenKind::LeftBrace, "Expected '{' after metric name")?;
+
+            let mut attributes = Vec::new();
+            while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+                if self.match_token(&TokenKind::Newline) || self.match_comment() {
+                    continue;
+                }
+
+                let attr_pos = self.current_position();
+                let attr_name = self.consume_identifier("Expected metric attribute name")?;
+                self.consume_token(TokenKind::Assign, "Expected '=' after metric attribute name")?;
+                let value = self.parse_expression()?;
+                attributes.push(MetricAttribute {
+                    name: attr_name,
+                    value,
+                    position: attr_pos,
+                });
+
+                self.consume_optional_semicolon();
+                self.consume_optional_comma();
+            }
+
+            self.consume_token(TokenKind::RightBrace, "Expected '}' after metric attributes")?;
+            self.consume_optional_semicolon();
+
+            fields.push(MetricField {
+                name,
+                attributes,
+                position: field_pos,
+            });
+        }
+
+        self.consume_token(TokenKind::RightBrace, "Expected '}' after metrics")?;
+        self.consume_optional_semicolon();
+
+        Ok(MetricsBlock { fields, position })
+    }
+
     /// Parse event declaration: EVENT name { ... }
     fn parse_event(&mut self) -> Result<EventDeclaration> {
         let position = self.current_position();
@@ -1195,6 +1501,12 @@ impl Parser {
                 TokenKind::Keyword(Keyword::Metadata) => {
                     fields.push(self.parse_event_metadata_field()?);
                 }
+                TokenKind::Keyword(Keyword::Provenance) => {
+                    fields.push(EventField::Provenance(self.parse_provenance()?));
+                }
+                TokenKind::Keyword(Keyword::Review) => {
+                    fields.push(EventField::Review(self.parse_review()?));
+                }
                 TokenKind::Annotation(_) => {
                     let annotation = self.parse_annotation()?;
                     fields.push(EventField::Annotation {
@@ -1313,7 +1625,7 @@ impl Parser {
         })
     }
 
-    /// Parse entity role: id = 123; role = "buyer"; etc.
+    /// Parse entity role: id = 123; person = 9001; role = "buyer"; etc.
     fn parse_entity_role(&mut self) -> Result<EntityRole> {
         let position = self.current_position();
 
@@ -1332,6 +1644,13 @@ impl Parser {
                 self.consume_token(TokenKind::Semicolon, "Expected ';'")?;
                 Ok(EntityRole::Id { value, position })
             }
+            TokenKind::Keyword(Keyword::Person) => {
+                self.advance();
+                self.consume_token(TokenKind::Assign, "Expected '='")?;
+                let value = self.consume_number("Expected person ID")?;
+                self.consume_token(TokenKind::Semicolon, "Expected ';'")?;
+                Ok(EntityRole::Person { value, position })
+            }
             TokenKind::Keyword(Keyword::Role) => {
                 self.advance();
                 self.consume_token(TokenKind::Assign, "Expected '='")?;
@@ -1591,6 +1910,16 @@ impl Parser {
                 fields.push(DiachronicField::Comment(comment));
                 continue;
             }
+
+            if self.check(&TokenKind::Keyword(Keyword::Provenance)) {
+                fields.push(DiachronicField::Provenance(self.parse_provenance()?));
+                continue;
+            }
+
+            if self.check(&TokenKind::Keyword(Keyword::Review)) {
+                fields.push(DiachronicField::Review(self.parse_review()?));
+                continue;
+            }
             
             // Parse field assignments - handle both identifiers and keywords
             let field_name = match &self.current_token().kind {
@@ -1623,7 +1952,7 @@ impl Parser {
                     let date_string = self.consume_string("Expected event date")?;
                     // Create a simple date range from the string
                     let date_range = DateRange {
-                        from: DateExpression::Literal(date_string),
+                        from: Some(DateExpression::Literal(date_string)),
                         to: None,
                         position: field_pos,
                     };
@@ -1688,6 +2017,16 @@ impl Parser {
                 fields.push(SynchronousField::Comment(comment));
                 continue;
             }
+
+            if self.check(&TokenKind::Keyword(Keyword::Provenance)) {
+                fields.push(SynchronousField::Provenance(self.parse_provenance()?));
+                continue;
+            }
+
+            if self.check(&TokenKind::Keyword(Keyword::Review)) {
+                fields.push(SynchronousField::Review(self.parse_review()?));
+                continue;
+            }
             
             // Parse field assignments - handle both identifiers and keywords  
             let field_name = match &self.current_token().kind {
@@ -1722,12 +2061,31 @@ impl Parser {
                     } else {
                         DateExpression::Literal(self.consume_string("Expected date or CURRENT")?)
                     };
-                    let date_range = DateRange {
-                        from: date,
-                        to: None,
-                        position: field_pos,
-                    };
-                    fields.push(SynchronousField::Period { value: date_range, position: field_pos });
+                    // period_start and period_end fill the bounds of one period
+                    let existing = fields.iter_mut().find_map(|field| match field {
+                        SynchronousField::Period { value, .. } => Some(value),
+                        _ => None,
+                    });
+                    match (existing, field_name.as_str()) {
+                        (Some(range), "period_start") => range.from = Some(date),
+                        (Some(range), _) => range.to = Some(date),
+                        (None, "period_start") => {
+                            let date_range = DateRange { from: Some(date), to: None, position: field_pos };
+                            fields.push(SynchronousField::Period { value: date_range, position: field_pos });
+                        }
+                        (None, _) => {
+                            let date_range = DateRange {
+                                from: None,
+                                to: Some(date),
+                                position: field_pos,
+                            };
+                            fields.push(SynchronousField::Period { value: date_range, position: field_pos });
+                        }
+                    }
+                }
+                "details" => {
+                    let value = self.consume_string("Expected details")?;
+                    fields.push(SynchronousField::Details { value, position: field_pos });
                 }
                 "created_by_event" => {
                     let value = self.consume_identifier("Expected event identifier")?;
@@ -1754,6 +2112,727 @@ impl Parser {
         })
     }
 
+    /// Parse company declaration: COMPANY "Name" { id = 400001; legal_form = "GmbH"; }
+    fn parse_company(&mut self) -> Result<CompanyDeclaration> {
+        let position = self.current_position();
+        self.consume_keyword(Keyword::Company, "Expected 'company'")?;
+
+        let name = if self.check_string() {
+            self.consume_string("Expected company name")?
+        } else {
+            self.consume_identifier("Expected company name")?
+        };
+
+        let fields = self.parse_company_fields("company")?;
+
+        Ok(CompanyDeclaration {
+            name,
+            fields,
+            position,
+        })
+    }
+
+    /// Parse person declaration: PERSON "Name" { id = 9001; source_url = "..."; }
+    fn parse_person(&mut self) -> Result<PersonDeclaration> {
+        let position = self.current_position();
+        self.consume_keyword(Keyword::Person, "Expected 'person'")?;
+
+        let name = if self.check_string() {
+            self.consume_string("Expected person name")?
+        } else {
+            self.consume_identifier("Expected person name")?
+        };
+
+        let fields = self.parse_company_fields("person")?;
+
+        Ok(PersonDeclaration {
+            name,
+            fields,
+            position,
+        })
+    }
+
+    /// Parse the `{ ... }` body of a company or person declaration
+    fn parse_company_fields(&mut self, kind: &str) -> Result<Vec<CompanyField>> {
+        self.consume_token(
+            TokenKind::LeftBrace,
+            &format!("Expected '{{' after {} name", kind),
+        )?;
+
+        let mut fields = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            if self.match_token(&TokenKind::Newline) {
+                continue;
+            }
+
+            if self.is_comment_token(&self.current_token().kind) {
+                let comment = self.parse_comment()?;
+                fields.push(CompanyField::Comment(comment));
+                continue;
+            }
+
+            if let TokenKind::Annotation(_) = &self.current_token().kind {
+                let annotation = self.parse_annotation()?;
+                fields.push(CompanyField::Annotation(annotation));
+                self.consume_optional_semicolon();
+                continue;
+            }
+
+            if self.check(&TokenKind::Keyword(Keyword::Provenance)) {
+                fields.push(CompanyField::Provenance(self.parse_provenance()?));
+                continue;
+            }
+
+            if self.check(&TokenKind::Keyword(Keyword::Review)) {
+                fields.push(CompanyField::Review(self.parse_review()?));
+                continue;
+            }
+
+            let field_pos = self.current_position();
+            let field_name =
+                self.consume_identifier(&format!("Expected field name in {}", kind))?;
+
+            self.consume_token(TokenKind::Assign, "Expected '=' after field name")?;
+            let value = self.parse_expression()?;
+            fields.push(CompanyField::Assignment {
+                name: field_name,
+                value,
+                position: field_pos,
+            });
+
+            self.consume_optional_semicolon();
+            self.consume_optional_comma();
+            self.skip_whitespace_and_comments();
+        }
+
+        self.consume_token(
+            TokenKind::RightBrace,
+            &format!("Expected '}}' after {} fields", kind),
+        )?;
+        self.consume_optional_semicolon();
+
+        Ok(fields)
+    }
+
+    /// Parse outlet-party link: OUTLET_PARTY_LINK name { outlet = 1; party = 2; role = "publisher"; }
+    fn parse_outlet_party_link(&mut self) -> Result<OutletPartyLink> {
+        let position = self.current_position();
+        self.consume_keyword(Keyword::OutletPartyLink, "Expected 'outlet_party_link'")?;
+
+        let name = if self.check_string() {
+            self.consume_string("Expected link name")?
+        } else {
+            self.consume_identifier("Expected link name")?
+        };
+
+        self.consume_token(TokenKind::LeftBrace, "Expected '{'")?;
+
+        let mut fields = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            if matches!(self.current_token().kind, TokenKind::Newline) {
+                self.advance();
+                continue;
+            }
+
+            if self.is_comment_token(&self.current_token().kind) {
+                let comment = self.parse_comment()?;
+                fields.push(OutletPartyField::Comment(comment));
+                continue;
+            }
+
+            if let TokenKind::Annotation(_) = &self.current_token().kind {
+                let annotation = self.parse_annotation()?;
+                fields.push(OutletPartyField::Annotation(annotation));
+                self.consume_optional_semicolon();
+                continue;
+            }
+
+            if self.check(&TokenKind::Keyword(Keyword::Provenance)) {
+                fields.push(OutletPartyField::Provenance(self.parse_provenance()?));
+                continue;
+            }
+
+            if self.check(&TokenKind::Keyword(Keyword::Review)) {
+                fields.push(OutletPartyField::Review(self.parse_review()?));
+                continue;
+            }
+
+            let field_name = match &self.current_token().kind {
+                TokenKind::Identifier(name) => name.clone(),
+                TokenKind::Keyword(kw) => kw.to_string(),
+                _ => {
+                    return Err(self.error(format!(
+                        "Expected field name in outlet party link, found {:?}",
+                        self.current_token().kind
+                    )));
+                }
+            };
+
+            let field_pos = self.current_position();
+            self.advance(); // consume field name
+
+            self.consume_token(TokenKind::Assign, "Expected '=' after field name")?;
+
+            match field_name.as_str() {
+                "outlet" => {
+                    let value = self.consume_number("Expected outlet ID")?;
+                    fields.push(OutletPartyField::Outlet { value, position: field_pos });
+                }
+                "party" | "company" => {
+                    let value = self.consume_number("Expected company ID")?;
+                    fields.push(OutletPartyField::Party { value, position: field_pos });
+                }
+                "role" => {
+                    let value = self.consume_string("Expected role")?;
+                    fields.push(OutletPartyField::Role { value, position: field_pos });
+                }
+                "period_start" | "period_end" => {
+                    let date = if self.match_keyword(Keyword::Current) {
+                        DateExpression::Current
+                    } else {
+                        DateExpression::Literal(self.consume_string("Expected date or CURRENT")?)
+                    };
+                    if field_name == "period_start" {
+                        fields.push(OutletPartyField::PeriodStart { value: date, position: field_pos });
+                    } else {
+                        fields.push(OutletPartyField::PeriodEnd { value: date, position: field_pos });
+                    }
+                }
+                "source" => {
+                    let value = self.consume_string("Expected source")?;
+                    fields.push(OutletPartyField::Source { value, position: field_pos });
+                }
+                _ => {
+                    return Err(self.error(format!("Unknown outlet party link field: {}", field_name)));
+                }
+            }
+
+            self.consume_optional_semicolon();
+            self.consume_optional_comma();
+            self.skip_whitespace_and_comments();
+        }
+
+        self.consume_token(TokenKind::RightBrace, "Expected '}'")?;
+        self.consume_optional_semicolon();
+
+        Ok(OutletPartyLink {
+            name,
+            fields,
+            position,
+        })
+    }
+
+    /// Parse ownership relation: OWNERSHIP name { owner_company = 1; owned_company = 2; share_pct = 50; }
+    fn parse_ownership(&mut self) -> Result<OwnershipDeclaration> {
+        let position = self.current_position();
+        self.consume_keyword(Keyword::Ownership, "Expected 'ownership'")?;
+
+        let name = if self.check_string() {
+            self.consume_string("Expected ownership name")?
+        } else {
+            self.consume_identifier("Expected ownership name")?
+        };
+
+        self.consume_token(TokenKind::LeftBrace, "Expected '{'")?;
+
+        let mut fields = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            if matches!(self.current_token().kind, TokenKind::Newline) {
+                self.advance();
+                continue;
+            }
+
+            if self.is_comment_token(&self.current_token().kind) {
+                let comment = self.parse_comment()?;
+                fields.push(OwnershipField::Comment(comment));
+                continue;
+            }
+
+            if let TokenKind::Annotation(_) = &self.current_token().kind {
+                let annotation = self.parse_annotation()?;
+                fields.push(OwnershipField::Annotation(annotation));
+                self.consume_optional_semicolon();
+                continue;
+            }
+
+            if self.check(&TokenKind::Keyword(Keyword::Provenance)) {
+                fields.push(OwnershipField::Provenance(self.parse_provenance()?));
+                continue;
+            }
+
+            if self.check(&TokenKind::Keyword(Keyword::Review)) {
+                fields.push(OwnershipField::Review(self.parse_review()?));
+                continue;
+            }
+
+            let field_pos = self.current_position();
+            let field_name = self.consume_identifier("Expected field name in ownership")?;
+
+            self.consume_token(TokenKind::Assign, "Expected '=' after field name")?;
+
+            match field_name.as_str() {
+                "owner_company" => {
+                    let value = self.consume_number("Expected company ID")?;
+                    fields.push(OwnershipField::OwnerCompany { value, position: field_pos });
+                }
+                "owner_person" => {
+                    let value = self.consume_number("Expected person ID")?;
+                    fields.push(OwnershipField::OwnerPerson { value, position: field_pos });
+                }
+                "owned_company" => {
+                    let value = self.consume_number("Expected company ID")?;
+                    fields.push(OwnershipField::OwnedCompany { value, position: field_pos });
+                }
+                "share_pct" => {
+                    let value = self.consume_number("Expected share percentage")?;
+                    fields.push(OwnershipField::SharePct { value, position: field_pos });
+                }
+                "role" => {
+                    let value = self.consume_string("Expected role")?;
+                    fields.push(OwnershipField::Role { value, position: field_pos });
+                }
+                "period_start" | "period_end" => {
+                    let date = if self.match_keyword(Keyword::Current) {
+                        DateExpression::Current
+                    } else {
+                        DateExpression::Literal(self.consume_string("Expected date or CURRENT")?)
+                    };
+                    if field_name == "period_start" {
+                        fields.push(OwnershipField::PeriodStart { value: date, position: field_pos });
+                    } else {
+                        fields.push(OwnershipField::PeriodEnd { value: date, position: field_pos });
+                    }
+                }
+                "source_url" => {
+                    let value = self.consume_string("Expected source URL")?;
+                    fields.push(OwnershipField::SourceUrl { value, position: field_pos });
+                }
+                "scraped_at" => {
+                    let value = self.consume_string("Expected scrape timestamp")?;
+                    fields.push(OwnershipField::ScrapedAt { value, position: field_pos });
+                }
+                _ => {
+                    return Err(self.error(format!("Unknown ownership field: {}", field_name)));
+                }
+            }
+
+            self.consume_optional_semicolon();
+            self.consume_optional_comma();
+            self.skip_whitespace_and_comments();
+        }
+
+        self.consume_token(TokenKind::RightBrace, "Expected '}'")?;
+        self.consume_optional_semicolon();
+
+        Ok(OwnershipDeclaration {
+            name,
+            fields,
+            position,
+        })
+    }
+
+    /// Parse identifier link: IDENTIFIER_LINK name { company = 1; scheme = "northdata"; value = "123"; }
+    fn parse_identifier_link(&mut self) -> Result<IdentifierLink> {
+        let position = self.current_position();
+        self.consume_keyword(Keyword::IdentifierLink, "Expected 'identifier_link'")?;
+
+        let name = if self.check_string() {
+            self.consume_string("Expected link name")?
+        } else {
+            self.consume_identifier("Expected link name")?
+        };
+
+        self.consume_token(TokenKind::LeftBrace, "Expected '{'")?;
+
+        let mut fields = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            if matches!(self.current_token().kind, TokenKind::Newline) {
+                self.advance();
+                continue;
+            }
+
+            if self.is_comment_token(&self.current_token().kind) {
+                let comment = self.parse_comment()?;
+                fields.push(IdentifierLinkField::Comment(comment));
+                continue;
+            }
+
+            if let TokenKind::Annotation(_) = &self.current_token().kind {
+                let annotation = self.parse_annotation()?;
+                fields.push(IdentifierLinkField::Annotation(annotation));
+                self.consume_optional_semicolon();
+                continue;
+            }
+
+            if self.check(&TokenKind::Keyword(Keyword::Provenance)) {
+                fields.push(IdentifierLinkField::Provenance(self.parse_provenance()?));
+                continue;
+            }
+
+            if self.check(&TokenKind::Keyword(Keyword::Review)) {
+                fields.push(IdentifierLinkField::Review(self.parse_review()?));
+                continue;
+            }
+
+            let field_pos = self.current_position();
+            let field_name = self.consume_identifier("Expected field name in identifier link")?;
+
+            self.consume_token(TokenKind::Assign, "Expected '=' after field name")?;
+
+            match field_name.as_str() {
+                "company" => {
+                    let value = self.consume_number("Expected company ID")?;
+                    fields.push(IdentifierLinkField::Company { value, position: field_pos });
+                }
+                "scheme" => {
+                    let value = self.consume_string("Expected identifier scheme")?;
+                    fields.push(IdentifierLinkField::Scheme { value, position: field_pos });
+                }
+                "value" => {
+                    // Numeric identifiers may be written without quotes
+                    let value = if self.check_string() {
+                        self.consume_string("Expected identifier value")?
+                    } else {
+                        self.consume_number("Expected identifier value")?.to_string()
+                    };
+                    fields.push(IdentifierLinkField::Value { value, position: field_pos });
+                }
+                "status" => {
+                    let value = self.consume_string("Expected status")?;
+                    fields.push(IdentifierLinkField::Status { value, position: field_pos });
+                }
+                "score" => {
+                    let value = self.consume_number("Expected score")?;
+                    fields.push(IdentifierLinkField::Score { value, position: field_pos });
+                }
+                "issued_by" => {
+                    let value = self.consume_string("Expected issuer")?;
+                    fields.push(IdentifierLinkField::IssuedBy { value, position: field_pos });
+                }
+                _ => {
+                    return Err(self.error(format!("Unknown identifier link field: {}", field_name)));
+                }
+            }
+
+            self.consume_optional_semicolon();
+            self.consume_optional_comma();
+            self.skip_whitespace_and_comments();
+        }
+
+        self.consume_token(TokenKind::RightBrace, "Expected '}'")?;
+        self.consume_optional_semicolon();
+
+        Ok(IdentifierLink {
+            name,
+            fields,
+            position,
+        })
+    }
+
+    /// Parse redaction policy: REDACTION public { persons = "pseudonymise"; salt = "..."; }
+    fn parse_redaction(&mut self) -> Result<RedactionPolicy> {
+        let position = self.current_position();
+        self.consume_keyword(Keyword::Redaction, "Expected 'redaction'")?;
+
+        let name = if self.check_string() {
+            self.consume_string("Expected policy name")?
+        } else {
+            self.consume_identifier("Expected policy name")?
+        };
+
+        self.consume_token(TokenKind::LeftBrace, "Expected '{'")?;
+
+        let mut fields = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            if matches!(self.current_token().kind, TokenKind::Newline) {
+                self.advance();
+                continue;
+            }
+
+            if self.is_comment_token(&self.current_token().kind) {
+                let comment = self.parse_comment()?;
+                fields.push(RedactionField::Comment(comment));
+                continue;
+            }
+
+            let field_pos = self.current_position();
+            let field_name = self.consume_identifier("Expected field name in redaction policy")?;
+
+            self.consume_token(TokenKind::Assign, "Expected '=' after field name")?;
+
+            match field_name.as_str() {
+                "persons" => {
+                    let value = self.consume_string("Expected redaction action")?;
+                    fields.push(RedactionField::Persons { value, position: field_pos });
+                }
+                "event_persons" => {
+                    let value = self.consume_string("Expected redaction action")?;
+                    fields.push(RedactionField::EventPersons { value, position: field_pos });
+                }
+                "person_metadata" => {
+                    // Comma-separated list of field names
+                    let value = self
+                        .consume_string("Expected metadata field names")?
+                        .split(',')
+                        .map(|name| name.trim().to_string())
+                        .filter(|name| !name.is_empty())
+                        .collect();
+                    fields.push(RedactionField::PersonMetadata { value, position: field_pos });
+                }
+                "salt" => {
+                    let value = self.consume_string("Expected salt")?;
+                    fields.push(RedactionField::Salt { value, position: field_pos });
+                }
+                _ => {
+                    return Err(self.error(format!("Unknown redaction field: {}", field_name)));
+                }
+            }
+
+            self.consume_optional_semicolon();
+            self.skip_whitespace_and_comments();
+        }
+
+        self.consume_token(TokenKind::RightBrace, "Expected '}'")?;
+        self.consume_optional_semicolon();
+
+        Ok(RedactionPolicy {
+            name,
+            fields,
+            position,
+        })
+    }
+
+    /// Parse relationship type: RELATIONSHIP_TYPE umbrella { direction = "directed"; roles = "source/target"; }
+    fn parse_relationship_type(&mut self) -> Result<RelationshipTypeDeclaration> {
+        let position = self.current_position();
+        self.consume_keyword(Keyword::RelationshipType, "Expected 'relationship_type'")?;
+
+        let name = if self.check_string() {
+            self.consume_string("Expected relationship type name")?
+        } else {
+            self.consume_identifier("Expected relationship type name")?
+        };
+
+        self.consume_token(TokenKind::LeftBrace, "Expected '{'")?;
+
+        let mut fields = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            if matches!(self.current_token().kind, TokenKind::Newline) {
+                self.advance();
+                continue;
+            }
+
+            if self.is_comment_token(&self.current_token().kind) {
+                let comment = self.parse_comment()?;
+                fields.push(RelationshipTypeField::Comment(comment));
+                continue;
+            }
+
+            let field_pos = self.current_position();
+            let field_name = self.consume_identifier("Expected field name in relationship type")?;
+
+            self.consume_token(TokenKind::Assign, "Expected '=' after field name")?;
+
+            match field_name.as_str() {
+                "direction" => {
+                    let value = self.consume_string("Expected direction")?;
+                    fields.push(RelationshipTypeField::Direction {
+                        value,
+                        position: field_pos,
+                    });
+                }
+                "roles" => {
+                    // Comma-separated pairs such as "source/target, primary/integrated"
+                    let mut value = Vec::new();
+                    for pair in self.consume_string("Expected role pairs")?.split(',') {
+                        match pair.split_once('/') {
+                            Some((from, to))
+                                if !from.trim().is_empty() && !to.trim().is_empty() =>
+                            {
+                                value.push((from.trim().to_string(), to.trim().to_string()));
+                            }
+                            _ if pair.trim().is_empty() => {}
+                            _ => {
+                                return Err(self.error(format!(
+                                    "Expected role pair like \"source/target\", found \"{}\"",
+                                    pair.trim()
+                                )));
+                            }
+                        }
+                    }
+                    fields.push(RelationshipTypeField::Roles {
+                        value,
+                        position: field_pos,
+                    });
+                }
+                _ => {
+                    return Err(self.error(format!(
+                        "Unknown relationship type field: {}",
+                        field_name
+                    )));
+                }
+            }
+
+            self.consume_optional_semicolon();
+            self.skip_whitespace_and_comments();
+        }
+
+        self.consume_token(TokenKind::RightBrace, "Expected '}'")?;
+        self.consume_optional_semicolon();
+
+        Ok(RelationshipTypeDeclaration {
+            name,
+            fields,
+            position,
+        })
+    }
+
+    /// Parse provenance clause: PROVENANCE [target] { source = "oeak"; confidence = 0.9; }
+    fn parse_provenance(&mut self) -> Result<ProvenanceClause> {
+        let position = self.current_position();
+        self.consume_keyword(Keyword::Provenance, "Expected 'provenance'")?;
+
+        // Without a target the clause applies to the enclosing declaration
+        let target = if self.check(&TokenKind::LeftBrace) {
+            None
+        } else if self.check_string() {
+            Some(self.consume_string("Expected provenance target")?)
+        } else {
+            Some(self.consume_identifier("Expected provenance target")?)
+        };
+
+        self.consume_token(TokenKind::LeftBrace, "Expected '{' after 'provenance'")?;
+
+        let mut fields = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            if matches!(self.current_token().kind, TokenKind::Newline) {
+                self.advance();
+                continue;
+            }
+
+            if self.is_comment_token(&self.current_token().kind) {
+                let comment = self.parse_comment()?;
+                fields.push(ProvenanceField::Comment(comment));
+                continue;
+            }
+
+            let field_pos = self.current_position();
+            let field_name = self.consume_identifier("Expected field name in provenance")?;
+
+            self.consume_token(TokenKind::Assign, "Expected '=' after field name")?;
+
+            match field_name.as_str() {
+                "source" => {
+                    let value = self.consume_string("Expected source name")?;
+                    fields.push(ProvenanceField::Source { value, position: field_pos });
+                }
+                "confidence" => {
+                    let value = self.consume_number("Expected confidence")?;
+                    fields.push(ProvenanceField::Confidence { value, position: field_pos });
+                }
+                "verification" => {
+                    let value = self.consume_string("Expected verification status")?;
+                    fields.push(ProvenanceField::Verification { value, position: field_pos });
+                }
+                "verified_at" => {
+                    let value = self.consume_string("Expected verification date")?;
+                    fields.push(ProvenanceField::VerifiedAt { value, position: field_pos });
+                }
+                "note" => {
+                    let value = self.consume_string("Expected note")?;
+                    fields.push(ProvenanceField::Note { value, position: field_pos });
+                }
+                _ => {
+                    return Err(self.error(format!("Unknown provenance field: {}", field_name)));
+                }
+            }
+
+            self.consume_optional_semicolon();
+            self.consume_optional_comma();
+            self.skip_whitespace_and_comments();
+        }
+
+        self.consume_token(TokenKind::RightBrace, "Expected '}' after provenance fields")?;
+        self.consume_optional_semicolon();
+
+        Ok(ProvenanceClause {
+            target,
+            fields,
+            position,
+        })
+    }
+
+    /// Parse review clause: REVIEW [target] { status = "published"; reviewer = "mk"; }
+    fn parse_review(&mut self) -> Result<ReviewClause> {
+        let position = self.current_position();
+        self.consume_keyword(Keyword::Review, "Expected 'review'")?;
+
+        // Without a target the clause applies to the enclosing declaration
+        let target = if self.check(&TokenKind::LeftBrace) {
+            None
+        } else if self.check_string() {
+            Some(self.consume_string("Expected review target")?)
+        } else {
+            Some(self.consume_identifier("Expected review target")?)
+        };
+
+        self.consume_token(TokenKind::LeftBrace, "Expected '{' after 'review'")?;
+
+        let mut fields = Vec::new();
+        while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
+            if matches!(self.current_token().kind, TokenKind::Newline) {
+                self.advance();
+                continue;
+            }
+
+            if self.is_comment_token(&self.current_token().kind) {
+                let comment = self.parse_comment()?;
+                fields.push(ReviewField::Comment(comment));
+                continue;
+            }
+
+            let field_pos = self.current_position();
+            let field_name = self.consume_identifier("Expected field name in review")?;
+
+            self.consume_token(TokenKind::Assign, "Expected '=' after field name")?;
+
+            match field_name.as_str() {
+                "status" => {
+                    let value = self.consume_string("Expected publication status")?;
+                    fields.push(ReviewField::Status { value, position: field_pos });
+                }
+                "reviewer" => {
+                    let value = self.consume_string("Expected reviewer")?;
+                    fields.push(ReviewField::Reviewer { value, position: field_pos });
+                }
+                "reviewed_at" => {
+                    let value = self.consume_string("Expected review date")?;
+                    fields.push(ReviewField::ReviewedAt { value, position: field_pos });
+                }
+                "note" => {
+                    let value = self.consume_string("Expected note")?;
+                    fields.push(ReviewField::Note { value, position: field_pos });
+                }
+                _ => {
+                    return Err(self.error(format!("Unknown review field: {}", field_name)));
+                }
+            }
+
+            self.consume_optional_semicolon();
+            self.consume_optional_comma();
+            self.skip_whitespace_and_comments();
+        }
+
+        self.consume_token(TokenKind::RightBrace, "Expected '}' after review fields")?;
+        self.consume_optional_semicolon();
+
+        Ok(ReviewClause {
+            target,
+            fields,
+            position,
+        })
+    }
+
     fn parse_outlet_spec(&mut self) -> Result<OutletSpec> {
         self.consume_token(TokenKind::LeftBrace, "Expected '{' for outlet spec")?;
         
@@ -2007,6 +3086,10 @@ impl Parser {
                     Keyword::Date => "date",
                     Keyword::Entities => "entities",
                     Keyword::Impact => "impact",
+                    Keyword::Source => "source",
+                    Keyword::Company => "company",
+                    Keyword::Person => "person",
+                    Keyword::Ownership => "ownership",
                     _ => return Err(self.error(message.to_string())),
                 };
                 self.advance();
@@ -2102,7 +3185,13 @@ impl Parser {
                 | TokenKind::Keyword(Keyword::Vocabulary)
                 | TokenKind::Keyword(Keyword::Family)
                 | TokenKind::Keyword(Keyword::Template)
-                | TokenKind::Keyword(Keyword::Data) => return,
+                | TokenKind::Keyword(Keyword::Data)
+                | TokenKind::Keyword(Keyword::Company)
+                | TokenKind::Keyword(Keyword::OutletPartyLink)
+                | TokenKind::Keyword(Keyword::Person)
+                | TokenKind::Keyword(Keyword::Ownership)
+                | TokenKind::Keyword(Keyword::IdentifierLink)
+                | TokenKind::Keyword(Keyword::Redaction) => return,
                 _ => {}
             }
 
@@ -7,7 +7,11 @@
 //! - Domain-specific MediaLanguage validation
 
 use crate::error::SourcePosition;
+use crate::ir::nodes::{
+    IRRelationshipType, IRSource, IRSyncOutlet, LinkDirection, RedactionAction, RoleOrder,
+};
 use crate::parser::ast::*;
+use crate::utils::dates::overlaps;
 use std::collections::{HashMap, HashSet};
 
 /// Validation severity levels
@@ -62,6 +66,59 @@ pub struct ValidationSummary {
     pub total_constructs: usize,
 }
 
+/// Review states of an IDENTIFIER_LINK
+const KNOWN_IDENTIFIER_STATUSES: &[&str] = &["proposed", "accepted", "rejected"];
+
+/// Verification states of a PROVENANCE clause
+const KNOWN_VERIFICATION_STATUSES: &[&str] = &["unverified", "verified", "disputed", "retracted"];
+
+/// Publication states of a REVIEW clause; only `published` passes `--publish-only`
+const KNOWN_PUBLICATION_STATUSES: &[&str] =
+    &["draft", "in_review", "reviewed", "published", "withdrawn"];
+
+/// Field names a PROVENANCE clause may target, per construct with a fixed field set
+const OWNERSHIP_TARGETS: &[&str] = &[
+    "owner_company",
+    "owner_person",
+    "owned_company",
+    "share_pct",
+    "role",
+    "period_start",
+    "period_end",
+    "source_url",
+    "scraped_at",
+];
+const IDENTIFIER_LINK_TARGETS: &[&str] =
+    &["company", "scheme", "value", "status", "score", "issued_by"];
+const OUTLET_PARTY_TARGETS: &[&str] =
+    &["outlet", "party", "role", "period_start", "period_end", "source"];
+const DIACHRONIC_TARGETS: &[&str] = &[
+    "predecessor",
+    "successor",
+    "relationship_type",
+    "event_date",
+    "triggered_by_event",
+];
+const SYNCHRONOUS_TARGETS: &[&str] = &[
+    "outlet_1",
+    "outlet_2",
+    "relationship_type",
+    "period_start",
+    "period_end",
+    "details",
+    "created_by_event",
+];
+const EVENT_TARGETS: &[&str] = &["type", "date", "status", "entities", "impact", "metadata"];
+
+/// Roles a company can hold towards an outlet in an OUTLET_PARTY_LINK
+const KNOWN_PARTY_ROLES: &[&str] = &[
+    "publisher",
+    "broadcaster",
+    "licence_holder",
+    "operator",
+    "owner",
+];
+
 /// Symbol table for tracking declarations and references
 #[derive(Debug, Clone)]
 struct SymbolTable {
@@ -81,6 +138,29 @@ struct SymbolTable {
     outlets: HashMap<u32, SourcePosition>,
     /// Outlet names (name -> ID)
     outlet_names: HashMap<String, u32>,
+    /// Company declarations (ID -> position)
+    companies: HashMap<u32, SourcePosition>,
+    /// Person declarations (ID -> position)
+    persons: HashMap<u32, SourcePosition>,
+    /// Catalog sources (name -> position)
+    sources: HashMap<String, SourcePosition>,
+    /// Catalog sources declaring an ANMI type code
+    anmi_sources: HashSet<String>,
+    /// Declared relationship types (name -> semantics, position)
+    relationship_types: HashMap<String, (IRRelationshipType, SourcePosition)>,
+}
+
+/// A synchronous link as needed for the duplicate check
+#[derive(Debug, Clone)]
+struct SyncLinkKey {
+    name: String,
+    relationship_type: String,
+    /// Outlets in the canonical order of the type
+    endpoints: (u32, u32),
+    outlet_1: u32,
+    period_start: Option<String>,
+    period_end: Option<String>,
+    position: SourcePosition,
 }
 
 /// Comprehensive semantic validator
@@ -91,6 +171,8 @@ pub struct Validator {
     issues: Vec<ValidationIssue>,
     /// Current validation context
     context: Vec<String>,
+    /// Synchronous links seen so far, for the duplicate check
+    sync_links: Vec<SyncLinkKey>,
 }
 
 impl Validator {
@@ -106,9 +188,15 @@ impl Validator {
                 families: HashMap::new(),
                 outlets: HashMap::new(),
                 outlet_names: HashMap::new(),
+                companies: HashMap::new(),
+                persons: HashMap::new(),
+                sources: HashMap::new(),
+                anmi_sources: HashSet::new(),
+                relationship_types: HashMap::new(),
             },
             issues: Vec::new(),
             context: Vec::new(),
+            sync_links: Vec::new(),
         }
     }
 
@@ -257,6 +345,132 @@ impl Validator {
                         }
                     }
                 }
+                Statement::Company(company) => {
+                    if let Some(id) = self.extract_company_id(&company.fields) {
+                        if let Some(existing) =
+                            self.symbols.companies.insert(id, company.position)
+                        {
+                            self.add_error(
+                                "COMPANY_ID_DUPLICATE",
+                                format!("Company ID {} is already used", id),
+                                company.position,
+                                Some(format!(
+                                    "Previous company at {}:{}",
+                                    existing.line, existing.column
+                                )),
+                            );
+                        }
+                    }
+                }
+                Statement::Person(person) => {
+                    if let Some(id) = self.extract_company_id(&person.fields) {
+                        if let Some(existing) = self.symbols.persons.insert(id, person.position) {
+                            self.add_error(
+                                "PERSON_ID_DUPLICATE",
+                                format!("Person ID {} is already used", id),
+                                person.position,
+                                Some(format!(
+                                    "Previous person at {}:{}",
+                                    existing.line, existing.column
+                                )),
+                            );
+                        }
+                    }
+                }
+                Statement::RelationshipType(declaration) => {
+                    let mut semantics = IRRelationshipType {
+                        name: declaration.name.clone(),
+                        direction: LinkDirection::Directed,
+                        role_pairs: Vec::new(),
+                    };
+                    for field in &declaration.fields {
+                        match field {
+                            RelationshipTypeField::Direction { value, .. } => {
+                                if let Some(direction) = LinkDirection::parse(value) {
+                                    semantics.direction = direction;
+                                }
+                            }
+                            RelationshipTypeField::Roles { value, .. } => {
+                                semantics.role_pairs.extend(value.iter().cloned())
+                            }
+                            RelationshipTypeField::Comment(_) => {}
+                        }
+                    }
+                    if let Some((_, existing)) = self
+                        .symbols
+                        .relationship_types
+                        .insert(declaration.name.clone(), (semantics, declaration.position))
+                    {
+                        self.add_error(
+                            "RELATIONSHIP_TYPE_REDECLARED",
+                            format!(
+                                "Relationship type '{}' is already declared",
+                                declaration.name
+                            ),
+                            declaration.position,
+                            Some(format!(
+                                "Previous declaration at {}:{}",
+                                existing.line, existing.column
+                            )),
+                        );
+                    }
+                }
+                Statement::Catalog(catalog) => {
+                    for source in &catalog.sources {
+                        if let Some(existing) = self
+                            .symbols
+                            .sources
+                            .insert(source.name.clone(), source.position)
+                        {
+                            self.add_error(
+                                "SOURCE_DUPLICATE",
+                                format!("Source '{}' is already declared", source.name),
+                                source.position,
+                                Some(format!(
+                                    "Previous declaration at {}:{}; sources are referred to by name alone",
+                                    existing.line, existing.column
+                                )),
+                            );
+                        }
+                        let type_code = source.fields.iter().find_map(|field| match field {
+                            SourceField::NestedAssignment { name, fields, .. }
+                                if name == "anmi_source_id_components" =>
+                            {
+                                fields.iter().find_map(|nested| match nested {
+                                    NestedSourceField::Assignment {
+                                        name,
+                                        value,
+                                        position,
+                                    } if name == "type_code" => Some((value, *position)),
+                                    _ => None,
+                                })
+                            }
+                            _ => None,
+                        });
+                        match type_code {
+                            Some((Expression::Number(n), _))
+                                if IRSource::type_code(*n).is_some() =>
+                            {
+                                self.symbols.anmi_sources.insert(source.name.clone());
+                            }
+                            Some((_, position)) => {
+                                self.add_error(
+                                    "SOURCE_TYPE_CODE_INVALID",
+                                    format!(
+                                        "Source '{}' has an invalid ANMI type_code",
+                                        source.name
+                                    ),
+                                    position,
+                                    Some(format!(
+                                        "Use a whole number from 0 to {}; it forms the leading digits of the composite source ID",
+                                        IRSource::MAX_TYPE_CODE
+                                    )),
+                                );
+                            }
+                            None => {}
+                        }
+                    }
+                }
                 _ => {}
             }
         }
@@ -274,6 +488,16 @@ impl Validator {
                 Statement::Family(family) => self.validate_family(family),
                 Statement::Data(data) => self.validate_data(data),
                 Statement::Relationship(rel) => self.validate_relationship(rel),
+                Statement::Company(company) => self.validate_company(company),
+                Statement::OutletPartyLink(link) => self.validate_outlet_party_link(link),
+                Statement::Person(person) => self.validate_person(person),
+                Statement::Ownership(ownership) => self.validate_ownership(ownership),
+                Statement::IdentifierLink(link) => self.validate_identifier_link(link),
+                Statement::Event(event) => self.validate_event(event),
+                Statement::Redaction(policy) => self.validate_redaction(policy),
+                Statement::RelationshipType(declaration) => {
+                    self.validate_relationship_type(declaration)
+                }
                 _ => {}
             }
         }
@@ -534,6 +758,8 @@ impl Validator {
         let mut outlet_count = 0;
         let mut relationship_count = 0;
         let mut _data_count = 0;
+        let mut reviews = Vec::new();
+        let mut sourced = Vec::new();
 
         for member in &family.members {
             match member {
@@ -549,10 +775,17 @@ impl Validator {
                     _data_count += 1;
                     self.validate_data(data);
                 }
+                FamilyMember::Provenance(clause) => {
+                    sourced.push(clause.target.as_deref());
+                    self.validate_provenance(clause, None);
+                }
+                FamilyMember::Review(clause) => reviews.push(clause),
                 _ => {}
             }
         }
 
+        self.validate_reviews(&reviews, None, &sourced);
+
         // Validate family structure
         if outlet_count == 0 {
             self.add_warning(
@@ -595,6 +828,21 @@ impl Validator {
             self.validate_outlet_block(block);
         }
 
+        let targets = Self::outlet_field_names(outlet);
+        let mut reviews = Vec::new();
+        let mut sourced = Vec::new();
+        for block in &outlet.blocks {
+            match block {
+                OutletBlock::Provenance(clause) => {
+                    sourced.push(clause.target.as_deref());
+                    self.validate_provenance(clause, Some(&targets));
+                }
+                OutletBlock::Review(clause) => reviews.push(clause),
+                _ => {}
+            }
+        }
+        self.validate_reviews(&reviews, Some(&targets), &sourced);
+
         if !has_identity {
             self.add_error(
                 "OUTLET_NO_IDENTITY",
@@ -663,6 +911,9 @@ impl Validator {
             OutletBlock::Metadata(metadata) => {
                 self.validate_metadata_block(metadata);
             }
+            OutletBlock::Provenance(_) | OutletBlock::Review(_) => {
+                // Validated against the whole outlet in validate_outlet
+            }
             OutletBlock::Comment(_) => {
                 // Comments don't need validation
             }
@@ -814,9 +1065,99 @@ impl Validator {
             );
         }
 
+        // A source for the whole data declaration also covers every year
+        let data_sourced = data.blocks.iter().any(|block| {
+            matches!(block, DataBlock::Provenance(clause) if clause.target.is_none())
+        });
+        let mut reviews = Vec::new();
+
+        for block in &data.blocks {
+            match block {
+                DataBlock::Provenance(clause) => self.validate_provenance(clause, None),
+                DataBlock::Review(clause) => reviews.push(clause),
+                DataBlock::Year(year) => {
+                    let metrics: Vec<&str> = year
+                        .blocks
+                        .iter()
+                        .filter_map(|block| match block {
+                            YearBlock::Metrics(metrics) => Some(metrics),
+                            _ => None,
+                        })
+                        .flat_map(|metrics| metrics.fields.iter().map(|f| f.name.as_str()))
+                        .collect();
+                    let mut year_reviews = Vec::new();
+                    let mut year_sourced = Vec::new();
+                    if data_sourced {
+                        year_sourced.push(None);
+                    }
+                    for year_block in &year.blocks {
+                        match year_block {
+                            YearBlock::Provenance(clause) => {
+                                year_sourced.push(clause.target.as_deref());
+                                self.validate_provenance(clause, Some(&metrics));
+                            }
+                            YearBlock::Review(clause) => year_reviews.push(clause),
+                            YearBlock::Metrics(metrics_block) => {
+                                for metric in &metrics_block.fields {
+                                    for attribute in
+                                        metric.attributes.iter().filter(|a| a.name == "source")
+                                    {
+                                        if let Expression::String(source) = &attribute.value {
+                                            year_sourced.push(Some(metric.name.as_str()));
+                                            self.validate_metric_source(source, attribute.position);
+                                        }
+                                    }
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    self.validate_reviews(&year_reviews, Some(&metrics), &year_sourced);
+                }
+                _ => {}
+            }
+        }
+
+        let sourced: &[Option<&str>] = if data_sourced { &[None] } else { &[] };
+        self.validate_reviews(&reviews, None, sourced);
+
         self.pop_context();
     }
 
+    /// Validate a metric source against the catalog sources
+    fn validate_metric_source(&mut self, source: &str, position: SourcePosition) {
+        // Numeric sources are composite ANMI source IDs carried over from an import
+        if !source.is_empty() && source.chars().all(|c| c.is_ascii_digit()) {
+            return;
+        }
+
+        if self.symbols.sources.is_empty() {
+            self.add_warning(
+                "METRIC_SOURCE_UNRESOLVED",
+                format!("Metric source '{}' cannot be resolved, no CATALOG is declared", source),
+                position,
+                Some("Declare the source in a CATALOG block".to_string()),
+            );
+        } else if !self.symbols.sources.contains_key(source) {
+            self.add_error(
+                "METRIC_UNKNOWN_SOURCE",
+                format!("Metric source '{}' is not declared in any CATALOG", source),
+                position,
+                Some("Add the source to a CATALOG block".to_string()),
+            );
+        } else if !self.symbols.anmi_sources.contains(source) {
+            self.add_warning(
+                "METRIC_SOURCE_NO_TYPE_CODE",
+                format!(
+                    "Source '{}' has no anmi_source_id_components {{ type_code }}, its ANMI source ID cannot be computed",
+                    source
+                ),
+                position,
+                Some("Add anmi_source_id_components { type_code = ...; } to the source".to_string()),
+            );
+        }
+    }
+
     /// Validate relationship declaration
     fn validate_relationship(&mut self, rel: &RelationshipDeclaration) {
         match rel {
@@ -836,6 +1177,8 @@ impl Validator {
         // Extract outlet IDs from fields
         let mut predecessor_id = None;
         let mut successor_id = None;
+        let mut reviews = Vec::new();
+        let mut sourced = Vec::new();
 
         for field in &diachronic.fields {
             match field {
@@ -845,10 +1188,17 @@ impl Validator {
                 DiachronicField::Successor { value, .. } => {
                     successor_id = Some(*value as u32);
                 }
+                DiachronicField::Provenance(clause) => {
+                    sourced.push(clause.target.as_deref());
+                    self.validate_provenance(clause, Some(DIACHRONIC_TARGETS));
+                }
+                DiachronicField::Review(clause) => reviews.push(clause),
                 _ => {}
             }
         }
 
+        self.validate_reviews(&reviews, Some(DIACHRONIC_TARGETS), &sourced);
+
         // Validate outlet references
         if let Some(pred_id) = predecessor_id {
             if !self.symbols.outlets.contains_key(&pred_id) {
@@ -892,19 +1242,44 @@ impl Validator {
         // Extract outlet IDs from fields
         let mut outlet_1_id = None;
         let mut outlet_2_id = None;
+        let mut outlet_1_role = None;
+        let mut outlet_2_role = None;
+        let mut relationship_type = None;
+        let mut period = (None, None);
+        let mut reviews = Vec::new();
+        let mut sourced = Vec::new();
 
         for field in &sync.fields {
             match field {
                 SynchronousField::Outlet1 { spec, .. } => {
                     outlet_1_id = Some(spec.id as u32);
+                    outlet_1_role = spec.role.clone();
                 }
                 SynchronousField::Outlet2 { spec, .. } => {
                     outlet_2_id = Some(spec.id as u32);
+                    outlet_2_role = spec.role.clone();
                 }
+                SynchronousField::RelationshipType { value, .. } => {
+                    relationship_type = Some(value.clone());
+                }
+                SynchronousField::Period { value, .. } => {
+                    let date = |date: &DateExpression| match date {
+                        DateExpression::Literal(date) => date.clone(),
+                        DateExpression::Current => "CURRENT".to_string(),
+                    };
+                    period = (value.from.as_ref().map(date), value.to.as_ref().map(date));
+                }
+                SynchronousField::Provenance(clause) => {
+                    sourced.push(clause.target.as_deref());
+                    self.validate_provenance(clause, Some(SYNCHRONOUS_TARGETS));
+                }
+                SynchronousField::Review(clause) => reviews.push(clause),
                 _ => {}
             }
         }
 
+        self.validate_reviews(&reviews, Some(SYNCHRONOUS_TARGETS), &sourced);
+
         // Validate outlet references
         if let Some(id1) = outlet_1_id {
             if !self.symbols.outlets.contains_key(&id1) {
@@ -938,9 +1313,785 @@ impl Validator {
             );
         }
 
+        if let (Some(id_1), Some(id_2), Some(relationship_type)) =
+            (outlet_1_id, outlet_2_id, relationship_type)
+        {
+            let outlet_1 = IRSyncOutlet {
+                id: id_1,
+                role: outlet_1_role.unwrap_or_default(),
+            };
+            let outlet_2 = IRSyncOutlet {
+                id: id_2,
+                role: outlet_2_role.unwrap_or_default(),
+            };
+            let semantics = self.relationship_type(&relationship_type);
+            if let Some(semantics) = &semantics {
+                if semantics.role_order(&outlet_1.role, &outlet_2.role) == RoleOrder::NotAllowed {
+                    let pairs: Vec<String> = semantics
+                        .role_pairs
+                        .iter()
+                        .map(|(from, to)| format!("{}/{}", from, to))
+                        .collect();
+                    self.add_warning(
+                        "SYNC_LINK_ROLES_NOT_ALLOWED",
+                        format!(
+                            "Roles '{}'/'{}' are not allowed for relationship type '{}'",
+                            outlet_1.role, outlet_2.role, relationship_type
+                        ),
+                        sync.position,
+                        Some(format!("Allowed role pairs: {}", pairs.join(", "))),
+                    );
+                }
+            }
+            self.sync_links.push(SyncLinkKey {
+                name: sync.name.clone(),
+                endpoints: match &semantics {
+                    Some(semantics) => semantics.endpoints(&outlet_1, &outlet_2),
+                    None => (id_1, id_2),
+                },
+                relationship_type,
+                outlet_1: id_1,
+                period_start: period.0,
+                period_end: period.1,
+                position: sync.position,
+            });
+        }
+
+        self.pop_context();
+    }
+
+    /// Semantics of a relationship type: declared, else built in
+    fn relationship_type(&self, name: &str) -> Option<IRRelationshipType> {
+        self.symbols
+            .relationship_types
+            .get(name)
+            .map(|(semantics, _)| semantics.clone())
+            .or_else(|| IRRelationshipType::builtin(name))
+    }
+
+    /// Validate relationship type declaration
+    fn validate_relationship_type(&mut self, declaration: &RelationshipTypeDeclaration) {
+        self.push_context(&format!("RelationshipType({})", declaration.name));
+
+        for field in &declaration.fields {
+            if let RelationshipTypeField::Direction { value, position } = field {
+                if LinkDirection::parse(value).is_none() {
+                    self.add_error(
+                        "RELATIONSHIP_TYPE_UNKNOWN_DIRECTION",
+                        format!("Unknown direction '{}'", value),
+                        *position,
+                        Some(format!(
+                            "Known directions: {}",
+                            LinkDirection::NAMES.join(", ")
+                        )),
+                    );
+                }
+            }
+        }
+
+        self.pop_context();
+    }
+
+    /// Validate company declaration
+    fn validate_company(&mut self, company: &CompanyDeclaration) {
+        self.push_context(&format!("Company({})", company.name));
+
+        if self.extract_company_id(&company.fields).is_none() {
+            self.add_error(
+                "COMPANY_NO_ID",
+                format!("Company '{}' has no numeric 'id' field", company.name),
+                company.position,
+                Some("Add 'id = <number>' to the company".to_string()),
+            );
+        }
+
+        self.validate_company_provenance(&company.fields);
+
+        self.pop_context();
+    }
+
+    /// Validate person declaration
+    fn validate_person(&mut self, person: &PersonDeclaration) {
+        self.push_context(&format!("Person({})", person.name));
+
+        if self.extract_company_id(&person.fields).is_none() {
+            self.add_error(
+                "PERSON_NO_ID",
+                format!("Person '{}' has no numeric 'id' field", person.name),
+                person.position,
+                Some("Add 'id = <number>' to the person".to_string()),
+            );
+        }
+
+        self.validate_company_provenance(&person.fields);
+
+        self.pop_context();
+    }
+
+    /// Validate ownership relation
+    fn validate_ownership(&mut self, ownership: &OwnershipDeclaration) {
+        self.push_context(&format!("Ownership({})", ownership.name));
+
+        let mut owner = None;
+        let mut owned_id = None;
+        let mut share = None;
+        let mut period_start = None;
+        let mut period_end = None;
+        let mut has_source = false;
+        let mut reviews = Vec::new();
+        let mut sourced = Vec::new();
+
+        for field in &ownership.fields {
+            match field {
+                OwnershipField::OwnerCompany { value, position } => {
+                    if owner.is_some() {
+                        self.add_error(
+                            "OWNERSHIP_MULTIPLE_OWNERS",
+                            "Ownership relation has more than one owner".to_string(),
+                            *position,
+                            Some("Use one OWNERSHIP block per owner".to_string()),
+                        );
+                    }
+                    owner = Some(("Company", *value as u32));
+                }
+                OwnershipField::OwnerPerson { value, position } => {
+                    if owner.is_some() {
+                        self.add_error(
+                            "OWNERSHIP_MULTIPLE_OWNERS",
+                            "Ownership relation has more than one owner".to_string(),
+                            *position,
+                            Some("Use one OWNERSHIP block per owner".to_string()),
+                        );
+                    }
+                    owner = Some(("Person", *value as u32));
+                }
+                OwnershipField::OwnedCompany { value, .. } => owned_id = Some(*value as u32),
+                OwnershipField::SharePct { value, position } => share = Some((*value, position)),
+                OwnershipField::PeriodStart { value, .. } => period_start = Some(value),
+                OwnershipField::PeriodEnd { value, .. } => period_end = Some(value),
+                OwnershipField::SourceUrl { .. } => {
+                    has_source = true;
+                    sourced.push(None);
+                }
+                OwnershipField::Provenance(clause) => {
+                    has_source = true;
+                    sourced.push(clause.target.as_deref());
+                    self.validate_provenance(clause, Some(OWNERSHIP_TARGETS));
+                }
+                OwnershipField::Review(clause) => reviews.push(clause),
+                _ => {}
+            }
+        }
+
+        match owner {
+            Some((kind, id)) => {
+                let known = if kind == "Company" {
+                    self.symbols.companies.contains_key(&id)
+                } else {
+                    self.symbols.persons.contains_key(&id)
+                };
+                if !known {
+                    self.add_error(
+                        "OWNERSHIP_OWNER_NOT_FOUND",
+                        format!("{} with ID {} not found", kind, id),
+                        ownership.position,
+                        Some(format!(
+                            "Declare the owner with a {} block",
+                            kind.to_uppercase()
+                        )),
+                    );
+                }
+            }
+            None => {
+                self.add_error(
+                    "OWNERSHIP_NO_OWNER",
+                    "Ownership relation has no 'owner_company' or 'owner_person' field"
+                        .to_string(),
+                    ownership.position,
+                    Some("Add 'owner_company = <id>' or 'owner_person = <id>'".to_string()),
+                );
+            }
+        }
+
+        match owned_id {
+            Some(id) if !self.symbols.companies.contains_key(&id) => {
+                self.add_error(
+                    "OWNERSHIP_OWNED_NOT_FOUND",
+                    format!("Company with ID {} not found", id),
+                    ownership.position,
+                    Some("Declare the company with a COMPANY block".to_string()),
+                );
+            }
+            Some(_) => {}
+            None => {
+                self.add_error(
+                    "OWNERSHIP_NO_OWNED",
+                    "Ownership relation has no 'owned_company' field".to_string(),
+                    ownership.position,
+                    Some("Add 'owned_company = <company id>' to the relation".to_string()),
+                );
+            }
+        }
+
+        if let Some((value, position)) = share {
+            if !(0.0..=100.0).contains(&value) {
+                self.add_error(
+                    "OWNERSHIP_INVALID_SHARE",
+                    format!("Share {} is outside 0-100%", value),
+                    *position,
+                    None,
+                );
+            }
+        }
+
+        if let (Some(DateExpression::Literal(start)), Some(DateExpression::Literal(end))) =
+            (period_start, period_end)
+        {
+            if end < start {
+                self.add_error(
+                    "OWNERSHIP_INVALID_PERIOD",
+                    format!("Period end '{}' is before period start '{}'", end, start),
+                    ownership.position,
+                    None,
+                );
+            }
+        }
+
+        self.validate_reviews(&reviews, Some(OWNERSHIP_TARGETS), &sourced);
+
+        if !has_source {
+            self.add_info(
+                "OWNERSHIP_NO_SOURCE",
+                "Ownership relation has no 'source_url'".to_string(),
+                ownership.position,
+                Some("Record where the relation was taken from".to_string()),
+            );
+        }
+
+        self.pop_context();
+    }
+
+    /// Validate identifier link
+    fn validate_identifier_link(&mut self, link: &IdentifierLink) {
+        self.push_context(&format!("IdentifierLink({})", link.name));
+
+        let mut company_id = None;
+        let mut has_scheme = false;
+        let mut has_value = false;
+        let mut reviews = Vec::new();
+        let mut sourced = Vec::new();
+
+        for field in &link.fields {
+            match field {
+                IdentifierLinkField::Company { value, .. } => company_id = Some(*value as u32),
+                IdentifierLinkField::Scheme { .. } => has_scheme = true,
+                IdentifierLinkField::Value { .. } => has_value = true,
+                IdentifierLinkField::Status { value, position }
+                    if !KNOWN_IDENTIFIER_STATUSES.contains(&value.as_str()) =>
+                {
+                    self.add_warning(
+                        "IDENTIFIER_UNKNOWN_STATUS",
+                        format!("Unknown identifier link status '{}'", value),
+                        *position,
+                        Some(format!(
+                            "Known statuses: {}",
+                            KNOWN_IDENTIFIER_STATUSES.join(", ")
+                        )),
+                    );
+                }
+                IdentifierLinkField::Status { .. } => {}
+                IdentifierLinkField::Score { value, position } if !(0.0..=1.0).contains(value) => {
+                    self.add_error(
+                        "IDENTIFIER_INVALID_SCORE",
+                        format!("Score {} is outside 0-1", value),
+                        *position,
+                        None,
+                    );
+                }
+                IdentifierLinkField::Score { .. } => {}
+                IdentifierLinkField::Provenance(clause) => {
+                    sourced.push(clause.target.as_deref());
+                    self.validate_provenance(clause, Some(IDENTIFIER_LINK_TARGETS));
+                }
+                IdentifierLinkField::Review(clause) => reviews.push(clause),
+                _ => {}
+            }
+        }
+
+        self.validate_reviews(&reviews, Some(IDENTIFIER_LINK_TARGETS), &sourced);
+
+        match company_id {
+            Some(id) if !self.symbols.companies.contains_key(&id) => {
+                self.add_error(
+                    "IDENTIFIER_COMPANY_NOT_FOUND",
+                    format!("Company with ID {} not found", id),
+                    link.position,
+                    Some("Declare the company with a COMPANY block".to_string()),
+                );
+            }
+            Some(_) => {}
+            None => {
+                self.add_error(
+                    "IDENTIFIER_NO_COMPANY",
+                    "Identifier link has no 'company' field".to_string(),
+                    link.position,
+                    Some("Add 'company = <company id>' to the link".to_string()),
+                );
+            }
+        }
+
+        if !has_scheme || !has_value {
+            self.add_error(
+                "IDENTIFIER_INCOMPLETE",
+                "Identifier link needs both 'scheme' and 'value'".to_string(),
+                link.position,
+                Some("Add 'scheme = \"northdata\"' and 'value = \"...\"'".to_string()),
+            );
+        }
+
+        self.pop_context();
+    }
+
+    /// Validate outlet-party link
+    fn validate_outlet_party_link(&mut self, link: &OutletPartyLink) {
+        self.push_context(&format!("OutletPartyLink({})", link.name));
+
+        let mut outlet_id = None;
+        let mut party_id = None;
+        let mut role = None;
+        let mut period_start = None;
+        let mut period_end = None;
+        let mut reviews = Vec::new();
+        let mut sourced = Vec::new();
+
+        for field in &link.fields {
+            match field {
+                OutletPartyField::Outlet { value, .. } => outlet_id = Some(*value as u32),
+                OutletPartyField::Party { value, .. } => party_id = Some(*value as u32),
+                OutletPartyField::Role { value, position } => role = Some((value, position)),
+                OutletPartyField::PeriodStart { value, .. } => period_start = Some(value),
+                OutletPartyField::PeriodEnd { value, .. } => period_end = Some(value),
+                OutletPartyField::Source { .. } => sourced.push(None),
+                OutletPartyField::Provenance(clause) => {
+                    sourced.push(clause.target.as_deref());
+                    self.validate_provenance(clause, Some(OUTLET_PARTY_TARGETS));
+                }
+                OutletPartyField::Review(clause) => reviews.push(clause),
+                _ => {}
+            }
+        }
+
+        self.validate_reviews(&reviews, Some(OUTLET_PARTY_TARGETS), &sourced);
+
+        match outlet_id {
+            Some(id) if !self.symbols.outlets.contains_key(&id) => {
+                self.add_error(
+                    "OUTLET_PARTY_OUTLET_NOT_FOUND",
+                    format!("Outlet with ID {} not found", id),
+                    link.position,
+                    Some("Declare the outlet before referencing it".to_string()),
+                );
+            }
+            Some(_) => {}
+            None => {
+                self.add_error(
+                    "OUTLET_PARTY_NO_OUTLET",
+                    "Outlet-party link has no 'outlet' field".to_string(),
+                    link.position,
+                    Some("Add 'outlet = <outlet id>' to the link".to_string()),
+                );
+            }
+        }
+
+        match party_id {
+            Some(id) if !self.symbols.companies.contains_key(&id) => {
+                self.add_error(
+                    "OUTLET_PARTY_COMPANY_NOT_FOUND",
+                    format!("Company with ID {} not found", id),
+                    link.position,
+                    Some("Declare the company with a COMPANY block".to_string()),
+                );
+            }
+            Some(_) => {}
+            None => {
+                self.add_error(
+                    "OUTLET_PARTY_NO_PARTY",
+                    "Outlet-party link has no 'party' field".to_string(),
+                    link.position,
+                    Some("Add 'party = <company id>' to the link".to_string()),
+                );
+            }
+        }
+
+        match role {
+            Some((value, position)) => {
+                if !KNOWN_PARTY_ROLES.contains(&value.as_str()) {
+                    self.add_warning(
+                        "OUTLET_PARTY_UNKNOWN_ROLE",
+                        format!("Unknown outlet-party role '{}'", value),
+                        *position,
+                        Some(format!("Known roles: {}", KNOWN_PARTY_ROLES.join(", "))),
+                    );
+                }
+            }
+            None => {
+                self.add_warning(
+                    "OUTLET_PARTY_NO_ROLE",
+                    "Outlet-party link has no 'role' field".to_string(),
+                    link.position,
+                    Some("Add 'role = \"publisher\"' or similar".to_string()),
+                );
+            }
+        }
+
+        match (period_start, period_end) {
+            (None, _) => {
+                self.add_warning(
+                    "OUTLET_PARTY_NO_PERIOD",
+                    "Outlet-party link has no 'period_start'".to_string(),
+                    link.position,
+                    Some("Add 'period_start = \"YYYY-MM-DD\"' to the link".to_string()),
+                );
+            }
+            (
+                Some(DateExpression::Literal(start)),
+                Some(DateExpression::Literal(end)),
+            ) if end < start => {
+                self.add_error(
+                    "OUTLET_PARTY_INVALID_PERIOD",
+                    format!("Period end '{}' is before period start '{}'", end, start),
+                    link.position,
+                    None,
+                );
+            }
+            _ => {}
+        }
+
+        self.pop_context();
+    }
+
+    /// Validate event declaration
+    fn validate_event(&mut self, event: &EventDeclaration) {
+        self.push_context(&format!("Event({})", event.name));
+
+        let mut targets: Vec<&str> = EVENT_TARGETS.to_vec();
+        for field in &event.fields {
+            if let EventField::Entities { entities, .. } = field {
+                targets.extend(entities.iter().map(|entity| entity.name.as_str()));
+            }
+        }
+
+        // Person references must name a declared PERSON
+        let persons = event
+            .fields
+            .iter()
+            .filter_map(|field| match field {
+                EventField::Entities { entities, .. } => Some(entities),
+                _ => None,
+            })
+            .flatten()
+            .flat_map(|entity| &entity.roles)
+            .filter_map(|role| match role {
+                EntityRole::Person { value, position } => Some((*value as u32, *position)),
+                _ => None,
+            });
+        let unknown: Vec<(u32, SourcePosition)> = persons
+            .filter(|(id, _)| !self.symbols.persons.contains_key(id))
+            .collect();
+        for (id, position) in unknown {
+            self.add_error(
+                "EVENT_PERSON_NOT_FOUND",
+                format!("Person with ID {} not found", id),
+                position,
+                Some("Declare the person with a PERSON block".to_string()),
+            );
+        }
+
+        let mut reviews = Vec::new();
+        let mut sourced = Vec::new();
+        for field in &event.fields {
+            match field {
+                EventField::Provenance(clause) => {
+                    sourced.push(clause.target.as_deref());
+                    self.validate_provenance(clause, Some(&targets));
+                }
+                EventField::Review(clause) => reviews.push(clause),
+                _ => {}
+            }
+        }
+        self.validate_reviews(&reviews, Some(&targets), &sourced);
+
         self.pop_context();
     }
 
+    /// Validate redaction policy
+    fn validate_redaction(&mut self, policy: &RedactionPolicy) {
+        self.push_context(&format!("Redaction({})", policy.name));
+
+        let mut pseudonymises = false;
+        let mut has_salt = false;
+
+        for field in &policy.fields {
+            match field {
+                RedactionField::Persons { value, position }
+                | RedactionField::EventPersons { value, position } => {
+                    match RedactionAction::parse(value) {
+                        Some(RedactionAction::Pseudonymise) => pseudonymises = true,
+                        Some(_) => {}
+                        None => {
+                            self.add_error(
+                                "REDACTION_UNKNOWN_ACTION",
+                                format!("Unknown redaction action '{}'", value),
+                                *position,
+                                Some(format!(
+                                    "Known actions: {}",
+                                    RedactionAction::NAMES.join(", ")
+                                )),
+                            );
+                        }
+                    }
+                }
+                RedactionField::Salt { value, .. } => has_salt = !value.is_empty(),
+                _ => {}
+            }
+        }
+
+        // Without a salt, pseudonyms of small numeric IDs can be reversed by brute force
+        if pseudonymises && !has_salt {
+            self.add_warning(
+                "REDACTION_NO_SALT",
+                "Pseudonymising redaction policy has no 'salt'".to_string(),
+                policy.position,
+                Some("Add a secret 'salt = \"...\"' to the policy".to_string()),
+            );
+        }
+
+        self.pop_context();
+    }
+
+    /// Validate the provenance and review clauses of a company or person declaration
+    fn validate_company_provenance(&mut self, fields: &[CompanyField]) {
+        let targets: Vec<&str> = fields
+            .iter()
+            .filter_map(|field| match field {
+                CompanyField::Assignment { name, .. } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        let mut reviews = Vec::new();
+        let mut sourced = Vec::new();
+        for field in fields {
+            match field {
+                CompanyField::Provenance(clause) => {
+                    sourced.push(clause.target.as_deref());
+                    self.validate_provenance(clause, Some(&targets));
+                }
+                CompanyField::Review(clause) => reviews.push(clause),
+                _ => {}
+            }
+        }
+        self.validate_reviews(&reviews, Some(&targets), &sourced);
+    }
+
+    /// Validate a provenance clause; `targets` lists the fields it may refer to
+    fn validate_provenance(&mut self, clause: &ProvenanceClause, targets: Option<&[&str]>) {
+        let mut source = None;
+
+        for field in &clause.fields {
+            match field {
+                ProvenanceField::Source { value, position } => source = Some((value, position)),
+                ProvenanceField::Confidence { value, position } if !(0.0..=1.0).contains(value) => {
+                    self.add_error(
+                        "PROVENANCE_INVALID_CONFIDENCE",
+                        format!("Confidence {} is outside 0-1", value),
+                        *position,
+                        None,
+                    );
+                }
+                ProvenanceField::Verification { value, position }
+                    if !KNOWN_VERIFICATION_STATUSES.contains(&value.as_str()) =>
+                {
+                    self.add_warning(
+                        "PROVENANCE_UNKNOWN_VERIFICATION",
+                        format!("Unknown verification status '{}'", value),
+                        *position,
+                        Some(format!(
+                            "Known statuses: {}",
+                            KNOWN_VERIFICATION_STATUSES.join(", ")
+                        )),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        match source {
+            // Without any catalog in scope (e.g. it lives in an imported file) sources cannot be checked
+            Some((name, position)) if self.symbols.sources.is_empty() => {
+                self.add_warning(
+                    "PROVENANCE_SOURCE_UNRESOLVED",
+                    format!(
+                        "Source '{}' cannot be resolved, no CATALOG is declared",
+                        name
+                    ),
+                    *position,
+                    Some("Declare the source in a CATALOG block".to_string()),
+                );
+            }
+            Some((name, position)) if !self.symbols.sources.contains_key(name) => {
+                self.add_error(
+                    "PROVENANCE_UNKNOWN_SOURCE",
+                    format!("Source '{}' is not declared in any CATALOG", name),
+                    *position,
+                    Some("Add the source to a CATALOG block".to_string()),
+                );
+            }
+            Some(_) => {}
+            None => {
+                self.add_error(
+                    "PROVENANCE_NO_SOURCE",
+                    "Provenance clause has no 'source' field".to_string(),
+                    clause.position,
+                    Some("Add 'source = \"<catalog source>\"' to the clause".to_string()),
+                );
+            }
+        }
+
+        if let (Some(target), Some(targets)) = (&clause.target, targets) {
+            if !targets.contains(&target.as_str()) {
+                self.add_warning(
+                    "PROVENANCE_UNKNOWN_TARGET",
+                    format!(
+                        "Provenance target '{}' is not a field of this declaration",
+                        target
+                    ),
+                    clause.position,
+                    Some(format!("Known fields: {}", targets.join(", "))),
+                );
+            }
+        }
+    }
+
+    /// Validate the review clauses of a declaration
+    ///
+    /// `sourced` holds the targets of the declaration's provenance records, with `None`
+    /// standing for a source of the whole declaration. Published items need a source.
+    fn validate_reviews(
+        &mut self,
+        reviews: &[&ReviewClause],
+        targets: Option<&[&str]>,
+        sourced: &[Option<&str>],
+    ) {
+        for clause in reviews {
+            let mut status = None;
+            let mut reviewer = false;
+            let mut reviewed_at = false;
+
+            for field in &clause.fields {
+                match field {
+                    ReviewField::Status { value, position } => status = Some((value, position)),
+                    ReviewField::Reviewer { .. } => reviewer = true,
+                    ReviewField::ReviewedAt { .. } => reviewed_at = true,
+                    _ => {}
+                }
+            }
+
+            match status {
+                Some((value, position)) if !KNOWN_PUBLICATION_STATUSES.contains(&value.as_str()) => {
+                    self.add_warning(
+                        "REVIEW_UNKNOWN_STATUS",
+                        format!("Unknown publication status '{}'", value),
+                        *position,
+                        Some(format!(
+                            "Known statuses: {}",
+                            KNOWN_PUBLICATION_STATUSES.join(", ")
+                        )),
+                    );
+                }
+                Some(_) => {}
+                None => {
+                    self.add_error(
+                        "REVIEW_NO_STATUS",
+                        "Review clause has no 'status' field".to_string(),
+                        clause.position,
+                        Some("Add 'status = \"draft\"' or similar to the clause".to_string()),
+                    );
+                }
+            }
+
+            let status = status.map(|(value, _)| value.as_str());
+            if matches!(status, Some("reviewed") | Some("published")) && !(reviewer && reviewed_at)
+            {
+                self.add_warning(
+                    "REVIEW_NO_REVIEWER",
+                    "Reviewed item does not record 'reviewer' and 'reviewed_at'".to_string(),
+                    clause.position,
+                    Some("Record who reviewed the item and when".to_string()),
+                );
+            }
+
+            if status == Some("published") {
+                let has_source = sourced.iter().any(|source| {
+                    source.is_none() || (clause.target.is_some() && *source == clause.target.as_deref())
+                });
+                if !has_source {
+                    self.add_error(
+                        "REVIEW_PUBLISHED_WITHOUT_SOURCE",
+                        match &clause.target {
+                            Some(target) => format!("Published field '{}' has no source", target),
+                            None => "Published declaration has no source".to_string(),
+                        },
+                        clause.position,
+                        Some("Add a PROVENANCE clause naming a catalog source".to_string()),
+                    );
+                }
+            }
+
+            if let (Some(target), Some(targets)) = (&clause.target, targets) {
+                if !targets.contains(&target.as_str()) {
+                    self.add_warning(
+                        "REVIEW_UNKNOWN_TARGET",
+                        format!("Review target '{}' is not a field of this declaration", target),
+                        clause.position,
+                        Some(format!("Known fields: {}", targets.join(", "))),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Names of the fields and blocks of an outlet a provenance clause may target
+    fn outlet_field_names(outlet: &OutletDeclaration) -> Vec<&str> {
+        let mut names = vec!["identity", "lifecycle", "characteristics", "metadata"];
+
+        for block in &outlet.blocks {
+            match block {
+                OutletBlock::Identity(identity) => {
+                    names.extend(identity.fields.iter().filter_map(|field| match field {
+                        IdentityField::Assignment { name, .. } => Some(name.as_str()),
+                        _ => None,
+                    }));
+                }
+                OutletBlock::Characteristics(chars) => {
+                    names.extend(chars.fields.iter().filter_map(|field| match field {
+                        CharacteristicField::Assignment { name, .. } => Some(name.as_str()),
+                        _ => None,
+                    }));
+                }
+                OutletBlock::Metadata(meta) => {
+                    names.extend(meta.fields.iter().filter_map(|field| match field {
+                        MetadataField::Assignment { name, .. } => Some(name.as_str()),
+                        _ => None,
+                    }));
+                }
+                _ => {}
+            }
+        }
+
+        names
+    }
+
     /// Validate cross-references in the program
     fn validate_references(&mut self, program: &Program) {
         self.push_context("References");
@@ -970,6 +2121,45 @@ impl Validator {
     fn validate_business_rules(&mut self, _program: &Program) {
         self.push_context("BusinessRules");
 
+        // Links of one type between the same outlets may not overlap in time;
+        // for symmetric types the order of the outlets does not matter
+        let links = std::mem::take(&mut self.sync_links);
+        for (index, second) in links.iter().enumerate() {
+            for first in &links[..index] {
+                if first.relationship_type == second.relationship_type
+                    && first.endpoints == second.endpoints
+                    && overlaps(
+                        first.period_start.as_deref(),
+                        first.period_end.as_deref(),
+                        second.period_start.as_deref(),
+                        second.period_end.as_deref(),
+                    )
+                {
+                    self.add_warning(
+                        "SYNC_LINK_DUPLICATE",
+                        format!(
+                            "Synchronous link '{}' repeats '{}' ({} between {} and {}){}",
+                            second.name,
+                            first.name,
+                            second.relationship_type,
+                            second.endpoints.0,
+                            second.endpoints.1,
+                            if first.outlet_1 != second.outlet_1 {
+                                " in reverse order"
+                            } else {
+                                ""
+                            }
+                        ),
+                        second.position,
+                        Some(format!(
+                            "Remove one of the links or give them non-overlapping periods (first at {}:{})",
+                            first.position.line, first.position.column
+                        )),
+                    );
+                }
+            }
+        }
+
         // TODO: Implement MediaLanguage-specific business rules:
         // - Outlet ID ranges (e.g., 200000-299999 for newspapers)
         // - Required characteristics for certain outlet types
@@ -1021,6 +2211,18 @@ impl Validator {
         None
     }
 
+    /// Extract the numeric ID of a company or person declaration
+    fn extract_company_id(&self, fields: &[CompanyField]) -> Option<u32> {
+        fields.iter().find_map(|field| match field {
+            CompanyField::Assignment {
+                name,
+                value: Expression::Number(n),
+                ..
+            } if name == "id" => Some(*n as u32),
+            _ => None,
+        })
+    }
+
     /// Add an error to the validation results
     fn add_error(
         &mut self,
@@ -1113,7 +2315,8 @@ impl Validator {
             total_constructs: self.symbols.templates.len()
                 + self.symbols.units.len()
                 + self.symbols.vocabularies.len()
-                + self.symbols.families.len(),
+                + self.symbols.families.len()
+                + self.symbols.companies.len(),
         }
     }
 }
@@ -0,0 +1,114 @@
+//! Minimal CSV reading and writing utilities
+//!
+//! Handles the RFC 4180 subset used by the ANMI CSV exports: comma separators,
+//! double-quoted fields with `""` escapes, embedded newlines and CRLF line endings.
+
+use std::collections::HashMap;
+
+/// A parsed CSV file with a header row
+#[derive(Debug, Clone, Default)]
+pub struct CsvTable {
+    /// Column names from the header row
+    pub headers: Vec<String>,
+    /// Data rows (without the header)
+    pub rows: Vec<Vec<String>>,
+    /// Column name -> index lookup
+    index: HashMap<String, usize>,
+}
+
+impl CsvTable {
+    /// Parse CSV content whose first record is the header row
+    pub fn parse(content: &str) -> Self {
+        let mut records = parse_records(content).into_iter();
+        let headers = records.next().unwrap_or_default();
+        let index = headers
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.trim().to_string(), i))
+            .collect();
+
+        Self {
+            headers,
+            rows: records.collect(),
+            index,
+        }
+    }
+
+    /// Check whether the table has a column with the given name
+    pub fn has_column(&self, column: &str) -> bool {
+        self.index.contains_key(column)
+    }
+
+    /// Get a field of a row by column name (empty fields are returned as `None`)
+    pub fn get<'a>(&self, row: &'a [String], column: &str) -> Option<&'a str> {
+        self.index
+            .get(column)
+            .and_then(|&i| row.get(i))
+            .map(|value| value.as_str())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+/// Parse CSV content into records of fields
+pub fn parse_records(content: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                // Skip blank lines
+                if !(record.len() == 1 && record[0].is_empty()) {
+                    records.push(std::mem::take(&mut record));
+                } else {
+                    record.clear();
+                }
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    records
+}
+
+/// Format one CSV record (with trailing newline), quoting fields where needed
+pub fn write_record<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut line = fields
+        .iter()
+        .map(|field| {
+            let field = field.as_ref();
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push('\n');
+    line
+}
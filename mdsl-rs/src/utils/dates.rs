@@ -0,0 +1,66 @@
+//! Date helpers for periods given as ISO date strings
+//!
+//! Dates in MDSL may be partial (`1985`, `1985-06`) and open ends are either
+//! missing or `CURRENT`. Padding a partial date to its first or last day makes
+//! plain string comparison order them correctly.
+
+/// First day of a possibly partial date (`1985` -> `1985-01-01`)
+pub fn first_day(date: &str) -> String {
+    match date.len() {
+        4 => format!("{}-01-01", date),
+        7 => format!("{}-01", date),
+        _ => date.to_string(),
+    }
+}
+
+/// Last day of a possibly partial date (`1985` -> `1985-12-31`,
+/// `1984-02` -> `1984-02-29`)
+pub fn last_day(date: &str) -> String {
+    match date.len() {
+        4 => format!("{}-12-31", date),
+        7 => format!("{}-{:02}", date, days_in_month(date)),
+        _ => date.to_string(),
+    }
+}
+
+/// Number of days in the month of a `YYYY-MM` date (31 if it does not parse)
+fn days_in_month(month: &str) -> u32 {
+    let year: u32 = month.get(..4).and_then(|y| y.parse().ok()).unwrap_or(0);
+    let leap = year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+    match month.get(5..).map(str::parse::<u32>) {
+        Some(Ok(4 | 6 | 9 | 11)) => 30,
+        Some(Ok(2)) if leap => 29,
+        Some(Ok(2)) => 28,
+        _ => 31,
+    }
+}
+
+/// Whether the period from `start` to `end` includes `date` (both ends
+/// inclusive; a missing or `CURRENT` bound is open)
+pub fn covers(start: Option<&str>, end: Option<&str>, date: &str) -> bool {
+    let open = |bound: &&str| *bound != "CURRENT";
+    let started = start
+        .filter(open)
+        .is_none_or(|start| first_day(start).as_str() <= date);
+    let not_ended = end
+        .filter(open)
+        .is_none_or(|end| last_day(end).as_str() >= date);
+    started && not_ended
+}
+
+/// Whether two periods share at least one day (missing or `CURRENT` bounds
+/// are open)
+pub fn overlaps(
+    start_1: Option<&str>,
+    end_1: Option<&str>,
+    start_2: Option<&str>,
+    end_2: Option<&str>,
+) -> bool {
+    let open = |bound: &&str| *bound != "CURRENT";
+    let starts_before_end =
+        |start: Option<&str>, end: Option<&str>| match (start.filter(open), end.filter(open)) {
+            (Some(start), Some(end)) => first_day(start) <= last_day(end),
+            _ => true,
+        };
+    starts_before_end(start_1, end_2) && starts_before_end(start_2, end_1)
+}
@@ -0,0 +1,424 @@
+//! Three-way merge of MDSL sources
+//!
+//! Merges two edited versions of a file against their common base statement
+//! by statement instead of line by line. Declarations are matched by ID
+//! (`OUTLET` blocks with an `id`) or by their head (`FAMILY "ORF"`,
+//! `identity`, `YEAR 2022`, `status "active"`), fields by name. An edit made
+//! on one side only is taken over; when both sides changed the same block,
+//! its statements are merged one level down. Only a field, or a one-line
+//! block, changed differently on both sides becomes a conflict, written with
+//! git-style markers:
+//!
+//! ```text
+//! <<<<<<< ours
+//!             title = "Kurier";
+//! =======
+//!             title = "Kurier Neu";
+//! >>>>>>> theirs
+//! ```
+//!
+//! The result is the `ours` source with the merged edits applied, so its
+//! comments and formatting are kept.
+
+use crate::error::Result;
+use crate::utils::source_map::{line_indent, own_line_start, SourceMap, SourceNode};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Line that opens a conflict, followed by the `ours` side
+pub const OURS_MARKER: &str = "<<<<<<< ours";
+
+/// Line between the `ours` and the `theirs` side of a conflict
+pub const SEPARATOR_MARKER: &str = "=======";
+
+/// Line that closes a conflict, after the `theirs` side
+pub const THEIRS_MARKER: &str = ">>>>>>> theirs";
+
+/// A statement both sides changed differently
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict {
+    /// Statements leading to the conflict (`FAMILY "ORF" > OUTLET 300040 > identity > title`)
+    pub path: String,
+    /// Line of the opening marker in the merged text, starting at 1
+    pub line: usize,
+}
+
+/// Merged source and the conflicts left in it
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeResult {
+    /// Merged source, with conflict markers if there are conflicts
+    pub text: String,
+    /// Conflicts in file order
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl MergeResult {
+    /// Whether all edits merged without conflicts
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Merge the edits of `ours` and `theirs` since `base`
+pub fn merge(base: &str, ours: &str, theirs: &str) -> Result<MergeResult> {
+    let base_map = SourceMap::parse(base)?;
+    let ours_map = SourceMap::parse(ours)?;
+    let theirs_map = SourceMap::parse(theirs)?;
+
+    let mut merger = Merger {
+        ours,
+        theirs,
+        edits: Vec::new(),
+    };
+    merger.merge_children(
+        Some(&base_map.nodes),
+        &ours_map.nodes,
+        &theirs_map.nodes,
+        None,
+        "",
+    );
+
+    // Edits never overlap, so applying them back to front keeps offsets
+    // valid; an insertion goes in after a removal starting at its offset
+    let mut edits = merger.edits;
+    edits.sort_by_key(|edit| (edit.range.start, !edit.range.is_empty()));
+    let mut text = ours.to_string();
+    for edit in edits.iter().rev() {
+        text.replace_range(edit.range.clone(), &edit.text);
+    }
+
+    let marker_lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| *line == OURS_MARKER)
+        .map(|(i, _)| i + 1);
+    let conflicts = edits
+        .into_iter()
+        .filter_map(|edit| edit.conflict)
+        .zip(marker_lines)
+        .map(|(path, line)| MergeConflict { path, line })
+        .collect();
+
+    Ok(MergeResult { text, conflicts })
+}
+
+/// Replacement of a byte range of `ours`; an empty range inserts
+struct Edit {
+    range: Range<usize>,
+    text: String,
+    /// Path of the conflict the edit writes, if it is one
+    conflict: Option<String>,
+}
+
+struct Merger<'a> {
+    ours: &'a str,
+    theirs: &'a str,
+    edits: Vec<Edit>,
+}
+
+impl Merger<'_> {
+    /// Merge the statements of a block; `parent` is the block in `ours`,
+    /// `None` at the top level
+    fn merge_children(
+        &mut self,
+        base: Option<&[SourceNode]>,
+        ours: &[SourceNode],
+        theirs: &[SourceNode],
+        parent: Option<&SourceNode>,
+        path: &str,
+    ) {
+        let base = keyed(base.unwrap_or_default());
+        let ours_keyed = keyed(ours);
+        let theirs_keyed = keyed(theirs);
+        let base_of: HashMap<&str, &SourceNode> = base
+            .iter()
+            .map(|(key, node)| (key.as_str(), *node))
+            .collect();
+        let ours_of: HashMap<&str, &SourceNode> = ours_keyed
+            .iter()
+            .map(|(key, node)| (key.as_str(), *node))
+            .collect();
+        let theirs_of: HashMap<&str, &SourceNode> = theirs_keyed
+            .iter()
+            .map(|(key, node)| (key.as_str(), *node))
+            .collect();
+
+        for (key, node) in &ours_keyed {
+            let path = child_path(path, key);
+            let base_node = base_of.get(key.as_str()).copied();
+            match (base_node, theirs_of.get(key.as_str())) {
+                (Some(base_node), None) if same(base_node, node) => self.delete(node),
+                // Deleted by them, changed by us
+                (Some(_), None) => self.conflict(node, String::new(), path),
+                // Added by us
+                (None, None) => {}
+                (base_node, Some(their_node)) => self.merge_node(base_node, node, their_node, path),
+            }
+        }
+
+        for (i, (key, node)) in theirs_keyed.iter().enumerate() {
+            if ours_of.contains_key(key.as_str()) {
+                continue;
+            }
+            match base_of.get(key.as_str()) {
+                // Deleted by both, or deleted by us and left alone by them
+                Some(base_node) if same(base_node, node) => {}
+                Some(_) => {
+                    // Deleted by us, changed by them
+                    let path = child_path(path, key);
+                    self.insert(&theirs_keyed[..i], &ours_of, ours, parent, node, Some(path));
+                }
+                None => self.insert(&theirs_keyed[..i], &ours_of, ours, parent, node, None),
+            }
+        }
+    }
+
+    fn merge_node(
+        &mut self,
+        base: Option<&SourceNode>,
+        ours: &SourceNode,
+        theirs: &SourceNode,
+        path: String,
+    ) {
+        if same(ours, theirs) || base.is_some_and(|base| same(base, theirs)) {
+            return;
+        }
+        if base.is_some_and(|base| same(base, ours)) {
+            let indent = line_indent(self.ours, ours.span.start);
+            let text = theirs.reindented(self.theirs, &indent);
+            self.edit(ours.span.clone(), text);
+            return;
+        }
+
+        // Changed on both sides: blocks spread over several lines are merged
+        // statement by statement, anything else conflicts
+        let one_line = !self.ours[ours.span.clone()].contains('\n');
+        if !ours.has_body() || !theirs.has_body() || one_line {
+            let indent = line_indent(self.ours, ours.span.start);
+            let text = theirs.reindented(self.theirs, &indent);
+            self.conflict(ours, text, path);
+            return;
+        }
+        if ours.head != theirs.head {
+            match base {
+                Some(base) if base.head == ours.head => {
+                    let head = self.theirs[theirs.head_span.clone()].to_string();
+                    self.edit(ours.head_span.clone(), head);
+                }
+                Some(base) if base.head == theirs.head => {}
+                _ => {
+                    let indent = line_indent(self.ours, ours.span.start);
+                    let text = theirs.reindented(self.theirs, &indent);
+                    self.conflict(ours, text, path);
+                    return;
+                }
+            }
+        }
+        self.merge_children(
+            base.map(|base| base.children.as_slice()),
+            &ours.children,
+            &theirs.children,
+            Some(ours),
+            &path,
+        );
+    }
+
+    /// Insert a statement of theirs after the closest preceding statement
+    /// that both sides have; with a conflict path, as the theirs side of a
+    /// conflict whose ours side is empty
+    fn insert(
+        &mut self,
+        preceding: &[(String, &SourceNode)],
+        ours_of: &HashMap<&str, &SourceNode>,
+        ours: &[SourceNode],
+        parent: Option<&SourceNode>,
+        node: &SourceNode,
+        conflict: Option<String>,
+    ) {
+        let anchor = preceding
+            .iter()
+            .rev()
+            .find_map(|(key, _)| ours_of.get(key.as_str()));
+        // Indentation to restore after the text, for a closing brace that
+        // shared its line with the statements of a one-line block
+        let mut closing = String::new();
+        let (offset, indent) = match (anchor, ours.first(), parent) {
+            (Some(anchor), _, _) => {
+                let lines = anchor.line_span(self.ours);
+                (lines.end, line_indent(self.ours, anchor.span.start))
+            }
+            (None, Some(first), _) => (
+                first.line_span(self.ours).start,
+                line_indent(self.ours, first.span.start),
+            ),
+            (None, None, Some(parent)) => {
+                let body_end = parent.body_end.unwrap_or(parent.span.end);
+                let closing_indent = line_indent(self.ours, body_end);
+                let indent = format!("{}    ", closing_indent);
+                match own_line_start(self.ours, body_end) {
+                    Some(line_start) => (line_start, indent),
+                    None => {
+                        closing = closing_indent;
+                        (body_end, indent)
+                    }
+                }
+            }
+            (None, None, None) => (self.ours.len(), String::new()),
+        };
+
+        let comments = leading_comments(self.theirs, node);
+        let mut text = String::new();
+        // Keep the blank line that separates the statement from the previous one
+        let first_line = node.line_span(self.theirs).start
+            - comments.iter().map(|line| line.len()).sum::<usize>();
+        if anchor.is_some() && self.theirs[..first_line].ends_with("\n\n") {
+            text.push('\n');
+        }
+        // Keep comment lines directly above the statement with it
+        for comment in comments.iter().map(|line| line.trim()) {
+            text.push_str(&format!("{}{}\n", indent, comment));
+        }
+        text.push_str(&format!(
+            "{}{}\n",
+            indent,
+            node.reindented(self.theirs, &indent)
+        ));
+        if !self.ours[..offset].ends_with('\n') && offset > 0 {
+            text.insert(0, '\n');
+        }
+
+        match conflict {
+            Some(path) => {
+                // Markers start a line of their own
+                let lead = if closing.is_empty() { "" } else { "\n" };
+                let text = format!(
+                    "{}{}\n{}\n{}{}\n{}",
+                    lead,
+                    OURS_MARKER,
+                    SEPARATOR_MARKER,
+                    text.trim_start_matches('\n'),
+                    THEIRS_MARKER,
+                    closing
+                );
+                self.edits.push(Edit {
+                    range: offset..offset,
+                    text,
+                    conflict: Some(path),
+                });
+            }
+            None => self.edit(offset..offset, text + &closing),
+        }
+    }
+
+    /// Replace the lines of a statement of ours with conflict markers around
+    /// both versions
+    fn conflict(&mut self, node: &SourceNode, theirs: String, path: String) {
+        let lines = node.line_span(self.ours);
+        let ours_side = &self.ours[lines.clone()];
+        let theirs_side = format!(
+            "{}{}{}",
+            &self.ours[lines.start..node.span.start],
+            theirs,
+            &self.ours[node.span.end..lines.end]
+        );
+
+        let mut text = format!("{}\n{}", OURS_MARKER, ours_side);
+        if !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(SEPARATOR_MARKER);
+        text.push('\n');
+        if !theirs_side.trim().is_empty() {
+            text.push_str(&theirs_side);
+            if !text.ends_with('\n') {
+                text.push('\n');
+            }
+        }
+        text.push_str(THEIRS_MARKER);
+        text.push('\n');
+
+        self.edits.push(Edit {
+            range: lines,
+            text,
+            conflict: Some(path),
+        });
+    }
+
+    fn delete(&mut self, node: &SourceNode) {
+        let range = node.removal_span(self.ours);
+        self.edit(range, String::new());
+    }
+
+    fn edit(&mut self, range: Range<usize>, text: String) {
+        self.edits.push(Edit {
+            range,
+            text,
+            conflict: None,
+        });
+    }
+}
+
+/// Statements with their matching keys; repeated keys are numbered
+fn keyed(nodes: &[SourceNode]) -> Vec<(String, &SourceNode)> {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    nodes
+        .iter()
+        .map(|node| {
+            let key = key(node);
+            let count = seen.entry(key.clone()).or_default();
+            *count += 1;
+            match *count {
+                1 => (key, node),
+                n => (format!("{} #{}", key, n), node),
+            }
+        })
+        .collect()
+}
+
+/// Key of a statement: keyword and ID of declarations with an ID, otherwise
+/// the head up to any `FROM` (so lifecycle statuses keep their key when their
+/// dates change)
+fn key(node: &SourceNode) -> String {
+    let declaration = node
+        .keyword()
+        .chars()
+        .all(|c| c.is_ascii_uppercase() || c == '_');
+    if declaration && node.has_body() {
+        let id = node.field("id").or_else(|| {
+            node.child("identity")
+                .and_then(|identity| identity.field("id"))
+        });
+        if let Some(id) = id {
+            return format!("{} {}", node.keyword(), id);
+        }
+    }
+    match node.head.find(" FROM ") {
+        Some(end) => node.head[..end].to_string(),
+        None => node.head.clone(),
+    }
+}
+
+fn same(a: &SourceNode, b: &SourceNode) -> bool {
+    a.normalized() == b.normalized()
+}
+
+fn child_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{} > {}", path, key)
+    }
+}
+
+/// `//` comment lines directly above a statement, in order and with their
+/// line breaks
+fn leading_comments<'a>(source: &'a str, node: &SourceNode) -> Vec<&'a str> {
+    let line_start = node.line_span(source).start;
+    let mut comments: Vec<&str> = source[..line_start]
+        .split_inclusive('\n')
+        .rev()
+        .take_while(|line| line.trim().starts_with("//"))
+        .collect();
+    comments.reverse();
+    comments
+}
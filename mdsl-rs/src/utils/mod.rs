@@ -1,3 +1,9 @@
-//! Utility functions and types (stub)
+//! Utility functions and types
 
+pub mod csv;
+pub mod dates;
+pub mod dot;
+pub mod json;
+pub mod merge;
 pub mod source_map;
+pub mod xml;
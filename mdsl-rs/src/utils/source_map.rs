@@ -1 +1,383 @@
-//! Source position tracking utilities (stub)
+//! Source position tracking utilities
+//!
+//! [`SourceMap`] outlines an MDSL source as a tree of statements with their
+//! byte spans. Tools that rewrite single declarations or fields use it to
+//! leave everything else in the file, comments and formatting included,
+//! exactly as it was.
+
+use crate::error::{Error, ParserError, Result};
+use crate::lexer::{Lexer, Token, TokenKind};
+use std::ops::Range;
+
+/// Outline of an MDSL source
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceMap {
+    /// Top-level statements in source order
+    pub nodes: Vec<SourceNode>,
+}
+
+/// A statement: a declaration or block with a `{ ... }` body, a
+/// `name = value;` field, or a bare statement such as an annotation
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceNode {
+    /// Tokens before the body or value, normalised (`OUTLET "Kurier"`, `circulation`)
+    pub head: String,
+    /// Value of a `name = value;` field, normalised
+    pub value: Option<String>,
+    /// Statements of the `{ ... }` body
+    pub children: Vec<SourceNode>,
+    /// Byte range of the whole statement, including its closing `}` or `;`
+    pub span: Range<usize>,
+    /// Byte range of the head
+    pub head_span: Range<usize>,
+    /// Byte offset of the closing `}` of the body, if there is one
+    pub body_end: Option<usize>,
+}
+
+impl SourceMap {
+    /// Outline a source; fails on lexer errors and unbalanced braces
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = Lexer::new(source).tokenize()?;
+        let mut outliner = Outliner {
+            source,
+            tokens,
+            current: 0,
+        };
+        let nodes = outliner.statements(None)?;
+        Ok(Self { nodes })
+    }
+
+    /// All statements, depth-first, paired with their parent
+    pub fn walk(&self) -> Vec<(Option<&SourceNode>, &SourceNode)> {
+        fn visit<'a>(
+            parent: Option<&'a SourceNode>,
+            nodes: &'a [SourceNode],
+            out: &mut Vec<(Option<&'a SourceNode>, &'a SourceNode)>,
+        ) {
+            for node in nodes {
+                out.push((parent, node));
+                visit(Some(node), &node.children, out);
+            }
+        }
+
+        let mut out = Vec::new();
+        visit(None, &self.nodes, &mut out);
+        out
+    }
+}
+
+impl SourceNode {
+    /// First word of the head (`OUTLET`, `identity`, `YEAR`, ...)
+    pub fn keyword(&self) -> &str {
+        self.head.split(' ').next().unwrap_or_default()
+    }
+
+    /// Whether the statement has a `{ ... }` body
+    pub fn has_body(&self) -> bool {
+        self.body_end.is_some()
+    }
+
+    /// First child statement with this head
+    pub fn child(&self, head: &str) -> Option<&SourceNode> {
+        self.children.iter().find(|child| child.head == head)
+    }
+
+    /// Value of the first child field with this name, string quotes removed
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.child(name)
+            .and_then(|child| child.value.as_deref())
+            .map(|value| value.trim_matches('"'))
+    }
+
+    /// Byte range of the full lines the statement is on, the final newline
+    /// included
+    pub fn line_span(&self, source: &str) -> Range<usize> {
+        let start = source[..self.span.start].rfind('\n').map_or(0, |i| i + 1);
+        let end = source[self.span.end..]
+            .find('\n')
+            .map_or(source.len(), |i| self.span.end + i + 1);
+        start..end
+    }
+
+    /// Byte range that removes the statement: its lines if it has them to
+    /// itself, together with one blank line so no gap is left
+    pub fn removal_span(&self, source: &str) -> Range<usize> {
+        let lines = self.line_span(source);
+        if !source[lines.start..self.span.start].trim().is_empty()
+            || !source[self.span.end..lines.end].trim().is_empty()
+        {
+            return self.span.clone();
+        }
+        let mut end = lines.end;
+        if source[end..].starts_with('\n') && source[..lines.start].ends_with("\n\n") {
+            end += 1;
+        }
+        lines.start..end
+    }
+
+    /// Source text of the statement with its continuation lines moved from
+    /// the statement's own indentation to `indent`; the first line is not
+    /// indented
+    pub fn reindented(&self, source: &str, indent: &str) -> String {
+        let own_indent = line_indent(source, self.span.start).len();
+        let text = &source[self.span.clone()];
+        let mut lines = text.lines();
+        let mut output = lines.next().unwrap_or_default().to_string();
+        for line in lines {
+            output.push('\n');
+            let stripped = line
+                .char_indices()
+                .find(|(i, c)| *i >= own_indent || !(*c == ' ' || *c == '\t'))
+                .map_or("", |(i, _)| &line[i..]);
+            if !stripped.is_empty() {
+                output.push_str(indent);
+                output.push_str(stripped);
+            }
+        }
+        output
+    }
+
+    /// The statement and its body, normalised, for comparing statements
+    /// regardless of formatting and comments
+    pub fn normalized(&self) -> String {
+        let mut text = self.head.clone();
+        if let Some(value) = &self.value {
+            text.push_str(" = ");
+            text.push_str(value);
+        }
+        if self.has_body() {
+            text.push_str(" {");
+            for child in &self.children {
+                text.push(' ');
+                text.push_str(&child.normalized());
+                text.push(';');
+            }
+            text.push_str(" }");
+        }
+        text
+    }
+}
+
+struct Outliner<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    current: usize,
+}
+
+impl Outliner<'_> {
+    /// Statements up to the closing brace at `open` (or the end of input)
+    fn statements(&mut self, open: Option<&Token>) -> Result<Vec<SourceNode>> {
+        let mut nodes = Vec::new();
+        loop {
+            self.skip(|kind| {
+                matches!(kind, TokenKind::Semicolon | TokenKind::Comma) || is_trivia(kind)
+            });
+            match self.peek_kind() {
+                TokenKind::Eof => {
+                    return match open {
+                        Some(open) => Err(unclosed(open)),
+                        None => Ok(nodes),
+                    }
+                }
+                TokenKind::RightBrace if open.is_some() => return Ok(nodes),
+                TokenKind::RightBrace => {
+                    let token = &self.tokens[self.current];
+                    return Err(Error::Parser(ParserError::UnexpectedToken {
+                        found: "}".to_string(),
+                        expected: vec!["statement".to_string()],
+                        position: token.position,
+                    }));
+                }
+                _ => nodes.push(self.statement()?),
+            }
+        }
+    }
+
+    fn statement(&mut self) -> Result<SourceNode> {
+        let start = self.current;
+
+        // Annotations run to the end of their line: `@comment "..."`
+        if matches!(self.peek_kind(), TokenKind::Annotation(_)) {
+            self.current += 1;
+            while !matches!(
+                self.peek_kind(),
+                TokenKind::Newline | TokenKind::Semicolon | TokenKind::RightBrace | TokenKind::Eof
+            ) {
+                self.current += 1;
+            }
+            let end = self.current;
+            if matches!(self.peek_kind(), TokenKind::Semicolon) {
+                self.current += 1;
+            }
+            return Ok(self.node(start, end, None, Vec::new(), None));
+        }
+
+        while !matches!(
+            self.peek_kind(),
+            TokenKind::Assign
+                | TokenKind::LeftBrace
+                | TokenKind::Semicolon
+                | TokenKind::Comma
+                | TokenKind::RightBrace
+                | TokenKind::Eof
+        ) {
+            self.current += 1;
+        }
+        let head_end = self.current;
+
+        match self.peek_kind() {
+            TokenKind::Assign => {
+                self.current += 1;
+                let value_start = self.current;
+                let mut depth = 0usize;
+                loop {
+                    match self.peek_kind() {
+                        TokenKind::LeftBrace | TokenKind::LeftBracket | TokenKind::LeftParen => {
+                            depth += 1
+                        }
+                        TokenKind::RightBrace | TokenKind::RightBracket | TokenKind::RightParen
+                            if depth > 0 =>
+                        {
+                            depth -= 1
+                        }
+                        TokenKind::Semicolon | TokenKind::Comma if depth == 0 => break,
+                        TokenKind::RightBrace => break,
+                        TokenKind::Eof if depth > 0 => {
+                            return Err(unclosed(&self.tokens[value_start]))
+                        }
+                        TokenKind::Eof => break,
+                        _ => {}
+                    }
+                    self.current += 1;
+                }
+                let value = self.text(value_start, self.current);
+                if matches!(self.peek_kind(), TokenKind::Semicolon | TokenKind::Comma) {
+                    self.current += 1;
+                }
+                Ok(self.node(start, head_end, Some(value), Vec::new(), None))
+            }
+            TokenKind::LeftBrace => {
+                let open = self.tokens[self.current].clone();
+                self.current += 1;
+                let children = self.statements(Some(&open))?;
+                let body_end = self.tokens[self.current].position.offset;
+                self.current += 1;
+                self.skip(is_trivia);
+                if matches!(self.peek_kind(), TokenKind::Semicolon) {
+                    self.current += 1;
+                }
+                Ok(self.node(start, head_end, None, children, Some(body_end)))
+            }
+            _ => {
+                if matches!(self.peek_kind(), TokenKind::Semicolon | TokenKind::Comma) {
+                    self.current += 1;
+                }
+                Ok(self.node(start, head_end, None, Vec::new(), None))
+            }
+        }
+    }
+
+    fn node(
+        &self,
+        start: usize,
+        head_end: usize,
+        value: Option<String>,
+        children: Vec<SourceNode>,
+        body_end: Option<usize>,
+    ) -> SourceNode {
+        // The statement ends with the last token it consumed, not at the
+        // newlines and comments skipped while looking for a trailing `;`
+        let mut last = self.current.saturating_sub(1).max(start);
+        while last > start && is_trivia(&self.tokens[last].kind) {
+            last -= 1;
+        }
+        SourceNode {
+            head: self.text(start, head_end),
+            value,
+            children,
+            span: self.tokens[start].position.offset..self.token_end(last),
+            head_span: self.tokens[start].position.offset
+                ..self.token_end(head_end.saturating_sub(1).max(start)),
+            body_end,
+        }
+    }
+
+    /// Normalised text of tokens `from..to`: single spaces between tokens,
+    /// numbers in canonical form
+    fn text(&self, from: usize, to: usize) -> String {
+        self.tokens[from..to]
+            .iter()
+            .enumerate()
+            .filter(|(_, token)| !is_trivia(&token.kind))
+            .map(|(i, token)| match &token.kind {
+                TokenKind::Number(n) => n.to_string(),
+                _ => self.raw(from + i).to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Source text of a token
+    fn raw(&self, index: usize) -> &str {
+        &self.source[self.tokens[index].position.offset..self.token_end(index)]
+    }
+
+    fn token_end(&self, index: usize) -> usize {
+        let start = self.tokens[index].position.offset;
+        let next = self
+            .tokens
+            .get(index + 1)
+            .map_or(self.source.len(), |token| token.position.offset);
+        match self.tokens[index].kind {
+            TokenKind::Newline => start + 1,
+            _ => start + self.source[start..next].trim_end().len(),
+        }
+    }
+
+    fn peek_kind(&self) -> &TokenKind {
+        self.tokens
+            .get(self.current)
+            .map_or(&TokenKind::Eof, |token| &token.kind)
+    }
+
+    fn skip(&mut self, skipped: fn(&TokenKind) -> bool) {
+        while skipped(self.peek_kind()) {
+            self.current += 1;
+        }
+    }
+}
+
+/// Indentation of the line containing `offset`
+pub fn line_indent(source: &str, offset: usize) -> String {
+    let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+    source[line_start..]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect()
+}
+
+/// Start of the line containing `offset`, if nothing but indentation
+/// precedes `offset` on it; `None` for a closing brace that shares its line
+/// with statements, as in a one-line block
+pub fn own_line_start(source: &str, offset: usize) -> Option<usize> {
+    let line_start = source[..offset].rfind('\n').map_or(0, |i| i + 1);
+    source[line_start..offset]
+        .chars()
+        .all(|c| c == ' ' || c == '\t')
+        .then_some(line_start)
+}
+
+/// Newlines and comments, which never end a statement
+fn is_trivia(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::Newline | TokenKind::Comment(_) | TokenKind::MultiLineComment(_)
+    )
+}
+
+fn unclosed(open: &Token) -> Error {
+    Error::Parser(ParserError::MissingClosingDelimiter {
+        delimiter: "}".to_string(),
+        position: open.position,
+    })
+}
@@ -1,7 +1,7 @@
 //! Code generation tests
 //! Tests for SQL and Cypher code generation from IR
 
-use mdsl_rs::codegen::{SqlGenerator, CypherGenerator};
+use mdsl_rs::codegen::{AnmiSqlGenerator, SqlGenerator, CypherGenerator};
 use mdsl_rs::ir::{nodes::*, transform};
 use mdsl_rs::parse;
 
@@ -563,6 +563,14 @@ fn test_empty_ir_generation() {
         vocabularies: Vec::new(),
         families: Vec::new(),
         events: Vec::new(),
+        companies: Vec::new(),
+        outlet_party_links: Vec::new(),
+        persons: Vec::new(),
+        ownerships: Vec::new(),
+        identifier_links: Vec::new(),
+        catalogs: Vec::new(),
+        redaction_policies: Vec::new(),
+        relationship_types: Vec::new(),
     };
     
     let sql_generator = SqlGenerator::new();
@@ -639,4 +647,517 @@ fn test_unicode_character_support() {
     assert!(sql.contains("Süddeutsche"));
     assert!(cypher.contains("Österreichische"));
     assert!(cypher.contains("Süddeutsche"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_outlet_party_link_generation() {
+    let source = r#"
+        FAMILY "Test Family" {
+            OUTLET "Test Outlet" {
+                IDENTITY {
+                    id = 100;
+                    title = "Test Outlet";
+                }
+            }
+        }
+
+        COMPANY "Test Verlag GmbH" {
+            id = 400001;
+            legal_form = "GmbH";
+            @comment "Publisher of the test outlet"
+        }
+
+        OUTLET_PARTY_LINK test_publisher {
+            outlet = 100;
+            party = 400001;
+            role = "publisher";
+            period_start = "1988-01-01";
+            period_end = CURRENT;
+            source = "firmenbuch";
+        }
+    "#;
+    let ast = parse(source).unwrap();
+    let ir = transform(&ast).unwrap();
+
+    assert_eq!(ir.companies.len(), 1);
+    assert_eq!(ir.outlet_party_links.len(), 1);
+
+    let sql = SqlGenerator::new().generate(&ir).unwrap();
+    assert!(sql.contains("CREATE TABLE companies"));
+    assert!(sql.contains("CREATE TABLE outlet_party_links"));
+    assert!(sql.contains("INSERT INTO companies (id, name, comment) VALUES (400001, 'Test Verlag GmbH', 'Publisher of the test outlet');"));
+    assert!(sql.contains("VALUES (400001, 'legal_form', 'GmbH');"));
+    assert!(sql.contains("VALUES ('test_publisher', 100, 400001, 'publisher', '1988-01-01', NULL, 'firmenbuch', NULL);"));
+
+    let cypher = CypherGenerator::new().generate(&ir).unwrap();
+    assert!(cypher.contains("MERGE (c:mdsl_Company {id_company: 400001})"));
+    assert!(cypher.contains("c.legal_form = 'GmbH'"));
+    assert!(cypher.contains("MERGE (c)-[r:mdsl_PUBLISHES {name: 'test_publisher'}]->(o)"));
+    assert!(cypher.contains("r.start_rel = datetime('1988-01-01'), r.end_rel = datetime('9999-01-01')"));
+}
+
+#[test]
+fn test_person_and_ownership_generation() {
+    let source = r#"
+        COMPANY "Test Holding" { id = 400001; }
+        COMPANY "Test Verlag GmbH" { id = 400002; }
+        PERSON "Test Owner" {
+            id = 9001;
+            scraped_at = "2026-03-11 00:44:27";
+        }
+
+        OWNERSHIP holding_stake {
+            owner_company = 400001;
+            owned_company = 400002;
+            share_pct = 74.5;
+            role = "shareholder";
+            source_url = "https://example.org/verlag";
+            scraped_at = "2026-03-11 00:44:27";
+        }
+
+        OWNERSHIP person_stake {
+            owner_person = 9001;
+            owned_company = 400001;
+            share_pct = 100;
+            period_end = "2020-12-31";
+        }
+    "#;
+    let ast = parse(source).unwrap();
+    let ir = transform(&ast).unwrap();
+
+    assert_eq!(ir.persons.len(), 1);
+    assert_eq!(ir.ownerships.len(), 2);
+
+    let sql = SqlGenerator::new().generate(&ir).unwrap();
+    assert!(sql.contains("CREATE TABLE persons"));
+    assert!(sql.contains("CREATE TABLE ownerships"));
+    assert!(sql.contains("INSERT INTO persons (id, name, comment) VALUES (9001, 'Test Owner', NULL);"));
+    assert!(sql.contains("VALUES (9001, 'scraped_at', '2026-03-11 00:44:27');"));
+    assert!(sql.contains("VALUES ('holding_stake', 400001, NULL, 400002, 74.5, 'shareholder', NULL, NULL, 'https://example.org/verlag', '2026-03-11 00:44:27', NULL);"));
+    assert!(sql.contains("VALUES ('person_stake', NULL, 9001, 400001, 100, NULL, NULL, '2020-12-31', NULL, NULL, NULL);"));
+
+    let cypher = CypherGenerator::new().generate(&ir).unwrap();
+    assert!(cypher.contains("MERGE (p:mdsl_Person {id_person: 9001})"));
+    assert!(cypher.contains("MATCH (h:mdsl_Company {id_company: 400001}), (c:mdsl_Company {id_company: 400002}) MERGE (h)-[r:mdsl_HOLDS_SHARE_IN {name: 'holding_stake'}]->(c) SET r.share_pct = 74.5"));
+    assert!(cypher.contains("MATCH (h:mdsl_Person {id_person: 9001})"));
+    assert!(cypher.contains("r.end_rel = datetime('2020-12-31')"));
+}
+
+#[test]
+fn test_identifier_link_generation() {
+    let source = r#"
+        COMPANY "Test Verlag" { id = 400001; }
+
+        IDENTIFIER_LINK match_400001_123 {
+            company = 400001;
+            scheme = "northdata";
+            value = "123";
+            status = "accepted";
+            score = 0.875;
+            issued_by = "mdsl match";
+        }
+    "#;
+    let ir = transform(&parse(source).unwrap()).unwrap();
+    assert_eq!(ir.identifier_links.len(), 1);
+
+    let sql = SqlGenerator::new().generate(&ir).unwrap();
+    assert!(sql.contains("CREATE TABLE company_identifiers"));
+    assert!(sql.contains("VALUES ('match_400001_123', 400001, 'northdata', '123', 'accepted', 0.875, 'mdsl match', NULL);"));
+
+    let cypher = CypherGenerator::new().generate(&ir).unwrap();
+    assert!(cypher.contains("MERGE (i:mdsl_Identifier {scheme: 'northdata', value: '123'}) MERGE (c)-[r:mdsl_HAS_IDENTIFIER {name: 'match_400001_123'}]->(i)"));
+}
+
+#[test]
+fn test_provenance_generation() {
+    let source = r#"
+        CATALOG sources {
+            SOURCE "northdata" {
+                quality_tier = "secondary";
+            }
+        }
+
+        COMPANY "Test Verlag" {
+            id = 400001;
+            register_court = "Berlin";
+            PROVENANCE register_court {
+                source = "northdata";
+                confidence = 0.8;
+            }
+        }
+
+        COMPANY "Test Holding" { id = 400002; }
+
+        OWNERSHIP holding_stake {
+            owner_company = 400002;
+            owned_company = 400001;
+            share_pct = 74.5;
+            PROVENANCE {
+                source = "northdata";
+                verification = "verified";
+                verified_at = "2026-03-11";
+            }
+        }
+    "#;
+    let ir = transform(&parse(source).unwrap()).unwrap();
+    assert_eq!(ir.companies[0].provenance.len(), 1);
+    assert_eq!(ir.companies[0].provenance[0].quality_tier.as_deref(), Some("secondary"));
+
+    let sql = SqlGenerator::new().generate(&ir).unwrap();
+    assert!(sql.contains("CREATE TABLE provenance"));
+    assert!(sql.contains("VALUES ('company', '400001', 'register_court', 'northdata', 'secondary', 0.8, NULL, NULL, NULL);"));
+    assert!(sql.contains("VALUES ('ownership', 'holding_stake', NULL, 'northdata', 'secondary', NULL, 'verified', '2026-03-11', NULL);"));
+
+    let cypher = CypherGenerator::new().generate(&ir).unwrap();
+    assert!(cypher.contains("MATCH (n:mdsl_Company {id_company: 400001}) MERGE (s:mdsl_Source {name: 'northdata'}) MERGE (n)-[p:mdsl_ASSERTED_BY {field: 'register_court'}]->(s) SET p.quality_tier = 'secondary', p.confidence = 0.8"));
+    assert!(cypher.contains("r.provenance_source = 'northdata', r.provenance_quality_tier = 'secondary'"));
+}
+
+#[test]
+fn test_data_provenance_generation() {
+    let source = r#"
+        FAMILY "Test Family" {
+            OUTLET "Test Outlet" {
+                IDENTITY { id = 100; title = "Test"; }
+            }
+            DATA FOR 100 {
+                years {
+                    2020 {
+                        circulation = 50000;
+                        PROVENANCE circulation {
+                            source = "ivw";
+                        }
+                    };
+                }
+            }
+        }
+    "#;
+    let ir = transform(&parse(source).unwrap()).unwrap();
+    let data_block = &ir.families[0].data_blocks[0];
+    assert_eq!(data_block.provenance.len(), 1);
+    assert_eq!(data_block.provenance[0].target.as_deref(), Some("2020.circulation"));
+    assert_eq!(data_block.provenance[0].source, "ivw");
+
+    let sql = AnmiSqlGenerator::new().generate(&ir).unwrap();
+    assert!(sql.contains("CREATE TABLE IF NOT EXISTS graphv3.provenance"));
+    assert!(sql.contains("VALUES ('mo_year', 100, NULL, NULL, '2020.circulation', 'ivw', NULL, NULL, NULL, NULL, NULL);"));
+
+    let cypher = CypherGenerator::new().generate(&ir).unwrap();
+    assert!(cypher.contains("MERGE (n)-[p:mdsl_ASSERTED_BY {field: 'data.2020.circulation'}]->(s)"));
+}
+
+#[test]
+fn test_anmi_provenance_rows() {
+    let source = r#"
+        COMPANY "Test Verlag" {
+            id = 400001;
+            PROVENANCE { source = "northdata"; }
+        }
+
+        FAMILY "Test Family" {
+            OUTLET "Curated" {
+                IDENTITY { id = 100; title = "Curated"; }
+                metadata { verified = "2024-10-15"; }
+                PROVENANCE { source = "oeak"; }
+            }
+            OUTLET "Imported" {
+                IDENTITY { id = 101; title = "Imported"; }
+                metadata { steward = "imported"; verified = "2024-01-01"; }
+            }
+        }
+
+        EVENT sale {
+            type = "acquisition";
+            date = "2020-01-01";
+            PROVENANCE { source = "press"; }
+        }
+    "#;
+    let ir = transform(&parse(source).unwrap()).unwrap();
+
+    // Legacy `verified` metadata marks the outlet's own records as verified
+    let curated = &ir.families[0].outlets[0].provenance;
+    assert_eq!(curated.len(), 1);
+    assert_eq!(curated[0].source, "oeak");
+    assert_eq!(curated[0].verification.as_deref(), Some("verified"));
+    assert_eq!(curated[0].verified_at.as_deref(), Some("2024-10-15"));
+    let imported = &ir.families[0].outlets[1].provenance;
+    assert_eq!(imported.len(), 1);
+    assert_eq!(imported[0].source, "imported");
+    assert_eq!(imported[0].verified_at.as_deref(), Some("2024-01-01"));
+
+    let sql = AnmiSqlGenerator::new().generate(&ir).unwrap();
+    assert!(sql.contains("VALUES ('mo_constant', 101, NULL, NULL, NULL, 'imported', NULL, NULL, 'verified', '2024-01-01', NULL);"));
+    assert!(sql.contains("VALUES ('company', 400001, NULL, NULL, NULL, 'northdata', NULL, NULL, NULL, NULL, NULL);"));
+    assert!(sql.contains("VALUES ('event', NULL, NULL, 'sale', NULL, 'press', NULL, NULL, NULL, NULL, NULL);"));
+
+    // Without provenance, the output has no provenance section
+    let plain = transform(
+        &parse(r#"FAMILY "F" { OUTLET "O" { IDENTITY { id = 1; title = "O"; } } }"#).unwrap(),
+    )
+    .unwrap();
+    let sql = AnmiSqlGenerator::new().generate(&plain).unwrap();
+    assert!(!sql.contains("-- Provenance"));
+}
+
+#[test]
+fn test_catalog_source_generation() {
+    let source = r#"
+        catalog sources {
+            source "oeak" {
+                anmi_source_id_components { type_code = 15; }
+                display_name = "ÖAK";
+                quality_tier = "primary";
+                @maps_to_table "Quellen";
+            }
+        }
+
+        FAMILY "Test Family" {
+            OUTLET "Test Outlet" {
+                IDENTITY { id = 100; title = "Test"; }
+            }
+            DATA FOR 100 {
+                YEAR 2020 {
+                    metrics {
+                        circulation = { value = 50000; unit = "copies"; source = "oeak"; };
+                    };
+                };
+            }
+        }
+
+        EVENT test_sale {
+            type = "acquisition";
+            @source "oeak"
+        }
+    "#;
+    let ir = transform(&parse(source).unwrap()).unwrap();
+    assert_eq!(ir.catalogs.len(), 1);
+    let oeak = &ir.catalogs[0].sources[0];
+    assert_eq!(oeak.catalog, "sources");
+    assert_eq!(oeak.quality_tier.as_deref(), Some("primary"));
+    assert_eq!(oeak.maps_to.as_deref(), Some("Quellen"));
+    assert!(oeak.fields.iter().any(|f| f.name == "anmi_source_id_components.type_code"));
+
+    let sql = SqlGenerator::new().generate(&ir).unwrap();
+    assert!(sql.contains("CREATE TABLE sources"));
+    assert!(sql.contains("INSERT INTO sources (name, catalog_name, quality_tier, maps_to) VALUES ('oeak', 'sources', 'primary', 'Quellen');"));
+    assert!(sql.contains("VALUES ('oeak', 'anmi_source_id_components.type_code', '15');"));
+
+    // The catalog source is written under its composite ANMI ID for the cited year
+    let anmi = AnmiSqlGenerator::new().generate(&ir).unwrap();
+    assert!(anmi.contains("INSERT INTO graphv3.sources_names (id_source, source_name) VALUES (1502020, 'oeak') ON CONFLICT DO NOTHING;"));
+
+    let cypher = CypherGenerator::new().generate(&ir).unwrap();
+    assert!(cypher.contains("MERGE (s:mdsl_Source {name: 'oeak'})\nON CREATE SET s.catalog = 'sources', s.quality_tier = 'primary'"));
+    assert!(cypher.contains("SET s.anmi_source_id_components_type_code = '15';"));
+    assert!(cypher.contains("MATCH (e:mdsl_Event {name: 'test_sale'}) MERGE (s:mdsl_Source {name: 'oeak'}) MERGE (e)-[c:mdsl_CITES]->(s);"));
+}
+
+#[test]
+fn test_redeclared_source_inserted_once() {
+    let source = r#"
+        catalog sources {
+            source "oeak" { quality_tier = "primary"; }
+        }
+        catalog legacy {
+            source "oeak" { quality_tier = "secondary"; }
+        }
+    "#;
+    let ir = transform(&parse(source).unwrap()).unwrap();
+
+    // The first declaration keeps the sources primary key unique
+    let sql = SqlGenerator::new().generate(&ir).unwrap();
+    assert_eq!(sql.matches("INSERT INTO sources ").count(), 1);
+    assert!(sql.contains("VALUES ('oeak', 'sources', 'primary', NULL);"));
+}
+
+#[test]
+fn test_anmi_composite_source_ids() {
+    let source = r#"
+        catalog sources {
+            source "oeak" {
+                anmi_source_id_components { type_code = 15; }
+                source_title_key = "Österreichische Auflagenkontrolle (ÖAK)";
+            }
+            source "survey" {
+                display_name = "Survey";
+            }
+        }
+
+        FAMILY "Test Family" {
+            OUTLET "Test Outlet" {
+                IDENTITY { id = 100; title = "Test"; }
+            }
+            DATA FOR 100 {
+                YEAR 2021 {
+                    metrics {
+                        circulation = { value = 436260; unit = "copies"; source = "oeak"; };
+                        reach_national = { value = 6.8; unit = "percent"; source = "2002021"; };
+                        market_share = { value = 16.1; unit = "percent"; source = "survey"; };
+                    };
+                };
+            }
+        }
+    "#;
+    let ir = transform(&parse(source).unwrap()).unwrap();
+    assert_eq!(ir.catalogs[0].sources[0].anmi_type_code, Some(15));
+
+    let sql = AnmiSqlGenerator::new().generate(&ir).unwrap();
+    assert!(sql.contains("INSERT INTO graphv3.sources_names (id_source, source_name) VALUES (1502021, 'Österreichische Auflagenkontrolle (ÖAK)') ON CONFLICT DO NOTHING;"));
+    assert!(sql.contains("VALUES (100, 2021, 1002021, 0, 436260, 1502021)"));
+    assert!(sql.contains("VALUES (100, 2021, 1002021, 0, 6.8, 2002021)"));
+    // A catalog source without a type code cannot be mapped to an ANMI ID
+    assert!(sql.contains("-- Source 'survey' has no ANMI source ID"));
+    assert!(sql.contains("VALUES (100, 2021, 1002021, 0, 16.1, NULL)"));
+}
+
+#[test]
+fn test_anmi_csv_generation() {
+    use mdsl_rs::codegen::AnmiCsvGenerator;
+
+    let source = r#"
+        FAMILY "Test Family" {
+            OUTLET "Abend, Der [1]" {
+                identity { id = 100101; title = "Abend, Der [1]"; };
+                lifecycle {
+                    status "inactive" FROM "1915-06-14" TO "1917-02-20" {
+                        precision_start = "known";
+                        precision_end = "known";
+                    };
+                };
+                characteristics {
+                    sector = 11;
+                    mandate = "2";
+                    location = "Wien";
+                    primary_distribution_area = 11;
+                    local = 0;
+                    language = "deutsch";
+                };
+                metadata {
+                    comment = "21.02.1917-06.03.1917 aus Zensurgründen nicht erschienen";
+                };
+            };
+            OUTLET "Krone" {
+                identity { id = 103680; title = "Krone"; };
+                lifecycle { status "active" FROM "1959-01-01" TO CURRENT { precision_start = "4"; }; };
+            };
+            DATA FOR 103680 {
+                YEAR 2021 {
+                    metrics {
+                        calc = { value = 1; unit = "code"; };
+                        circulation = { value = 480589; unit = "copies"; source = "1502021"; };
+                        reach_national = { value = 9.3; unit = "percent"; source = "2002021"; };
+                        market_share = { value = 17.8; unit = "percent"; source = "1502021"; };
+                    };
+                    comment = "Mo-Fr";
+                };
+            }
+        }
+
+        DIACHRONIC_LINK evolution_100421_succession {
+            predecessor = 100421;
+            successor = 100750;
+            relationship_type = "succession";
+        };
+        SYNCHRONOUS_LINK link_101251_umbrella {
+            outlet_1 = { id = 101251; role = "source"; };
+            outlet_2 = { id = 101250; role = "target"; };
+            relationship_type = "umbrella";
+            period_start = "1989-12-12";
+            period_end = "1991-10-31";
+        };
+    "#;
+    let ir = transform(&parse(source).unwrap()).unwrap();
+    let files = AnmiCsvGenerator::new().generate(&ir).unwrap();
+    assert_eq!(files.len(), 14);
+    let file = |name: &str| &files.iter().find(|(file, _)| file == name).unwrap().1;
+
+    // Rows match the shipped table dump byte for byte
+    let dump = |name: &str| {
+        std::fs::read_to_string(format!("../anmi-media-v1/02_csv_tables/{}", name)).unwrap()
+    };
+    for name in ["mo_constant.csv", "mo_year.csv", "11_succession.csv", "33_umbrella.csv"] {
+        let expected = dump(name);
+        let generated = file(name);
+        assert_eq!(generated.lines().next(), expected.lines().next(), "{} header", name);
+        let first_row = generated.lines().nth(1).unwrap();
+        assert!(expected.lines().any(|line| line == first_row), "{}: {}", name, first_row);
+    }
+
+    // Sentinels for unknown values and open periods
+    let krone = file("mo_constant.csv").lines().nth(2).unwrap();
+    assert_eq!(krone, "103680,Krone,99,99,KA,99,99,KA,1959-01-01,4,9999-01-01,9,KA,KA,KA");
+    assert_eq!(file("sources_names.csv").lines().count(), 3);
+    assert_eq!(file("23_merger.csv"), "id_mo,merger\n");
+}
+
+#[test]
+fn test_anmi_csv_unknown_start_dates() {
+    use mdsl_rs::codegen::AnmiCsvGenerator;
+
+    let source = r#"
+        FAMILY "Test Family" {
+            OUTLET "Undated" {
+                identity { id = 100; title = "Undated"; };
+            };
+        }
+
+        SYNCHRONOUS_LINK link_101251_umbrella {
+            outlet_1 = { id = 101251; role = "source"; };
+            outlet_2 = { id = 101250; role = "target"; };
+            relationship_type = "umbrella";
+            period_end = "1991-10-31";
+        };
+    "#;
+    let ir = transform(&parse(source).unwrap()).unwrap();
+    let files = AnmiCsvGenerator::new().generate(&ir).unwrap();
+    let file = |name: &str| &files.iter().find(|(file, _)| file == name).unwrap().1;
+    assert_eq!(
+        file("mo_constant.csv").lines().nth(1).unwrap(),
+        "100,Undated,99,99,KA,99,99,KA,KA,9,9999-01-01,9,KA,KA,KA"
+    );
+    assert_eq!(file("33_umbrella.csv").lines().nth(1).unwrap(), "101251,101250,KA,1991-10-31");
+}
+
+#[test]
+fn test_anmi_source_ids_from_source_table() {
+    let source = r#"
+        VOCABULARY sources_names {
+            SOURCES {
+                3002020: "ÖWA Jahresbericht 2020",
+                3042008: "ÖWA 4. Quartal 2008"
+            }
+        }
+
+        catalog sources {
+            source "owa" {
+                anmi_source_id_components { type_code = 30; }
+                source_title_key = "Österreichische Webanalyse (ÖWA)";
+            }
+        }
+
+        FAMILY "Test Family" {
+            OUTLET "Test Outlet" {
+                IDENTITY { id = 100; title = "Test"; }
+            }
+            DATA FOR 100 {
+                YEAR 2008 {
+                    metrics {
+                        unique_users = { value = 1200; unit = "users"; source = "owa"; };
+                    };
+                };
+                YEAR 2020 {
+                    metrics {
+                        unique_users = { value = 1500; unit = "users"; source = "owa"; };
+                    };
+                };
+            }
+        }
+    "#;
+    let ir = transform(&parse(source).unwrap()).unwrap();
+
+    // Quarterly sources cannot be derived from type code and year
+    let sql = AnmiSqlGenerator::new().generate(&ir).unwrap();
+    assert!(sql.contains("VALUES (3042008, 'ÖWA 4. Quartal 2008')"));
+    assert!(sql.contains("VALUES (3002020, 'ÖWA Jahresbericht 2020')"));
+    assert!(!sql.contains("3002008"));
+}
@@ -550,6 +550,12 @@ fn test_express_freeze3_constructs() {
                             FamilyMember::Relationship(_) => {
                                 // Skip relationships
                             }
+                            FamilyMember::Provenance(_) => {
+                                // Skip provenance
+                            }
+                            FamilyMember::Review(_) => {
+                                // Skip reviews
+                            }
                             FamilyMember::Comment(_) => {
                                 // Skip comments
                             }
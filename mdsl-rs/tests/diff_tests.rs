@@ -0,0 +1,200 @@
+//! Tests for the entity-level diff between two datasets
+
+use mdsl_rs::analysis::diff::*;
+use mdsl_rs::ir::nodes::IRProgram;
+use mdsl_rs::ir::transform;
+use mdsl_rs::parse;
+
+const OLD_RELEASE: &str = r#"
+    FAMILY "Kurier Group" {
+        OUTLET "Kurier" {
+            identity {
+                id = 200002;
+                title = "Kurier";
+            };
+            lifecycle {
+                status "active" FROM "1954-10-18" TO CURRENT {
+                    precision_start = "known";
+                };
+            };
+            characteristics {
+                sector = 11;
+                language = "deutsch";
+            };
+        };
+
+        OUTLET "Der Abend" {
+            identity {
+                id = 100101;
+                title = "Der Abend";
+            };
+        };
+
+        DIACHRONIC_LINK abend_kurier {
+            predecessor = 100101;
+            successor = 200002;
+            relationship_type = "succession";
+        };
+    }
+
+    SYNCHRONOUS_LINK kurier_umbrella {
+        outlet_1 = { id = 200002; role = "source"; };
+        outlet_2 = { id = 300001; role = "target"; };
+        relationship_type = "umbrella";
+        period_start = "1990-01-01";
+    };
+
+    DATA FOR 200002 {
+        YEAR 2022 {
+            metrics {
+                circulation = { value = 100000; unit = "copies"; source = "1502022"; };
+                reach_national = { value = 7.5; unit = "percent"; source = "2002022"; };
+            };
+        };
+    };
+
+    EVENT kurier_sale {
+        type = "acquisition";
+        date = "2001-01-01";
+        entities = {
+            buyer = { id = 400001; role = "acquirer"; stake_after = 50; };
+        };
+    }
+"#;
+
+const NEW_RELEASE: &str = r#"
+    FAMILY "Kurier Group" {
+        OUTLET "Kurier Neu" {
+            identity {
+                id = 200002;
+                title = "Kurier Neu";
+            };
+            lifecycle {
+                status "active" FROM "1954-10-18" TO CURRENT {
+                    precision_start = "known";
+                };
+            };
+            characteristics {
+                sector = 11;
+                language = "Deutsch";
+            };
+        };
+
+        OUTLET "Kurier Online" {
+            identity {
+                id = 200003;
+                title = "Kurier Online";
+            };
+        };
+    }
+
+    SYNCHRONOUS_LINK umbrella_kurier {
+        outlet_1 = { id = 300001; role = "target"; };
+        outlet_2 = { id = 200002; role = "source"; };
+        relationship_type = "umbrella";
+        period_start = "1990-01-01";
+        period_end = "2020-12-31";
+    };
+
+    DATA FOR 200002 {
+        YEAR 2022 {
+            metrics {
+                circulation = { value = 100000; unit = "copies"; source = "1502022"; };
+                reach_national = { value = 7.7; unit = "percent"; source = "2002022"; };
+            };
+        };
+    };
+
+    EVENT kurier_sale {
+        type = "acquisition";
+        date = "2001-06-30";
+        entities = {
+            buyer = { id = 400001; role = "acquirer"; stake_after = 50; };
+        };
+    }
+"#;
+
+fn program(source: &str) -> IRProgram {
+    transform(&parse(source).unwrap()).unwrap()
+}
+
+fn change<'a>(diff: &'a ProgramDiff, key: &str) -> &'a EntityChange {
+    diff.changes
+        .iter()
+        .find(|change| change.key.to_string() == key)
+        .unwrap_or_else(|| panic!("no change for {}", key))
+}
+
+#[test]
+fn test_identical_programs_have_no_diff() {
+    let diff = ProgramDiff::between(&program(OLD_RELEASE), &program(OLD_RELEASE));
+    assert!(diff.is_empty());
+    assert!(diff
+        .format_text()
+        .starts_with("outlets: 0 added, 0 removed, 0 modified\n"));
+}
+
+#[test]
+fn test_entities_are_matched_by_key() {
+    let diff = ProgramDiff::between(&program(OLD_RELEASE), &program(NEW_RELEASE));
+
+    assert_eq!(diff.count(EntityKind::Outlet, ChangeKind::Added), 1);
+    assert_eq!(diff.count(EntityKind::Outlet, ChangeKind::Removed), 1);
+    assert_eq!(diff.count(EntityKind::Outlet, ChangeKind::Modified), 1);
+    assert_eq!(diff.count(EntityKind::Link, ChangeKind::Removed), 1);
+    assert_eq!(diff.count(EntityKind::Metric, ChangeKind::Modified), 1);
+    assert_eq!(diff.count(EntityKind::Event, ChangeKind::Modified), 1);
+    assert_eq!(diff.changes.len(), 7);
+
+    // Renamed, matched by ID
+    let kurier = change(&diff, "outlet 200002");
+    assert_eq!(kurier.change, ChangeKind::Modified);
+    assert_eq!(kurier.label, "Kurier Neu");
+    let fields: Vec<&str> = kurier.fields.iter().map(|f| f.field.as_str()).collect();
+    assert_eq!(
+        fields,
+        vec!["characteristics.language", "identity.title", "name"]
+    );
+    assert_eq!(kurier.fields[0].old.as_deref(), Some("deutsch"));
+    assert_eq!(kurier.fields[0].new.as_deref(), Some("Deutsch"));
+
+    assert_eq!(change(&diff, "outlet 100101").change, ChangeKind::Removed);
+    assert_eq!(change(&diff, "outlet 200003").change, ChangeKind::Added);
+    assert_eq!(
+        change(&diff, "link succession 100101 -> 200002").change,
+        ChangeKind::Removed
+    );
+
+    // Synchronous links match regardless of endpoint order and link name
+    let umbrella = change(&diff, "link umbrella 200002 <-> 300001");
+    let fields: Vec<&str> = umbrella.fields.iter().map(|f| f.field.as_str()).collect();
+    assert_eq!(fields, vec!["name", "period_end"]);
+    assert_eq!(umbrella.fields[1].old, None);
+
+    let reach = change(&diff, "metric 200002/2022/reach_national");
+    assert_eq!(reach.label, "Kurier Neu");
+    assert_eq!(reach.fields[0].field, "value");
+    assert_eq!(reach.fields[0].new.as_deref(), Some("7.7"));
+
+    assert_eq!(change(&diff, "event kurier_sale").fields[0].field, "date");
+}
+
+#[test]
+fn test_diff_formats() {
+    let diff = ProgramDiff::between(&program(OLD_RELEASE), &program(NEW_RELEASE));
+
+    let text = diff.format_text();
+    assert!(text.contains("outlets: 1 added, 1 removed, 1 modified"));
+    assert!(text.contains(
+        "~ outlet 200002 (Kurier Neu)\n    characteristics.language: deutsch -> Deutsch\n"
+    ));
+    assert!(text.contains("    identity.title: Kurier -> Kurier Neu\n"));
+    assert!(text.contains("- outlet 100101 (Der Abend)\n"));
+    assert!(text.contains("    period_end: (unset) -> 2020-12-31\n"));
+
+    let json = diff.format_json();
+    assert!(json.contains("\"outlets\": { \"added\": 1, \"removed\": 1, \"modified\": 1 }"));
+    assert!(json.contains("\"key\": \"link umbrella 200002 <-> 300001\""));
+    assert!(json.contains("{ \"field\": \"period_end\", \"old\": null, \"new\": \"2020-12-31\" }"));
+    assert_eq!(json.matches("\"change\": ").count(), diff.changes.len());
+}
@@ -0,0 +1,118 @@
+//! Tests for matching curated against crawled companies
+
+use mdsl_rs::analysis::entity_resolution::*;
+use mdsl_rs::ir::transform;
+use mdsl_rs::parse;
+
+const CURATED: &str = r#"
+    COMPANY "Mediaprint Zeitungs- und Zeitschriftenverlag GmbH & Co KG" { id = 1001; location = "Wien"; }
+    COMPANY "Süddeutscher Verlag" { id = 1002; registry_id = "HRB 73185"; }
+"#;
+
+const CRAWLED: &str = r#"
+    COMPANY "Mediaprint Zeitungs und Zeitschriftenverlag Ges.m.b.H. & Co. KG" { id = 500001; jurisdiction = "Wien"; }
+    COMPANY "Mediaprint Zeitungs- und Zeitschriftenverlag GmbH" { id = 500002; jurisdiction = "Wien"; }
+    COMPANY "Süddeutsche Zeitung GmbH" { id = 500003; registry_id = "HRB73185"; }
+    COMPANY "Passauer Neue Presse GmbH" { id = 500004; }
+"#;
+
+fn records(source: &str) -> Vec<MatchRecord> {
+    let ast = parse(source).unwrap();
+    MatchRecord::from_program(&transform(&ast).unwrap())
+}
+
+#[test]
+fn test_name_normalisation() {
+    assert_eq!(
+        normalize_name("Mediaprint Zeitungs- und Zeitschriftenverlag Ges.m.b.H. & Co. KG"),
+        "mediaprint zeitungs und zeitschriftenverlag"
+    );
+    assert_eq!(normalize_name("Süddeutsche Zeitung GmbH"), "sueddeutsche zeitung");
+    assert_eq!(legal_form_of("Test Ges.m.b.H. & Co. KG").as_deref(), Some("gmbh co kg"));
+    assert_eq!(legal_form_of("Kronen Zeitung"), None);
+    assert_eq!(normalize_registry_id("FN 123 45a"), "FN12345A");
+}
+
+#[test]
+fn test_candidates_are_ranked() {
+    let matcher = EntityMatcher::new();
+    let candidates = matcher.propose(&records(CURATED), &records(CRAWLED));
+
+    let mediaprint: Vec<_> = candidates.iter().filter(|c| c.curated_id == 1001).collect();
+    assert_eq!(mediaprint[0].crawled_id, 500001);
+    assert_eq!(mediaprint[0].legal_form_match, Some(true));
+    // Same name, but the GmbH is a different legal entity than the GmbH & Co KG
+    assert_eq!(mediaprint[1].crawled_id, 500002);
+    assert!(mediaprint[1].score < mediaprint[0].score);
+
+    // A matching register number outweighs a weak name match
+    let sz = candidates.iter().find(|c| c.curated_id == 1002).unwrap();
+    assert_eq!(sz.crawled_id, 500003);
+    assert_eq!(sz.registry_match, Some(true));
+    assert!(sz.score >= 0.95);
+
+    assert!(!candidates.iter().any(|c| c.crawled_id == 500004));
+}
+
+#[test]
+fn test_review_csv_round_trip() {
+    let matcher = EntityMatcher::new().with_scheme("northdata");
+    let candidates = matcher.propose(&records(CURATED), &records(CRAWLED));
+    let csv = matcher.to_csv(&candidates);
+    assert!(csv.starts_with("curated_id,curated_name,crawled_id,crawled_name,score"));
+
+    // Accept the first proposal, reject the second
+    let reviewed: String = csv
+        .lines()
+        .enumerate()
+        .map(|(i, line)| match i {
+            1 => format!("{}accept\n", line),
+            2 => format!("{}reject\n", line),
+            _ => format!("{}\n", line),
+        })
+        .collect();
+    let links = matcher.accepted_from_csv(&reviewed).unwrap();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].company_id, 1001);
+    assert_eq!(links[0].value, "500001");
+    assert_eq!(links[0].status.as_deref(), Some("accepted"));
+
+    // Written back into the curated file, the links parse and validate
+    let mut mdsl = CURATED.to_string();
+    mdsl.push_str(&render_identifier_link(&links[0]));
+    let ast = parse(&mdsl).unwrap();
+    assert!(mdsl_rs::semantic::validate_program(&ast).passed);
+    let ir = transform(&ast).unwrap();
+    assert_eq!(accepted_from_program(&ir).len(), 1);
+}
+
+#[test]
+fn test_proposed_links_are_not_accepted() {
+    let matcher = EntityMatcher::new();
+    let candidates = matcher.propose(&records(CURATED), &records(CRAWLED));
+    let mdsl = format!("{}{}", CURATED, matcher.to_mdsl(&candidates));
+
+    let ir = transform(&parse(&mdsl).unwrap()).unwrap();
+    assert_eq!(ir.identifier_links.len(), candidates.len());
+    assert!(accepted_from_program(&ir).is_empty());
+}
+
+#[test]
+fn test_equal_ids_of_different_sources_still_match() {
+    let curated = r#"COMPANY "Styria Media Group AG" { id = 500001; }"#;
+    let crawled = r#"COMPANY "Styria Media Group AG" { id = 500001; }"#;
+    let ir = |source: &str| transform(&parse(source).unwrap()).unwrap();
+    let matcher = EntityMatcher::new();
+
+    // Curated and crawled IDs are separate namespaces
+    let candidates = matcher.propose(
+        &MatchRecord::from_source(&ir(curated), "curated.mdsl"),
+        &MatchRecord::from_source(&ir(crawled), "crawled.mdsl"),
+    );
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].crawled_id, 500001);
+
+    // A company does not match itself when a file is compared with itself
+    let records = MatchRecord::from_source(&ir(curated), "curated.mdsl");
+    assert!(matcher.propose(&records, &records).is_empty());
+}
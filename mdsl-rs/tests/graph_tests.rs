@@ -0,0 +1,172 @@
+//! Tests for GraphML, GEXF and DOT graph exports (feature-gated)
+
+#[cfg(feature = "visualization")]
+mod graph_tests {
+    use mdsl_rs::codegen::{DotGenerator, GexfGenerator, GraphMlGenerator};
+    use mdsl_rs::ir::nodes::IRProgram;
+    use mdsl_rs::ir::transform;
+    use mdsl_rs::parse;
+
+    const SOURCE: &str = r#"
+        FAMILY "Kurier & Partner" {
+            OUTLET "Kurier" {
+                identity {
+                    id = 200002;
+                    title = "Kurier";
+                };
+                lifecycle {
+                    status "active" FROM "1954-10" TO "1980" {
+                        precision_start = "known";
+                    };
+                    status "active" FROM "1990-01-01" TO CURRENT {
+                        precision_start = "known";
+                    };
+                };
+            };
+
+            OUTLET "Der Abend" {
+                identity {
+                    id = 100101;
+                    title = "Der Abend";
+                };
+                lifecycle {
+                    status "active" FROM "1945-08-01" TO "1954-02" {
+                        precision_start = "known";
+                    };
+                };
+            };
+
+            DIACHRONIC_LINK abend_kurier {
+                predecessor = 100101;
+                successor = 200002;
+                event_date = "1954-10-18";
+                relationship_type = "succession";
+            };
+
+            SYNCHRONOUS_LINK kurier_umbrella {
+                outlet_1 = { id = 200002; role = "source"; };
+                outlet_2 = { id = 300001; role = "target"; };
+                relationship_type = "umbrella";
+                period_start = "1990";
+            };
+        }
+
+        COMPANY "Kurier \"Verlag\"" { id = 400001; }
+
+        OUTLET_PARTY_LINK kurier_publisher {
+            outlet = 200002;
+            party = 400001;
+            role = "publisher";
+        }
+
+        OWNERSHIP holding {
+            owner_company = 400002;
+            owned_company = 400001;
+            share_pct = 49.4;
+            period_start = "1981-02";
+        }
+    "#;
+
+    fn program() -> IRProgram {
+        transform(&parse(SOURCE).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_graphml_export() {
+        let graphml = GraphMlGenerator::new().generate(&program()).unwrap();
+        assert!(graphml
+            .contains("<key id=\"start\" for=\"all\" attr.name=\"start\" attr.type=\"string\"/>"));
+        assert!(graphml.contains("<data key=\"label\">Kurier &amp; Partner</data>"));
+        assert!(graphml.contains("<data key=\"label\">Kurier &quot;Verlag&quot;</data>"));
+
+        // Outlets span their active statuses; months are padded to real days
+        let abend = graphml.split("<node id=\"outlet_100101\">").nth(1).unwrap();
+        let abend = &abend[..abend.find("</node>").unwrap()];
+        assert!(abend.contains("<data key=\"start\">1945-08-01</data>"));
+        assert!(abend.contains("<data key=\"end\">1954-02-28</data>"));
+
+        // Undeclared endpoints become nodes labelled with their ID
+        assert!(graphml
+            .contains("<node id=\"outlet_300001\">\n      <data key=\"label\">300001</data>"));
+        assert!(graphml.contains("<node id=\"company_400002\">"));
+        assert!(graphml.contains(
+            "<edge id=\"e3\" source=\"outlet_200002\" target=\"outlet_300001\" directed=\"false\">"
+        ));
+        assert!(graphml.contains("<data key=\"weight\">49.4</data>"));
+        assert_eq!(graphml.matches("<node ").count(), 6);
+        assert_eq!(graphml.matches("<edge ").count(), 6);
+    }
+
+    #[test]
+    fn test_gexf_export_is_dynamic() {
+        let gexf = GexfGenerator::new().generate(&program()).unwrap();
+        assert!(gexf
+            .contains("<graph mode=\"dynamic\" defaultedgetype=\"directed\" timeformat=\"date\">"));
+
+        // Several active periods become spells, a single one node bounds
+        assert!(gexf.contains(
+            "<spells>\n          <spell start=\"1954-10-01\" end=\"1980-12-31\"/>\n          <spell start=\"1990-01-01\"/>\n        </spells>"
+        ));
+        assert!(gexf.contains(
+            "<node id=\"outlet_100101\" label=\"Der Abend\" start=\"1945-08-01\" end=\"1954-02-28\">"
+        ));
+        assert!(gexf.contains(
+            "<edge id=\"e2\" source=\"outlet_100101\" target=\"outlet_200002\" label=\"succession\" start=\"1954-10-18\" end=\"1954-10-18\">"
+        ));
+        assert!(gexf.contains("label=\"umbrella\" type=\"undirected\" start=\"1990-01-01\">"));
+        assert!(gexf.contains("label=\"owner\" weight=\"49.4\" start=\"1981-02-01\">"));
+        assert!(gexf.contains("<attvalue for=\"family\" value=\"Kurier &amp; Partner\"/>"));
+    }
+
+    #[test]
+    fn test_dot_export_clusters_families() {
+        let dot = DotGenerator::new().generate(&program()).unwrap();
+        assert!(dot.starts_with("digraph mdsl {\n"));
+        assert!(dot.contains(
+            "  subgraph \"cluster_0\" {\n    label=\"Kurier & Partner\";\n    \"outlet_200002\" [label=\"Kurier\"];\n    \"outlet_100101\" [label=\"Der Abend\"];\n  }\n"
+        ));
+        assert!(dot
+            .contains("  \"company_400001\" [label=\"Kurier \\\"Verlag\\\"\", shape=ellipse];\n"));
+        assert!(dot.contains(
+            "  \"outlet_200002\" -> \"outlet_300001\" [label=\"umbrella\\nsince 1990-01-01\", dir=none, style=dashed];\n"
+        ));
+        assert!(dot.contains("  \"company_400002\" -> \"company_400001\" [label=\"owner 49.4%\\nsince 1981-02-01\"];\n"));
+        // Membership is shown by the cluster, not by edges
+        assert!(!dot.contains("\"family_0\""));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_dropped_persons_are_missing_from_every_format() {
+        use mdsl_rs::ir::redaction::redact;
+
+        let source = format!(
+            "{}{}",
+            SOURCE,
+            r#"
+            PERSON "Max Mustermann" { id = 9001; }
+            OWNERSHIP max_stake {
+                owner_person = 9001;
+                owned_company = 400001;
+                share_pct = 30;
+            }
+            REDACTION public { persons = "drop"; }
+            "#
+        );
+        let ir = transform(&parse(&source).unwrap()).unwrap();
+        let policy = ir.redaction_policies[0].clone();
+        let (redacted, _) = redact(&ir, &policy);
+        assert!(DotGenerator::new().generate(&ir).unwrap().contains("Max Mustermann"));
+
+        let exports = [
+            GraphMlGenerator::new().generate(&redacted).unwrap(),
+            GexfGenerator::new().generate(&redacted).unwrap(),
+            DotGenerator::new().generate(&redacted).unwrap(),
+        ];
+        for export in &exports {
+            assert!(!export.contains("Max Mustermann"));
+            assert!(!export.contains("person_9001"));
+            assert!(export.contains("company_400001"));
+        }
+    }
+}
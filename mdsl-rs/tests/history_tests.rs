@@ -0,0 +1,159 @@
+//! Tests for per-outlet chronological histories
+
+use mdsl_rs::analysis::history::*;
+use mdsl_rs::ir::nodes::IRProgram;
+use mdsl_rs::ir::transform;
+use mdsl_rs::parse;
+
+const SOURCE: &str = r#"
+    FAMILY "Kurier Group" {
+        OUTLET "Kurier" {
+            identity {
+                id = 200002;
+                title = "Kurier";
+            };
+            lifecycle {
+                status "active" FROM "1954-10-18" TO CURRENT {
+                    precision_start = "known";
+                };
+            };
+        };
+
+        OUTLET "Der Abend" {
+            identity {
+                id = 100101;
+                title = "Der Abend";
+            };
+            lifecycle {
+                status "active" FROM "1945-08-01" TO "1954-10-17" {
+                    precision_start = "known";
+                };
+                status "inactive" FROM "1954-10-18" TO CURRENT {
+                    precision_start = "known";
+                };
+            };
+        };
+
+        DIACHRONIC_LINK abend_kurier {
+            predecessor = 100101;
+            successor = 200002;
+            event_date = "1954-10-18";
+            relationship_type = "succession";
+        };
+
+        SYNCHRONOUS_LINK kurier_umbrella {
+            outlet_1 = { id = 200002; role = "source"; };
+            outlet_2 = { id = 300001; role = "target"; };
+            relationship_type = "umbrella";
+            period_start = "1990-01-01";
+            period_end = "2000-12-31";
+        };
+    }
+
+    DATA FOR 200002 {
+        YEAR 2021 {
+            metrics {
+                circulation = { value = 120000; unit = "copies"; source = "1502021"; };
+                reach_national = { value = 7.5; unit = "percent"; source = "2002021"; };
+            };
+        };
+        YEAR 1995 {
+            metrics {
+                circulation = { value = 350000; unit = "copies"; source = "1501995"; };
+            };
+        };
+    };
+
+    EVENT kurier_sale {
+        type = "acquisition";
+        date = "2001-06-30";
+        entities = {
+            kurier = { id = 200002; role = "target"; stake_before = 100; stake_after = 49.4; };
+        };
+    }
+"#;
+
+fn program() -> IRProgram {
+    transform(&parse(SOURCE).unwrap()).unwrap()
+}
+
+#[test]
+fn test_history_merges_sources_chronologically() {
+    let history = OutletHistory::build(&program(), 200002).unwrap();
+    assert_eq!(history.name.as_deref(), Some("Kurier"));
+    assert_eq!(history.family.as_deref(), Some("Kurier Group"));
+
+    let timeline: Vec<(Option<&str>, EntryKind)> = history
+        .entries
+        .iter()
+        .map(|entry| (entry.date.as_deref(), entry.kind))
+        .collect();
+    assert_eq!(
+        timeline,
+        vec![
+            (Some("1954-10-18"), EntryKind::Status),
+            (Some("1954-10-18"), EntryKind::Predecessor),
+            (Some("1990-01-01"), EntryKind::LinkStart),
+            (Some("1995"), EntryKind::Metrics),
+            (Some("2000-12-31"), EntryKind::LinkEnd),
+            (Some("2001-06-30"), EntryKind::Event),
+            (Some("2021"), EntryKind::Metrics),
+        ]
+    );
+
+    let predecessor = &history.entries[1];
+    assert_eq!(predecessor.description, "succession: Der Abend (100101)");
+    assert_eq!(predecessor.related, Some(100101));
+    assert_eq!(
+        history.entries[2].description,
+        "umbrella with 300001 as source"
+    );
+    assert_eq!(
+        history.entries[5].description,
+        "acquisition kurier_sale as target, stake 100% -> 49.4%"
+    );
+    assert_eq!(
+        history.entries[6].description,
+        "circulation 120000 copies, reach_national 7.5 percent"
+    );
+
+    let abend = OutletHistory::build(&program(), 100101).unwrap();
+    let kinds: Vec<EntryKind> = abend.entries.iter().map(|entry| entry.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![EntryKind::Status, EntryKind::Status, EntryKind::Successor]
+    );
+}
+
+#[test]
+fn test_undeclared_outlets() {
+    // Only mentioned in a link
+    let umbrella = OutletHistory::build(&program(), 300001).unwrap();
+    assert_eq!(umbrella.name, None);
+    assert_eq!(umbrella.entries.len(), 2);
+    assert!(umbrella
+        .format_text()
+        .starts_with("History of Outlet 300001 (not declared)\n"));
+
+    assert!(OutletHistory::build(&program(), 999999).is_none());
+}
+
+#[test]
+fn test_history_formats() {
+    let history = OutletHistory::build(&program(), 200002).unwrap();
+
+    let text = history.format_text();
+    assert!(text.starts_with("History of Kurier (200002)\nFamily: Kurier Group\n\n"));
+    assert!(text.contains("1954-10-18  status       active (ongoing)\n"));
+    assert!(text.contains("2000-12-31  link end     umbrella with 300001 as source\n"));
+
+    let markdown = history.format_markdown();
+    assert!(markdown
+        .starts_with("## Kurier (200002)\n\nFamily: Kurier Group\n\n| Date | Type | Entry |\n"));
+    assert!(markdown.contains("| 1995 | metrics | circulation 350000 copies |\n"));
+
+    let json = history.format_json();
+    assert!(json.contains("\"outlet_id\": 200002"));
+    assert!(json.contains("{ \"date\": \"1954-10-18\", \"type\": \"predecessor\", \"description\": \"succession: Der Abend (100101)\", \"related\": 100101 }"));
+    assert_eq!(json.matches("\"type\": ").count(), history.entries.len());
+}
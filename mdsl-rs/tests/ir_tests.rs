@@ -1,7 +1,7 @@
 //! IR transformer tests
 //! Tests for AST to IR transformation
 
-use mdsl_rs::ir::{nodes::*, transform};
+use mdsl_rs::ir::{nodes::*, publication::publish_only, transform};
 use mdsl_rs::parse;
 
 #[test]
@@ -593,4 +593,161 @@ fn test_transform_family_with_comment() {
     // Just check that the family is created successfully with the correct structure
     assert_eq!(family.outlets.len(), 1);
     assert_eq!(family.outlets[0].name, "Test Outlet");
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_publish_only_filter() {
+    let source = r#"
+        FAMILY "Test Family" {
+            REVIEW { status = "published"; }
+
+            OUTLET "Published Outlet" {
+                IDENTITY { id = 100; title = "Published"; founder = "Unverified"; }
+                REVIEW founder { status = "in_review"; }
+            }
+            OUTLET "Draft Outlet" {
+                IDENTITY { id = 200; title = "Draft"; }
+                REVIEW { status = "draft"; }
+            }
+
+            DIACHRONIC_LINK split {
+                predecessor = 100;
+                successor = 200;
+                relationship_type = "split";
+            }
+        }
+
+        COMPANY "Published Verlag" {
+            id = 400001;
+            REVIEW { status = "published"; }
+        }
+        COMPANY "Unreviewed Verlag" {
+            id = 400002;
+        }
+
+        OWNERSHIP holding_stake {
+            owner_company = 400002;
+            owned_company = 400001;
+            share_pct = 80;
+            REVIEW { status = "published"; }
+        }
+    "#;
+    let ir = transform(&parse(source).unwrap()).unwrap();
+    assert_eq!(ir.families[0].outlets[0].reviews.len(), 1);
+    assert_eq!(ir.families[0].outlets[0].reviews[0].status, "in_review");
+
+    let published = publish_only(&ir);
+    let family = &published.families[0];
+    assert_eq!(family.outlets.len(), 1);
+    let outlet = &family.outlets[0];
+    assert_eq!(outlet.id, Some(100));
+    if let IROutletBlock::Identity(fields) = &outlet.blocks[0] {
+        assert!(fields.iter().any(|field| field.name == "title"));
+        assert!(!fields.iter().any(|field| field.name == "founder"));
+    } else {
+        panic!("Expected identity block");
+    }
+    // The link would point at the withheld draft outlet
+    assert!(family.relationships.is_empty());
+
+    assert_eq!(published.companies.len(), 1);
+    assert_eq!(published.companies[0].id, 400001);
+    // The owner is not published, so neither is the stake
+    assert!(published.ownerships.is_empty());
+}
+
+#[test]
+fn test_publish_only_withholds_draft_family_and_event_entities() {
+    let source = r#"
+        FAMILY "Draft Family" {
+            REVIEW { status = "draft"; }
+
+            OUTLET "Approved Outlet" {
+                IDENTITY { id = 300; title = "Approved"; }
+                REVIEW { status = "published"; }
+            }
+        }
+
+        FAMILY "Published Family" {
+            REVIEW { status = "published"; }
+
+            OUTLET "Published Outlet" {
+                IDENTITY { id = 100; title = "Published"; }
+            }
+        }
+
+        COMPANY "Unreviewed Verlag" {
+            id = 400002;
+        }
+
+        EVENT sale {
+            type = "acquisition";
+            date = "2001-01-01";
+            entities = {
+                kept = { id = 100; role = "target"; };
+                draft_outlet = { id = 300; role = "target"; };
+                buyer = { id = 400002; role = "acquirer"; stake_after = 50; };
+            };
+            REVIEW { status = "published"; }
+        }
+    "#;
+    let ir = transform(&parse(source).unwrap()).unwrap();
+    let published = publish_only(&ir);
+
+    assert_eq!(published.families.len(), 1);
+    assert_eq!(published.families[0].name, "Published Family");
+    assert_eq!(published.events.len(), 1);
+    let entities: Vec<&str> = published.events[0]
+        .entities
+        .iter()
+        .map(|entity| entity.name.as_str())
+        .collect();
+    assert_eq!(entities, ["kept"]);
+}
+
+#[test]
+fn test_publish_only_filters_overrides() {
+    let source = r#"
+        FAMILY "Express" {
+            REVIEW { status = "published"; }
+
+            OUTLET "Express" {
+                IDENTITY { id = 300001; title = "Express"; }
+                REVIEW "characteristics" { status = "in_review"; }
+            }
+            OUTLET "Abend" {
+                IDENTITY { id = 300002; title = "Abend"; }
+                REVIEW { status = "draft"; }
+            }
+        }
+
+        FAMILY "Kronen Zeitung" {
+            REVIEW { status = "published"; }
+
+            OUTLET_REF 300001 ["Express"] {
+                OVERRIDE FROM "1971-01-01" {
+                    identity { title = "Express (Krone)"; };
+                    characteristics { editorial_stance = "Aligned with Kronen Zeitung"; };
+                };
+            };
+            OUTLET_REF 300002 ["Abend"] {
+                OVERRIDE FROM "1971-01-01" {
+                    characteristics { editorial_stance = "Aligned with Kronen Zeitung"; };
+                };
+            };
+        }
+    "#;
+    let ir = transform(&parse(source).unwrap()).unwrap();
+    assert_eq!(ir.families[1].overrides.len(), 2);
+
+    let published = publish_only(&ir);
+    let overrides = &published.families[1].overrides;
+    // The draft outlet's override is dropped, the withheld block of the other
+    assert_eq!(overrides.len(), 1);
+    assert_eq!(overrides[0].outlet_id, 300001);
+    assert!(!overrides[0].blocks.is_empty());
+    assert!(!overrides[0]
+        .blocks
+        .iter()
+        .any(|block| matches!(block, IROutletBlock::Characteristics(_))));
+}
@@ -0,0 +1,171 @@
+//! Tests for outlet genealogies built from diachronic links
+
+use mdsl_rs::analysis::lineage::*;
+use mdsl_rs::ir::transform;
+use mdsl_rs::parse;
+
+const SOURCE: &str = r#"
+    FAMILY "Kronen Zeitung" {
+        OUTLET "Express" {
+            identity {
+                id = 300001;
+                title = "Express";
+            };
+            lifecycle {
+                status "active" FROM "1958-01-01" TO "1971-12-31" {
+                    precision_start = "known";
+                };
+            };
+        };
+
+        OUTLET "Kronen Zeitung" {
+            identity {
+                id = 200001;
+                title = "Kronen Zeitung";
+            };
+            lifecycle {
+                status "active" FROM "1959-04-11" TO CURRENT {
+                    precision_start = "known";
+                };
+            };
+        };
+
+        OUTLET "Krone Bunt" {
+            identity {
+                id = 200005;
+                title = "Krone Bunt";
+            };
+            lifecycle {
+                status "active" FROM "1975" TO CURRENT {
+                    precision_start = "known";
+                };
+            };
+        };
+
+        DIACHRONIC_LINK express_krone {
+            predecessor = 300001;
+            successor = 200001;
+            event_date = "1971-12-31";
+            relationship_type = "amalgamation";
+        };
+
+        DIACHRONIC_LINK krone_bunt {
+            predecessor = 200001;
+            successor = 200005;
+            event_date = "1975";
+            relationship_type = "offshoot";
+        };
+    }
+"#;
+
+fn lineage(extra: &str) -> LineageGraph {
+    let source = format!("{}\n{}", SOURCE, extra);
+    LineageGraph::from_ir(&transform(&parse(&source).unwrap()).unwrap())
+}
+
+#[test]
+fn test_genealogy_tree() {
+    let graph = lineage("");
+    assert!(graph.check().is_empty());
+
+    let tree = graph.tree(200001).unwrap();
+    let generations: Vec<(u32, i32)> = tree
+        .members
+        .iter()
+        .map(|member| (member.outlet.id, member.generation))
+        .collect();
+    assert_eq!(generations, vec![(300001, -1), (200001, 0), (200005, 1)]);
+    assert_eq!(tree.links.len(), 2);
+    assert_eq!(tree.members[0].outlet.end.as_deref(), Some("1971-12-31"));
+    assert_eq!(tree.members[1].outlet.end, None);
+
+    // A leaf sees its whole ancestry
+    let leaf = graph.tree(200005).unwrap();
+    assert_eq!(leaf.members[0].generation, -2);
+    assert!(graph.tree(999999).is_none());
+}
+
+#[test]
+fn test_cycles_and_impossible_orderings() {
+    let graph = lineage(
+        r#"
+        FAMILY "Errors" {
+            DIACHRONIC_LINK krone_express {
+                predecessor = 200001;
+                successor = 300001;
+                event_date = "1965";
+                relationship_type = "split_off";
+            };
+
+            DIACHRONIC_LINK before_start {
+                predecessor = 200005;
+                successor = 100001;
+                event_date = "1970-06";
+                relationship_type = "succession";
+            };
+        }
+        "#,
+    );
+    let issues = graph.check();
+    let kinds: Vec<IssueKind> = issues.iter().map(|issue| issue.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            IssueKind::Cycle,
+            IssueKind::BeforePredecessorStart,
+            IssueKind::OutOfOrder
+        ]
+    );
+    assert_eq!(
+        issues[0].message,
+        "Kronen Zeitung (200001) -> Express (300001) -> Kronen Zeitung (200001)"
+    );
+    assert_eq!(issues[0].links, vec!["krone_express", "express_krone"]);
+    assert_eq!(
+        issues[1].to_string(),
+        "before predecessor start: succession on 1970-06, but Krone Bunt (200005) started 1975 [before_start]"
+    );
+    assert_eq!(issues[2].links, vec!["krone_bunt", "before_start"]);
+
+    // A link into an outlet that an amalgamation had already ended
+    let late = lineage(
+        r#"
+        FAMILY "Late" {
+            DIACHRONIC_LINK late {
+                predecessor = 100001;
+                successor = 300001;
+                event_date = "1972";
+                relationship_type = "succession";
+            };
+        }
+        "#,
+    );
+    let kinds: Vec<IssueKind> = late.check().iter().map(|issue| issue.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![IssueKind::AfterSuccessorEnd, IssueKind::OutOfOrder]
+    );
+    assert_eq!(late.tree(200001).unwrap().issues.len(), 2);
+}
+
+#[test]
+fn test_tree_formats() {
+    let tree = lineage("").tree(200001).unwrap();
+
+    let dot = tree.format_dot();
+    assert!(dot.starts_with("digraph \"lineage_200001\" {\n"));
+    assert!(dot.contains("  200001 [label=\"Kronen Zeitung\\n200001 1959-\", style=bold];\n"));
+    assert!(dot.contains("  300001 -> 200001 [label=\"amalgamation 1971-12-31\"];\n"));
+
+    let svg = tree.format_svg();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert_eq!(svg.matches("<rect ").count(), 3);
+    assert_eq!(svg.matches("<line ").count(), 2);
+    assert!(svg.contains(">300001 1958-1971</text>"));
+    assert!(svg.ends_with("</svg>\n"));
+
+    let json = tree.format_json();
+    assert!(json.contains("{ \"id\": 300001, \"name\": \"Express\", \"start\": \"1958-01-01\", \"end\": \"1971-12-31\", \"generation\": -1 }"));
+    assert!(json.contains("{ \"name\": \"krone_bunt\", \"predecessor\": 200001, \"successor\": 200005, \"type\": \"offshoot\", \"date\": \"1975\" }"));
+    assert!(json.ends_with("\"issues\": []\n}"));
+}
@@ -0,0 +1,180 @@
+//! Tests for the three-way merge of MDSL files
+
+use mdsl_rs::utils::merge::*;
+
+const BASE: &str = r#"// Kurier family
+FAMILY "Kurier Group" {
+    OUTLET "Kurier" {
+        identity {
+            id = 200002;
+            title = "Kurier";
+        };
+        lifecycle {
+            status "active" FROM "1954-10-18" TO CURRENT {
+                precision_start = "known";
+            };
+        };
+        characteristics {
+            sector = 11;
+            language = "deutsch";
+        };
+    };
+
+    OUTLET "Der Abend" {
+        identity {
+            id = 100101;
+            title = "Der Abend";
+        };
+    };
+}
+
+DATA FOR 200002 {
+    YEAR 2022 {
+        metrics {
+            reach_national = { value = 7.5; unit = "percent"; source = "2002022"; };
+        };
+    };
+};
+"#;
+
+/// Apply `(from, to)` replacements to the base, each of which must match
+fn edit(replacements: &[(&str, &str)]) -> String {
+    let mut source = BASE.to_string();
+    for (from, to) in replacements {
+        assert!(source.contains(from), "{} not found", from);
+        source = source.replacen(from, to, 1);
+    }
+    source
+}
+
+#[test]
+fn test_non_overlapping_edits_merge_cleanly() {
+    let ours = edit(&[
+        ("title = \"Kurier\";", "title = \"Kurier (Wien)\";"),
+        (
+            "        identity {\n            id = 100101;",
+            "        // Checked in the ANNO archive\n        identity {\n            id = 100101;",
+        ),
+    ]);
+    let theirs = edit(&[
+        (
+            "language = \"deutsch\";",
+            "language = \"Deutsch\";\n            mandate = \"1\";",
+        ),
+        ("TO CURRENT", "TO \"2030-01-01\""),
+        (
+            "    OUTLET \"Der Abend\" {",
+            "    // New in 2024\n    OUTLET \"Kurier Online\" {\n        identity {\n            id = 200003;\n        };\n    };\n\n    OUTLET \"Der Abend\" {",
+        ),
+        (
+            "    YEAR 2022 {",
+            "    YEAR 2021 {\n        metrics {\n            reach_national = { value = 8.0; };\n        };\n    };\n    YEAR 2022 {",
+        ),
+        ("value = 7.5", "value = 7.7"),
+    ]);
+
+    let result = merge(BASE, &ours, &theirs).unwrap();
+    assert!(result.is_clean());
+    let text = &result.text;
+    assert!(text.contains("title = \"Kurier (Wien)\";"));
+    assert!(text.contains("language = \"Deutsch\";\n            mandate = \"1\";"));
+    assert!(text.contains("status \"active\" FROM \"1954-10-18\" TO \"2030-01-01\" {"));
+    assert!(text.contains("    };\n\n    // New in 2024\n    OUTLET \"Kurier Online\" {"));
+    assert!(text.contains("// Checked in the ANNO archive"));
+    assert!(text.find("YEAR 2021").unwrap() < text.find("YEAR 2022").unwrap());
+    assert!(text.contains("value = 7.7;"));
+    assert!(mdsl_rs::parse(text).is_ok());
+
+    // Merging is symmetric up to formatting
+    let swapped = merge(BASE, &theirs, &ours).unwrap();
+    assert!(swapped.is_clean());
+    assert!(swapped.text.contains("title = \"Kurier (Wien)\";"));
+}
+
+#[test]
+fn test_deletions_merge() {
+    let ours = edit(&[(
+        "    OUTLET \"Der Abend\" {\n        identity {\n            id = 100101;\n            title = \"Der Abend\";\n        };\n    };\n",
+        "",
+    )]);
+    let theirs = edit(&[("value = 7.5", "value = 7.7")]);
+
+    let result = merge(BASE, &ours, &theirs).unwrap();
+    assert!(result.is_clean());
+    assert!(!result.text.contains("Der Abend"));
+    assert!(result.text.contains("value = 7.7;"));
+
+    // Deleted on both sides
+    let result = merge(BASE, &ours, &ours).unwrap();
+    assert_eq!(result.text, ours);
+}
+
+#[test]
+fn test_conflicting_edits_get_markers() {
+    let ours = edit(&[
+        ("title = \"Kurier\";", "title = \"Kurier (Wien)\";"),
+        (
+            "    OUTLET \"Der Abend\" {\n        identity {\n            id = 100101;\n            title = \"Der Abend\";\n        };\n    };\n",
+            "",
+        ),
+    ]);
+    let theirs = edit(&[
+        ("title = \"Kurier\";", "title = \"Kurier Neu\";"),
+        ("title = \"Der Abend\";", "title = \"Abend, Der\";"),
+        ("value = 7.5", "value = 7.7"),
+    ]);
+
+    let result = merge(BASE, &ours, &theirs).unwrap();
+    assert_eq!(result.conflicts.len(), 2);
+    assert_eq!(
+        result.conflicts[0].path,
+        "FAMILY \"Kurier Group\" > OUTLET 200002 > identity > title"
+    );
+    assert_eq!(
+        result.conflicts[1].path,
+        "FAMILY \"Kurier Group\" > OUTLET 100101"
+    );
+
+    let lines: Vec<&str> = result.text.lines().collect();
+    let first = result.conflicts[0].line;
+    assert_eq!(
+        lines[first - 1..first + 4],
+        [
+            OURS_MARKER,
+            "            title = \"Kurier (Wien)\";",
+            SEPARATOR_MARKER,
+            "            title = \"Kurier Neu\";",
+            THEIRS_MARKER,
+        ]
+    );
+    // Deleted by us, changed by them: our side is empty
+    let second = result.conflicts[1].line;
+    assert_eq!(
+        lines[second - 1..second + 2],
+        [OURS_MARKER, SEPARATOR_MARKER, "    OUTLET \"Der Abend\" {"]
+    );
+    assert!(result
+        .text
+        .contains("            title = \"Abend, Der\";\n        };\n    };\n>>>>>>> theirs\n"));
+    // Non-conflicting edits still merge
+    assert!(result.text.contains("value = 7.7;"));
+}
+
+#[test]
+fn test_insert_before_closing_brace_that_shares_its_line() {
+    // The closing brace of the status block follows a comment with non-ASCII
+    // text on its line; theirs adds the first statement to the block
+    let base = "FAMILY \"Blätter\" {\n    OUTLET \"Blätter\" {\n        lifecycle {\n            status \"active\" FROM \"1954-10-18\" TO CURRENT {\n            /* ÖÖÖÖÖ*/ };\n        };\n    };\n}\n";
+    let ours = base.replace("1954-10-18", "1954-10-19");
+    let theirs = base.replace(
+        "TO CURRENT {\n",
+        "TO CURRENT {\n                precision_start = \"known\";\n",
+    );
+
+    let result = merge(base, &ours, &theirs).unwrap();
+    assert!(result.is_clean());
+    assert!(result.text.contains(
+        "FROM \"1954-10-19\" TO CURRENT {\n            /* ÖÖÖÖÖ*/ \n                precision_start = \"known\";\n            };"
+    ));
+    assert!(mdsl_rs::parse(&result.text).is_ok());
+}
@@ -0,0 +1,233 @@
+//! Tests for the beneficial ownership / ultimate controlling entity analysis
+
+use mdsl_rs::analysis::ownership::*;
+use mdsl_rs::codegen::SqlGenerator;
+use mdsl_rs::ir::transform;
+use mdsl_rs::parse;
+use mdsl_rs::utils::csv::CsvTable;
+
+const OWNERSHIP_SOURCE: &str = r#"
+    FAMILY "Test Family" {
+        OUTLET "Test Zeitung" {
+            IDENTITY {
+                id = 200001;
+                title = "Test Zeitung";
+            }
+        }
+    }
+
+    COMPANY "Verlag GmbH" { id = 400001; }
+    COMPANY "Holding A" { id = 400002; }
+    COMPANY "Holding B" { id = 400003; }
+
+    OUTLET_PARTY_LINK zeitung_publisher {
+        outlet = 200001;
+        party = 400001;
+        role = "publisher";
+        period_start = "1990-01-01";
+    }
+
+    EVENT verlag_founded {
+        type = "joint_venture";
+        date = "1990-01-01";
+        status = "completed";
+        entities = {
+            holding_a = { id = 400002; role = "jv_partner"; stake_after = 70; };
+            holding_b = { id = 400003; role = "jv_partner"; stake_after = 30; };
+            verlag = { id = 400001; role = "joint_venture"; };
+        };
+    }
+
+    EVENT holding_b_exit {
+        type = "acquisition";
+        date = "2010-06-30";
+        status = "completed";
+        entities = {
+            holding_a = { id = 400002; role = "acquirer"; stake_before = 70; stake_after = 100; };
+            holding_b = { id = 400003; role = "seller"; stake_before = 30; stake_after = 0; };
+            verlag = { id = 400001; role = "target"; };
+        };
+    }
+"#;
+
+const ND_OWNERSHIP: &str = "id,owner_company_id,owner_person_id,owned_company_id,share_pct,role,is_current,source_url,scraped_at\n\
+1,,9001,400002,40,shareholder,t,https://example.org/a,2026-03-11 00:44:27\n\
+2,,9002,400002,60,shareholder,t,https://example.org/a,2026-03-11 00:44:27\n\
+3,,9003,400003,100,shareholder,t,https://example.org/b,2026-03-11 00:44:27\n";
+
+fn build_graph() -> OwnershipGraph {
+    let ast = parse(OWNERSHIP_SOURCE).unwrap();
+    let ir = transform(&ast).unwrap();
+    let mut graph = OwnershipGraph::from_ir(&ir);
+    assert_eq!(graph.load_nd_ownership_csv(ND_OWNERSHIP).unwrap(), 3);
+    graph
+}
+
+#[test]
+fn test_indirect_shares_are_multiplied() {
+    let graph = build_graph();
+    let report = graph.analyze(PartyRef::outlet(200001), "2000-01-01", DEFAULT_THRESHOLD_PCT);
+
+    // person 9002 holds 60% of Holding A, which holds 70% of the publisher
+    let bo = report
+        .beneficial_owners
+        .iter()
+        .find(|bo| bo.bo_person_id == Some(9002))
+        .expect("person 9002 should be a beneficial owner");
+    assert!((bo.bo_cashflow_pct - 42.0).abs() < 1e-9);
+    assert_eq!(bo.bo_votes_pct, 100.0);
+    assert_eq!(bo.bo_basis, Some(ControlBasis::Both));
+
+    // person 9003 holds 30% of the publisher through Holding B
+    let bo = report
+        .beneficial_owners
+        .iter()
+        .find(|bo| bo.bo_person_id == Some(9003))
+        .expect("person 9003 should be a beneficial owner");
+    assert!((bo.bo_cashflow_pct - 30.0).abs() < 1e-9);
+
+    // person 9001 reaches 28% cash-flow; votes pass through Holding A's majority
+    let bo = report
+        .beneficial_owners
+        .iter()
+        .find(|bo| bo.bo_person_id == Some(9001))
+        .expect("person 9001 should be a beneficial owner");
+    assert!((bo.bo_cashflow_pct - 28.0).abs() < 1e-9);
+    assert_eq!(bo.bo_votes_pct, 40.0);
+
+    let uce_ids: Vec<u64> = report.ultimate_entities.iter().map(|u| u.uce_entity_id).collect();
+    assert!(uce_ids.contains(&400002));
+    assert!(uce_ids.contains(&400003));
+    assert!(!uce_ids.contains(&400001), "the publisher is held by Holding A");
+}
+
+#[test]
+fn test_events_are_replayed_by_date() {
+    let graph = build_graph();
+
+    let before = graph.analyze(PartyRef::outlet(200001), "1985-01-01", DEFAULT_THRESHOLD_PCT);
+    assert!(before.paths.is_empty());
+    assert_eq!(before.beneficial_owners.len(), 1);
+    assert!(before.beneficial_owners[0].fallback_reason.is_some());
+
+    let after = graph.analyze(PartyRef::outlet(200001), "2015-01-01", DEFAULT_THRESHOLD_PCT);
+    assert!(!after
+        .paths
+        .iter()
+        .any(|path| path.parties.contains(&PartyRef::company(400003))));
+    let uce_ids: Vec<u64> = after.ultimate_entities.iter().map(|u| u.uce_entity_id).collect();
+    assert_eq!(uce_ids, vec![400002]);
+}
+
+#[test]
+fn test_undated_event_replaces_the_open_stake() {
+    let source = r#"
+        COMPANY "Verlag GmbH" { id = 400001; }
+        COMPANY "Holding A" { id = 400002; }
+
+        OWNERSHIP holding_a_stake {
+            owner_company = 400002;
+            owned_company = 400001;
+            share_pct = 60;
+        }
+
+        EVENT stake_increase {
+            type = "acquisition";
+            entities = {
+                holding_a = { id = 400002; role = "acquirer"; stake_before = 60; stake_after = 80; };
+                verlag = { id = 400001; role = "target"; };
+            };
+        }
+    "#;
+    let graph = OwnershipGraph::from_ir(&transform(&parse(source).unwrap()).unwrap());
+
+    // The holder's share is not counted twice
+    let shares: Vec<f64> = graph
+        .active_stakes("2020-01-01")
+        .iter()
+        .filter(|stake| stake.owner == PartyRef::company(400002))
+        .map(|stake| stake.share_pct)
+        .collect();
+    assert_eq!(shares, vec![80.0]);
+}
+
+#[test]
+fn test_circular_holdings_are_cut() {
+    let mut graph = OwnershipGraph::new();
+    let a = PartyRef::company(1);
+    let b = PartyRef::company(2);
+    for (owner, owned, share_pct) in [(a, b, 60.0), (b, a, 30.0)] {
+        graph.add_stake(OwnershipStake {
+            owner,
+            owned,
+            share_pct,
+            valid_from: None,
+            valid_to: None,
+            source: "test".to_string(),
+        });
+    }
+
+    let report = graph.analyze(b, "CURRENT", DEFAULT_THRESHOLD_PCT);
+    assert!(report.cycle_detected);
+    assert_eq!(report.paths.len(), 1);
+    assert_eq!(report.paths[0].parties, vec![a, b]);
+}
+
+#[test]
+fn test_ownership_marts_sql() {
+    let graph = build_graph();
+    let report = graph.analyze(PartyRef::outlet(200001), "2000-01-01", DEFAULT_THRESHOLD_PCT);
+    let sql = SqlGenerator::new().generate_ownership_marts(&[report]).unwrap();
+
+    assert!(sql.contains("CREATE TABLE IF NOT EXISTS beneficial_owner_snapshot"));
+    assert!(sql.contains("CREATE TABLE IF NOT EXISTS ultimate_entity_snapshot"));
+    assert!(sql.contains("VALUES ('outlet', 200001, 9002, '2000-01-01', 42.000, 100.000, 3, 1"));
+    assert!(sql.contains("VALUES ('outlet', 200001, 400002, '2000-01-01', 3, 1, 'mdsl-ownership-1');"));
+}
+
+#[test]
+fn test_csv_table_quoting() {
+    let table = CsvTable::parse("id,name,comment\r\n1,\"Verlag, GmbH\",\"said \"\"hi\"\"\nline two\"\r\n2,,\n");
+    assert_eq!(table.rows.len(), 2);
+    assert_eq!(table.get(&table.rows[0], "name"), Some("Verlag, GmbH"));
+    assert_eq!(table.get(&table.rows[0], "comment"), Some("said \"hi\"\nline two"));
+    assert_eq!(table.get(&table.rows[1], "name"), None);
+}
+
+#[test]
+fn test_declared_ownership_relations() {
+    let source = r#"
+        COMPANY "Holding" { id = 400001; }
+        COMPANY "Verlag" { id = 400002; }
+        PERSON "Owner" { id = 9001; }
+
+        OWNERSHIP holding_stake {
+            owner_company = 400001;
+            owned_company = 400002;
+            share_pct = 80;
+            source_url = "https://example.org/verlag";
+        }
+
+        OWNERSHIP person_stake {
+            owner_person = 9001;
+            owned_company = 400001;
+            share_pct = 50;
+            period_end = "2020-01-01";
+        }
+    "#;
+    let ast = parse(source).unwrap();
+    let ir = transform(&ast).unwrap();
+    let graph = OwnershipGraph::from_ir(&ir);
+
+    assert_eq!(graph.name_of(&PartyRef::person(9001)), Some("Owner"));
+    assert_eq!(graph.stakes()[0].source, "https://example.org/verlag");
+
+    let report = graph.analyze(PartyRef::company(400002), "2015-01-01", DEFAULT_THRESHOLD_PCT);
+    let bo = &report.beneficial_owners[0];
+    assert_eq!(bo.bo_person_id, Some(9001));
+    assert!((bo.bo_cashflow_pct - 40.0).abs() < 1e-9);
+
+    // The person's stake ended in 2020
+    let report = graph.analyze(PartyRef::company(400002), "2021-01-01", DEFAULT_THRESHOLD_PCT);
+    assert!(report.beneficial_owners[0].fallback_reason.is_some());
+}
@@ -0,0 +1,206 @@
+//! Tests for the query language over the compiled model
+
+use mdsl_rs::analysis::query::*;
+use mdsl_rs::ir::nodes::IRProgram;
+use mdsl_rs::ir::transform;
+use mdsl_rs::parse;
+
+const SOURCE: &str = r#"
+    FAMILY "Kurier Group" {
+        OUTLET "Kurier" {
+            identity {
+                id = 200002;
+                title = "Kurier";
+            };
+            lifecycle {
+                status "active" FROM "1954-10-18" TO CURRENT {
+                    precision_start = "known";
+                };
+            };
+            characteristics {
+                sector = 11;
+                location = "Wien";
+                language = "deutsch";
+            };
+        };
+
+        OUTLET "Der Abend" {
+            identity {
+                id = 100101;
+                title = "Der Abend";
+            };
+            lifecycle {
+                status "active" FROM "1945-08-01" TO "1990-12-31" {
+                    precision_start = "known";
+                };
+                status "inactive" FROM "1991-01-01" TO CURRENT {
+                    precision_start = "known";
+                };
+            };
+            characteristics {
+                sector = 11;
+                location = "Wien";
+            };
+        };
+
+        OUTLET "Kurier Online" {
+            identity {
+                id = 200003;
+                title = "Kurier Online";
+            };
+            lifecycle {
+                status "active" FROM "1996" TO CURRENT {
+                    precision_start = "known";
+                };
+            };
+            characteristics {
+                sector = 40;
+                location = "Wien";
+            };
+        };
+
+        DIACHRONIC_LINK abend_kurier {
+            predecessor = 100101;
+            successor = 200002;
+            relationship_type = "succession";
+        };
+    }
+
+    FAMILY "Kleine Zeitung" {
+        OUTLET "Kleine Zeitung" {
+            identity {
+                id = 200010;
+                title = "Kleine Zeitung";
+            };
+            lifecycle {
+                status "active" FROM "1904-11-22" TO CURRENT {
+                    precision_start = "known";
+                };
+            };
+            characteristics {
+                sector = 11;
+                location = "Graz";
+            };
+        };
+    }
+
+    SYNCHRONOUS_LINK kurier_online {
+        outlet_1 = { id = 200002; role = "main"; };
+        outlet_2 = { id = 200003; role = "side"; };
+        relationship_type = "main_media_outlet";
+    };
+
+    DATA FOR 200002 {
+        YEAR 2021 {
+            metrics {
+                circulation = { value = 120000; unit = "copies"; source = "1502021"; };
+            };
+        };
+    };
+
+    DATA FOR 200010 {
+        YEAR 2021 {
+            metrics {
+                circulation = { value = 250000; unit = "copies"; source = "1502021"; };
+            };
+        };
+    };
+"#;
+
+fn program() -> IRProgram {
+    transform(&parse(SOURCE).unwrap()).unwrap()
+}
+
+fn ids(query: &str) -> Vec<String> {
+    let result = Query::parse(query).unwrap().run(&program());
+    result.rows.iter().map(|row| row[0].to_string()).collect()
+}
+
+#[test]
+fn test_field_and_lifecycle_conditions() {
+    assert_eq!(
+        ids("sector = 11 and location = \"Wien\" and active in 1985"),
+        vec!["200002", "100101"]
+    );
+    assert_eq!(
+        ids("sector = 11 and location = wien and active in 1995"),
+        vec!["200002"]
+    );
+    assert_eq!(
+        ids("active between 1991 and 1995"),
+        vec!["200002", "200010"]
+    );
+    assert_eq!(
+        ids("active on \"1996-06-01\" and sector = 40"),
+        vec!["200003"]
+    );
+    assert_eq!(ids("not active"), vec!["100101"]);
+    assert_eq!(
+        ids("characteristics.sector >= 40 or name ~ \"abend\""),
+        vec!["100101", "200003"]
+    );
+    assert_eq!(ids("status = inactive"), vec!["100101"]);
+    assert_eq!(ids("start < 1950"), vec!["100101", "200010"]);
+}
+
+#[test]
+fn test_metrics_links_and_ordering() {
+    assert_eq!(
+        ids("circulation > 100000 in 2021"),
+        vec!["200002", "200010"]
+    );
+    assert_eq!(ids("circulation in 2021 > 200000"), vec!["200010"]);
+    assert_eq!(
+        ids("circulation > 0 in 2021 order by circulation in 2021 desc"),
+        vec!["200010", "200002"]
+    );
+    assert_eq!(ids("linked via succession from 100101"), vec!["200002"]);
+    assert_eq!(ids("linked via succession to 200002"), vec!["100101"]);
+    assert_eq!(
+        ids("linked with (name = \"Kurier Online\")"),
+        vec!["200002"]
+    );
+    assert_eq!(ids("not linked"), vec!["200010"]);
+    assert_eq!(ids("order by id limit 2"), vec!["100101", "200002"]);
+
+    let error = Query::parse("sector = 11 and").unwrap_err();
+    assert!(error.to_string().contains("query: expected"));
+    assert!(Query::parse("sector 11").is_err());
+}
+
+#[test]
+fn test_columns_and_formats() {
+    let result = Query::parse("circulation > 100000 in 2021 and location = Wien")
+        .unwrap()
+        .run(&program());
+    assert_eq!(
+        result.columns,
+        vec!["id", "name", "family", "circulation 2021", "location"]
+    );
+    assert_eq!(
+        result.rows,
+        vec![vec![
+            Value::Number(200002.0),
+            Value::Text("Kurier".to_string()),
+            Value::Text("Kurier Group".to_string()),
+            Value::Number(120000.0),
+            Value::Text("Wien".to_string()),
+        ]]
+    );
+
+    let table = result.format_table();
+    assert!(table.starts_with("id      name    family        circulation 2021  location\n"));
+    assert!(table.ends_with("(1 outlet)\n"));
+
+    let json = result.format_json();
+    assert!(json.contains("{ \"id\": 200002, \"name\": \"Kurier\", \"family\": \"Kurier Group\", \"circulation 2021\": 120000, \"location\": \"Wien\" }"));
+
+    let csv = Query::parse("name ~ kurier select id, name, end")
+        .unwrap()
+        .run(&program())
+        .format_csv();
+    assert_eq!(
+        csv,
+        "id,name,end\n200002,Kurier,CURRENT\n200003,Kurier Online,CURRENT\n"
+    );
+}
@@ -0,0 +1,257 @@
+//! Tests for redacting natural persons from exports
+
+use mdsl_rs::codegen::{CypherGenerator, SqlGenerator};
+use mdsl_rs::ir::nodes::*;
+use mdsl_rs::ir::redaction::{pseudonym, pseudonym_id, redact, AGGREGATE_NAME};
+use mdsl_rs::ir::transform;
+use mdsl_rs::parse;
+
+const PERSON_SOURCE: &str = r#"
+    FAMILY "Test Family" {
+        OUTLET "Test Zeitung" {
+            IDENTITY { id = 200001; title = "Test Zeitung"; }
+            METADATA { editor_in_chief = "Erika Muster"; steward = "ORF"; }
+        }
+    }
+
+    COMPANY "Verlag GmbH" { id = 400001; }
+    PERSON "Max Mustermann" { id = 9001; birth_year = 1960; }
+    PERSON "Erika Muster" { id = 9002; }
+
+    OWNERSHIP max_stake {
+        owner_person = 9001;
+        owned_company = 400001;
+        share_pct = 30;
+        source_url = "https://example.org/person/9001";
+    }
+    OWNERSHIP erika_stake {
+        owner_person = 9002;
+        owned_company = 400001;
+        share_pct = 20;
+    }
+    OWNERSHIP erika_old_stake {
+        owner_person = 9002;
+        owned_company = 400001;
+        share_pct = 50;
+        period_end = "2010-01-01";
+    }
+
+    EVENT verlag_sale {
+        type = "acquisition";
+        date = "2015-06-01";
+        entities = {
+            verlag = { id = 400001; role = "target"; };
+            max = { person = 9001; role = "seller"; stake_before = 40; stake_after = 30; };
+            erika = { person = 9002; role = "buyer"; stake_before = 0; stake_after = 20; };
+        };
+        metadata = {
+            advisor = "Hans Berater";
+            deal_value = "undisclosed";
+        };
+    }
+"#;
+
+fn policy(source: &str) -> (IRProgram, IRRedactionPolicy) {
+    let program = format!("{}\n{}", PERSON_SOURCE, source);
+    let mut ir = transform(&parse(&program).unwrap()).unwrap();
+    let policy = ir.redaction_policies.remove(0);
+    (ir, policy)
+}
+
+#[test]
+fn test_parse_redaction_policy() {
+    let (_, policy) = policy(
+        r#"
+        REDACTION public_export {
+            persons = "pseudonymise";
+            person_metadata = "advisor, editor_in_chief";
+            salt = "s3cret";
+        }
+        "#,
+    );
+    assert_eq!(policy.name, "public_export");
+    assert_eq!(policy.persons, RedactionAction::Pseudonymise);
+    // Event entities follow the person action unless set
+    assert_eq!(policy.event_persons, RedactionAction::Pseudonymise);
+    assert_eq!(policy.person_metadata, vec!["advisor", "editor_in_chief"]);
+    assert_eq!(policy.salt, "s3cret");
+
+    let error = transform(&parse(r#"REDACTION bad { persons = "hide"; }"#).unwrap());
+    assert!(error.is_err());
+}
+
+#[test]
+fn test_event_entities_are_persons_only_by_reference() {
+    // A company whose ID equals a person ID is not a person
+    let (ir, policy) = policy(
+        r#"
+        COMPANY "Druckerei GmbH" { id = 9001; }
+
+        EVENT print_contract {
+            type = "contract";
+            date = "2016-01-01";
+            entities = {
+                printer = { id = 9001; role = "printer"; };
+                max = { person = 9001; role = "signatory"; };
+            };
+        }
+
+        REDACTION strict { persons = "drop"; }
+        "#,
+    );
+    let (redacted, _) = redact(&ir, &policy);
+    let entities: Vec<&str> = redacted.events[1]
+        .entities
+        .iter()
+        .map(|entity| entity.name.as_str())
+        .collect();
+    assert_eq!(entities, ["printer"]);
+
+    // Person references must name a declared person
+    let source = format!(
+        "{}\nEVENT e {{ type = \"x\"; entities = {{ ghost = {{ person = 9999; }}; }}; }}",
+        PERSON_SOURCE
+    );
+    let result = mdsl_rs::semantic::validate_program(&parse(&source).unwrap());
+    let codes: Vec<&str> = result.issues.iter().map(|issue| issue.code.as_str()).collect();
+    assert!(codes.contains(&"EVENT_PERSON_NOT_FOUND"));
+}
+
+#[test]
+fn test_pseudonymise_persons() {
+    let (ir, policy) = policy(
+        r#"
+        REDACTION public_export {
+            persons = "pseudonymise";
+            person_metadata = "advisor, editor_in_chief";
+            salt = "s3cret";
+        }
+        "#,
+    );
+    let (redacted, report) = redact(&ir, &policy);
+
+    // Pseudonyms are stable for a salt and differ between salts
+    let max = pseudonym("s3cret", "person", "9001");
+    assert_eq!(max, pseudonym("s3cret", "person", "9001"));
+    assert_ne!(max, pseudonym("other", "person", "9001"));
+    assert!(max.starts_with("person_"));
+
+    assert_eq!(redacted.persons[0].name, max);
+    assert!(redacted.persons[0].fields.is_empty());
+    assert_eq!(redacted.ownerships.len(), 3);
+    assert!(redacted.ownerships.iter().all(|o| o.source_url.is_none()));
+
+    let entities = &redacted.events[0].entities;
+    assert!(entities.iter().any(|e| e.name == "verlag"));
+    let max_id = pseudonym_id("s3cret", 9001);
+    assert_eq!(redacted.persons[0].id, max_id);
+    assert!(entities
+        .iter()
+        .any(|e| e.name == max && e.id == max_id && e.person == Some(max_id)));
+    assert!(!entities.iter().any(|e| e.name == "max"));
+
+    let advisor = &redacted.events[0].metadata[0];
+    assert_eq!(advisor.name, "advisor");
+    assert!(matches!(&advisor.value, IRExpression::String(v) if v.starts_with("name_")));
+
+    let text = report.format_text();
+    assert!(text.contains("Redaction policy 'public_export'"));
+    assert!(text.contains("Max Mustermann"));
+    assert!(text.contains("verlag_sale.max"));
+    assert!(text.contains("Test Zeitung.editor_in_chief"));
+    assert!(!text.contains("steward"));
+
+    // Names of persons no longer reach the generated SQL
+    let sql = SqlGenerator::new().generate(&redacted).unwrap();
+    assert!(!sql.contains("Max Mustermann"));
+    assert!(!sql.contains("Erika Muster"));
+    assert!(!sql.contains("Hans Berater"));
+    assert!(sql.contains(&max));
+}
+
+#[test]
+fn test_pseudonymise_replaces_person_ids() {
+    let (ir, policy) = policy(
+        r#"
+        EVENT board_meeting {
+            type = "meeting";
+            date = "2016-03-01";
+            metadata = { chair = 9001; };
+        }
+
+        REDACTION public_export {
+            persons = "pseudonymise";
+            person_metadata = "chair";
+            salt = "s3cret";
+        }
+        "#,
+    );
+    let (redacted, _) = redact(&ir, &policy);
+    let max_id = pseudonym_id("s3cret", 9001);
+    let erika_id = pseudonym_id("s3cret", 9002);
+    assert_ne!(max_id, erika_id);
+
+    let person_ids: Vec<u32> = redacted.persons.iter().map(|p| p.id).collect();
+    assert_eq!(person_ids, [max_id, erika_id]);
+    assert_eq!(redacted.ownerships[0].owner, Some(IROwner::Person(max_id)));
+    assert_eq!(redacted.ownerships[1].owner, Some(IROwner::Person(erika_id)));
+    assert!(matches!(
+        redacted.events[1].metadata[0].value,
+        IRExpression::Number(n) if n == max_id as f64
+    ));
+
+    // The register IDs reach neither the SQL nor the Cypher output
+    let sql = SqlGenerator::new().generate(&redacted).unwrap();
+    let cypher = CypherGenerator::new().generate(&redacted).unwrap();
+    for output in [&sql, &cypher] {
+        assert!(!output.contains("9001"));
+        assert!(!output.contains("9002"));
+        assert!(output.contains(&max_id.to_string()));
+    }
+}
+
+#[test]
+fn test_drop_and_aggregate_persons() {
+    let (ir, policy) = policy(
+        r#"
+        REDACTION public_export {
+            persons = "aggregate";
+            event_persons = "drop";
+            person_metadata = "advisor";
+        }
+        "#,
+    );
+    let (redacted, report) = redact(&ir, &policy);
+
+    assert!(redacted.persons.is_empty());
+    // Current stakes are summed, the historic one is dropped
+    assert_eq!(redacted.ownerships.len(), 1);
+    let aggregate = &redacted.ownerships[0];
+    assert_eq!(aggregate.name, format!("{}_400001", AGGREGATE_NAME));
+    assert_eq!(aggregate.owner, None);
+    assert_eq!(aggregate.share_pct, Some(50.0));
+
+    let event = &redacted.events[0];
+    assert_eq!(event.entities.len(), 1);
+    assert_eq!(event.entities[0].name, "verlag");
+    assert!(!event.metadata.iter().any(|m| m.name == "advisor"));
+    assert!(event.metadata.iter().any(|m| m.name == "deal_value"));
+
+    // 2 persons, 3 stakes, 2 event entities and 1 metadata field
+    assert_eq!(report.entries.len(), 8);
+    assert!(report
+        .entries
+        .iter()
+        .any(|e| e.subject == "erika_old_stake" && e.detail == "historic stake removed"));
+
+    let (ir, policy) = self::policy(r#"REDACTION agg { persons = "keep"; event_persons = "aggregate"; }"#);
+    let (redacted, _) = redact(&ir, &policy);
+    assert_eq!(redacted.persons.len(), 2);
+    let merged = redacted.events[0]
+        .entities
+        .iter()
+        .find(|e| e.name == AGGREGATE_NAME)
+        .unwrap();
+    assert_eq!(merged.stake_before, Some(40.0));
+    assert_eq!(merged.stake_after, Some(50.0));
+}
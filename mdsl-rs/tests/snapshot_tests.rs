@@ -0,0 +1,334 @@
+//! Tests for point-in-time snapshots of the media landscape
+
+use mdsl_rs::codegen::SqlGenerator;
+use mdsl_rs::ir::nodes::*;
+use mdsl_rs::ir::snapshot::*;
+use mdsl_rs::ir::transform;
+use mdsl_rs::parse;
+
+const SOURCE: &str = r#"
+    FAMILY "Kurier Group" {
+        OUTLET "Kurier" {
+            identity {
+                id = 200002;
+                title = "Kurier";
+            };
+            lifecycle {
+                status "active" FROM "1954-10-18" TO CURRENT {
+                    precision_start = "known";
+                };
+            };
+            characteristics {
+                sector = 11;
+            };
+        };
+
+        OUTLET "Der Abend" {
+            identity {
+                id = 100101;
+                title = "Der Abend";
+            };
+            lifecycle {
+                status "active" FROM "1945-08-01" TO "1990-12-31" {
+                    precision_start = "known";
+                };
+                status "inactive" FROM "1991-01-01" TO CURRENT {
+                    precision_start = "known";
+                };
+            };
+        };
+
+        OUTLET "Kurier Online" {
+            identity {
+                id = 200003;
+                title = "Kurier Online";
+            };
+            lifecycle {
+                status "active" FROM "1996" TO CURRENT {
+                    precision_start = "known";
+                };
+            };
+        };
+
+        SYNCHRONOUS_LINK kurier_abend {
+            outlet_1 = { id = 200002; role = "main"; };
+            outlet_2 = { id = 100101; role = "side"; };
+            relationship_type = "combination";
+            period_start = "1980-01-01";
+            period_end = "1990-12-31";
+        };
+
+        SYNCHRONOUS_LINK kurier_online {
+            outlet_1 = { id = 200002; role = "main"; };
+            outlet_2 = { id = 200003; role = "side"; };
+            relationship_type = "main_media_outlet";
+            period_start = "1996-01-01";
+        };
+
+        DIACHRONIC_LINK abend_kurier {
+            predecessor = 100101;
+            successor = 200002;
+            relationship_type = "succession";
+        };
+    }
+
+    FAMILY "Express" {
+        OUTLET "Express" {
+            identity {
+                id = 300001;
+                title = "Express";
+            };
+            lifecycle {
+                status "active" FROM "1958-01-01" TO "1971-12-31" {
+                    precision_start = "known";
+                };
+            };
+            characteristics {
+                editorial_stance = "independent";
+            };
+        };
+    }
+
+    FAMILY "Kronen Zeitung" {
+        OUTLET "Kronen Zeitung" {
+            identity {
+                id = 200001;
+                title = "Kronen Zeitung";
+            };
+            lifecycle {
+                status "active" FROM "1959-04-11" TO CURRENT {
+                    precision_start = "known";
+                };
+            };
+        };
+
+        OUTLET_REF 300001 ["Express"] {
+            @comment "Express acquired by Kronen Zeitung in 1971";
+            INHERITS_FROM 300001 UNTIL "1971-01-01";
+
+            OVERRIDE FROM "1971-01-01" {
+                @family "Kronen Zeitung";
+
+                FOR_PERIOD "1971-01-01" TO "1971-12-31" {
+                    lifecycle {
+                        contextual_status = "integrated_ceased";
+                    };
+                    characteristics {
+                        editorial_stance = "Aligned with Kronen Zeitung";
+                    };
+                };
+            };
+        };
+    }
+
+    DATA FOR 200002 {
+        YEAR 1983 {
+            metrics {
+                reach_national = { value = 20.5; unit = "percent"; source = "2001983"; };
+            };
+        };
+        YEAR 1984 {
+            metrics {
+                circulation = { value = 300000; unit = "copies"; source = "1501984"; };
+            };
+        };
+        YEAR 1986 {
+            metrics {
+                circulation = { value = 350000; unit = "copies"; source = "1501986"; };
+            };
+        };
+    };
+
+    COMPANY "Kurier Verlag" { id = 400001; }
+    COMPANY "Kurier Holding" { id = 400002; }
+
+    OUTLET_PARTY_LINK kurier_publisher {
+        outlet = 200002;
+        party = 400001;
+        role = "publisher";
+        period_start = "1954-10-18";
+        period_end = CURRENT;
+    }
+
+    OWNERSHIP holding_until_1980 {
+        owner_company = 400002;
+        owned_company = 400001;
+        share_pct = 100;
+        period_end = "1980-12-31";
+    }
+
+    OWNERSHIP holding_since_1981 {
+        owner_company = 400002;
+        owned_company = 400001;
+        share_pct = 49.4;
+        period_start = "1981-01-01";
+    }
+
+    EVENT kurier_sale {
+        type = "acquisition";
+        date = "1988-06-30";
+        entities = {
+            buyer = { id = 400002; role = "acquirer"; stake_after = 49.4; };
+        };
+    }
+"#;
+
+fn program() -> IRProgram {
+    transform(&parse(SOURCE).unwrap()).unwrap()
+}
+
+fn outlets(snapshot: &IRProgram) -> Vec<(String, u32)> {
+    snapshot
+        .families
+        .iter()
+        .flat_map(|family| {
+            family
+                .outlets
+                .iter()
+                .map(move |outlet| (family.name.clone(), outlet.id.unwrap()))
+        })
+        .collect()
+}
+
+fn characteristic(outlet: &IROutlet, name: &str) -> Option<String> {
+    outlet.blocks.iter().find_map(|block| match block {
+        IROutletBlock::Characteristics(fields) => fields
+            .iter()
+            .find(|field| field.name == name)
+            .map(|field| match &field.value {
+                IRExpression::String(s) => s.clone(),
+                other => format!("{:?}", other),
+            }),
+        _ => None,
+    })
+}
+
+#[test]
+fn test_snapshot_keeps_the_state_at_the_date() {
+    let ir = program();
+    // The outer override moves the outlet, the nested period overrides fields
+    let overrides = &ir.families[2].overrides;
+    assert_eq!(overrides.len(), 2);
+    assert_eq!(overrides[0].end_date, None);
+    assert!(overrides[0].blocks.is_empty());
+    assert_eq!(overrides[1].end_date.as_deref(), Some("1971-12-31"));
+    assert_eq!(overrides[1].family.as_deref(), Some("Kronen Zeitung"));
+
+    let snapshot = snapshot_at(&ir, "1985-01-01");
+    assert_eq!(
+        outlets(&snapshot),
+        vec![
+            ("Kurier Group".to_string(), 200002),
+            ("Kurier Group".to_string(), 100101),
+            ("Kronen Zeitung".to_string(), 200001),
+        ]
+    );
+
+    // Only the combination link is in effect; succession is a transition
+    let kurier = &snapshot.families[0];
+    assert_eq!(kurier.relationships.len(), 1);
+    assert!(matches!(
+        &kurier.relationships[0],
+        IRRelationship::Synchronous(link) if link.name == "kurier_abend"
+    ));
+
+    // Latest value of each metric up to 1985
+    let data = &kurier.data_blocks[0];
+    let years: Vec<(u32, Vec<&str>)> = data
+        .years
+        .iter()
+        .map(|year| {
+            (
+                year.year,
+                year.metrics.iter().map(|m| m.name.as_str()).collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        years,
+        vec![(1983, vec!["reach_national"]), (1984, vec!["circulation"])]
+    );
+
+    assert_eq!(snapshot.outlet_party_links.len(), 1);
+    assert_eq!(snapshot.ownerships.len(), 1);
+    assert_eq!(snapshot.ownerships[0].name, "holding_since_1981");
+    assert!(snapshot.events.is_empty());
+
+    // Years and open periods
+    let later = snapshot_at(&ir, "1997");
+    assert_eq!(
+        outlets(&later),
+        vec![
+            ("Kurier Group".to_string(), 200002),
+            ("Kurier Group".to_string(), 200003),
+            ("Kronen Zeitung".to_string(), 200001),
+        ]
+    );
+    assert_eq!(later.families[0].relationships.len(), 1);
+    assert_eq!(later.events.len(), 1);
+    let lifecycle = later.families[0].outlets[0]
+        .blocks
+        .iter()
+        .find_map(|block| match block {
+            IROutletBlock::Lifecycle(statuses) => Some(statuses),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(lifecycle.len(), 1);
+}
+
+#[test]
+fn test_overrides_apply_within_their_period() {
+    let ir = program();
+
+    let before = snapshot_at(&ir, "1970-06-01");
+    let express = &before.families[1];
+    assert_eq!(express.name, "Express");
+    assert_eq!(
+        characteristic(&express.outlets[0], "editorial_stance").as_deref(),
+        Some("independent")
+    );
+
+    // Moved into the acquiring family, with its fields overridden
+    let after = snapshot_at(&ir, "1971-06-01");
+    assert_eq!(
+        outlets(&after),
+        vec![
+            ("Kurier Group".to_string(), 200002),
+            ("Kurier Group".to_string(), 100101),
+            ("Kronen Zeitung".to_string(), 300001),
+            ("Kronen Zeitung".to_string(), 200001),
+        ]
+    );
+    assert_eq!(
+        characteristic(&after.families[1].outlets[0], "editorial_stance").as_deref(),
+        Some("Aligned with Kronen Zeitung")
+    );
+}
+
+#[test]
+fn test_snapshot_formats() {
+    let snapshot = snapshot_at(&program(), "1985-01-01");
+
+    let mdsl = format_mdsl(&snapshot, "1985-01-01");
+    assert!(mdsl.starts_with("// Snapshot as of 1985-01-01\n"));
+    assert!(mdsl.contains("status \"active\" FROM \"1954-10-18\" TO CURRENT {"));
+    assert!(mdsl
+        .contains("circulation = { value = 300000; unit = \"copies\"; source = \"1501984\"; };"));
+    let reparsed = transform(&parse(&mdsl).unwrap()).unwrap();
+    assert_eq!(outlets(&reparsed), outlets(&snapshot));
+    assert_eq!(reparsed.families[0].relationships.len(), 1);
+    assert_eq!(reparsed.ownerships.len(), 1);
+    assert_eq!(reparsed.outlet_party_links.len(), 1);
+
+    let json = format_json(&snapshot, "1985");
+    assert!(json.contains("\"as_of\": \"1985-01-01\""));
+    assert!(json.contains("{ \"id\": 200002, \"name\": \"Kurier\", \"status\": \"active\", \"since\": \"1954-10-18\", \"fields\": { \"identity.id\": 200002, \"identity.title\": \"Kurier\", \"characteristics.sector\": 11 }"));
+    assert!(json.contains("{ \"metric\": \"circulation\", \"year\": 1984, \"value\": 300000, \"unit\": \"copies\", \"source\": \"1501984\" }"));
+    assert!(json.contains("\"name\": \"kurier_abend\", \"type\": \"combination\""));
+    assert!(json.contains("\"share_pct\": 49.4"));
+
+    let sql = SqlGenerator::new().generate(&snapshot).unwrap();
+    assert!(sql.contains("INSERT INTO"));
+    assert!(!sql.contains("Kurier Online"));
+}
@@ -0,0 +1,229 @@
+//! Tests for direction and symmetry semantics of synchronous link types
+
+use mdsl_rs::codegen::CypherGenerator;
+use mdsl_rs::ir::nodes::{IRProgram, IRRelationship, LinkDirection, RoleOrder};
+use mdsl_rs::ir::sync_links::{duplicate_links, normalize};
+use mdsl_rs::ir::transform;
+use mdsl_rs::parse;
+use mdsl_rs::semantic::validate_program;
+
+const SOURCE: &str = r#"
+    RELATIONSHIP_TYPE "content_sharing" {
+        direction = "symmetric";
+    }
+
+    RELATIONSHIP_TYPE "syndication" {
+        direction = "directed";
+        roles = "supplier/client";
+    }
+
+    FAMILY "Mediaprint" {
+        SYNCHRONOUS_LINK krone_kurier {
+            outlet_1 = { id = 200002; role = "partner"; };
+            outlet_2 = { id = 200001; role = "partner"; };
+            relationship_type = "collaboration";
+            period_start = "1988";
+        };
+
+        SYNCHRONOUS_LINK kurier_krone {
+            outlet_1 = { id = 200001; role = "partner"; };
+            outlet_2 = { id = 200002; role = "partner"; };
+            relationship_type = "collaboration";
+            period_start = "1995";
+            period_end = "2001";
+        };
+
+        SYNCHRONOUS_LINK krone_bunt {
+            outlet_1 = { id = 200005; role = "target"; };
+            outlet_2 = { id = 200001; role = "source"; };
+            relationship_type = "umbrella";
+            period_start = "1975";
+        };
+
+        SYNCHRONOUS_LINK bunt_krone {
+            outlet_1 = { id = 200001; role = "source"; };
+            outlet_2 = { id = 200005; role = "target"; };
+            relationship_type = "umbrella";
+            period_start = "1970";
+            period_end = "1974";
+        };
+
+        SYNCHRONOUS_LINK apa_krone {
+            outlet_1 = { id = 200001; role = "client"; };
+            outlet_2 = { id = 300001; role = "target"; };
+            relationship_type = "syndication";
+            period_start = "1990";
+        };
+    }
+"#;
+
+fn program() -> IRProgram {
+    transform(&parse(SOURCE).unwrap()).unwrap()
+}
+
+#[test]
+fn test_relationship_type_semantics() {
+    let ir = program();
+    assert_eq!(ir.relationship_types.len(), 2);
+
+    let sharing = ir.relationship_type("content_sharing").unwrap();
+    assert_eq!(sharing.direction, LinkDirection::Symmetric);
+    assert!(sharing.role_pairs.is_empty());
+
+    let syndication = ir.relationship_type("syndication").unwrap();
+    assert_eq!(syndication.direction, LinkDirection::Directed);
+    assert_eq!(
+        syndication.role_order("supplier", "client"),
+        RoleOrder::Declared
+    );
+    assert_eq!(
+        syndication.role_order("client", "supplier"),
+        RoleOrder::Reversed
+    );
+    assert_eq!(
+        syndication.role_order("client", "target"),
+        RoleOrder::NotAllowed
+    );
+
+    // Built-in types apply unless redeclared
+    let umbrella = ir.relationship_type("umbrella").unwrap();
+    assert_eq!(umbrella.direction, LinkDirection::Directed);
+    assert_eq!(
+        umbrella.role_order("integrated", "primary"),
+        RoleOrder::Declared
+    );
+    assert_eq!(
+        umbrella.role_order("primary", "integrated"),
+        RoleOrder::Reversed
+    );
+    assert_eq!(
+        ir.relationship_type("collaboration").unwrap().direction,
+        LinkDirection::Symmetric
+    );
+    assert!(ir.relationship_type("franchise").is_none());
+
+    assert!(parse(r#"RELATIONSHIP_TYPE "x" { roles = "source"; }"#).is_err());
+}
+
+#[test]
+fn test_normalization_and_duplicates() {
+    let ir = program();
+
+    // Symmetric links list the lower ID first, directed links the source
+    let normalized = normalize(&ir);
+    let endpoints: Vec<(String, u32, u32)> = normalized.families[0]
+        .relationships
+        .iter()
+        .filter_map(|relationship| match relationship {
+            IRRelationship::Synchronous(link) => {
+                Some((link.name.clone(), link.outlet_1.id, link.outlet_2.id))
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        endpoints,
+        vec![
+            ("krone_kurier".to_string(), 200001, 200002),
+            ("kurier_krone".to_string(), 200001, 200002),
+            ("krone_bunt".to_string(), 200001, 200005),
+            ("bunt_krone".to_string(), 200001, 200005),
+            ("apa_krone".to_string(), 200001, 300001),
+        ]
+    );
+
+    // The reversed collaboration overlaps; the umbrella periods do not
+    let duplicates = duplicate_links(&ir);
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(duplicates[0].first, "krone_kurier");
+    assert_eq!(duplicates[0].second, "kurier_krone");
+    assert_eq!(duplicates[0].relationship_type, "collaboration");
+    assert!(duplicates[0].reversed);
+
+    let result = validate_program(&parse(SOURCE).unwrap());
+    let codes: Vec<&str> = result
+        .issues
+        .iter()
+        .map(|issue| issue.code.as_str())
+        .filter(|code| code.starts_with("SYNC_LINK") || code.starts_with("RELATIONSHIP_TYPE"))
+        .collect();
+    assert_eq!(
+        codes,
+        vec!["SYNC_LINK_ROLES_NOT_ALLOWED", "SYNC_LINK_DUPLICATE"]
+    );
+
+    let invalid = validate_program(
+        &parse(
+            r#"
+            RELATIONSHIP_TYPE "x" { direction = "both"; }
+            RELATIONSHIP_TYPE "x" { direction = "directed"; }
+            "#,
+        )
+        .unwrap(),
+    );
+    let codes: Vec<&str> = invalid
+        .issues
+        .iter()
+        .map(|issue| issue.code.as_str())
+        .collect();
+    assert!(codes.contains(&"RELATIONSHIP_TYPE_REDECLARED"));
+    assert!(codes.contains(&"RELATIONSHIP_TYPE_UNKNOWN_DIRECTION"));
+    assert!(!invalid.passed);
+}
+
+#[test]
+fn test_cypher_edge_direction() {
+    let cypher = CypherGenerator::with_prefix("")
+        .generate(&program())
+        .unwrap();
+
+    // Symmetric: undirected, lower ID first
+    assert!(cypher.contains(
+        "MATCH (o1:media_outlet {id_mo: 200001}), (o2:media_outlet {id_mo: 200002}) MERGE (o1)-[r:collaboration]-(o2) SET r.start_rel = datetime('1988')"
+    ));
+    // Directed: from the source, whichever order the outlets were written in
+    assert!(cypher.contains(
+        "MATCH (o1:media_outlet {id_mo: 200001}), (o2:media_outlet {id_mo: 200005}) MERGE (o1)-[r:umbrella]->(o2) SET r.start_rel = datetime('1975')"
+    ));
+    assert!(!cypher.contains("{id_mo: 200005}), (o2:media_outlet {id_mo: 200001})"));
+    // Unknown roles keep the written order
+    assert!(cypher.contains(
+        "MATCH (o1:media_outlet {id_mo: 200001}), (o2:media_outlet {id_mo: 300001}) MERGE (o1)-[r:syndication]->(o2)"
+    ));
+}
+
+#[test]
+fn test_imported_and_curated_roles_agree_on_direction() {
+    // The importer writes the member as source, curated files write the
+    // main outlet as primary and the member as integrated
+    let source = r#"
+        FAMILY "Kronen Zeitung" {
+            SYNCHRONOUS_LINK link_300001_main_media_outlet {
+                outlet_1 = { id = 300001; role = "source"; };
+                outlet_2 = { id = 200001; role = "target"; };
+                relationship_type = "main_media_outlet";
+                period_start = "1972-01-01";
+            };
+
+            SYNCHRONOUS_LINK combination {
+                outlet_1 = { id = 200001; role = "primary"; };
+                outlet_2 = { id = 300001; role = "integrated"; };
+                relationship_type = "main_media_outlet";
+                period_start = "1975-01-01";
+            };
+        }
+    "#;
+    let ir = transform(&parse(source).unwrap()).unwrap();
+    let cypher = CypherGenerator::with_prefix("").generate(&ir).unwrap();
+
+    for start in ["1972-01-01", "1975-01-01"] {
+        assert!(cypher.contains(&format!(
+            "MATCH (o1:media_outlet {{id_mo: 300001}}), (o2:media_outlet {{id_mo: 200001}}) MERGE (o1)-[r:main_media_outlet]->(o2) SET r.start_rel = datetime('{}')",
+            start
+        )));
+    }
+    // Written either way round, the two links state the same relationship
+    let duplicates = duplicate_links(&ir);
+    assert_eq!(duplicates.len(), 1);
+    assert!(duplicates[0].reversed);
+}
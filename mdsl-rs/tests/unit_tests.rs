@@ -539,6 +539,53 @@ fn test_parse_event_created_synchronous_relationship() {
     }
 }
 
+#[test]
+fn test_parse_synchronous_link_details() {
+    let input = r#"
+    SYNCHRONOUS_LINK print_shop {
+        outlet_1 = { id = 200001; };
+        outlet_2 = { id = 300014; };
+        relationship_type = "collaboration";
+        details = "Shared print shop";
+    }
+    "#;
+    let ast = parse_input(input);
+
+    if let Statement::Relationship(RelationshipDeclaration::Synchronous(link)) = &ast.statements[0] {
+        let has_details = link.fields.iter().any(|f| {
+            matches!(f, SynchronousField::Details { value, .. } if value == "Shared print shop")
+        });
+        assert!(has_details, "Should have details field");
+    } else {
+        panic!("Expected synchronous link");
+    }
+}
+
+#[test]
+fn test_parse_synchronous_period_without_start() {
+    let input = r#"
+    SYNCHRONOUS_LINK umbrella_link {
+        outlet_1 = { id = 101251; role = "source"; };
+        outlet_2 = { id = 101250; role = "target"; };
+        relationship_type = "umbrella";
+        period_end = "1991-10-31";
+    }
+    "#;
+    let ast = parse_input(input);
+
+    if let Statement::Relationship(RelationshipDeclaration::Synchronous(link)) = &ast.statements[0] {
+        let period = link.fields.iter().find_map(|f| match f {
+            SynchronousField::Period { value, .. } => Some(value),
+            _ => None,
+        });
+        let period = period.expect("Should have a period");
+        assert_eq!(period.from, None);
+        assert!(matches!(period.to, Some(DateExpression::Literal(ref date)) if date == "1991-10-31"));
+    } else {
+        panic!("Expected synchronous link");
+    }
+}
+
 #[test]
 fn test_parse_lifecycle_block() {
     let input = r#"
@@ -762,3 +809,238 @@ fn test_parse_lifecycle_with_comments() {
         }
     }
 }
+
+#[test]
+fn test_parse_company_and_outlet_party_link() {
+    let input = r#"
+    COMPANY "Mediaprint Zeitungs- und Zeitschriftenverlag" {
+        id = 400001;
+        legal_form = "GmbH & Co KG";
+        @comment "Publisher of Kronen Zeitung and Kurier"
+    }
+
+    OUTLET_PARTY_LINK krone_publisher {
+        outlet = 200001;
+        party = 400001;
+        role = "publisher";
+        period_start = "1988-01-01";
+        period_end = CURRENT;
+        source = "firmenbuch";
+    }
+    "#;
+    let ast = parse_input(input);
+
+    assert_eq!(ast.statements.len(), 2);
+    if let Statement::Company(company) = &ast.statements[0] {
+        assert_eq!(company.name, "Mediaprint Zeitungs- und Zeitschriftenverlag");
+        assert_eq!(company.fields.len(), 3);
+        assert!(company.fields.iter().any(|f| matches!(f, CompanyField::Annotation(a) if a.name == "comment")));
+    } else {
+        panic!("Expected company declaration");
+    }
+
+    if let Statement::OutletPartyLink(link) = &ast.statements[1] {
+        assert_eq!(link.name, "krone_publisher");
+        assert!(link.fields.iter().any(|f| matches!(f, OutletPartyField::Outlet { value, .. } if *value == 200001.0)));
+        assert!(link.fields.iter().any(|f| matches!(f, OutletPartyField::Party { value, .. } if *value == 400001.0)));
+        assert!(link.fields.iter().any(|f| matches!(f, OutletPartyField::Role { value, .. } if value == "publisher")));
+        assert!(link.fields.iter().any(|f| matches!(f, OutletPartyField::PeriodEnd { value: DateExpression::Current, .. })));
+        assert!(link.fields.iter().any(|f| matches!(f, OutletPartyField::Source { value, .. } if value == "firmenbuch")));
+    } else {
+        panic!("Expected outlet party link");
+    }
+}
+
+#[test]
+fn test_parse_person_and_ownership() {
+    let input = r#"
+    PERSON "Eva Dichand" {
+        id = 9001;
+        scraped_at = "2026-03-11 00:44:27";
+    }
+
+    OWNERSHIP nd_ownership_1 {
+        owner_person = 9001;
+        owned_company = 400001;
+        share_pct = 50;
+        role = "shareholder";
+        period_end = "2020-01-01";
+        source_url = "https://www.northdata.de/example";
+        scraped_at = "2026-03-11 00:44:27";
+    }
+    "#;
+    let ast = parse_input(input);
+
+    assert_eq!(ast.statements.len(), 2);
+    if let Statement::Person(person) = &ast.statements[0] {
+        assert_eq!(person.name, "Eva Dichand");
+        assert_eq!(person.fields.len(), 2);
+    } else {
+        panic!("Expected person declaration");
+    }
+
+    if let Statement::Ownership(ownership) = &ast.statements[1] {
+        assert_eq!(ownership.name, "nd_ownership_1");
+        assert!(ownership.fields.iter().any(|f| matches!(f, OwnershipField::OwnerPerson { value, .. } if *value == 9001.0)));
+        assert!(ownership.fields.iter().any(|f| matches!(f, OwnershipField::OwnedCompany { value, .. } if *value == 400001.0)));
+        assert!(ownership.fields.iter().any(|f| matches!(f, OwnershipField::SharePct { value, .. } if *value == 50.0)));
+        assert!(ownership.fields.iter().any(|f| matches!(f, OwnershipField::SourceUrl { .. })));
+        assert!(ownership.fields.iter().any(|f| matches!(f, OwnershipField::ScrapedAt { .. })));
+    } else {
+        panic!("Expected ownership declaration");
+    }
+}
+
+#[test]
+fn test_parse_identifier_link() {
+    let input = r#"
+    IDENTIFIER_LINK match_400001_123 {
+        company = 400001;
+        scheme = "northdata";
+        value = 123;
+        status = "proposed";
+        score = 0.82;
+        issued_by = "mdsl match";
+    }
+    "#;
+    let ast = parse_input(input);
+
+    assert_eq!(ast.statements.len(), 1);
+    if let Statement::IdentifierLink(link) = &ast.statements[0] {
+        assert_eq!(link.name, "match_400001_123");
+        assert_eq!(link.fields.len(), 6);
+        assert!(link.fields.iter().any(|f| matches!(f, IdentifierLinkField::Company { value, .. } if *value == 400001.0)));
+        assert!(link.fields.iter().any(|f| matches!(f, IdentifierLinkField::Value { value, .. } if value == "123")));
+        assert!(link.fields.iter().any(|f| matches!(f, IdentifierLinkField::Score { value, .. } if *value == 0.82)));
+    } else {
+        panic!("Expected identifier link");
+    }
+}
+
+#[test]
+fn test_parse_provenance_clause() {
+    let input = r#"
+    OWNERSHIP holding_stake {
+        owner_company = 400001;
+        owned_company = 400002;
+        share_pct = 74.5;
+        PROVENANCE {
+            source = "northdata";
+            confidence = 0.9;
+            verification = "verified";
+        }
+        PROVENANCE share_pct {
+            source = "handelsregister";
+            verified_at = "2026-03-11";
+            note = "Gesellschafterliste";
+        }
+    }
+    "#;
+    let ast = parse_input(input);
+
+    assert_eq!(ast.statements.len(), 1);
+    if let Statement::Ownership(ownership) = &ast.statements[0] {
+        let clauses: Vec<&ProvenanceClause> = ownership
+            .fields
+            .iter()
+            .filter_map(|f| match f {
+                OwnershipField::Provenance(clause) => Some(clause),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(clauses.len(), 2);
+        assert_eq!(clauses[0].target, None);
+        assert_eq!(clauses[0].fields.len(), 3);
+        assert!(clauses[0].fields.iter().any(|f| matches!(f, ProvenanceField::Confidence { value, .. } if *value == 0.9)));
+        assert_eq!(clauses[1].target.as_deref(), Some("share_pct"));
+        assert!(clauses[1].fields.iter().any(|f| matches!(f, ProvenanceField::VerifiedAt { value, .. } if value == "2026-03-11")));
+    } else {
+        panic!("Expected ownership declaration");
+    }
+}
+
+#[test]
+fn test_parse_review_clause() {
+    let input = r#"
+    COMPANY "Test Verlag" {
+        id = 400001;
+        register_court = "Berlin";
+        REVIEW {
+            status = "published";
+            reviewer = "jdoe";
+            reviewed_at = "2026-04-02";
+        }
+        REVIEW register_court {
+            status = "draft";
+            note = "Awaiting register extract";
+        }
+    }
+    "#;
+    let ast = parse_input(input);
+
+    if let Statement::Company(company) = &ast.statements[0] {
+        let clauses: Vec<&ReviewClause> = company
+            .fields
+            .iter()
+            .filter_map(|f| match f {
+                CompanyField::Review(clause) => Some(clause),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(clauses.len(), 2);
+        assert_eq!(clauses[0].target, None);
+        assert!(clauses[0].fields.iter().any(|f| matches!(f, ReviewField::Status { value, .. } if value == "published")));
+        assert!(clauses[0].fields.iter().any(|f| matches!(f, ReviewField::Reviewer { value, .. } if value == "jdoe")));
+        assert_eq!(clauses[1].target.as_deref(), Some("register_court"));
+        assert!(clauses[1].fields.iter().any(|f| matches!(f, ReviewField::Note { .. })));
+    } else {
+        panic!("Expected company declaration");
+    }
+}
+
+#[test]
+fn test_parse_year_metrics() {
+    let input = r#"
+    DATA FOR 200001 {
+        @maps_to "MedienangebotMarktdaten";
+        aggregation = {
+            circulation = "national";
+            reach = "none";
+        };
+
+        YEAR 2021 {
+            metrics {
+                circulation = {
+                    value = 700000;
+                    unit = "copies";
+                    source = "oeak";
+                };
+            };
+            comment = "Verified";
+        };
+    }
+    "#;
+    let ast = parse_input(input);
+
+    assert_eq!(ast.statements.len(), 1);
+    if let Statement::Data(data) = &ast.statements[0] {
+        assert_eq!(data.blocks.len(), 3);
+        assert!(matches!(&data.blocks[0], DataBlock::Annotation(a) if a.name == "maps_to"));
+        assert!(matches!(&data.blocks[1], DataBlock::Aggregation(agg) if agg.fields.len() == 2));
+        if let DataBlock::Year(year) = &data.blocks[2] {
+            assert_eq!(year.year, 2021.0);
+            assert_eq!(year.blocks.len(), 2);
+            if let YearBlock::Metrics(metrics) = &year.blocks[0] {
+                assert_eq!(metrics.fields[0].name, "circulation");
+                assert_eq!(metrics.fields[0].attributes.len(), 3);
+            } else {
+                panic!("Expected metrics block");
+            }
+            assert!(matches!(&year.blocks[1], YearBlock::CommentAssignment { value, .. } if value == "Verified"));
+        } else {
+            panic!("Expected year declaration");
+        }
+    } else {
+        panic!("Expected data declaration");
+    }
+}
@@ -746,3 +746,374 @@ fn test_validation_summary_counts() {
     );
     assert!(!result.passed, "Should fail validation");
 }
+
+#[test]
+fn test_outlet_party_link_resolves_company() {
+    let content = r#"
+        FAMILY "Test Family" {
+            OUTLET "Test Outlet" {
+                IDENTITY {
+                    id = 100001,
+                    title = "Test Outlet"
+                }
+            }
+        }
+
+        COMPANY "Test Verlag" {
+            id = 400001;
+        }
+
+        OUTLET_PARTY_LINK valid_link {
+            outlet = 100001;
+            party = 400001;
+            role = "publisher";
+            period_start = "1990-01-01";
+        }
+
+        OUTLET_PARTY_LINK broken_link {
+            outlet = 100002;
+            party = 400002;
+            role = "sponsor";
+            period_start = "2000-01-01";
+            period_end = "1999-12-31";
+        }
+    "#;
+
+    let result = validate_content(content);
+    assert!(!result.passed, "Unresolved outlet-party link should fail");
+
+    let codes: Vec<&str> = result.issues.iter().map(|issue| issue.code.as_str()).collect();
+    assert!(codes.contains(&"OUTLET_PARTY_OUTLET_NOT_FOUND"));
+    assert!(codes.contains(&"OUTLET_PARTY_COMPANY_NOT_FOUND"));
+    assert!(codes.contains(&"OUTLET_PARTY_UNKNOWN_ROLE"));
+    assert!(codes.contains(&"OUTLET_PARTY_INVALID_PERIOD"));
+
+    // The valid link must not produce any issues of its own
+    assert_eq!(
+        result
+            .issues
+            .iter()
+            .filter(|issue| issue.code.starts_with("OUTLET_PARTY"))
+            .count(),
+        4
+    );
+}
+
+#[test]
+fn test_duplicate_company_id_error() {
+    let content = r#"
+        COMPANY "First" { id = 400001; }
+        COMPANY "Second" { id = 400001; }
+    "#;
+
+    let result = validate_content(content);
+    assert!(result
+        .issues
+        .iter()
+        .any(|issue| issue.code == "COMPANY_ID_DUPLICATE"
+            && issue.severity == ValidationSeverity::Error));
+}
+
+#[test]
+fn test_ownership_resolves_owner_and_owned_company() {
+    let content = r#"
+        COMPANY "Holding" { id = 400001; }
+        COMPANY "Verlag" { id = 400002; }
+        PERSON "Owner" { id = 9001; }
+
+        OWNERSHIP valid_stake {
+            owner_person = 9001;
+            owned_company = 400001;
+            share_pct = 60;
+            source_url = "https://example.org/holding";
+        }
+
+        OWNERSHIP broken_stake {
+            owner_person = 9002;
+            owned_company = 400003;
+            share_pct = 140;
+            period_start = "2010-01-01";
+            period_end = "2009-01-01";
+        }
+    "#;
+
+    let result = validate_content(content);
+    assert!(!result.passed, "Unresolved ownership should fail");
+
+    let codes: Vec<&str> = result.issues.iter().map(|issue| issue.code.as_str()).collect();
+    assert!(codes.contains(&"OWNERSHIP_OWNER_NOT_FOUND"));
+    assert!(codes.contains(&"OWNERSHIP_OWNED_NOT_FOUND"));
+    assert!(codes.contains(&"OWNERSHIP_INVALID_SHARE"));
+    assert!(codes.contains(&"OWNERSHIP_INVALID_PERIOD"));
+    assert!(codes.contains(&"OWNERSHIP_NO_SOURCE"));
+
+    // The valid stake must not produce any issues of its own
+    assert_eq!(
+        result
+            .issues
+            .iter()
+            .filter(|issue| issue.code.starts_with("OWNERSHIP"))
+            .count(),
+        5
+    );
+}
+
+#[test]
+fn test_identifier_link_validation() {
+    let content = r#"
+        COMPANY "Test Verlag" { id = 400001; }
+
+        IDENTIFIER_LINK valid_link {
+            company = 400001;
+            scheme = "northdata";
+            value = 123;
+            status = "accepted";
+            score = 0.9;
+        }
+
+        IDENTIFIER_LINK broken_link {
+            company = 400002;
+            scheme = "northdata";
+            status = "maybe";
+            score = 1.5;
+        }
+    "#;
+
+    let result = validate_content(content);
+    assert!(!result.passed);
+
+    let codes: Vec<&str> = result.issues.iter().map(|issue| issue.code.as_str()).collect();
+    assert!(codes.contains(&"IDENTIFIER_COMPANY_NOT_FOUND"));
+    assert!(codes.contains(&"IDENTIFIER_INCOMPLETE"));
+    assert!(codes.contains(&"IDENTIFIER_UNKNOWN_STATUS"));
+    assert!(codes.contains(&"IDENTIFIER_INVALID_SCORE"));
+    assert_eq!(
+        result
+            .issues
+            .iter()
+            .filter(|issue| issue.code.starts_with("IDENTIFIER"))
+            .count(),
+        4
+    );
+}
+
+#[test]
+fn test_provenance_validation() {
+    let content = r#"
+        CATALOG sources {
+            SOURCE "northdata" {
+                quality_tier = "secondary";
+            }
+        }
+
+        COMPANY "Test Verlag" {
+            id = 400001;
+            register_court = "Berlin";
+            PROVENANCE register_court {
+                source = "northdata";
+                confidence = 0.8;
+                verification = "verified";
+            }
+            PROVENANCE capital {
+                source = "handelsregister";
+                confidence = 1.5;
+                verification = "checked";
+            }
+            PROVENANCE {
+                confidence = 0.5;
+            }
+        }
+    "#;
+
+    let result = validate_content(content);
+    assert!(!result.passed);
+
+    let codes: Vec<&str> = result.issues.iter().map(|issue| issue.code.as_str()).collect();
+    assert!(codes.contains(&"PROVENANCE_UNKNOWN_SOURCE"));
+    assert!(codes.contains(&"PROVENANCE_INVALID_CONFIDENCE"));
+    assert!(codes.contains(&"PROVENANCE_UNKNOWN_VERIFICATION"));
+    assert!(codes.contains(&"PROVENANCE_UNKNOWN_TARGET"));
+    assert!(codes.contains(&"PROVENANCE_NO_SOURCE"));
+    // The clause on register_court is fully valid
+    assert_eq!(
+        result
+            .issues
+            .iter()
+            .filter(|issue| issue.code.starts_with("PROVENANCE"))
+            .count(),
+        5
+    );
+}
+
+#[test]
+fn test_review_validation() {
+    let content = r#"
+        CATALOG sources {
+            SOURCE "northdata" {
+                quality_tier = "secondary";
+            }
+        }
+
+        COMPANY "Sourced Verlag" {
+            id = 400001;
+            register_court = "Berlin";
+            capital = 25000;
+            PROVENANCE register_court {
+                source = "northdata";
+            }
+            REVIEW register_court {
+                status = "published";
+                reviewer = "jdoe";
+                reviewed_at = "2026-04-02";
+            }
+            REVIEW capital {
+                status = "published";
+                reviewer = "jdoe";
+                reviewed_at = "2026-04-02";
+            }
+            REVIEW legal_form {
+                status = "draft";
+            }
+        }
+
+        COMPANY "Unsourced Verlag" {
+            id = 400002;
+            REVIEW {
+                status = "published";
+            }
+            REVIEW {
+                status = "approved";
+            }
+            REVIEW {
+                reviewer = "jdoe";
+            }
+        }
+    "#;
+
+    let result = validate_content(content);
+    assert!(!result.passed);
+
+    let count = |code: &str| result.issues.iter().filter(|issue| issue.code == code).count();
+    // Only the capital field and the unsourced company lack a source
+    assert_eq!(count("REVIEW_PUBLISHED_WITHOUT_SOURCE"), 2);
+    assert_eq!(count("REVIEW_NO_REVIEWER"), 1);
+    assert_eq!(count("REVIEW_UNKNOWN_STATUS"), 1);
+    assert_eq!(count("REVIEW_NO_STATUS"), 1);
+    assert_eq!(count("REVIEW_UNKNOWN_TARGET"), 1);
+}
+
+#[test]
+fn test_redaction_validation() {
+    let content = r#"
+        REDACTION public_export {
+            persons = "pseudonymise";
+            event_persons = "hide";
+        }
+    "#;
+
+    let result = validate_content(content);
+    assert!(!result.passed);
+
+    let codes: Vec<&str> = result.issues.iter().map(|issue| issue.code.as_str()).collect();
+    assert!(codes.contains(&"REDACTION_UNKNOWN_ACTION"));
+    assert!(codes.contains(&"REDACTION_NO_SALT"));
+}
+
+#[test]
+fn test_source_redeclared_in_another_catalog() {
+    let content = r#"
+        catalog sources {
+            source "oeak" { quality_tier = "primary"; }
+        }
+        catalog legacy {
+            source "oeak" { quality_tier = "secondary"; }
+        }
+    "#;
+
+    let result = validate_content(content);
+    assert!(!result.passed);
+    let duplicates: Vec<_> = result
+        .issues
+        .iter()
+        .filter(|issue| issue.code == "SOURCE_DUPLICATE")
+        .collect();
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(duplicates[0].position.line, 6);
+}
+
+#[test]
+fn test_metric_source_validation() {
+    let content = r#"
+        catalog sources {
+            source "oeak" {
+                anmi_source_id_components { type_code = 15; }
+            }
+            source "survey" {
+                display_name = "Survey";
+            }
+        }
+
+        FAMILY "Test Family" {
+            OUTLET "Test Outlet" {
+                IDENTITY { id = 100; title = "Test"; }
+            }
+        }
+
+        DATA FOR 100 {
+            YEAR 2021 {
+                metrics {
+                    circulation = { value = 436260; source = "oeak"; };
+                    reach_national = { value = 6.8; source = "2002021"; };
+                    market_share = { value = 16.1; source = "survey"; };
+                    unique_users = { value = 99; source = "owa"; };
+                };
+            };
+        }
+    "#;
+
+    let result = validate_content(content);
+    assert!(!result.passed);
+
+    let codes: Vec<&str> = result.issues.iter().map(|issue| issue.code.as_str()).collect();
+    assert!(codes.contains(&"METRIC_UNKNOWN_SOURCE"));
+    assert!(codes.contains(&"METRIC_SOURCE_NO_TYPE_CODE"));
+    // Declared sources with a type code and composite IDs are accepted
+    assert_eq!(
+        result
+            .issues
+            .iter()
+            .filter(|issue| issue.code.starts_with("METRIC"))
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn test_source_type_code_validation() {
+    let content = r#"
+        catalog sources {
+            source "oeak" { anmi_source_id_components { type_code = 15; } }
+            source "fraction" { anmi_source_id_components { type_code = 1.5; } }
+            source "wide" { anmi_source_id_components { type_code = 150; } }
+            source "text" { anmi_source_id_components { type_code = "15"; } }
+        }
+    "#;
+
+    let result = validate_content(content);
+    let invalid: Vec<usize> = result
+        .issues
+        .iter()
+        .filter(|issue| issue.code == "SOURCE_TYPE_CODE_INVALID")
+        .map(|issue| issue.position.line)
+        .collect();
+    assert_eq!(invalid, [4, 5, 6]);
+
+    // Invalid codes give no ANMI source ID
+    let ir = mdsl_rs::ir::transform(&mdsl_rs::parse(content).unwrap()).unwrap();
+    let codes: Vec<Option<u32>> = ir.catalogs[0]
+        .sources
+        .iter()
+        .map(|source| source.anmi_type_code)
+        .collect();
+    assert_eq!(codes, [Some(15), None, None, None]);
+}